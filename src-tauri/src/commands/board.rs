@@ -0,0 +1,195 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::State;
+use serde::{Deserialize, Serialize};
+use crate::models::{FootprintPoint, Project, Zone};
+use crate::services::{
+    AutorouteOptions, AutorouteResult, AutorouterService, BackAnnotationReport, BackAnnotationResult, BackAnnotationService,
+    BatchRouteOutcome, BoardService, BoardUpdateResult, DRCChecker, DRCReport, DxfImportOptions, DxfImportResult, DxfService,
+    FootprintService, NetClassAssignment, PlacementOptions, PlacementResult, PlacementService, Ratsnest, ZoneFillService,
+    ZoneFillSummary,
+};
+use crate::utils::error::{AppError, Result};
+
+#[tauri::command]
+pub async fn create_board(
+    mut project: Project,
+    copper_layers: usize,
+    board_thickness: f64,
+    width: f64,
+    height: f64,
+) -> Result<Project> {
+    BoardService::create_board(&mut project, copper_layers, board_thickness, width, height)?;
+    Ok(project)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FootprintMoveResult {
+    pub project: Project,
+    pub ratsnest: Ratsnest,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoneFillResult {
+    pub project: Project,
+    pub zones: Vec<ZoneFillSummary>,
+}
+
+/// Creates or refreshes footprint instances on the board from the schematic's footprint assignments.
+#[tauri::command]
+pub async fn update_pcb_from_schematic(
+    mut project: Project,
+    footprint_service: State<'_, Arc<FootprintService>>,
+) -> Result<BoardUpdateResult> {
+    let report = BoardService::update_from_schematic(&mut project, &footprint_service)?;
+    Ok(BoardUpdateResult { project, report })
+}
+
+/// Checks the project's board against the design rules in its settings.
+#[tauri::command]
+pub async fn run_drc_check(project: Project) -> Result<DRCReport> {
+    let board = project.board
+        .ok_or_else(|| AppError::InvalidOperation("Project has no board".to_string()))?;
+    let rules = project.settings.design_rules;
+    tokio::task::spawn_blocking(move || DRCChecker::new(board, rules).run_check())
+        .await
+        .map_err(|e| AppError::ThreadError(e.to_string()))?
+}
+
+#[tauri::command]
+pub async fn compute_ratsnest(project: Project) -> Result<Ratsnest> {
+    let board = project.board
+        .ok_or_else(|| AppError::InvalidOperation("Project has no board".to_string()))?;
+    Ok(Ratsnest::build(&board))
+}
+
+/// Moves a footprint and refreshes the ratsnest. When the current ratsnest is passed in,
+/// only the nets on the moved footprint are recomputed.
+#[tauri::command]
+pub async fn move_footprint(
+    mut project: Project,
+    footprint_id: String,
+    x: f64,
+    y: f64,
+    rotation: f64,
+    ratsnest: Option<Ratsnest>,
+) -> Result<FootprintMoveResult> {
+    let board = project.board.as_mut()
+        .ok_or_else(|| AppError::InvalidOperation("Project has no board".to_string()))?;
+    board.move_footprint(&footprint_id, FootprintPoint { x, y }, rotation)?;
+    let ratsnest = match ratsnest {
+        Some(mut ratsnest) => {
+            ratsnest.update_footprints(board, &[footprint_id]);
+            ratsnest
+        }
+        None => Ratsnest::build(board),
+    };
+    project.update_modified();
+    Ok(FootprintMoveResult { project, ratsnest })
+}
+
+#[tauri::command]
+pub async fn get_net_class_assignments(project: Project) -> Result<Vec<NetClassAssignment>> {
+    Ok(BoardService::net_class_assignments(&project))
+}
+
+/// Autoroutes the open connections of the project's board.
+#[tauri::command]
+pub async fn autoroute_board(mut project: Project, options: Option<AutorouteOptions>) -> Result<AutorouteResult> {
+    tokio::task::spawn_blocking(move || {
+        let report = AutorouterService::route_project(&mut project, options.unwrap_or_default())?;
+        Ok(AutorouteResult { project, report })
+    })
+    .await
+    .map_err(|e| AppError::ThreadError(e.to_string()))?
+}
+
+/// Autoroutes project files without opening them, writing each routed copy beside the
+/// original or into `output_directory`.
+#[tauri::command]
+pub async fn autoroute_batch(
+    project_paths: Vec<String>,
+    output_directory: Option<String>,
+    options: Option<AutorouteOptions>,
+) -> Result<Vec<BatchRouteOutcome>> {
+    let paths: Vec<PathBuf> = project_paths.into_iter().map(PathBuf::from).collect();
+    let options = options.unwrap_or_default();
+    tokio::task::spawn_blocking(move || {
+        AutorouterService::route_files(&paths, output_directory.as_deref().map(std::path::Path::new), &options)
+    })
+    .await
+    .map_err(|e| AppError::ThreadError(e.to_string()))
+}
+
+/// Lists the board-side changes back-annotation would carry into the schematic.
+#[tauri::command]
+pub async fn preview_back_annotation(
+    project: Project,
+    footprint_service: State<'_, Arc<FootprintService>>,
+) -> Result<BackAnnotationReport> {
+    BackAnnotationService::preview(&project, &footprint_service)
+}
+
+/// Applies the previewed back-annotation changes of the given packages.
+#[tauri::command]
+pub async fn back_annotate(
+    mut project: Project,
+    component_ids: Vec<String>,
+    footprint_service: State<'_, Arc<FootprintService>>,
+) -> Result<BackAnnotationResult> {
+    let applied = BackAnnotationService::apply(&mut project, &footprint_service, &component_ids)?;
+    Ok(BackAnnotationResult { project, applied })
+}
+
+/// Takes the board outline and mounting holes from a mechanical DXF drawing.
+#[tauri::command]
+pub async fn import_dxf_outline(mut project: Project, path: String, options: Option<DxfImportOptions>) -> Result<DxfImportResult> {
+    let text = tokio::fs::read_to_string(&path).await?;
+    let report = DxfService::import(&mut project, &text, &options.unwrap_or_default())?;
+    Ok(DxfImportResult { project, report })
+}
+
+/// Places the selected footprints (all unlocked ones when none are selected) by connectivity.
+#[tauri::command]
+pub async fn auto_place_footprints(mut project: Project, options: Option<PlacementOptions>) -> Result<PlacementResult> {
+    tokio::task::spawn_blocking(move || {
+        let report = PlacementService::auto_place(&mut project, &options.unwrap_or_default())?;
+        Ok(PlacementResult { project, report })
+    })
+    .await
+    .map_err(|e| AppError::ThreadError(e.to_string()))?
+}
+
+#[tauri::command]
+pub async fn add_zone(mut project: Project, zone: Zone) -> Result<Project> {
+    let board = project.board.as_mut()
+        .ok_or_else(|| AppError::InvalidOperation("Project has no board".to_string()))?;
+    board.add_zone(zone)?;
+    project.update_modified();
+    Ok(project)
+}
+
+/// Refills every copper zone against the board as it is now.
+#[tauri::command]
+pub async fn fill_zones(mut project: Project) -> Result<ZoneFillResult> {
+    tokio::task::spawn_blocking(move || {
+        let board = project.board.as_mut()
+            .ok_or_else(|| AppError::InvalidOperation("Project has no board".to_string()))?;
+        let zones = ZoneFillService::fill_zones(board, &project.settings.design_rules)?;
+        project.update_modified();
+        Ok(ZoneFillResult { project, zones })
+    })
+    .await
+    .map_err(|e| AppError::ThreadError(e.to_string()))?
+}
+
+#[tauri::command]
+pub async fn clear_zone_fills(mut project: Project) -> Result<Project> {
+    let board = project.board.as_mut()
+        .ok_or_else(|| AppError::InvalidOperation("Project has no board".to_string()))?;
+    ZoneFillService::clear_fills(board);
+    project.update_modified();
+    Ok(project)
+}
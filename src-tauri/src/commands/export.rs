@@ -3,6 +3,9 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::State;
 use crate::services::project_service::ProjectService;
+use crate::services::library_service::LibraryService;
+use crate::services::part_provider::{BomPricing, FilePartProvider, PartPricingService};
+use crate::models::{DigitalWaveforms, Project, Waveforms};
 
 #[tauri::command]
 pub async fn export_to_pdf(
@@ -32,6 +35,7 @@ pub async fn export_to_svg(
     schematic_id: String,
     output_path: String,
     project_service: State<'_, Arc<ProjectService>>,
+    library_service: State<'_, Arc<LibraryService>>,
 ) -> Result<String, String> {
     let path = PathBuf::from(output_path);
     
@@ -45,7 +49,9 @@ pub async fn export_to_svg(
         .find(|s| s.id == schematic_id)
         .ok_or("Schematic not found")?;
     
-    ExportService::export_to_svg(schematic, &path)
+    // Falls back to the project's symbol cache for libraries that aren't installed
+    let symbols = library_service.component_symbols(&project, schematic);
+    ExportService::export_to_svg(schematic, &symbols, &path)
         .await
         .map_err(|e| e.to_string())
 }
@@ -76,7 +82,83 @@ pub async fn export_netlist(
         .find(|s| s.id == schematic_id)
         .ok_or("Schematic not found")?;
     
-    ExportService::export_netlist(schematic, &path, netlist_format)
+    ExportService::export_netlist(schematic, &path, netlist_format, &project.footprint_assignments)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn export_pick_and_place(
+    project: Project,
+    output_path: String,
+) -> Result<(), String> {
+    let path = PathBuf::from(output_path);
+    let board = project.board.as_ref().ok_or("Project has no board")?;
+    
+    ExportService::export_pick_and_place(board, &path)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn export_gerbers(
+    output_directory: String,
+    project_service: State<'_, Arc<ProjectService>>,
+) -> Result<Vec<String>, String> {
+    let project = project_service
+        .get_current_project()
+        .await
+        .ok_or("No project currently open")?;
+    let board = project.board.as_ref().ok_or("Project has no board")?;
+    
+    ExportService::export_to_gerber(board, &PathBuf::from(output_directory))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn export_ipc2581(
+    output_path: String,
+    project_service: State<'_, Arc<ProjectService>>,
+) -> Result<(), String> {
+    let project = project_service
+        .get_current_project()
+        .await
+        .ok_or("No project currently open")?;
+
+    ExportService::export_to_ipc2581(&project, &PathBuf::from(output_path))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn export_ipc_d356(
+    output_path: String,
+    project_service: State<'_, Arc<ProjectService>>,
+) -> Result<(), String> {
+    let project = project_service
+        .get_current_project()
+        .await
+        .ok_or("No project currently open")?;
+    let board = project.board.as_ref().ok_or("Project has no board")?;
+
+    ExportService::export_to_ipc_d356(board, &PathBuf::from(output_path))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn export_dxf(
+    output_path: String,
+    project_service: State<'_, Arc<ProjectService>>,
+) -> Result<(), String> {
+    let project = project_service
+        .get_current_project()
+        .await
+        .ok_or("No project currently open")?;
+    let board = project.board.as_ref().ok_or("Project has no board")?;
+
+    ExportService::export_to_dxf(board, &PathBuf::from(output_path))
         .await
         .map_err(|e| e.to_string())
 }
@@ -107,6 +189,70 @@ pub async fn export_bom(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn price_bom(
+    price_list_path: String,
+    build_quantity: u32,
+    currency: Option<String>,
+    project_service: State<'_, Arc<ProjectService>>,
+) -> Result<BomPricing, String> {
+    let project = project_service
+        .get_current_project()
+        .await
+        .ok_or("No project currently open")?;
+    
+    let provider = FilePartProvider::load(&PathBuf::from(price_list_path))
+        .map_err(|e| e.to_string())?;
+    PartPricingService::price_project(&project, &provider, build_quantity, currency.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn export_costed_bom(
+    output_path: String,
+    price_list_path: String,
+    build_quantity: u32,
+    currency: Option<String>,
+    project_service: State<'_, Arc<ProjectService>>,
+) -> Result<BomPricing, String> {
+    let path = PathBuf::from(output_path);
+    
+    let project = project_service
+        .get_current_project()
+        .await
+        .ok_or("No project currently open")?;
+    
+    let provider = FilePartProvider::load(&PathBuf::from(price_list_path))
+        .map_err(|e| e.to_string())?;
+    let pricing = PartPricingService::price_project(&project, &provider, build_quantity, currency.as_deref())
+        .map_err(|e| e.to_string())?;
+    
+    ExportService::export_costed_bom(&project, &path, &pricing)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(pricing)
+}
+
+#[tauri::command]
+pub async fn export_waveforms(
+    waveforms: Waveforms,
+    output_path: String,
+) -> Result<(), String> {
+    ExportService::export_waveforms_csv(&waveforms, &PathBuf::from(output_path))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn export_vcd(
+    waveforms: DigitalWaveforms,
+    output_path: String,
+) -> Result<(), String> {
+    ExportService::export_vcd(&waveforms, &PathBuf::from(output_path))
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn export_project_archive(
     _output_path: String,
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+use tauri::State;
+use crate::models::footprint::{Footprint, FootprintAssignmentRule, FootprintLibrary, PinMappingReport};
+use crate::models::Project;
+use crate::services::FootprintService;
+use crate::utils::error::Result;
+
+#[tauri::command]
+pub async fn get_footprint_libraries(
+    footprint_service: State<'_, Arc<FootprintService>>,
+) -> Result<Vec<FootprintLibrary>> {
+    Ok(footprint_service.get_all_libraries())
+}
+
+#[tauri::command]
+pub async fn get_footprint(
+    footprint_id: String,
+    footprint_service: State<'_, Arc<FootprintService>>,
+) -> Result<Option<Footprint>> {
+    Ok(footprint_service.get_footprint(&footprint_id))
+}
+
+#[tauri::command]
+pub async fn assign_footprint(
+    mut project: Project,
+    component_id: String,
+    footprint_id: String,
+    variant_id: Option<String>,
+    footprint_service: State<'_, Arc<FootprintService>>,
+) -> Result<Project> {
+    footprint_service.assign_footprint(&mut project, &component_id, &footprint_id, variant_id.as_deref())?;
+    Ok(project)
+}
+
+#[tauri::command]
+pub async fn bulk_assign_footprints(
+    mut project: Project,
+    rules: Vec<FootprintAssignmentRule>,
+    footprint_service: State<'_, Arc<FootprintService>>,
+) -> Result<Project> {
+    footprint_service.bulk_assign(&mut project, &rules)?;
+    Ok(project)
+}
+
+#[tauri::command]
+pub async fn validate_footprint_assignments(
+    project: Project,
+    footprint_service: State<'_, Arc<FootprintService>>,
+) -> Result<Vec<PinMappingReport>> {
+    Ok(footprint_service.validate_assignments(&project))
+}
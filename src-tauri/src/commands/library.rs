@@ -1,7 +1,10 @@
+use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::State;
 use crate::models::component::{ComponentLibrary, ComponentTemplate, ComponentCategory};
-use crate::services::LibraryService;
+use crate::models::Project;
+use crate::services::{ComponentUpdateReport, LibraryService, LibraryUpdateService};
+use crate::services::library_service::RescueResult;
 use crate::utils::error::Result;
 
 #[tauri::command]
@@ -75,4 +78,110 @@ pub async fn get_all_categories(
     all_categories.dedup_by(|a, b| a.id == b.id);
     
     Ok(all_categories)
-}
\ No newline at end of file
+}
+
+#[tauri::command]
+pub async fn get_library_paths(
+    library_service: State<'_, Arc<LibraryService>>,
+) -> Result<Vec<PathBuf>> {
+    Ok(library_service.get_library_paths())
+}
+
+#[tauri::command]
+pub async fn set_library_paths(
+    paths: Vec<PathBuf>,
+    library_service: State<'_, Arc<LibraryService>>,
+) -> Result<()> {
+    library_service.set_library_paths(paths)
+}
+
+#[tauri::command]
+pub async fn create_library(
+    name: String,
+    description: Option<String>,
+    project_id: Option<String>,
+    library_service: State<'_, Arc<LibraryService>>,
+) -> Result<ComponentLibrary> {
+    library_service.create_library(name, description, project_id)
+}
+
+#[tauri::command]
+pub async fn rename_library(
+    library_id: String,
+    name: String,
+    library_service: State<'_, Arc<LibraryService>>,
+) -> Result<ComponentLibrary> {
+    library_service.rename_library(&library_id, name)
+}
+
+#[tauri::command]
+pub async fn delete_library(
+    library_id: String,
+    library_service: State<'_, Arc<LibraryService>>,
+) -> Result<()> {
+    library_service.delete_library(&library_id)
+}
+
+#[tauri::command]
+pub async fn add_component_template(
+    library_id: String,
+    template: ComponentTemplate,
+    library_service: State<'_, Arc<LibraryService>>,
+) -> Result<ComponentLibrary> {
+    library_service.add_component_template(&library_id, template)
+}
+
+#[tauri::command]
+pub async fn update_component_template(
+    library_id: String,
+    template: ComponentTemplate,
+    library_service: State<'_, Arc<LibraryService>>,
+) -> Result<ComponentLibrary> {
+    library_service.update_component_template(&library_id, template)
+}
+
+#[tauri::command]
+pub async fn remove_component_template(
+    library_id: String,
+    component_id: String,
+    library_service: State<'_, Arc<LibraryService>>,
+) -> Result<ComponentLibrary> {
+    library_service.remove_component_template(&library_id, &component_id)
+}
+
+#[tauri::command]
+pub async fn check_library_updates(
+    project: Project,
+    library_service: State<'_, Arc<LibraryService>>,
+) -> Result<Vec<ComponentUpdateReport>> {
+    Ok(LibraryUpdateService::check_for_updates(&project, &library_service))
+}
+
+#[tauri::command]
+pub async fn update_components_from_library(
+    mut project: Project,
+    component_ids: Vec<String>,
+    library_service: State<'_, Arc<LibraryService>>,
+) -> Result<Project> {
+    LibraryUpdateService::apply_updates(&mut project, &library_service, &component_ids)?;
+    Ok(project)
+}
+
+#[tauri::command]
+pub async fn get_project_component_template(
+    project: Project,
+    library_id: String,
+    component_id: String,
+    library_service: State<'_, Arc<LibraryService>>,
+) -> Result<Option<ComponentTemplate>> {
+    Ok(library_service.get_project_template(&project, &library_id, &component_id))
+}
+
+#[tauri::command]
+pub async fn rescue_cached_templates(
+    mut project: Project,
+    library_service: State<'_, Arc<LibraryService>>,
+) -> Result<RescueResult> {
+    let rescued = library_service.rescue_cached_templates(&mut project)?;
+    Ok(RescueResult { project, rescued })
+}
@@ -1,11 +1,19 @@
+pub mod board;
 pub mod export;
 pub mod file;
+pub mod footprint;
 pub mod library;
 pub mod project;
 pub mod schematic;
+pub mod simulation;
+pub mod symbol;
 
+pub use board::*;
 pub use export::*;
 pub use file::*;
+pub use footprint::*;
 pub use library::*;
 pub use project::*;
-pub use schematic::*;
\ No newline at end of file
+pub use schematic::*;
+pub use simulation::*;
+pub use symbol::*;
\ No newline at end of file
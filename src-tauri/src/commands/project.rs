@@ -1,5 +1,6 @@
 use crate::models::{Project, Schematic};
 use crate::services::project_service::ProjectService;
+use crate::services::{AnnotationResult, AnnotationService, LibraryService};
 use tauri::State;
 use std::sync::Arc;
 
@@ -16,27 +17,38 @@ pub async fn create_project(name: String) -> Result<Project, String> {
 #[tauri::command]
 pub async fn open_project(
     path: String,
-    project_service: State<'_, Arc<ProjectService>>
+    project_service: State<'_, Arc<ProjectService>>,
+    library_service: State<'_, Arc<LibraryService>>
 ) -> Result<Project, String> {
     if !crate::utils::validators::validate_file_path(&path) {
         return Err("Invalid file path".to_string());
     }
     
-    project_service.load_project(&path)
+    let project = project_service.load_project(&path)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    
+    library_service.mount_project_library(&project)
+        .map_err(|e| e.to_string())?;
+    
+    Ok(project)
 }
 
 #[tauri::command]
 pub async fn save_project(
-    project: Project,
+    mut project: Project,
     path: String,
-    project_service: State<'_, Arc<ProjectService>>
+    project_service: State<'_, Arc<ProjectService>>,
+    library_service: State<'_, Arc<LibraryService>>
 ) -> Result<(), String> {
     if !crate::utils::validators::validate_file_path(&path) {
         return Err("Invalid file path".to_string());
     }
     
+    library_service.sync_project_library(&mut project)
+        .map_err(|e| e.to_string())?;
+    library_service.refresh_symbol_cache(&mut project);
+    
     project_service.save_project(&project, &path)
         .await
         .map_err(|e| e.to_string())
@@ -44,14 +56,19 @@ pub async fn save_project(
 
 #[tauri::command]
 pub async fn save_project_as(
-    project: Project,
+    mut project: Project,
     new_path: String,
-    project_service: State<'_, Arc<ProjectService>>
+    project_service: State<'_, Arc<ProjectService>>,
+    library_service: State<'_, Arc<LibraryService>>
 ) -> Result<(), String> {
     if !crate::utils::validators::validate_file_path(&new_path) {
         return Err("Invalid file path".to_string());
     }
     
+    library_service.sync_project_library(&mut project)
+        .map_err(|e| e.to_string())?;
+    library_service.refresh_symbol_cache(&mut project);
+    
     project_service.save_project(&project, &new_path)
         .await
         .map_err(|e| e.to_string())
@@ -103,4 +120,13 @@ pub async fn update_project_settings(
     project_service.update_settings(&project_id, settings)
         .await
         .map_err(|e| e.to_string())
-}
\ No newline at end of file
+}
+
+#[tauri::command]
+pub async fn annotate_project(
+    mut project: Project,
+    reset: bool,
+) -> Result<AnnotationResult, String> {
+    let changes = AnnotationService::annotate_project(&mut project, reset);
+    Ok(AnnotationResult { project, changes })
+}
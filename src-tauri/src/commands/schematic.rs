@@ -1,6 +1,9 @@
 use crate::models::{Component, Wire, Schematic, Point};
+use crate::services::LibraryService;
 use crate::utils::error::Result;
 use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::State;
 
 #[tauri::command]
 pub async fn add_component(
@@ -16,6 +19,23 @@ pub async fn add_component(
     Ok(component)
 }
 
+#[tauri::command]
+pub async fn add_component_unit(
+    library_id: String,
+    component_id: String,
+    unit_id: String,
+    x: f64,
+    y: f64,
+    library_service: State<'_, Arc<LibraryService>>,
+) -> Result<Component> {
+    crate::utils::validators::validate_coordinate_strict(x)?;
+    crate::utils::validators::validate_coordinate_strict(y)?;
+    
+    let template = library_service.get_component_template(&library_id, &component_id)
+        .ok_or_else(|| crate::utils::error::AppError::ComponentNotFound(component_id.clone()))?;
+    Component::from_template_unit(&template, &unit_id, library_id, x, y)
+}
+
 #[tauri::command]
 pub async fn update_component(
     _schematic_id: String,
@@ -103,8 +123,8 @@ pub async fn generate_netlist(schematic: Schematic) -> Result<String> {
     netlist.push_str(&format!("* Project: {}\n", schematic.name));
     netlist.push('\n');
     
-    // Add components
-    for component in &schematic.components {
+    // Add components, one line per package
+    for component in schematic.package_components() {
         netlist.push_str(&format!("{} ", component.reference));
         // Add pins/nets connections
         for pin in &component.pins {
@@ -138,8 +158,8 @@ pub async fn validate_schematic(schematic: Schematic) -> Result<ValidationResult
     // Check for duplicate references
     let mut references = std::collections::HashSet::new();
     for component in &schematic.components {
-        if !references.insert(&component.reference) {
-            errors.push(format!("Duplicate reference: {}", component.reference));
+        if !references.insert(component.display_reference()) {
+            errors.push(format!("Duplicate reference: {}", component.display_reference()));
         }
     }
     
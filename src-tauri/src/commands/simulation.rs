@@ -0,0 +1,66 @@
+use crate::models::{
+    AcSweepSettings, DigitalSettings, DigitalWaveforms, OperatingPoint, ProbeTarget, Schematic, SpiceAnalysis,
+    SpiceResult, TransientSettings, Waveforms,
+};
+use crate::services::simulation::{AcSolver, DcSolver, DigitalSimulator, NgspiceRunner, TransientSolver};
+use crate::utils::error::Result;
+
+#[tauri::command]
+pub async fn solve_operating_point(schematic: Schematic) -> Result<OperatingPoint> {
+    DcSolver::operating_point(&schematic)
+}
+
+/// Solves the schematic and stores the result on it, refreshing every probe reading.
+#[tauri::command]
+pub async fn simulate_schematic(mut schematic: Schematic) -> Result<Schematic> {
+    let operating_point = DcSolver::operating_point(&schematic)?;
+    schematic.attach_operating_point(operating_point);
+    Ok(schematic)
+}
+
+#[tauri::command]
+pub async fn run_transient(schematic: Schematic, settings: TransientSettings) -> Result<Waveforms> {
+    TransientSolver::run(&schematic, &settings)
+}
+
+#[tauri::command]
+pub async fn run_ac_sweep(schematic: Schematic, settings: AcSweepSettings) -> Result<Waveforms> {
+    AcSolver::run(&schematic, &settings)
+}
+
+/// Runs the digital simulator; stimulus from `vector_file` is added to the settings' own.
+#[tauri::command]
+pub async fn run_digital_simulation(
+    schematic: Schematic,
+    mut settings: DigitalSettings,
+    vector_file: Option<String>,
+) -> Result<DigitalWaveforms> {
+    if let Some(path) = vector_file {
+        let text = tokio::fs::read_to_string(&path).await?;
+        settings.stimuli.extend(DigitalSimulator::parse_vectors(&text)?);
+    }
+    DigitalSimulator::run(&schematic, &settings)
+}
+
+/// Runs the analysis in an external ngspice; `ngspice_path` overrides the binary found on PATH.
+#[tauri::command]
+pub async fn run_ngspice(schematic: Schematic, analysis: SpiceAnalysis, ngspice_path: Option<String>) -> Result<SpiceResult> {
+    NgspiceRunner::new(ngspice_path.map(Into::into)).run(&schematic, &analysis).await
+}
+
+#[tauri::command]
+pub async fn ngspice_version(ngspice_path: Option<String>) -> Result<String> {
+    NgspiceRunner::new(ngspice_path.map(Into::into)).version().await
+}
+
+#[tauri::command]
+pub async fn add_probe(mut schematic: Schematic, target: ProbeTarget, x: f64, y: f64) -> Result<Schematic> {
+    schematic.add_probe(target, x, y)?;
+    Ok(schematic)
+}
+
+#[tauri::command]
+pub async fn remove_probe(mut schematic: Schematic, probe_id: String) -> Result<Schematic> {
+    schematic.remove_probe(&probe_id)?;
+    Ok(schematic)
+}
@@ -0,0 +1,128 @@
+use std::sync::Arc;
+use tauri::State;
+use crate::models::component::{ComponentTemplate, DrawCommand, PinTemplate};
+use crate::services::{LibraryService, PinUpdate, SymbolEditor, SymbolValidationReport};
+use crate::utils::error::{AppError, Result};
+
+#[tauri::command]
+pub async fn add_symbol_draw_command(
+    library_id: String,
+    component_id: String,
+    command: DrawCommand,
+    library_service: State<'_, Arc<LibraryService>>,
+) -> Result<ComponentTemplate> {
+    library_service.edit_component_template(&library_id, &component_id, |template| {
+        SymbolEditor::add_draw_command(template, command).map(|_| ())
+    })
+}
+
+#[tauri::command]
+pub async fn move_symbol_draw_command(
+    library_id: String,
+    component_id: String,
+    index: usize,
+    dx: f64,
+    dy: f64,
+    library_service: State<'_, Arc<LibraryService>>,
+) -> Result<ComponentTemplate> {
+    library_service.edit_component_template(&library_id, &component_id, |template| {
+        SymbolEditor::move_draw_command(template, index, dx, dy)
+    })
+}
+
+#[tauri::command]
+pub async fn update_symbol_draw_command(
+    library_id: String,
+    component_id: String,
+    index: usize,
+    command: DrawCommand,
+    library_service: State<'_, Arc<LibraryService>>,
+) -> Result<ComponentTemplate> {
+    library_service.edit_component_template(&library_id, &component_id, |template| {
+        SymbolEditor::replace_draw_command(template, index, command)
+    })
+}
+
+#[tauri::command]
+pub async fn delete_symbol_draw_command(
+    library_id: String,
+    component_id: String,
+    index: usize,
+    library_service: State<'_, Arc<LibraryService>>,
+) -> Result<ComponentTemplate> {
+    library_service.edit_component_template(&library_id, &component_id, |template| {
+        SymbolEditor::delete_draw_command(template, index).map(|_| ())
+    })
+}
+
+#[tauri::command]
+pub async fn add_symbol_pin(
+    library_id: String,
+    component_id: String,
+    pin: PinTemplate,
+    library_service: State<'_, Arc<LibraryService>>,
+) -> Result<ComponentTemplate> {
+    library_service.edit_component_template(&library_id, &component_id, |template| {
+        SymbolEditor::add_pin(template, pin)
+    })
+}
+
+#[tauri::command]
+pub async fn update_symbol_pin(
+    library_id: String,
+    component_id: String,
+    pin_id: String,
+    update: PinUpdate,
+    library_service: State<'_, Arc<LibraryService>>,
+) -> Result<ComponentTemplate> {
+    library_service.edit_component_template(&library_id, &component_id, |template| {
+        SymbolEditor::update_pin(template, &pin_id, update)
+    })
+}
+
+#[tauri::command]
+pub async fn remove_symbol_pin(
+    library_id: String,
+    component_id: String,
+    pin_id: String,
+    library_service: State<'_, Arc<LibraryService>>,
+) -> Result<ComponentTemplate> {
+    library_service.edit_component_template(&library_id, &component_id, |template| {
+        SymbolEditor::remove_pin(template, &pin_id).map(|_| ())
+    })
+}
+
+#[tauri::command]
+pub async fn reorder_symbol_pins(
+    library_id: String,
+    component_id: String,
+    pin_ids: Vec<String>,
+    library_service: State<'_, Arc<LibraryService>>,
+) -> Result<ComponentTemplate> {
+    library_service.edit_component_template(&library_id, &component_id, |template| {
+        SymbolEditor::reorder_pins(template, &pin_ids)
+    })
+}
+
+#[tauri::command]
+pub async fn recompute_symbol_bounds(
+    library_id: String,
+    component_id: String,
+    library_service: State<'_, Arc<LibraryService>>,
+) -> Result<ComponentTemplate> {
+    library_service.edit_component_template(&library_id, &component_id, |template| {
+        SymbolEditor::recompute_bounds(template);
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn validate_symbol(
+    library_id: String,
+    component_id: String,
+    library_service: State<'_, Arc<LibraryService>>,
+) -> Result<SymbolValidationReport> {
+    let template = library_service.get_component_template(&library_id, &component_id)
+        .ok_or(AppError::ComponentNotFound(component_id))?;
+    Ok(SymbolEditor::validate(&template))
+}
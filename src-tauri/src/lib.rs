@@ -4,8 +4,9 @@ pub mod models;
 pub mod services;
 pub mod utils;
 
+use std::path::PathBuf;
 use std::sync::Arc;
-use services::{ProjectService, LibraryService};
+use services::{AutorouteOptions, AutorouterService, ProjectService, LibraryService, FootprintService};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -13,6 +14,7 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .manage(Arc::new(ProjectService::new()))
         .manage(Arc::new(LibraryService::new()))
+        .manage(Arc::new(FootprintService::new()))
         .invoke_handler(tauri::generate_handler![
             // Project commands
             commands::project::create_project,
@@ -23,6 +25,7 @@ pub fn run() {
             commands::project::add_schematic_to_project,
             commands::project::get_project_info,
             commands::project::update_project_settings,
+            commands::project::annotate_project,
             
             // File commands
             commands::file::show_open_dialog,
@@ -35,6 +38,7 @@ pub fn run() {
             
             // Schematic commands
             commands::schematic::add_component,
+            commands::schematic::add_component_unit,
             commands::schematic::update_component,
             commands::schematic::delete_component,
             commands::schematic::add_wire,
@@ -52,12 +56,80 @@ pub fn run() {
             commands::library::get_components_by_category,
             commands::library::get_library_categories,
             commands::library::get_all_categories,
+            commands::library::get_library_paths,
+            commands::library::set_library_paths,
+            commands::library::create_library,
+            commands::library::rename_library,
+            commands::library::delete_library,
+            commands::library::add_component_template,
+            commands::library::update_component_template,
+            commands::library::remove_component_template,
+            commands::library::check_library_updates,
+            commands::library::update_components_from_library,
+            commands::library::get_project_component_template,
+            commands::library::rescue_cached_templates,
+            
+            // Symbol editor commands
+            commands::symbol::add_symbol_draw_command,
+            commands::symbol::move_symbol_draw_command,
+            commands::symbol::update_symbol_draw_command,
+            commands::symbol::delete_symbol_draw_command,
+            commands::symbol::add_symbol_pin,
+            commands::symbol::update_symbol_pin,
+            commands::symbol::remove_symbol_pin,
+            commands::symbol::reorder_symbol_pins,
+            commands::symbol::recompute_symbol_bounds,
+            commands::symbol::validate_symbol,
+            
+            // Simulation commands
+            commands::simulation::solve_operating_point,
+            commands::simulation::simulate_schematic,
+            commands::simulation::run_transient,
+            commands::simulation::run_ac_sweep,
+            commands::simulation::run_digital_simulation,
+            commands::simulation::run_ngspice,
+            commands::simulation::ngspice_version,
+            commands::simulation::add_probe,
+            commands::simulation::remove_probe,
+            
+            // Footprint commands
+            commands::footprint::get_footprint_libraries,
+            commands::footprint::get_footprint,
+            commands::footprint::assign_footprint,
+            commands::footprint::bulk_assign_footprints,
+            commands::footprint::validate_footprint_assignments,
+            
+            // Board commands
+            commands::board::create_board,
+            commands::board::update_pcb_from_schematic,
+            commands::board::run_drc_check,
+            commands::board::compute_ratsnest,
+            commands::board::move_footprint,
+            commands::board::get_net_class_assignments,
+            commands::board::autoroute_board,
+            commands::board::autoroute_batch,
+            commands::board::auto_place_footprints,
+            commands::board::preview_back_annotation,
+            commands::board::back_annotate,
+            commands::board::import_dxf_outline,
+            commands::board::add_zone,
+            commands::board::fill_zones,
+            commands::board::clear_zone_fills,
             
             // Export commands
             commands::export::export_to_pdf,
             commands::export::export_to_svg,
             commands::export::export_netlist,
             commands::export::export_bom,
+            commands::export::price_bom,
+            commands::export::export_costed_bom,
+            commands::export::export_waveforms,
+            commands::export::export_vcd,
+            commands::export::export_pick_and_place,
+            commands::export::export_gerbers,
+            commands::export::export_ipc2581,
+            commands::export::export_ipc_d356,
+            commands::export::export_dxf,
             commands::export::export_project_archive,
             
             // ERC commands
@@ -66,4 +138,50 @@ pub fn run() {
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
-}
\ No newline at end of file
+}
+
+/// Headless autorouting for `rust-eda --autoroute [--output-dir DIR] PROJECT...`. Prints a
+/// line per project and returns the process exit code: non-zero when any project failed or
+/// left nets unrouted. Windows release builds have no console of their own, so `main`
+/// attaches to the one the command was started from before calling this.
+pub fn run_autoroute_batch(args: &[String]) -> i32 {
+    let mut output_directory: Option<PathBuf> = None;
+    let mut paths: Vec<PathBuf> = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--output-dir" => output_directory = args.next().map(PathBuf::from),
+            path => paths.push(PathBuf::from(path)),
+        }
+    }
+    if paths.is_empty() {
+        eprintln!("usage: rust-eda --autoroute [--output-dir DIR] PROJECT...");
+        return 2;
+    }
+
+    let mut exit_code = 0;
+    for outcome in AutorouterService::route_files(&paths, output_directory.as_deref(), &AutorouteOptions::default()) {
+        match (outcome.report, outcome.error) {
+            (Some(report), _) => {
+                println!(
+                    "{}: {} nets routed, {} unroutable, {} tracks, {} vias -> {}",
+                    outcome.project_path,
+                    report.routed_nets.len(),
+                    report.unroutable.len(),
+                    report.tracks_added,
+                    report.vias_added,
+                    outcome.output_path.unwrap_or_default(),
+                );
+                for net in &report.unroutable {
+                    println!("  unroutable: {} ({} open connections)", net.net_name, net.open_connections);
+                    exit_code = 1;
+                }
+            }
+            (None, error) => {
+                eprintln!("{}: {}", outcome.project_path, error.unwrap_or_default());
+                exit_code = 1;
+            }
+        }
+    }
+    exit_code
+}
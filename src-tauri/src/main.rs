@@ -2,5 +2,25 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("--autoroute") {
+        #[cfg(windows)]
+        attach_parent_console();
+        std::process::exit(rust_eda_lib::run_autoroute_batch(&args[1..]));
+    }
     rust_eda_lib::run()
 }
+
+// The GUI subsystem starts without a console, which would swallow the batch output; borrow
+// the console of the shell that ran us. Fails harmlessly when there is none.
+#[cfg(windows)]
+fn attach_parent_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
@@ -0,0 +1,690 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+use crate::models::footprint::{
+    Footprint, FootprintPoint, GraphicElement, GraphicType, KeepoutArea, LayerType, Pad, PadShape, PadType, ThermalRelief,
+};
+use crate::utils::geometry::{polygon_area, Shape, Vec2};
+use crate::utils::{error::AppError, validators};
+use crate::utils::error::Result;
+
+const MAX_COPPER_LAYERS: usize = 32;
+const DEFAULT_BOARD_THICKNESS: f64 = 1.6;
+const COPPER_THICKNESS: f64 = 0.035;
+const SOLDER_MASK_THICKNESS: f64 = 0.01;
+const DEFAULT_ZONE_CLEARANCE: f64 = 0.3;
+const DEFAULT_ZONE_MIN_WIDTH: f64 = 0.25;
+// Segments used for circles and arcs when they are flattened
+const ARC_SEGMENTS: usize = 64;
+
+// All board coordinates and sizes are millimetres; rotations are degrees
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Board {
+    pub id: String,
+    pub name: String,
+    pub stackup: LayerStackup,
+    pub outline: Vec<GraphicElement>,
+    pub footprints: Vec<FootprintInstance>,
+    pub nets: Vec<BoardNet>,
+    pub tracks: Vec<Track>,
+    pub vias: Vec<Via>,
+    pub zones: Vec<Zone>,
+    pub keepouts: Vec<KeepoutArea>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayerStackup {
+    // Physical layers from the top surface down
+    pub layers: Vec<StackupLayer>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StackupLayer {
+    pub name: String,
+    pub kind: StackupLayerKind,
+    pub layer: Option<LayerType>,
+    pub thickness: f64,
+    pub material: Option<String>,
+    pub dielectric_constant: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum StackupLayerKind {
+    SolderMask,
+    Copper,
+    Dielectric,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PlacementSide {
+    Top,
+    Bottom,
+}
+
+/// A footprint placed on the board for one schematic package. The footprint is copied
+/// in so the board stays self-contained when libraries change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FootprintInstance {
+    pub id: String,
+    pub component_id: String,
+    pub reference: String,
+    pub value: String,
+    pub footprint_id: String,
+    pub footprint: Footprint,
+    pub position: FootprintPoint,
+    pub rotation: f64,
+    pub side: PlacementSide,
+    pub locked: bool,
+    pub pad_nets: HashMap<String, String>, // pad_number -> board net id
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BoardNet {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Track {
+    pub id: String,
+    pub net_id: Option<String>,
+    pub layer: LayerType,
+    pub start: FootprintPoint,
+    pub end: FootprintPoint,
+    pub width: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Via {
+    pub id: String,
+    pub net_id: Option<String>,
+    pub position: FootprintPoint,
+    pub diameter: f64,
+    pub drill: f64,
+    pub start_layer: LayerType,
+    pub end_layer: LayerType,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Zone {
+    pub id: String,
+    pub net_id: Option<String>,
+    pub layer: LayerType,
+    pub outline: Vec<FootprintPoint>,
+    pub clearance: f64,
+    pub min_width: f64,
+    pub priority: u32,
+    pub thermal_relief: Option<ThermalRelief>, // for pads without their own
+    #[serde(default)]
+    pub fill: Vec<FilledPolygon>, // empty until the zone is filled
+}
+
+/// Copper of a filled zone: a polygon, or a thermal spoke when there are two points, drawn
+/// with a round pen `width` wide so every part of the fill is at least that wide.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FilledPolygon {
+    pub points: Vec<FootprintPoint>,
+    pub width: f64,
+}
+
+impl LayerStackup {
+    /// Evenly spaced copper layers with the dielectric filling the rest of `board_thickness`.
+    pub fn new(copper_count: usize, board_thickness: f64) -> Result<Self> {
+        if copper_count == 0 || copper_count > MAX_COPPER_LAYERS || (copper_count > 1 && !copper_count.is_multiple_of(2)) {
+            return Err(AppError::InvalidInput(format!(
+                "Copper layer count must be 1 or an even number up to {}", MAX_COPPER_LAYERS
+            )));
+        }
+        let copper_total = copper_count as f64 * COPPER_THICKNESS;
+        let dielectric_count = copper_count.saturating_sub(1).max(1);
+        if !board_thickness.is_finite() || board_thickness <= copper_total + 2.0 * SOLDER_MASK_THICKNESS {
+            return Err(AppError::InvalidInput("Board is too thin for its copper layers".to_string()));
+        }
+        let dielectric = (board_thickness - copper_total - 2.0 * SOLDER_MASK_THICKNESS) / dielectric_count as f64;
+
+        let mask = |name: &str, layer: LayerType| StackupLayer {
+            name: name.to_string(),
+            kind: StackupLayerKind::SolderMask,
+            layer: Some(layer),
+            thickness: SOLDER_MASK_THICKNESS,
+            material: None,
+            dielectric_constant: Some(3.3),
+        };
+        let copper = |name: String, layer: LayerType| StackupLayer {
+            name,
+            kind: StackupLayerKind::Copper,
+            layer: Some(layer),
+            thickness: COPPER_THICKNESS,
+            material: None,
+            dielectric_constant: None,
+        };
+        let core = |index: usize| StackupLayer {
+            name: format!("Dielectric {}", index),
+            kind: StackupLayerKind::Dielectric,
+            layer: None,
+            thickness: dielectric,
+            material: Some("FR4".to_string()),
+            dielectric_constant: Some(4.5),
+        };
+
+        let mut layers = vec![mask("Top Solder Mask", LayerType::TopSolderMask)];
+        if copper_count == 1 {
+            layers.push(copper("Top Copper".to_string(), LayerType::TopCopper));
+            layers.push(core(1));
+        } else {
+            for (index, layer) in copper_sequence(copper_count).into_iter().enumerate() {
+                if index > 0 {
+                    layers.push(core(index));
+                }
+                let name = match layer {
+                    LayerType::TopCopper => "Top Copper".to_string(),
+                    LayerType::BottomCopper => "Bottom Copper".to_string(),
+                    LayerType::InnerCopper(n) => format!("Inner {}", n),
+                    _ => unreachable!(),
+                };
+                layers.push(copper(name, layer));
+            }
+        }
+        layers.push(mask("Bottom Solder Mask", LayerType::BottomSolderMask));
+
+        Ok(Self { layers })
+    }
+
+    /// Copper layers from top to bottom.
+    pub fn copper_layers(&self) -> Vec<LayerType> {
+        self.layers.iter()
+            .filter(|l| l.kind == StackupLayerKind::Copper)
+            .filter_map(|l| l.layer.clone())
+            .collect()
+    }
+
+    pub fn copper_count(&self) -> usize {
+        self.copper_layers().len()
+    }
+
+    pub fn has_layer(&self, layer: &LayerType) -> bool {
+        !layer.is_copper() || self.copper_layers().contains(layer)
+    }
+
+    pub fn thickness(&self) -> f64 {
+        self.layers.iter().map(|l| l.thickness).sum()
+    }
+}
+
+impl Default for LayerStackup {
+    fn default() -> Self {
+        Self::new(2, DEFAULT_BOARD_THICKNESS).expect("default stackup is valid")
+    }
+}
+
+fn copper_sequence(copper_count: usize) -> Vec<LayerType> {
+    let mut layers = vec![LayerType::TopCopper];
+    layers.extend((1..copper_count - 1).map(|n| LayerType::InnerCopper(n as u8)));
+    layers.push(LayerType::BottomCopper);
+    layers
+}
+
+impl FootprintInstance {
+    pub fn new(component_id: String, reference: String, value: String, footprint: Footprint, position: FootprintPoint) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            component_id,
+            reference,
+            value,
+            footprint_id: footprint.id.clone(),
+            footprint,
+            position,
+            rotation: 0.0,
+            side: PlacementSide::Top,
+            locked: false,
+            pad_nets: HashMap::new(),
+        }
+    }
+
+    /// Maps a point from footprint coordinates to board coordinates. Bottom-side parts are
+    /// mirrored in x before rotating, as seen from the top.
+    pub fn transform(&self, point: FootprintPoint) -> FootprintPoint {
+        let x = match self.side {
+            PlacementSide::Top => point.x,
+            PlacementSide::Bottom => -point.x,
+        };
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        FootprintPoint {
+            x: self.position.x + x * cos - point.y * sin,
+            y: self.position.y + x * sin + point.y * cos,
+        }
+    }
+
+    pub fn pad_position(&self, pad: &Pad) -> FootprintPoint {
+        self.transform(FootprintPoint { x: pad.position.x, y: pad.position.y })
+    }
+
+    /// Board rotation of a pad: the footprint's plus the pad's own, mirrored on the bottom.
+    pub fn pad_rotation(&self, pad: &Pad) -> f64 {
+        match self.side {
+            PlacementSide::Top => self.rotation + pad.position.rotation,
+            PlacementSide::Bottom => self.rotation - pad.position.rotation,
+        }
+    }
+
+    /// Board position of a point given in the pad's own frame.
+    pub fn pad_point(&self, pad: &Pad, x: f64, y: f64) -> FootprintPoint {
+        let (sin, cos) = pad.position.rotation.to_radians().sin_cos();
+        self.transform(FootprintPoint {
+            x: pad.position.x + x * cos - y * sin,
+            y: pad.position.y + x * sin + y * cos,
+        })
+    }
+
+    /// Copper outline of a pad on the board.
+    pub fn pad_shape(&self, pad: &Pad) -> Shape {
+        let (w, h) = (pad.size.width / 2.0, pad.size.height / 2.0);
+        let point = |x: f64, y: f64| {
+            let p = self.pad_point(pad, x, y);
+            (p.x, p.y)
+        };
+        let rectangle = |w: f64, h: f64| vec![point(-w, -h), point(w, -h), point(w, h), point(-w, h)];
+
+        match &pad.shape {
+            PadShape::Circle => Shape::circle(point(0.0, 0.0), w),
+            PadShape::Rectangle => Shape::polygon(rectangle(w, h)),
+            PadShape::RoundedRectangle { radius } => {
+                let radius = radius.clamp(0.0, w.min(h));
+                Shape { points: rectangle(w - radius, h - radius), radius }
+            }
+            PadShape::Oval if w >= h => Shape::segment(point(h - w, 0.0), point(w - h, 0.0), h),
+            PadShape::Oval => Shape::segment(point(0.0, w - h), point(0.0, h - w), w),
+            PadShape::Trapezoid { delta } => {
+                let (top, bottom) = (w - delta / 2.0, w + delta / 2.0);
+                Shape::polygon(vec![point(-bottom, -h), point(bottom, -h), point(top, h), point(-top, h)])
+            }
+            PadShape::Custom(points) => Shape::polygon(points.iter().map(|p| point(p.x, p.y)).collect()),
+        }
+    }
+
+    /// Copper layers of the stackup the pad occupies; plated holes reach every layer.
+    pub fn pad_copper_layers(&self, pad: &Pad, stackup: &LayerStackup) -> Vec<LayerType> {
+        let plated = pad.drill.is_some() && matches!(pad.pad_type, PadType::ThroughHole | PadType::Castellated | PadType::Via);
+        let layers = self.pad_layers(pad);
+        stackup.copper_layers().into_iter()
+            .filter(|l| layers.contains(l) || (plated && matches!(l, LayerType::InnerCopper(_))))
+            .collect()
+    }
+
+    /// Courtyard areas on the board; rectangles and polygons become polygons.
+    pub fn courtyard(&self) -> Vec<Shape> {
+        let point = |p: &FootprintPoint| {
+            let p = self.transform(*p);
+            (p.x, p.y)
+        };
+        self.footprint.courtyard.iter().filter_map(|g| match &g.element_type {
+            GraphicType::Rectangle { top_left, bottom_right } => Some(Shape::polygon(vec![
+                point(top_left),
+                point(&FootprintPoint { x: bottom_right.x, y: top_left.y }),
+                point(bottom_right),
+                point(&FootprintPoint { x: top_left.x, y: bottom_right.y }),
+            ])),
+            GraphicType::Polygon { points } if points.len() >= 3 => Some(Shape::polygon(points.iter().map(point).collect())),
+            GraphicType::Circle { center, radius } => Some(Shape::circle(point(center), *radius)),
+            _ => None,
+        }).collect()
+    }
+
+    /// The footprint's own keepout, moved onto the board.
+    pub fn keepout(&self) -> Option<KeepoutArea> {
+        self.footprint.keepout.as_ref().map(|keepout| KeepoutArea {
+            area: keepout.area.iter().map(|p| self.transform(*p)).collect(),
+            layers: keepout.layers.iter().map(|l| self.board_layer(l)).collect(),
+            restrictions: keepout.restrictions.clone(),
+        })
+    }
+
+    /// Board layer for a layer of the footprint definition.
+    pub fn board_layer(&self, layer: &LayerType) -> LayerType {
+        match self.side {
+            PlacementSide::Top => layer.clone(),
+            PlacementSide::Bottom => layer.flipped(),
+        }
+    }
+
+    pub fn pad_layers(&self, pad: &Pad) -> Vec<LayerType> {
+        pad.layers.iter().map(|l| self.board_layer(l)).collect()
+    }
+
+    pub fn pad_net(&self, pad_number: &str) -> Option<&str> {
+        self.pad_nets.get(pad_number).map(|s| s.as_str())
+    }
+}
+
+impl Board {
+    /// A two-layer 100 x 80 mm board.
+    pub fn new(name: String) -> Result<Self> {
+        if !validators::validate_string_length(&name, 100) {
+            return Err(AppError::InvalidInput("Invalid board name".to_string()));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4().to_string(),
+            name,
+            stackup: LayerStackup::default(),
+            outline: rectangular_outline(100.0, 80.0),
+            footprints: Vec::new(),
+            nets: Vec::new(),
+            tracks: Vec::new(),
+            vias: Vec::new(),
+            zones: Vec::new(),
+            keepouts: Vec::new(),
+        })
+    }
+
+    pub fn set_outline(&mut self, outline: Vec<GraphicElement>) -> Result<()> {
+        if outline.iter().any(|g| g.layer != LayerType::EdgeCuts) {
+            return Err(AppError::InvalidInput("Board outline must be drawn on EdgeCuts".to_string()));
+        }
+        self.outline = outline;
+        Ok(())
+    }
+
+    pub fn set_rectangular_outline(&mut self, width: f64, height: f64) -> Result<()> {
+        if width <= 0.0 || height <= 0.0 || !validators::validate_coordinate(width) || !validators::validate_coordinate(height) {
+            return Err(AppError::InvalidInput("Invalid board size".to_string()));
+        }
+        self.outline = rectangular_outline(width, height);
+        Ok(())
+    }
+
+    /// Bounding box of the outline as (min x, min y, max x, max y).
+    pub fn outline_bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        let points: Vec<FootprintPoint> = self.outline.iter().flat_map(|g| match &g.element_type {
+            GraphicType::Line { start, end } => vec![*start, *end],
+            GraphicType::Rectangle { top_left, bottom_right } => vec![*top_left, *bottom_right],
+            GraphicType::Polygon { points } => points.clone(),
+            GraphicType::Circle { center, radius } => vec![
+                FootprintPoint { x: center.x - radius, y: center.y - radius },
+                FootprintPoint { x: center.x + radius, y: center.y + radius },
+            ],
+            GraphicType::Arc { center, start, .. } => {
+                let radius = ((start.x - center.x).powi(2) + (start.y - center.y).powi(2)).sqrt();
+                vec![
+                    FootprintPoint { x: center.x - radius, y: center.y - radius },
+                    FootprintPoint { x: center.x + radius, y: center.y + radius },
+                ]
+            }
+            GraphicType::Text { .. } => Vec::new(),
+        }).collect();
+
+        let first = points.first()?;
+        Some(points.iter().fold((first.x, first.y, first.x, first.y), |(x0, y0, x1, y1), p| {
+            (x0.min(p.x), y0.min(p.y), x1.max(p.x), y1.max(p.y))
+        }))
+    }
+
+    /// Outline drawn as straight segments, with circles and arcs flattened.
+    pub fn outline_segments(&self) -> Vec<(Vec2, Vec2)> {
+        let mut segments = Vec::new();
+        for graphic in &self.outline {
+            let points = match &graphic.element_type {
+                GraphicType::Line { start, end } => vec![(start.x, start.y), (end.x, end.y)],
+                GraphicType::Rectangle { top_left, bottom_right } => vec![
+                    (top_left.x, top_left.y),
+                    (bottom_right.x, top_left.y),
+                    (bottom_right.x, bottom_right.y),
+                    (top_left.x, bottom_right.y),
+                    (top_left.x, top_left.y),
+                ],
+                GraphicType::Polygon { points } => points.iter()
+                    .chain(points.first())
+                    .map(|p| (p.x, p.y))
+                    .collect(),
+                GraphicType::Circle { center, radius } => arc_points((center.x, center.y), (center.x + radius, center.y), 360.0),
+                GraphicType::Arc { center, start, angle } => arc_points((center.x, center.y), (start.x, start.y), *angle),
+                GraphicType::Text { .. } => Vec::new(),
+            };
+            segments.extend(points.windows(2).map(|w| (w[0], w[1])));
+        }
+        segments
+    }
+
+    /// The outline as closed polygons, chaining segments end to end: the outer board edge
+    /// first, then any cutouts inside it. `None` when a contour doesn't close.
+    pub fn outline_contours(&self) -> Option<Vec<Vec<Vec2>>> {
+        const TOLERANCE: f64 = 1e-3;
+        let near = |p: Vec2, q: Vec2| (p.0 - q.0).abs() < TOLERANCE && (p.1 - q.1).abs() < TOLERANCE;
+        let mut remaining: Vec<(Vec2, Vec2)> = self.outline_segments().into_iter()
+            .filter(|&(a, b)| !near(a, b))
+            .collect();
+        let mut contours = Vec::new();
+        while let Some((start, mut current)) = remaining.pop() {
+            let mut polygon = vec![start];
+            while !near(current, start) {
+                let index = remaining.iter().position(|&(a, b)| near(a, current) || near(b, current))?;
+                let (a, b) = remaining.swap_remove(index);
+                polygon.push(current);
+                current = if near(a, current) { b } else { a };
+            }
+            if polygon.len() < 3 {
+                return None;
+            }
+            contours.push(polygon);
+        }
+        // The outer edge encloses the most area
+        contours.sort_by(|a, b| polygon_area(b).abs().total_cmp(&polygon_area(a).abs()));
+        (!contours.is_empty()).then_some(contours)
+    }
+
+    /// The outer board edge as one closed polygon; `None` when the outline doesn't close.
+    /// Cutouts are left out, see [`Board::outline_contours`].
+    pub fn outline_polygon(&self) -> Option<Vec<Vec2>> {
+        self.outline_contours().map(|mut contours| contours.swap_remove(0))
+    }
+
+    pub fn get_footprint(&self, id: &str) -> Option<&FootprintInstance> {
+        self.footprints.iter().find(|f| f.id == id)
+    }
+
+    pub fn get_footprint_mut(&mut self, id: &str) -> Option<&mut FootprintInstance> {
+        self.footprints.iter_mut().find(|f| f.id == id)
+    }
+
+    pub fn move_footprint(&mut self, id: &str, position: FootprintPoint, rotation: f64) -> Result<()> {
+        let instance = self.get_footprint_mut(id)
+            .ok_or_else(|| AppError::ComponentNotFound(format!("Footprint {} is not on the board", id)))?;
+        if instance.locked {
+            return Err(AppError::InvalidOperation(format!("{} is locked", instance.reference)));
+        }
+        instance.position = position;
+        instance.rotation = rotation.rem_euclid(360.0);
+        Ok(())
+    }
+
+    pub fn footprint_for_component(&self, component_id: &str) -> Option<&FootprintInstance> {
+        self.footprints.iter().find(|f| f.component_id == component_id)
+    }
+
+    pub fn get_net(&self, net_id: &str) -> Option<&BoardNet> {
+        self.nets.iter().find(|n| n.id == net_id)
+    }
+
+    pub fn add_track(&mut self, track: Track) -> Result<()> {
+        if !track.layer.is_copper() || !self.stackup.has_layer(&track.layer) {
+            return Err(AppError::InvalidInput(format!("Tracks need a copper layer of this board, got {:?}", track.layer)));
+        }
+        if track.width.is_nan() || track.width <= 0.0 {
+            return Err(AppError::InvalidInput("Track width must be positive".to_string()));
+        }
+        self.tracks.push(track);
+        Ok(())
+    }
+
+    pub fn add_via(&mut self, via: Via) -> Result<()> {
+        for layer in [&via.start_layer, &via.end_layer] {
+            if !layer.is_copper() || !self.stackup.has_layer(layer) {
+                return Err(AppError::InvalidInput(format!("Via layer {:?} is not a copper layer of this board", layer)));
+            }
+        }
+        if via.drill.is_nan() || via.drill <= 0.0 || via.diameter.is_nan() || via.diameter <= via.drill {
+            return Err(AppError::InvalidInput("Via diameter must exceed its drill".to_string()));
+        }
+        self.vias.push(via);
+        Ok(())
+    }
+
+    pub fn add_zone(&mut self, zone: Zone) -> Result<()> {
+        if !zone.layer.is_copper() || !self.stackup.has_layer(&zone.layer) {
+            return Err(AppError::InvalidInput(format!("Zones need a copper layer of this board, got {:?}", zone.layer)));
+        }
+        if zone.outline.len() < 3 {
+            return Err(AppError::InvalidInput("Zone outline needs at least three points".to_string()));
+        }
+        self.zones.push(zone);
+        Ok(())
+    }
+}
+
+// Points along an arc turning `angle` degrees from `start` around `center`
+fn arc_points(center: Vec2, start: Vec2, angle: f64) -> Vec<Vec2> {
+    let steps = ((angle.abs() / 360.0 * ARC_SEGMENTS as f64).ceil() as usize).max(1);
+    let (dx, dy) = (start.0 - center.0, start.1 - center.1);
+    (0..=steps).map(|i| {
+        let (sin, cos) = (angle * i as f64 / steps as f64).to_radians().sin_cos();
+        (center.0 + dx * cos - dy * sin, center.1 + dx * sin + dy * cos)
+    }).collect()
+}
+
+fn rectangular_outline(width: f64, height: f64) -> Vec<GraphicElement> {
+    vec![GraphicElement {
+        element_type: GraphicType::Rectangle {
+            top_left: FootprintPoint { x: 0.0, y: 0.0 },
+            bottom_right: FootprintPoint { x: width, y: height },
+        },
+        layer: LayerType::EdgeCuts,
+        width: 0.1,
+    }]
+}
+
+impl Zone {
+    pub fn new(net_id: Option<String>, layer: LayerType, outline: Vec<FootprintPoint>) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            net_id,
+            layer,
+            outline,
+            clearance: DEFAULT_ZONE_CLEARANCE,
+            min_width: DEFAULT_ZONE_MIN_WIDTH,
+            priority: 0,
+            thermal_relief: None,
+            fill: Vec::new(),
+        }
+    }
+
+    pub fn is_filled(&self) -> bool {
+        !self.fill.is_empty()
+    }
+}
+
+impl FilledPolygon {
+    pub fn shape(&self) -> Shape {
+        Shape { points: self.points.iter().map(|p| (p.x, p.y)).collect(), radius: self.width / 2.0 }
+    }
+}
+
+impl Track {
+    pub fn new(net_id: Option<String>, layer: LayerType, start: FootprintPoint, end: FootprintPoint, width: f64) -> Self {
+        Self { id: Uuid::new_v4().to_string(), net_id, layer, start, end, width }
+    }
+
+    pub fn length(&self) -> f64 {
+        ((self.end.x - self.start.x).powi(2) + (self.end.y - self.start.y).powi(2)).sqrt()
+    }
+}
+
+impl Via {
+    /// A through via spanning the outer copper layers.
+    pub fn new(net_id: Option<String>, position: FootprintPoint, diameter: f64, drill: f64) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            net_id,
+            position,
+            diameter,
+            drill,
+            start_layer: LayerType::TopCopper,
+            end_layer: LayerType::BottomCopper,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::geometry::point_in_contours;
+
+    #[test]
+    fn test_stackup_and_placement() {
+        let four = LayerStackup::new(4, 1.6).unwrap();
+        assert_eq!(four.copper_layers(), vec![
+            LayerType::TopCopper,
+            LayerType::InnerCopper(1),
+            LayerType::InnerCopper(2),
+            LayerType::BottomCopper,
+        ]);
+        assert!((four.thickness() - 1.6).abs() < 1e-9);
+        assert!(LayerStackup::new(3, 1.6).is_err());
+
+        let mut board = Board::new("Main".to_string()).unwrap();
+        assert_eq!(board.outline_bounds(), Some((0.0, 0.0, 100.0, 80.0)));
+        let point = FootprintPoint { x: 1.0, y: 1.0 };
+        assert!(board.add_track(Track::new(None, LayerType::InnerCopper(1), point, point, 0.2)).is_err());
+        assert!(board.add_track(Track::new(None, LayerType::BottomCopper, point, point, 0.2)).is_ok());
+
+        let footprint = crate::services::FootprintService::new().get_footprint("R_0603").unwrap();
+        let pad = footprint.get_pad("1").unwrap().clone();
+        let mut instance = FootprintInstance::new("c1".to_string(), "R1".to_string(), "10k".to_string(), footprint, FootprintPoint { x: 10.0, y: 10.0 });
+        instance.rotation = 90.0;
+        instance.side = PlacementSide::Bottom;
+        // Pad 1 sits at -x; mirrored to +x, then turned onto +y
+        let position = instance.pad_position(&pad);
+        assert!((position.x - 10.0).abs() < 1e-9 && (position.y - (10.0 + pad.position.x.abs())).abs() < 1e-9);
+        assert_eq!(instance.pad_layers(&pad)[0], LayerType::BottomCopper);
+    }
+
+    #[test]
+    fn test_outline_contours() {
+        let mut board = Board::new("Main".to_string()).unwrap();
+        let edge = |element_type| GraphicElement { element_type, layer: LayerType::EdgeCuts, width: 0.1 };
+        board.outline.push(edge(GraphicType::Circle { center: FootprintPoint { x: 50.0, y: 40.0 }, radius: 5.0 }));
+        board.outline.push(edge(GraphicType::Polygon {
+            points: vec![
+                FootprintPoint { x: 12.0, y: 12.0 },
+                FootprintPoint { x: 18.0, y: 12.0 },
+                FootprintPoint { x: 18.0, y: 18.0 },
+                FootprintPoint { x: 12.0, y: 18.0 },
+            ],
+        }));
+
+        // Largest first, so the board edge leads whatever order it was drawn in
+        let contours = board.outline_contours().unwrap();
+        assert_eq!(contours.len(), 3);
+        assert_eq!(contours[0].len(), 4);
+        assert_eq!(contours[1].len(), ARC_SEGMENTS);
+        assert_eq!(board.outline_polygon(), Some(contours[0].clone()));
+        assert!(point_in_contours((5.0, 5.0), &contours));
+        assert!(!point_in_contours((50.0, 40.0), &contours));
+        assert!(!point_in_contours((15.0, 15.0), &contours));
+        assert!(!point_in_contours((150.0, 15.0), &contours));
+
+        // One open contour spoils the whole outline
+        board.outline.push(edge(GraphicType::Line { start: FootprintPoint { x: 70.0, y: 10.0 }, end: FootprintPoint { x: 80.0, y: 10.0 } }));
+        assert!(board.outline_contours().is_none());
+        assert!(board.outline_polygon().is_none());
+    }
+}
@@ -39,6 +39,76 @@ pub struct ComponentTemplate {
     pub default_properties: HashMap<String, ComponentProperty>,
     pub pins: Vec<PinTemplate>,
     pub keywords: Vec<String>,
+    #[serde(default)]
+    pub units: Vec<SymbolUnit>,
+    #[serde(default)]
+    pub extends: Option<TemplateBase>,
+    #[serde(default, skip_serializing_if = "PartInfo::is_empty")]
+    pub part_info: PartInfo,
+}
+
+// Base a derived template inherits from; without a library id the base lives in the same library
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateBase {
+    pub library_id: Option<String>,
+    pub component_id: String,
+}
+
+// Sourcing data for a part. Templates carry the generic choice; placed components
+// start from it and may pin down a different manufacturer part.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartInfo {
+    pub manufacturer: Option<String>,
+    pub mpn: Option<String>,
+    #[serde(default)]
+    pub suppliers: Vec<SupplierPart>,
+    #[serde(default)]
+    pub lifecycle: LifecycleStatus,
+    // URL or local file path
+    pub datasheet: Option<String>,
+    #[serde(default)]
+    pub alternates: Vec<AlternatePart>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupplierPart {
+    pub supplier: String,
+    pub part_number: String,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlternatePart {
+    pub manufacturer: Option<String>,
+    pub mpn: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LifecycleStatus {
+    #[default]
+    Unknown,
+    Active,
+    NotRecommended,
+    LastTimeBuy,
+    Obsolete,
+}
+
+// One gate of a multi-unit package. Template pins not claimed by any unit are
+// shared and appear on every unit; power pins usually live in a `power` unit instead.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolUnit {
+    pub id: String,
+    pub name: String,
+    pub symbol: ComponentSymbol,
+    pub pin_ids: Vec<String>,
+    #[serde(default)]
+    pub power: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -137,6 +207,13 @@ pub struct Component {
     pub mirrored: bool,
     pub properties: HashMap<String, ComponentProperty>,
     pub pins: Vec<Pin>,
+    #[serde(default)]
+    pub unit: Option<String>,
+    // Content hash of the template this instance was last placed or updated from
+    #[serde(default)]
+    pub template_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "PartInfo::is_empty")]
+    pub part_info: PartInfo,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -198,6 +275,9 @@ impl Component {
             mirrored: false,
             properties: HashMap::new(),
             pins: Vec::new(),
+            unit: None,
+            template_hash: None,
+            part_info: PartInfo::default(),
         })
     }
 
@@ -223,21 +303,85 @@ impl Component {
             rotation: 0.0,
             mirrored: false,
             properties: template.default_properties.clone(),
-            pins: template.pins.iter().map(|pt| Pin {
-                id: pt.id.clone(),
-                name: pt.name.clone(),
-                number: pt.number.clone(),
-                x: pt.x,
-                y: pt.y,
-                pin_type: pt.pin_type.clone(),
-                electrical: pt.electrical.clone(),
-                connected: false,
-            }).collect(),
+            pins: template.pins.iter().map(Pin::from_template).collect(),
+            unit: None,
+            template_hash: Some(template.content_hash()),
+            part_info: template.part_info.clone(),
         };
         
         Ok(component)
     }
 
+    /// Places a single unit of a multi-unit template. The reference ends in `?`
+    /// until annotation packs the unit into a package.
+    pub fn from_template_unit(template: &ComponentTemplate, unit_id: &str, library_id: String, x: f64, y: f64) -> Result<Self> {
+        let pins = template.unit_pins(unit_id)
+            .ok_or_else(|| AppError::InvalidInput(format!("Unit {} not found in {}", unit_id, template.name)))?;
+
+        let mut component = Self::from_template(template, library_id, x, y)?;
+        component.reference = format!("{}?", reference_prefix(&component.reference));
+        component.pins = pins.into_iter().map(Pin::from_template).collect();
+        component.unit = Some(unit_id.to_string());
+        Ok(component)
+    }
+
+    /// Refreshes pins and properties from a newer template. Position, reference, value and
+    /// unit stay as placed, editable properties keep their instance values and custom
+    /// properties the template doesn't know about are kept.
+    pub fn refresh_from_template(&mut self, template: &ComponentTemplate) -> Result<()> {
+        let pins: Vec<Pin> = match &self.unit {
+            Some(unit) => template.unit_pins(unit)
+                .ok_or_else(|| AppError::InvalidInput(format!("Unit {} not found in {}", unit, template.name)))?
+                .into_iter()
+                .map(Pin::from_template)
+                .collect(),
+            None => template.pins.iter().map(Pin::from_template).collect(),
+        };
+
+        let connected: Vec<String> = self.pins.iter()
+            .filter(|p| p.connected)
+            .map(|p| p.number.clone())
+            .collect();
+        self.pins = pins;
+        for pin in &mut self.pins {
+            pin.connected = connected.contains(&pin.number);
+        }
+        if self.rotation != 0.0 || self.mirrored {
+            self.update_pin_positions();
+        }
+
+        let mut properties = template.default_properties.clone();
+        for (key, property) in &self.properties {
+            match properties.get(key) {
+                Some(default) if !default.editable => {}
+                _ => {
+                    properties.insert(key.clone(), property.clone());
+                }
+            }
+        }
+        self.properties = properties;
+        self.part_info.fill_missing(&template.part_info);
+        self.type_id = template.id.clone();
+        self.template_hash = Some(template.content_hash());
+        Ok(())
+    }
+
+    /// True for two units placed in the same annotated package.
+    pub fn same_package(&self, other: &Component) -> bool {
+        self.unit.is_some()
+            && other.unit.is_some()
+            && !self.reference.ends_with('?')
+            && self.reference == other.reference
+    }
+
+    // Units share the package reference; the unit letter is only appended for display
+    pub fn display_reference(&self) -> String {
+        match &self.unit {
+            Some(unit) => format!("{}{}", self.reference, unit),
+            None => self.reference.clone(),
+        }
+    }
+
     pub fn set_position(&mut self, x: f64, y: f64) -> Result<()> {
         if !validators::validate_coordinate(x) || !validators::validate_coordinate(y) {
             return Err(crate::utils::error::AppError::InvalidInput("Invalid coordinates".to_string()));
@@ -295,6 +439,15 @@ impl Component {
         }
     }
 
+    // Pin coordinates are stored relative to the component origin
+    pub fn pin_position(&self, pin: &Pin) -> (f64, f64) {
+        (self.x + pin.x, self.y + pin.y)
+    }
+
+    pub fn get_pin_by_number(&self, number: &str) -> Option<&Pin> {
+        self.pins.iter().find(|p| p.number == number)
+    }
+
     pub fn get_bounding_box(&self) -> (f64, f64, f64, f64) {
         let mut min_x = f64::MAX;
         let mut min_y = f64::MAX;
@@ -356,6 +509,17 @@ impl ComponentLibrary {
         Ok(())
     }
 
+    pub fn update_component_template(&mut self, template: ComponentTemplate) -> Result<()> {
+        if !self.components.contains_key(&template.id) {
+            return Err(AppError::ComponentNotFound(template.id));
+        }
+        self.add_component_template(template)
+    }
+
+    pub fn remove_component_template(&mut self, id: &str) -> Option<ComponentTemplate> {
+        self.components.remove(id)
+    }
+
     pub fn get_component_template(&self, id: &str) -> Option<&ComponentTemplate> {
         self.components.get(id)
     }
@@ -366,18 +530,133 @@ impl ComponentLibrary {
             .collect()
     }
 
+    /// Best matches first, ranked and filtered as the library search does; an empty query
+    /// lists every component.
     pub fn search_components(&self, query: &str) -> Vec<&ComponentTemplate> {
-        let query_lower = query.to_lowercase();
-        self.components.values()
-            .filter(|c| {
-                c.name.to_lowercase().contains(&query_lower) ||
-                c.keywords.iter().any(|k| k.to_lowercase().contains(&query_lower)) ||
-                c.description.as_ref().map_or(false, |d| d.to_lowercase().contains(&query_lower))
-            })
+        use crate::services::component_search::{ComponentSearchIndex, SearchQuery};
+
+        ComponentSearchIndex::build([self])
+            .search(&SearchQuery::parse(query), usize::MAX)
+            .into_iter()
+            .filter_map(|(_, component_id, _)| self.components.get(&component_id))
             .collect()
     }
 }
 
+impl DrawCommandType {
+    // Parameter layouts:
+    //   Rectangle [x, y, width, height]     Circle [cx, cy, r]
+    //   Line [x1, y1, x2, y2]               Arc [cx, cy, r, start_deg, end_deg]
+    //   Text [x, y, size?, value?]          Polygon [x1, y1, x2, y2, x3, y3, ...]
+    // Parameters are numbers only, so a Text command carries no string; the
+    // optional fourth value is drawn as-is by the symbol preview
+    pub fn accepts_parameter_count(&self, count: usize) -> bool {
+        match self {
+            DrawCommandType::Rectangle => count == 4,
+            DrawCommandType::Circle => count == 3,
+            DrawCommandType::Line => count == 4,
+            DrawCommandType::Arc => count == 5,
+            DrawCommandType::Text => (2..=4).contains(&count),
+            DrawCommandType::Polygon => count >= 6 && count.is_multiple_of(2),
+        }
+    }
+
+    // Indices of parameters that are (x, y) coordinate pairs
+    fn point_indices(&self, count: usize) -> Vec<usize> {
+        match self {
+            DrawCommandType::Line | DrawCommandType::Polygon => (0..count / 2).map(|i| i * 2).collect(),
+            _ if count >= 2 => vec![0],
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl DrawCommand {
+    pub fn translate(&mut self, dx: f64, dy: f64) {
+        for i in self.command_type.point_indices(self.parameters.len()) {
+            self.parameters[i] += dx;
+            self.parameters[i + 1] += dy;
+        }
+    }
+
+    /// Axis-aligned extent as (min_x, min_y, max_x, max_y), or None for malformed commands.
+    pub fn extent(&self) -> Option<(f64, f64, f64, f64)> {
+        if !self.command_type.accepts_parameter_count(self.parameters.len()) {
+            return None;
+        }
+        let p = &self.parameters;
+        match self.command_type {
+            DrawCommandType::Rectangle => Some((p[0].min(p[0] + p[2]), p[1].min(p[1] + p[3]), p[0].max(p[0] + p[2]), p[1].max(p[1] + p[3]))),
+            DrawCommandType::Circle | DrawCommandType::Arc => Some((p[0] - p[2], p[1] - p[2], p[0] + p[2], p[1] + p[2])),
+            DrawCommandType::Text => Some((p[0], p[1], p[0], p[1])),
+            DrawCommandType::Line | DrawCommandType::Polygon => {
+                p.chunks(2).fold(None, |acc: Option<(f64, f64, f64, f64)>, pt| {
+                    Some(match acc {
+                        Some((x0, y0, x1, y1)) => (x0.min(pt[0]), y0.min(pt[1]), x1.max(pt[0]), y1.max(pt[1])),
+                        None => (pt[0], pt[1], pt[0], pt[1]),
+                    })
+                })
+            }
+        }
+    }
+}
+
+impl ComponentSymbol {
+    /// Recomputes width/height and graphics bounds from the draw commands and pins.
+    /// Symbols are drawn around the origin, so the box is kept symmetric.
+    pub fn recompute_bounds(&mut self, pins: &[PinTemplate]) {
+        let half_extent = self.draw_commands.iter()
+            .filter_map(|c| c.extent())
+            .map(|(x0, y0, x1, y1)| (x0.abs().max(x1.abs()), y0.abs().max(y1.abs())))
+            .chain(pins.iter().map(|p| (p.x.abs(), p.y.abs())))
+            .fold((0.0_f64, 0.0_f64), |(w, h), (x, y)| (w.max(x), h.max(y)));
+
+        self.width = half_extent.0 * 2.0;
+        self.height = half_extent.1 * 2.0;
+        self.graphics = Some(SymbolGraphics {
+            bounds: GraphicsBounds { width: self.width, height: self.height },
+        });
+    }
+}
+
+impl PartInfo {
+    pub fn is_empty(&self) -> bool {
+        *self == PartInfo::default()
+    }
+
+    pub fn has_mpn(&self) -> bool {
+        self.mpn.as_deref().is_some_and(|mpn| !mpn.trim().is_empty())
+    }
+
+    /// Fills the fields left unset here from `other`; anything already set is kept.
+    pub fn fill_missing(&mut self, other: &PartInfo) {
+        // Manufacturer and MPN name one part together, so they are only taken as a pair
+        if !self.has_mpn() {
+            self.manufacturer = other.manufacturer.clone().or(self.manufacturer.take());
+            self.mpn = other.mpn.clone();
+        }
+        if self.suppliers.is_empty() {
+            self.suppliers = other.suppliers.clone();
+        }
+        if self.lifecycle == LifecycleStatus::Unknown {
+            self.lifecycle = other.lifecycle;
+        }
+        if self.datasheet.is_none() {
+            self.datasheet = other.datasheet.clone();
+        }
+        if self.alternates.is_empty() {
+            self.alternates = other.alternates.clone();
+        }
+    }
+
+    pub fn supplier_summary(&self) -> String {
+        self.suppliers.iter()
+            .map(|s| format!("{}: {}", s.supplier, s.part_number))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
 impl ComponentTemplate {
     pub fn new(name: String, category_id: String, symbol: ComponentSymbol) -> Result<Self> {
         validators::validate_string_length_range(&name, 1, 100)?;
@@ -393,6 +672,9 @@ impl ComponentTemplate {
             default_properties: HashMap::new(),
             pins: Vec::new(),
             keywords: Vec::new(),
+            units: Vec::new(),
+            extends: None,
+            part_info: PartInfo::default(),
         })
     }
 
@@ -409,6 +691,135 @@ impl ComponentTemplate {
         self.pins.push(pin);
         Ok(())
     }
+
+    /// Fills in whatever this derived template leaves unset from an already resolved base.
+    /// Symbol, pins and units are inherited when empty; parameters and properties are
+    /// merged with this template's entries winning; footprint, description and keywords
+    /// fall back to the base only when not overridden.
+    pub fn inherit_from(&self, base: &ComponentTemplate) -> ComponentTemplate {
+        let mut resolved = self.clone();
+
+        if resolved.symbol.draw_commands.is_empty() {
+            resolved.symbol = base.symbol.clone();
+        }
+        if resolved.pins.is_empty() {
+            resolved.pins = base.pins.clone();
+        }
+        if resolved.units.is_empty() {
+            resolved.units = base.units.clone();
+        }
+
+        resolved.parameters = base.parameters.clone();
+        resolved.parameters.extend(self.parameters.clone());
+        resolved.default_properties = base.default_properties.clone();
+        resolved.default_properties.extend(self.default_properties.clone());
+
+        if resolved.footprint.is_none() {
+            resolved.footprint = base.footprint.clone();
+        }
+        if resolved.description.is_none() {
+            resolved.description = base.description.clone();
+        }
+        if resolved.keywords.is_empty() {
+            resolved.keywords = base.keywords.clone();
+        }
+        resolved.part_info.fill_missing(&base.part_info);
+
+        resolved
+    }
+
+    /// Stable fingerprint of the template content; placed components remember it so
+    /// later library edits can be detected.
+    pub fn content_hash(&self) -> String {
+        let value = serde_json::to_value(self).unwrap_or(serde_json::Value::Null);
+        let mut hash = FNV_OFFSET_BASIS;
+        hash_canonical_json(&value, &mut hash);
+        format!("{:016x}", hash)
+    }
+
+    pub fn is_multi_unit(&self) -> bool {
+        !self.units.is_empty()
+    }
+
+    pub fn get_unit(&self, unit_id: &str) -> Option<&SymbolUnit> {
+        self.units.iter().find(|u| u.id == unit_id)
+    }
+
+    /// Pins drawn on a unit: its own pins followed by the shared ones.
+    pub fn unit_pins(&self, unit_id: &str) -> Option<Vec<&PinTemplate>> {
+        let unit = self.get_unit(unit_id)?;
+        let own = unit.pin_ids.iter().filter_map(|id| self.pins.iter().find(|p| &p.id == id));
+        let shared = self.pins.iter()
+            .filter(|p| !self.units.iter().any(|u| u.pin_ids.contains(&p.id)));
+        Some(own.chain(shared).collect())
+    }
+}
+
+impl Pin {
+    fn from_template(pin: &PinTemplate) -> Self {
+        Self {
+            id: pin.id.clone(),
+            name: pin.name.clone(),
+            number: pin.number.clone(),
+            x: pin.x,
+            y: pin.y,
+            pin_type: pin.pin_type.clone(),
+            electrical: pin.electrical.clone(),
+            connected: false,
+        }
+    }
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+// FNV-1a over JSON with object keys sorted, so HashMap ordering doesn't change the hash
+fn hash_canonical_json(value: &serde_json::Value, hash: &mut u64) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            fnv_feed(hash, b"{");
+            for key in keys {
+                fnv_feed(hash, key.as_bytes());
+                fnv_feed(hash, b":");
+                hash_canonical_json(&map[key], hash);
+            }
+            fnv_feed(hash, b"}");
+        }
+        serde_json::Value::Array(items) => {
+            fnv_feed(hash, b"[");
+            for item in items {
+                hash_canonical_json(item, hash);
+            }
+            fnv_feed(hash, b"]");
+        }
+        other => fnv_feed(hash, other.to_string().as_bytes()),
+    }
+}
+
+fn fnv_feed(hash: &mut u64, bytes: &[u8]) {
+    for byte in bytes {
+        *hash ^= u64::from(*byte);
+        *hash = hash.wrapping_mul(FNV_PRIME);
+    }
+}
+
+/// Alphabetic part of a reference designator, e.g. `U` for `U12` or `U?`.
+pub fn reference_prefix(reference: &str) -> &str {
+    let end = reference.find(|c: char| !c.is_alphabetic()).unwrap_or(reference.len());
+    &reference[..end]
+}
+
+/// One component per physical package; further units of an annotated package are skipped.
+pub fn package_components<'a>(components: impl IntoIterator<Item = &'a Component>) -> Vec<&'a Component> {
+    let mut packages: Vec<&Component> = Vec::new();
+    for component in components {
+        if !packages.iter().any(|p| p.same_package(component)) {
+            packages.push(component);
+        }
+    }
+    packages
 }
 
 #[cfg(test)]
@@ -446,4 +857,161 @@ mod tests {
         assert!(prop.is_some());
         assert_eq!(prop.unwrap().value, serde_json::json!("10k"));
     }
+
+    #[test]
+    fn test_library_template_editing() {
+        let mut library = ComponentLibrary::new("User Parts".to_string()).unwrap();
+        library.add_category(ComponentCategory {
+            id: "passive".to_string(),
+            name: "Passive".to_string(),
+            parent_id: None,
+            description: None,
+            color: None,
+        }).unwrap();
+
+        let symbol = ComponentSymbol { width: 60.0, height: 20.0, draw_commands: Vec::new(), graphics: None };
+        let mut template = ComponentTemplate::new("Resistor".to_string(), "passive".to_string(), symbol).unwrap();
+        assert!(library.update_component_template(template.clone()).is_err());
+
+        library.add_component_template(template.clone()).unwrap();
+        template.name = "Precision Resistor".to_string();
+        library.update_component_template(template.clone()).unwrap();
+        assert_eq!(library.get_component_template(&template.id).unwrap().name, "Precision Resistor");
+
+        assert!(library.remove_component_template(&template.id).is_some());
+        assert!(library.components.is_empty());
+    }
+
+    #[test]
+    fn test_template_inheritance() {
+        let symbol = ComponentSymbol {
+            width: 60.0,
+            height: 20.0,
+            draw_commands: vec![DrawCommand {
+                command_type: DrawCommandType::Rectangle,
+                parameters: vec![-20.0, -10.0, 40.0, 20.0],
+                style: None,
+            }],
+            graphics: None,
+        };
+        let mut base = ComponentTemplate::new("Resistor".to_string(), "passive".to_string(), symbol).unwrap();
+        base.footprint = Some("R_0805".to_string());
+        base.keywords = vec!["resistor".to_string()];
+        base.default_properties.insert("tolerance".to_string(), ComponentProperty {
+            value: serde_json::json!("5%"),
+            visible: false,
+            editable: true,
+        });
+
+        let empty = ComponentSymbol { width: 0.0, height: 0.0, draw_commands: Vec::new(), graphics: None };
+        let mut derived = ComponentTemplate::new("10k 0603".to_string(), "passive".to_string(), empty).unwrap();
+        derived.footprint = Some("R_0603".to_string());
+        derived.default_properties.insert("value".to_string(), ComponentProperty {
+            value: serde_json::json!("10k"),
+            visible: true,
+            editable: true,
+        });
+
+        let resolved = derived.inherit_from(&base);
+        assert_eq!(resolved.symbol.draw_commands.len(), 1);
+        assert_eq!(resolved.footprint.as_deref(), Some("R_0603"));
+        assert_eq!(resolved.keywords, vec!["resistor".to_string()]);
+        assert_eq!(resolved.default_properties.len(), 2);
+        assert_eq!(resolved.name, "10k 0603");
+    }
+
+    #[test]
+    fn test_template_hash_and_refresh() {
+        let symbol = ComponentSymbol { width: 60.0, height: 20.0, draw_commands: Vec::new(), graphics: None };
+        let mut template = ComponentTemplate::new("Resistor".to_string(), "passive".to_string(), symbol).unwrap();
+        template.default_properties.insert("value".to_string(), ComponentProperty {
+            value: serde_json::json!("1k"),
+            visible: true,
+            editable: true,
+        });
+        let hash = template.content_hash();
+        assert_eq!(hash, template.clone().content_hash());
+
+        let mut component = Component::from_template(&template, "standard".to_string(), 0.0, 0.0).unwrap();
+        component.set_property("value".to_string(), serde_json::json!("4.7k"), true);
+        assert_eq!(component.template_hash.as_deref(), Some(hash.as_str()));
+
+        template.add_pin(PinTemplate {
+            id: "1".to_string(),
+            name: "~".to_string(),
+            number: "1".to_string(),
+            x: -30.0,
+            y: 0.0,
+            pin_type: PinType::Passive,
+            electrical: ElectricalType { voltage: None, current: None, impedance: None },
+        }).unwrap();
+        assert_ne!(template.content_hash(), hash);
+
+        component.refresh_from_template(&template).unwrap();
+        assert_eq!(component.pins.len(), 1);
+        assert_eq!(component.get_property("value").unwrap().value, serde_json::json!("4.7k"));
+        assert_eq!(component.template_hash, Some(template.content_hash()));
+    }
+
+    #[test]
+    fn test_symbol_bounds_recompute() {
+        let mut body = DrawCommand {
+            command_type: DrawCommandType::Rectangle,
+            parameters: vec![-20.0, -10.0, 40.0, 20.0],
+            style: None,
+        };
+        assert!(body.command_type.accepts_parameter_count(4));
+        assert!(!DrawCommandType::Polygon.accepts_parameter_count(5));
+
+        body.translate(5.0, 0.0);
+        assert_eq!(body.extent(), Some((-15.0, -10.0, 25.0, 10.0)));
+
+        let mut symbol = ComponentSymbol { width: 0.0, height: 0.0, draw_commands: vec![body], graphics: None };
+        let pins = vec![PinTemplate {
+            id: "1".to_string(),
+            name: "~".to_string(),
+            number: "1".to_string(),
+            x: -30.0,
+            y: 0.0,
+            pin_type: PinType::Passive,
+            electrical: ElectricalType { voltage: None, current: None, impedance: None },
+        }];
+        symbol.recompute_bounds(&pins);
+        assert_eq!(symbol.width, 60.0);
+        assert_eq!(symbol.height, 20.0);
+        assert_eq!(symbol.graphics.unwrap().bounds.width, 60.0);
+    }
+
+    #[test]
+    fn test_part_info_fill_missing() {
+        let symbol = ComponentSymbol { width: 60.0, height: 20.0, draw_commands: Vec::new(), graphics: None };
+        let mut template = ComponentTemplate::new("Regulator".to_string(), "power".to_string(), symbol).unwrap();
+        let hash_without_info = template.content_hash();
+        assert!(!serde_json::to_string(&template).unwrap().contains("partInfo"));
+
+        template.part_info = PartInfo {
+            manufacturer: Some("Texas Instruments".to_string()),
+            mpn: Some("LM1117-3.3".to_string()),
+            suppliers: vec![SupplierPart { supplier: "Digi-Key".to_string(), part_number: "296-LM1117-3.3-ND".to_string(), url: None }],
+            lifecycle: LifecycleStatus::Active,
+            datasheet: Some("https://www.ti.com/lit/ds/symlink/lm1117.pdf".to_string()),
+            alternates: Vec::new(),
+        };
+        assert_ne!(template.content_hash(), hash_without_info);
+
+        let mut component = Component::from_template(&template, "standard".to_string(), 0.0, 0.0).unwrap();
+        assert!(component.part_info.has_mpn());
+
+        // An instance-level MPN survives a refresh, the rest is filled from the template
+        component.part_info = PartInfo {
+            manufacturer: Some("Diodes Inc".to_string()),
+            mpn: Some("AZ1117-3.3".to_string()),
+            ..PartInfo::default()
+        };
+        component.refresh_from_template(&template).unwrap();
+        assert_eq!(component.part_info.mpn.as_deref(), Some("AZ1117-3.3"));
+        assert_eq!(component.part_info.manufacturer.as_deref(), Some("Diodes Inc"));
+        assert_eq!(component.part_info.supplier_summary(), "Digi-Key: 296-LM1117-3.3-ND");
+        assert_eq!(component.part_info.lifecycle, LifecycleStatus::Active);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,193 @@
+use serde::{Deserialize, Serialize};
+
+/// Board design rules for the whole project, in millimetres. Net classes override the
+/// widths, clearance and via size for the nets they list or whose names match one of
+/// their patterns; routing and DRC both resolve nets through them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesignRules {
+    pub clearance: f64,
+    pub min_track_width: f64,
+    pub min_annular_ring: f64,
+    pub min_drill: f64,
+    pub max_drill: f64,
+    pub edge_clearance: f64,
+    #[serde(default = "default_track_width")]
+    pub track_width: f64, // width new tracks are routed with
+    #[serde(default = "default_via_diameter")]
+    pub via_diameter: f64,
+    #[serde(default = "default_via_drill")]
+    pub via_drill: f64,
+    #[serde(default)]
+    pub net_classes: Vec<NetClass>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetClass {
+    pub name: String,
+    pub nets: Vec<String>, // net names
+    #[serde(default)]
+    pub patterns: Vec<String>, // net name globs such as "GND*" or "+?V*"
+    pub clearance: Option<f64>,
+    pub track_width: Option<f64>,
+    #[serde(default)]
+    pub via_diameter: Option<f64>,
+    #[serde(default)]
+    pub via_drill: Option<f64>,
+    #[serde(default)]
+    pub diff_pair_gap: Option<f64>,
+}
+
+fn default_track_width() -> f64 {
+    0.25
+}
+
+fn default_via_diameter() -> f64 {
+    0.6
+}
+
+fn default_via_drill() -> f64 {
+    0.3
+}
+
+impl Default for DesignRules {
+    fn default() -> Self {
+        Self {
+            clearance: 0.2,
+            min_track_width: 0.15,
+            min_annular_ring: 0.13,
+            min_drill: 0.3,
+            max_drill: 6.35,
+            edge_clearance: 0.3,
+            track_width: default_track_width(),
+            via_diameter: default_via_diameter(),
+            via_drill: default_via_drill(),
+            net_classes: Vec::new(),
+        }
+    }
+}
+
+impl NetClass {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            nets: Vec::new(),
+            patterns: Vec::new(),
+            clearance: None,
+            track_width: None,
+            via_diameter: None,
+            via_drill: None,
+            diff_pair_gap: None,
+        }
+    }
+
+    pub fn matches_pattern(&self, net_name: &str) -> bool {
+        self.patterns.iter().any(|p| glob_matches(p, net_name))
+    }
+}
+
+impl DesignRules {
+    /// The class a net belongs to: explicit membership first, then the first matching pattern.
+    pub fn net_class(&self, net_name: &str) -> Option<&NetClass> {
+        self.net_classes.iter().find(|c| c.nets.iter().any(|n| n == net_name))
+            .or_else(|| self.net_classes.iter().find(|c| c.matches_pattern(net_name)))
+    }
+
+    fn class_value(&self, net_name: Option<&str>, value: impl Fn(&NetClass) -> Option<f64>) -> Option<f64> {
+        net_name.and_then(|n| self.net_class(n)).and_then(value)
+    }
+
+    pub fn clearance_for(&self, net_name: Option<&str>) -> f64 {
+        self.class_value(net_name, |c| c.clearance).unwrap_or(self.clearance)
+    }
+
+    /// The larger of the two nets' clearances.
+    pub fn clearance_between(&self, a: Option<&str>, b: Option<&str>) -> f64 {
+        self.clearance_for(a).max(self.clearance_for(b))
+    }
+
+    /// Narrowest track DRC accepts on the net.
+    pub fn track_width_for(&self, net_name: Option<&str>) -> f64 {
+        self.class_value(net_name, |c| c.track_width).unwrap_or(self.min_track_width)
+    }
+
+    /// Width the router uses on the net.
+    pub fn route_width_for(&self, net_name: Option<&str>) -> f64 {
+        self.class_value(net_name, |c| c.track_width).unwrap_or(self.track_width)
+    }
+
+    /// Via (diameter, drill) for the net.
+    pub fn via_for(&self, net_name: Option<&str>) -> (f64, f64) {
+        (
+            self.class_value(net_name, |c| c.via_diameter).unwrap_or(self.via_diameter),
+            self.class_value(net_name, |c| c.via_drill).unwrap_or(self.via_drill),
+        )
+    }
+
+    pub fn diff_pair_gap_for(&self, net_name: Option<&str>) -> Option<f64> {
+        self.class_value(net_name, |c| c.diff_pair_gap)
+    }
+}
+
+/// Case-insensitive glob with `*` for any run of characters and `?` for one character.
+pub fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text position it was tried at
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_net_class_resolution() {
+        assert!(glob_matches("GND*", "gnd_analog"));
+        assert!(glob_matches("+?V*", "+5V0"));
+        assert!(glob_matches("*CLK", "SPI_CLK"));
+        assert!(!glob_matches("+?V", "+12V"));
+
+        let mut power = NetClass::new("Power".to_string());
+        power.patterns = vec!["GND*".to_string(), "+*V*".to_string()];
+        power.track_width = Some(0.5);
+        power.via_diameter = Some(0.8);
+        power.via_drill = Some(0.4);
+        let mut usb = NetClass::new("USB".to_string());
+        usb.nets = vec!["USB_D+".to_string(), "USB_D-".to_string()];
+        usb.diff_pair_gap = Some(0.15);
+        usb.clearance = Some(0.25);
+
+        let rules = DesignRules { net_classes: vec![power, usb], ..DesignRules::default() };
+        assert_eq!(rules.route_width_for(Some("GND")), 0.5);
+        assert_eq!(rules.route_width_for(Some("SDA")), 0.25);
+        assert_eq!(rules.track_width_for(Some("SDA")), 0.15);
+        assert_eq!(rules.via_for(Some("+3V3")), (0.8, 0.4));
+        assert_eq!(rules.diff_pair_gap_for(Some("USB_D-")), Some(0.15));
+        assert_eq!(rules.clearance_between(Some("USB_D+"), Some("GND")), 0.25);
+
+        // Files written before the via and width defaults existed still load
+        let json = r#"{"clearance":0.2,"minTrackWidth":0.15,"minAnnularRing":0.13,"minDrill":0.3,"maxDrill":6.35,"edgeClearance":0.3}"#;
+        let rules: DesignRules = serde_json::from_str(json).unwrap();
+        assert_eq!(rules.via_for(None), (0.6, 0.3));
+    }
+}
@@ -15,6 +15,8 @@ pub struct Footprint {
     pub keepout: Option<KeepoutArea>,
     pub dimensions: FootprintDimensions,
     pub metadata: FootprintMetadata,
+    #[serde(default)]
+    pub pin_map_variants: Vec<PinMapVariant>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -194,7 +196,7 @@ pub enum DrillShape {
     Slot { start: FootprintPoint, end: FootprintPoint, width: f64 },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum LayerType {
     TopCopper,
     BottomCopper,
@@ -214,6 +216,31 @@ pub enum LayerType {
     UserComments,
 }
 
+impl LayerType {
+    pub fn is_copper(&self) -> bool {
+        matches!(self, LayerType::TopCopper | LayerType::BottomCopper | LayerType::InnerCopper(_))
+    }
+
+    // The matching layer on the other side of the board; inner and user layers stay put
+    pub fn flipped(&self) -> LayerType {
+        match self {
+            LayerType::TopCopper => LayerType::BottomCopper,
+            LayerType::BottomCopper => LayerType::TopCopper,
+            LayerType::TopSolderMask => LayerType::BottomSolderMask,
+            LayerType::BottomSolderMask => LayerType::TopSolderMask,
+            LayerType::TopSilkscreen => LayerType::BottomSilkscreen,
+            LayerType::BottomSilkscreen => LayerType::TopSilkscreen,
+            LayerType::TopPaste => LayerType::BottomPaste,
+            LayerType::BottomPaste => LayerType::TopPaste,
+            LayerType::TopCourtyard => LayerType::BottomCourtyard,
+            LayerType::BottomCourtyard => LayerType::TopCourtyard,
+            LayerType::TopAssembly => LayerType::BottomAssembly,
+            LayerType::BottomAssembly => LayerType::TopAssembly,
+            other => other.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThermalRelief {
     pub gap: f64,
@@ -239,7 +266,7 @@ pub enum GraphicType {
     Text { position: FootprintPoint, text: String, size: f64, rotation: f64 },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct FootprintPoint {
     pub x: f64,
     pub y: f64,
@@ -282,6 +309,14 @@ pub struct FootprintMetadata {
     pub license: Option<String>,
 }
 
+// Alternate pinout for packages that ship with several pin orders (e.g. SOT-23 BCE/BEC/EBC)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinMapVariant {
+    pub id: String,
+    pub name: String,
+    pub pad_functions: HashMap<String, String>, // pad_number -> symbol pin id or name
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FootprintLibrary {
     pub id: String,
@@ -327,6 +362,21 @@ impl FootprintLibrary {
     }
 }
 
+impl Footprint {
+    pub fn get_pad(&self, pad_number: &str) -> Option<&Pad> {
+        self.pads.iter().find(|p| p.pad_number == pad_number)
+    }
+
+    pub fn get_pin_map_variant(&self, variant_id: &str) -> Option<&PinMapVariant> {
+        self.pin_map_variants.iter().find(|v| v.id == variant_id)
+    }
+
+    // Pads that carry a signal; NPTH mounting holes never need a pin
+    pub fn electrical_pads(&self) -> impl Iterator<Item = &Pad> {
+        self.pads.iter().filter(|p| !matches!(p.pad_type, PadType::NPTH))
+    }
+}
+
 // Component to Footprint Mapping
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComponentFootprintMap {
@@ -334,6 +384,14 @@ pub struct ComponentFootprintMap {
     pub footprint_id: String,
     pub pin_mapping: HashMap<String, String>, // component_pin -> pad_number
     pub placement_hints: PlacementHints,
+    #[serde(default)]
+    pub variant_id: Option<String>,
+}
+
+impl ComponentFootprintMap {
+    pub fn pad_for_pin(&self, pin_number: &str) -> Option<&str> {
+        self.pin_mapping.get(pin_number).map(|s| s.as_str())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -345,9 +403,46 @@ pub struct PlacementHints {
     pub mechanical_constraints: Option<String>,
 }
 
+impl Default for PlacementHints {
+    fn default() -> Self {
+        Self {
+            preferred_side: BoardSide::Either,
+            rotation: 0.0,
+            allow_rotation: true,
+            thermal_considerations: false,
+            mechanical_constraints: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum BoardSide {
     Top,
     Bottom,
     Either,
+}
+
+// Rule for bulk footprint assignment, e.g. "all C* with value < 1µF get C_0603"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FootprintAssignmentRule {
+    pub footprint_id: String,
+    pub variant_id: Option<String>,
+    pub reference_prefix: Option<String>,
+    pub type_id: Option<String>,
+    pub value_below: Option<f64>,
+    pub value_at_least: Option<f64>,
+    pub overwrite: bool,
+}
+
+// Result of checking one component's symbol pins against its footprint pads
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinMappingReport {
+    pub component_id: String,
+    pub reference: String,
+    pub footprint_id: String,
+    pub footprint_found: bool,
+    pub unmapped_pins: Vec<String>,   // symbol pin numbers with no pad
+    pub missing_pads: Vec<String>,    // mapping targets that don't exist on the footprint
+    pub unconnected_pads: Vec<String>, // footprint pads no pin maps to
+    pub valid: bool,
 }
\ No newline at end of file
@@ -5,6 +5,9 @@ pub mod wire;
 pub mod annotation;
 pub mod netlist;
 pub mod footprint;
+pub mod simulation;
+pub mod board;
+pub mod design_rules;
 
 pub use project::*;
 pub use schematic::*;
@@ -12,4 +15,7 @@ pub use component::*;
 pub use wire::*;
 pub use annotation::*;
 pub use netlist::*;
-pub use footprint::*;
\ No newline at end of file
+pub use footprint::*;
+pub use simulation::*;
+pub use board::*;
+pub use design_rules::*;
\ No newline at end of file
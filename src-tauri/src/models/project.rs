@@ -21,6 +21,23 @@ pub struct Project {
     pub schematics: Vec<super::Schematic>,
     pub settings: ProjectSettings,
     pub metadata: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub footprint_assignments: Vec<super::ComponentFootprintMap>,
+    #[serde(default)]
+    pub local_library: Option<super::ComponentLibrary>,
+    #[serde(default)]
+    pub symbol_cache: Vec<CachedTemplate>,
+    #[serde(default)]
+    pub board: Option<super::Board>,
+}
+
+// Resolved copy of a library template the project uses, refreshed on every save so
+// the project still renders on machines without that library
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedTemplate {
+    pub library_id: String,
+    pub template: super::ComponentTemplate,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +49,8 @@ pub struct ProjectSettings {
     pub auto_save_interval: u32,
     pub default_units: String,
     pub color_scheme: String,
+    #[serde(default)]
+    pub design_rules: super::DesignRules,
 }
 
 impl Default for ProjectSettings {
@@ -43,6 +62,7 @@ impl Default for ProjectSettings {
             auto_save_interval: 300, // 5 minutes
             default_units: "mm".to_string(),
             color_scheme: "light".to_string(),
+            design_rules: super::DesignRules::default(),
         }
     }
 }
@@ -65,6 +85,10 @@ impl Project {
             schematics: Vec::with_capacity(10),
             settings: ProjectSettings::default(),
             metadata: HashMap::with_capacity(10),
+            footprint_assignments: Vec::new(),
+            local_library: None,
+            symbol_cache: Vec::new(),
+            board: None,
         })
     }
     
@@ -100,6 +124,77 @@ impl Project {
             .map_err(|e| AppError::SerializationError(e.to_string()))
     }
     
+    // Footprints belong to packages, so any unit of a package resolves to its assignment
+    pub fn get_footprint_assignment(&self, component_id: &str) -> Option<&super::ComponentFootprintMap> {
+        let package = self.package_component_ids(component_id);
+        self.footprint_assignments.iter().find(|a| a.component_id == component_id)
+            .or_else(|| self.footprint_assignments.iter().find(|a| package.contains(&a.component_id.as_str())))
+    }
+
+    pub fn set_footprint_assignment(&mut self, assignment: super::ComponentFootprintMap) {
+        let package: Vec<String> = self.package_component_ids(&assignment.component_id)
+            .into_iter()
+            .map(|id| id.to_string())
+            .collect();
+        self.footprint_assignments.retain(|a| a.component_id != assignment.component_id && !package.contains(&a.component_id));
+        self.footprint_assignments.push(assignment);
+        self.modified_at = Utc::now();
+    }
+
+    pub fn find_component(&self, component_id: &str) -> Option<&super::Component> {
+        self.schematics.iter().find_map(|s| s.get_component(component_id))
+    }
+
+    pub fn cached_template(&self, library_id: &str, component_id: &str) -> Option<&super::ComponentTemplate> {
+        self.symbol_cache.iter()
+            .find(|c| c.library_id == library_id && c.template.id == component_id)
+            .map(|c| &c.template)
+    }
+
+    /// Distinct `(library id, template id)` pairs referenced by placed components.
+    pub fn used_templates(&self) -> Vec<(String, String)> {
+        let mut used: Vec<(String, String)> = self.schematics.iter()
+            .flat_map(|s| s.components.iter())
+            .filter_map(|c| c.library_id.as_ref().map(|library_id| (library_id.clone(), c.type_id.clone())))
+            .collect();
+        used.sort();
+        used.dedup();
+        used
+    }
+
+    /// Package representatives across every sheet, see [`super::component::package_components`].
+    pub fn package_components(&self) -> Vec<&super::Component> {
+        super::component::package_components(self.schematics.iter().flat_map(|s| s.components.iter()))
+    }
+
+    /// The component with the pins of every unit in its package merged in.
+    pub fn find_package(&self, component_id: &str) -> Option<super::Component> {
+        let mut package = self.find_component(component_id)?.clone();
+        let units: Vec<&super::Component> = self.schematics.iter()
+            .flat_map(|s| s.components.iter())
+            .filter(|c| c.id != package.id && c.same_package(&package))
+            .collect();
+        for unit in units {
+            for pin in &unit.pins {
+                if package.get_pin_by_number(&pin.number).is_none() {
+                    package.pins.push(pin.clone());
+                }
+            }
+        }
+        Some(package)
+    }
+
+    fn package_component_ids(&self, component_id: &str) -> Vec<&str> {
+        let Some(component) = self.find_component(component_id) else {
+            return Vec::new();
+        };
+        self.schematics.iter()
+            .flat_map(|s| s.components.iter())
+            .filter(|c| c.same_package(component))
+            .map(|c| c.id.as_str())
+            .collect()
+    }
+
     pub fn schematic_count(&self) -> usize {
         self.schematics.len()
     }
@@ -150,4 +245,58 @@ mod tests {
         assert_eq!(project.id, deserialized.id);
         assert_eq!(project.name, deserialized.name);
     }
+
+    #[test]
+    fn test_multi_unit_packages() {
+        let mut project = Project::new("Test Project".to_string()).unwrap();
+        let mut schematic = super::super::Schematic::new("Main".to_string()).unwrap();
+
+        for (unit, number) in [("A", "3"), ("B", "6")] {
+            let mut gate = super::super::Component::new("74HC00".to_string(), 0.0, 0.0).unwrap();
+            gate.reference = "U1".to_string();
+            gate.unit = Some(unit.to_string());
+            gate.pins.push(super::super::Pin {
+                id: format!("{}Y", unit),
+                name: "Y".to_string(),
+                number: number.to_string(),
+                x: 30.0,
+                y: 0.0,
+                pin_type: super::super::PinType::Output,
+                electrical: super::super::ElectricalType { voltage: None, current: None, impedance: None },
+                connected: false,
+            });
+            schematic.add_component(gate).unwrap();
+        }
+        let mut resistor = super::super::Component::new("resistor".to_string(), 0.0, 0.0).unwrap();
+        resistor.reference = "R1".to_string();
+        schematic.add_component(resistor).unwrap();
+
+        let first_unit = schematic.components[0].id.clone();
+        project.add_schematic(schematic).unwrap();
+
+        assert_eq!(project.package_components().len(), 2);
+        assert_eq!(project.schematics[0].components[1].display_reference(), "U1B");
+        let package = project.find_package(&first_unit).unwrap();
+        assert_eq!(package.pins.len(), 2);
+    }
+
+    #[test]
+    fn test_symbol_cache_round_trip() {
+        let symbol = super::super::ComponentSymbol { width: 40.0, height: 20.0, draw_commands: Vec::new(), graphics: None };
+        let template = super::super::ComponentTemplate::new("Resistor".to_string(), "passive".to_string(), symbol).unwrap();
+
+        let mut project = Project::new("Test Project".to_string()).unwrap();
+        let mut schematic = super::super::Schematic::new("Main".to_string()).unwrap();
+        for x in [0.0, 50.0] {
+            let component = super::super::Component::from_template(&template, "missing-lib".to_string(), x, 0.0).unwrap();
+            schematic.add_component(component).unwrap();
+        }
+        project.add_schematic(schematic).unwrap();
+        assert_eq!(project.used_templates(), vec![("missing-lib".to_string(), template.id.clone())]);
+
+        project.symbol_cache.push(CachedTemplate { library_id: "missing-lib".to_string(), template: template.clone() });
+        let restored = Project::from_json(&project.to_json().unwrap()).unwrap();
+        assert!(restored.cached_template("missing-lib", &template.id).is_some());
+        assert!(restored.cached_template("standard", &template.id).is_none());
+    }
 }
\ No newline at end of file
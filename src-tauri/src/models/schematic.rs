@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
+use std::collections::HashMap;
 use crate::utils::{error::AppError, validators};
 use crate::utils::error::Result;
 
@@ -7,6 +8,7 @@ const MAX_COMPONENTS: usize = 10000;
 const MAX_WIRES: usize = 50000;
 const MAX_NETS: usize = 10000;
 const MAX_LABELS: usize = 5000;
+const CONNECTION_TOLERANCE: f64 = 1.0;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -18,6 +20,10 @@ pub struct Schematic {
     pub nets: Vec<Net>,
     pub labels: Vec<Label>,
     pub metadata: SchematicMetadata,
+    #[serde(default)]
+    pub probes: Vec<crate::models::Probe>,
+    #[serde(default)]
+    pub operating_point: Option<crate::models::OperatingPoint>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -84,6 +90,8 @@ impl Schematic {
             nets: Vec::with_capacity(50),
             labels: Vec::with_capacity(20),
             metadata: SchematicMetadata::default(),
+            probes: Vec::new(),
+            operating_point: None,
         })
     }
     
@@ -178,13 +186,185 @@ impl Schematic {
         self.components.iter().find(|c| c.id == id)
     }
 
+    /// Package representatives on this sheet, see [`crate::models::component::package_components`].
+    pub fn package_components(&self) -> Vec<&crate::models::Component> {
+        crate::models::component::package_components(&self.components)
+    }
+
+    /// Stores a solved operating point and refreshes every probe's reading from it.
+    pub fn attach_operating_point(&mut self, operating_point: crate::models::OperatingPoint) {
+        for probe in &mut self.probes {
+            probe.value = probe.read(&operating_point);
+        }
+        self.operating_point = Some(operating_point);
+    }
+
+    /// Adds a probe, reading it from the stored operating point if there is one.
+    pub fn add_probe(&mut self, target: crate::models::ProbeTarget, x: f64, y: f64) -> Result<&crate::models::Probe> {
+        validators::validate_coordinate_strict(x)?;
+        validators::validate_coordinate_strict(y)?;
+
+        let mut probe = crate::models::Probe::new(target, x, y);
+        if let Some(operating_point) = &self.operating_point {
+            probe.value = probe.read(operating_point);
+        }
+        self.probes.push(probe);
+        Ok(&self.probes[self.probes.len() - 1])
+    }
+
+    pub fn remove_probe(&mut self, id: &str) -> Result<crate::models::Probe> {
+        match self.probes.iter().position(|p| p.id == id) {
+            Some(index) => Ok(self.probes.remove(index)),
+            None => Err(AppError::InvalidInput(format!("Probe not found: {}", id))),
+        }
+    }
+
     pub fn get_component_mut(&mut self, id: &str) -> Option<&mut crate::models::Component> {
         self.components.iter_mut().find(|c| c.id == id)
     }
 
+    /// Extracts connectivity from wire geometry, net labels and the explicit `nets` list.
+    /// Pins touching a wire, wires touching each other and labels sharing a name end up in one net.
     pub fn generate_netlist(&self) -> Vec<Net> {
-        // TODO: Implement netlist generation algorithm
-        self.nets.clone()
+        let pins: Vec<(usize, usize)> = self.components.iter().enumerate()
+            .flat_map(|(ci, c)| (0..c.pins.len()).map(move |pi| (ci, pi)))
+            .collect();
+        let wire_count = self.wires.len();
+        let mut sets = DisjointSet::new(wire_count + pins.len());
+
+        // Wire to wire: shared endpoints or an endpoint landing on another wire (T junction)
+        for (i, a) in self.wires.iter().enumerate() {
+            for (j, b) in self.wires.iter().enumerate().skip(i + 1) {
+                let touches = |from: &crate::models::Wire, to: &crate::models::Wire| {
+                    [from.points.first(), from.points.last()].iter().flatten()
+                        .any(|p| to.hit_test(p.x, p.y, CONNECTION_TOLERANCE))
+                };
+                if touches(a, b) || touches(b, a) {
+                    sets.union(i, j);
+                }
+            }
+        }
+
+        // Pin to wire
+        for (k, &(ci, pi)) in pins.iter().enumerate() {
+            let (x, y) = self.components[ci].pin_position(&self.components[ci].pins[pi]);
+            for (w, wire) in self.wires.iter().enumerate() {
+                if wire.hit_test(x, y, CONNECTION_TOLERANCE) {
+                    sets.union(wire_count + k, w);
+                }
+            }
+        }
+
+        // Labels with the same text connect the wires they sit on
+        let mut label_roots: HashMap<&str, usize> = HashMap::new();
+        let mut label_wires: Vec<(usize, &str)> = Vec::new();
+        for label in &self.labels {
+            if let Some(w) = self.wires.iter().position(|wire| wire.hit_test(label.x, label.y, CONNECTION_TOLERANCE)) {
+                label_wires.push((w, label.text.as_str()));
+                match label_roots.get(label.text.as_str()) {
+                    Some(&other) => sets.union(other, w),
+                    None => {
+                        label_roots.insert(label.text.as_str(), w);
+                    }
+                }
+            }
+        }
+
+        // Explicit nets
+        let pin_index: HashMap<(&str, &str), usize> = pins.iter().enumerate()
+            .map(|(k, &(ci, pi))| ((self.components[ci].id.as_str(), self.components[ci].pins[pi].id.as_str()), wire_count + k))
+            .collect();
+        for net in &self.nets {
+            let members: Vec<usize> = net.pins.iter()
+                .filter_map(|p| pin_index.get(&(p.component_id.as_str(), p.pin_id.as_str())).copied())
+                .collect();
+            for pair in members.windows(2) {
+                sets.union(pair[0], pair[1]);
+            }
+        }
+
+        // Units of one annotated package share physical pins, e.g. common power pins
+        let mut package_pins: HashMap<(&str, &str), usize> = HashMap::new();
+        for (k, &(ci, pi)) in pins.iter().enumerate() {
+            let component = &self.components[ci];
+            if component.unit.is_none() || component.reference.ends_with('?') {
+                continue;
+            }
+            let key = (component.reference.as_str(), component.pins[pi].number.as_str());
+            match package_pins.get(&key) {
+                Some(&other) => sets.union(other, wire_count + k),
+                None => {
+                    package_pins.insert(key, wire_count + k);
+                }
+            }
+        }
+
+        // Collect pins per root
+        let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (k, &(ci, pi)) in pins.iter().enumerate() {
+            let pin = &self.components[ci].pins[pi];
+            let on_wire = (0..wire_count).any(|w| sets.find(w) == sets.find(wire_count + k));
+            let explicit = self.nets.iter().any(|n| n.pins.iter().any(|p| p.component_id == self.components[ci].id && p.pin_id == pin.id));
+            if on_wire || explicit {
+                groups.entry(sets.find(wire_count + k)).or_default().push(k);
+            }
+        }
+
+        let mut nets: Vec<Net> = groups.into_iter().map(|(root, members)| {
+            let connections: Vec<PinConnection> = members.iter().map(|&k| {
+                let (ci, pi) = pins[k];
+                PinConnection {
+                    component_id: self.components[ci].id.clone(),
+                    pin_id: self.components[ci].pins[pi].id.clone(),
+                }
+            }).collect();
+
+            let explicit = self.nets.iter().find(|n| n.pins.iter().any(|p| {
+                connections.iter().any(|c| c.component_id == p.component_id && c.pin_id == p.pin_id)
+            }));
+            let label = label_wires.iter().find(|(w, _)| sets.find(*w) == root).map(|(_, text)| text.to_string());
+
+            let (id, name) = match (explicit, label) {
+                (Some(net), _) => (net.id.clone(), net.name.clone()),
+                (None, Some(text)) => (format!("net-{}", text), text),
+                (None, None) => {
+                    let (ci, pi) = pins[members[0]];
+                    let name = format!("Net-({}-Pad{})", self.components[ci].reference, self.components[ci].pins[pi].number);
+                    (format!("net-{}", name), name)
+                }
+            };
+
+            Net { id, name, pins: connections }
+        }).collect();
+
+        nets.sort_by(|a, b| a.name.cmp(&b.name));
+        nets
+    }
+}
+
+// Union-find used by netlist extraction
+struct DisjointSet {
+    parent: Vec<usize>,
+}
+
+impl DisjointSet {
+    fn new(size: usize) -> Self {
+        Self { parent: (0..size).collect() }
+    }
+
+    fn find(&mut self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            self.parent[x] = self.parent[self.parent[x]];
+            x = self.parent[x];
+        }
+        x
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (ra, rb) = (self.find(a), self.find(b));
+        if ra != rb {
+            self.parent[rb] = ra;
+        }
     }
 }
 
@@ -215,4 +395,39 @@ mod tests {
         assert!(removed.is_some());
         assert!(schematic.components.is_empty());
     }
+
+    #[test]
+    fn test_generate_netlist_from_wires() {
+        use crate::models::{Component, ElectricalType, Pin, PinType, Point, Wire};
+
+        let pin = |id: &str, x: f64| Pin {
+            id: id.to_string(),
+            name: id.to_string(),
+            number: id.to_string(),
+            x,
+            y: 0.0,
+            pin_type: PinType::Passive,
+            electrical: ElectricalType { voltage: None, current: None, impedance: None },
+            connected: false,
+        };
+
+        let mut schematic = Schematic::new("Test".to_string()).unwrap();
+        let mut r1 = Component::new("resistor".to_string(), 0.0, 0.0).unwrap();
+        r1.reference = "R1".to_string();
+        r1.pins = vec![pin("1", -30.0), pin("2", 30.0)];
+        let mut r2 = Component::new("resistor".to_string(), 100.0, 0.0).unwrap();
+        r2.reference = "R2".to_string();
+        r2.pins = vec![pin("1", -30.0), pin("2", 30.0)];
+        schematic.add_component(r1).unwrap();
+        schematic.add_component(r2).unwrap();
+
+        // R1.2 -> R2.1 via two wires meeting at a corner
+        schematic.add_wire(Wire::new(Point::new(30.0, 0.0), Point::new(50.0, 0.0))).unwrap();
+        schematic.add_wire(Wire::new(Point::new(50.0, 0.0), Point::new(70.0, 0.0))).unwrap();
+
+        let nets = schematic.generate_netlist();
+        assert_eq!(nets.len(), 1);
+        assert_eq!(nets[0].pins.len(), 2);
+        assert_eq!(nets[0].name, "Net-(R1-Pad2)");
+    }
 }
\ No newline at end of file
@@ -0,0 +1,400 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::utils::error::{AppError, Result};
+
+// DC operating point of a schematic, keyed to the nets and pins it was solved from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperatingPoint {
+    pub node_voltages: Vec<NodeVoltage>,
+    pub pin_currents: Vec<PinCurrent>,
+    pub iterations: usize,
+    // Components the solver has no model for; their pins are left open
+    pub unsupported: Vec<String>,
+    pub solved_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeVoltage {
+    pub net_id: String,
+    pub net_name: String,
+    pub voltage: f64,
+}
+
+// Positive current flows from the net into the pin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PinCurrent {
+    pub component_id: String,
+    pub reference: String,
+    pub pin_id: String,
+    pub current: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Probe {
+    pub id: String,
+    pub target: ProbeTarget,
+    pub x: f64,
+    pub y: f64,
+    pub value: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum ProbeTarget {
+    #[serde(rename_all = "camelCase")]
+    Voltage { net_id: String },
+    #[serde(rename_all = "camelCase")]
+    Current { component_id: String, pin_id: String },
+}
+
+impl OperatingPoint {
+    pub fn voltage(&self, net_id: &str) -> Option<f64> {
+        self.node_voltages.iter().find(|n| n.net_id == net_id).map(|n| n.voltage)
+    }
+
+    pub fn current(&self, component_id: &str, pin_id: &str) -> Option<f64> {
+        self.pin_currents.iter()
+            .find(|p| p.component_id == component_id && p.pin_id == pin_id)
+            .map(|p| p.current)
+    }
+}
+
+impl Probe {
+    pub fn new(target: ProbeTarget, x: f64, y: f64) -> Self {
+        Self { id: Uuid::new_v4().to_string(), target, x, y, value: None }
+    }
+
+    pub fn read(&self, operating_point: &OperatingPoint) -> Option<f64> {
+        match &self.target {
+            ProbeTarget::Voltage { net_id } => operating_point.voltage(net_id),
+            ProbeTarget::Current { component_id, pin_id } => operating_point.current(component_id, pin_id),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IntegrationMethod {
+    #[default]
+    Trapezoidal,
+    BackwardEuler,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransientSettings {
+    pub stop_time: f64,
+    pub step: f64,
+    // Samples before this time are simulated but not recorded
+    #[serde(default)]
+    pub start_time: f64,
+    #[serde(default)]
+    pub method: IntegrationMethod,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AcSweepSettings {
+    pub start_frequency: f64,
+    pub stop_frequency: f64,
+    pub points_per_decade: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AnalysisType {
+    Transient,
+    Ac,
+}
+
+// Simulation output in columns; the first column is the sweep variable (time or frequency)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Waveforms {
+    pub analysis: AnalysisType,
+    pub columns: Vec<WaveformColumn>,
+    pub unsupported: Vec<String>,
+    pub simulated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WaveformColumn {
+    pub name: String,
+    pub unit: String,
+    // Net or pin the column belongs to, so probes can pick their traces
+    pub target: Option<ProbeTarget>,
+    pub values: Vec<f64>,
+}
+
+const MAX_SIMULATION_POINTS: f64 = 1_000_000.0;
+
+impl TransientSettings {
+    pub fn validate(&self) -> Result<()> {
+        if !(self.stop_time.is_finite() && self.stop_time > 0.0) {
+            return Err(AppError::InvalidInput("Transient stop time must be positive".to_string()));
+        }
+        if !(self.step.is_finite() && self.step > 0.0 && self.step <= self.stop_time) {
+            return Err(AppError::InvalidInput("Transient step must be positive and no longer than the stop time".to_string()));
+        }
+        if !(0.0..self.stop_time).contains(&self.start_time) {
+            return Err(AppError::InvalidInput("Transient start time must lie between zero and the stop time".to_string()));
+        }
+        if self.stop_time / self.step > MAX_SIMULATION_POINTS {
+            return Err(AppError::InvalidInput("Transient step is too small for the stop time".to_string()));
+        }
+        Ok(())
+    }
+}
+
+impl AcSweepSettings {
+    pub fn validate(&self) -> Result<()> {
+        let range_valid = self.start_frequency.is_finite() && self.start_frequency > 0.0
+            && self.stop_frequency.is_finite() && self.stop_frequency >= self.start_frequency;
+        if !range_valid {
+            return Err(AppError::InvalidInput("AC sweep needs 0 < start frequency <= stop frequency".to_string()));
+        }
+        let points = self.points_per_decade as f64 * (self.stop_frequency / self.start_frequency).log10();
+        if self.points_per_decade == 0 || points > MAX_SIMULATION_POINTS {
+            return Err(AppError::InvalidInput("AC sweep points per decade out of range".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Logarithmically spaced sweep frequencies, both ends included.
+    pub fn frequencies(&self) -> Vec<f64> {
+        let decades = (self.stop_frequency / self.start_frequency).log10();
+        let count = (decades * self.points_per_decade as f64).ceil() as usize;
+        let mut frequencies: Vec<f64> = (0..count)
+            .map(|i| self.start_frequency * 10f64.powf(i as f64 / self.points_per_decade as f64))
+            .collect();
+        // Rounding in the decade count can land the last step on the stop frequency itself
+        while frequencies.last().is_some_and(|&f| f >= self.stop_frequency * (1.0 - 1e-9)) {
+            frequencies.pop();
+        }
+        frequencies.push(self.stop_frequency);
+        frequencies
+    }
+}
+
+impl Waveforms {
+    pub fn axis(&self) -> Option<&WaveformColumn> {
+        self.columns.first()
+    }
+
+    pub fn column(&self, name: &str) -> Option<&WaveformColumn> {
+        self.columns.iter().find(|c| c.name == name)
+    }
+
+    /// Columns recorded for a probe target; AC sweeps have a magnitude and a phase column each.
+    pub fn columns_for<'a>(&'a self, target: &'a ProbeTarget) -> impl Iterator<Item = &'a WaveformColumn> + 'a {
+        self.columns.iter().filter(move |c| c.target.as_ref() == Some(target))
+    }
+
+    pub fn len(&self) -> usize {
+        self.axis().map_or(0, |axis| axis.values.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Four-state logic value as used by the digital simulator and VCD files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LogicLevel {
+    #[serde(rename = "0")]
+    Low,
+    #[serde(rename = "1")]
+    High,
+    #[serde(rename = "x")]
+    Unknown,
+    #[default]
+    #[serde(rename = "z")]
+    HighZ,
+}
+
+impl LogicLevel {
+    pub fn from_char(c: char) -> Option<Self> {
+        match c.to_ascii_lowercase() {
+            '0' => Some(LogicLevel::Low),
+            '1' => Some(LogicLevel::High),
+            'x' => Some(LogicLevel::Unknown),
+            'z' => Some(LogicLevel::HighZ),
+            _ => None,
+        }
+    }
+
+    pub fn as_char(self) -> char {
+        match self {
+            LogicLevel::Low => '0',
+            LogicLevel::High => '1',
+            LogicLevel::Unknown => 'x',
+            LogicLevel::HighZ => 'z',
+        }
+    }
+
+    pub fn from_bool(value: bool) -> Self {
+        if value { LogicLevel::High } else { LogicLevel::Low }
+    }
+
+    // Known value, if any; undriven inputs read as unknown
+    pub fn to_bool(self) -> Option<bool> {
+        match self {
+            LogicLevel::Low => Some(false),
+            LogicLevel::High => Some(true),
+            _ => None,
+        }
+    }
+}
+
+/// Square wave on a net: low until `delay`, then high for `duty` of every period.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClockGenerator {
+    // Net name or id
+    pub net: String,
+    pub period: f64,
+    #[serde(default)]
+    pub delay: f64,
+    #[serde(default = "default_duty")]
+    pub duty: f64,
+}
+
+fn default_duty() -> f64 {
+    0.5
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigitalStimulus {
+    // Net name or id
+    pub net: String,
+    // (time in seconds, level) pairs in time order
+    pub changes: Vec<(f64, LogicLevel)>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigitalSettings {
+    pub stop_time: f64,
+    #[serde(default)]
+    pub clocks: Vec<ClockGenerator>,
+    #[serde(default)]
+    pub stimuli: Vec<DigitalStimulus>,
+    // Propagation delay for parts without a `delay` property
+    #[serde(default)]
+    pub default_delay: Option<f64>,
+}
+
+/// Value changes per net from a digital run. Times are integer picoseconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigitalWaveforms {
+    pub scope: String,
+    pub end_time: u64,
+    pub signals: Vec<DigitalSignal>,
+    pub unsupported: Vec<String>,
+    pub simulated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigitalSignal {
+    pub net_id: String,
+    pub name: String,
+    pub changes: Vec<LogicChange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogicChange {
+    pub time: u64,
+    pub value: LogicLevel,
+}
+
+impl DigitalSettings {
+    pub fn validate(&self) -> Result<()> {
+        if !(self.stop_time.is_finite() && self.stop_time > 0.0) {
+            return Err(AppError::InvalidInput("Digital simulation stop time must be positive".to_string()));
+        }
+        for clock in &self.clocks {
+            if !(clock.period.is_finite() && clock.period > 0.0 && clock.duty > 0.0 && clock.duty < 1.0 && clock.delay >= 0.0) {
+                return Err(AppError::InvalidInput(format!("Invalid clock on net {}", clock.net)));
+            }
+            if self.stop_time / clock.period > MAX_SIMULATION_POINTS {
+                return Err(AppError::InvalidInput(format!("Clock on net {} is too fast for the stop time", clock.net)));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl DigitalSignal {
+    /// Level at `time`, i.e. the last change at or before it.
+    pub fn value_at(&self, time: u64) -> LogicLevel {
+        self.changes.iter()
+            .take_while(|change| change.time <= time)
+            .last()
+            .map_or(LogicLevel::HighZ, |change| change.value)
+    }
+}
+
+impl DigitalWaveforms {
+    pub fn signal(&self, name: &str) -> Option<&DigitalSignal> {
+        self.signals.iter().find(|s| s.name == name || s.net_id == name)
+    }
+}
+
+/// Analysis requested from an external SPICE simulator.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum SpiceAnalysis {
+    OperatingPoint,
+    Transient(TransientSettings),
+    Ac(AcSweepSettings),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum SpiceResult {
+    OperatingPoint(OperatingPoint),
+    Waveforms(Waveforms),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ac_sweep_frequencies() {
+        let sweep = |start: f64, stop: f64, points_per_decade: u32| {
+            AcSweepSettings { start_frequency: start, stop_frequency: stop, points_per_decade }.frequencies()
+        };
+        let decades = sweep(1.0, 1000.0, 10);
+        assert_eq!(decades.len(), 31);
+        assert!((decades[10] - 10.0).abs() < 1e-9);
+        assert_eq!(sweep(50.0, 50.0, 10), vec![50.0]);
+        assert_eq!(sweep(1.0, 2.0, 1), vec![1.0, 2.0]);
+
+        // Ranges that end on a sweep step must not list the stop frequency twice, even when
+        // rounding puts the decade count just above a whole number of steps
+        assert_eq!(sweep(1.0, 10f64.powf(0.2), 5).len(), 2);
+        for start in [0.1, 1.0, 2.0, 20.0, 1e3] {
+            for points_per_decade in [1, 2, 5, 10, 20, 50] {
+                for steps in 1..=3 * points_per_decade {
+                    let stop = start * 10f64.powf(steps as f64 / points_per_decade as f64);
+                    let frequencies = sweep(start, stop, points_per_decade);
+                    assert_eq!(frequencies.len(), steps as usize + 1, "{} to {} at {}", start, stop, points_per_decade);
+                    assert_eq!(*frequencies.last().unwrap(), stop);
+                    assert!(frequencies.windows(2).all(|w| w[1] > w[0] * (1.0 + 1e-3)), "{} to {} at {}", start, stop, points_per_decade);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use crate::models::component::reference_prefix;
+use crate::models::Project;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnotationChange {
+    pub component_id: String,
+    pub old_reference: String,
+    pub new_reference: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnotationResult {
+    pub project: Project,
+    pub changes: Vec<AnnotationChange>,
+}
+
+// A numbered package that still has room for more units
+struct OpenPackage {
+    prefix: String,
+    number: u32,
+    library_id: Option<String>,
+    type_id: String,
+    value: String,
+    used_units: Vec<String>,
+}
+
+pub struct AnnotationService;
+
+impl AnnotationService {
+    /// Numbers every component whose reference still ends in `?` (all of them with `reset`),
+    /// sheet by sheet in reading order. Units of multi-unit parts fill existing packages of
+    /// the same part and value before a new package number is opened.
+    pub fn annotate_project(project: &mut Project, reset: bool) -> Vec<AnnotationChange> {
+        if reset {
+            for component in project.schematics.iter_mut().flat_map(|s| s.components.iter_mut()) {
+                component.reference = format!("{}?", reference_prefix(&component.reference));
+            }
+        }
+
+        let mut used: HashMap<String, HashSet<u32>> = HashMap::new();
+        let mut packages: Vec<OpenPackage> = Vec::new();
+        for component in project.schematics.iter().flat_map(|s| s.components.iter()) {
+            let prefix = reference_prefix(&component.reference);
+            let Ok(number) = component.reference[prefix.len()..].parse::<u32>() else {
+                continue;
+            };
+            used.entry(prefix.to_string()).or_default().insert(number);
+
+            if let Some(unit) = &component.unit {
+                match packages.iter_mut().find(|p| p.prefix == prefix && p.number == number) {
+                    Some(package) => package.used_units.push(unit.clone()),
+                    None => packages.push(OpenPackage {
+                        prefix: prefix.to_string(),
+                        number,
+                        library_id: component.library_id.clone(),
+                        type_id: component.type_id.clone(),
+                        value: component.value.clone(),
+                        used_units: vec![unit.clone()],
+                    }),
+                }
+            }
+        }
+
+        let mut changes = Vec::new();
+        for schematic in &mut project.schematics {
+            let mut order: Vec<usize> = (0..schematic.components.len())
+                .filter(|&i| schematic.components[i].reference.ends_with('?'))
+                .collect();
+            order.sort_by(|&a, &b| {
+                let (ca, cb) = (&schematic.components[a], &schematic.components[b]);
+                ca.y.total_cmp(&cb.y).then(ca.x.total_cmp(&cb.x))
+            });
+
+            for index in order {
+                let component = &mut schematic.components[index];
+                let prefix = match reference_prefix(&component.reference) {
+                    "" => "U".to_string(),
+                    prefix => prefix.to_string(),
+                };
+
+                let packed = component.unit.as_ref().and_then(|unit| {
+                    packages.iter_mut()
+                        .find(|p| {
+                            p.prefix == prefix
+                                && p.library_id == component.library_id
+                                && p.type_id == component.type_id
+                                && p.value == component.value
+                                && !p.used_units.contains(unit)
+                        })
+                        .map(|p| {
+                            p.used_units.push(unit.clone());
+                            p.number
+                        })
+                });
+
+                let number = match packed {
+                    Some(number) => number,
+                    None => {
+                        let taken = used.entry(prefix.clone()).or_default();
+                        let number = (1..).find(|n| !taken.contains(n)).unwrap_or(1);
+                        taken.insert(number);
+                        if let Some(unit) = &component.unit {
+                            packages.push(OpenPackage {
+                                prefix: prefix.clone(),
+                                number,
+                                library_id: component.library_id.clone(),
+                                type_id: component.type_id.clone(),
+                                value: component.value.clone(),
+                                used_units: vec![unit.clone()],
+                            });
+                        }
+                        number
+                    }
+                };
+
+                let old_reference = component.display_reference();
+                component.reference = format!("{}{}", prefix, number);
+                changes.push(AnnotationChange {
+                    component_id: component.id.clone(),
+                    old_reference,
+                    new_reference: component.display_reference(),
+                });
+            }
+        }
+
+        if !changes.is_empty() {
+            project.update_modified();
+        }
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Component, Schematic};
+
+    fn part(type_id: &str, reference: &str, unit: Option<&str>, y: f64) -> Component {
+        let mut component = Component::new(type_id.to_string(), 0.0, y).unwrap();
+        component.reference = reference.to_string();
+        component.unit = unit.map(str::to_string);
+        component.library_id = Some("standard".to_string());
+        component.value = type_id.to_string();
+        component
+    }
+
+    fn references(project: &Project) -> Vec<String> {
+        project.schematics.iter()
+            .flat_map(|s| s.components.iter())
+            .map(|c| c.display_reference())
+            .collect()
+    }
+
+    #[test]
+    fn test_annotate_packs_units() {
+        let mut project = Project::new("Test Project".to_string()).unwrap();
+        let mut first = Schematic::new("First".to_string()).unwrap();
+        for component in [
+            part("74HC00", "U1", Some("A"), 0.0),
+            part("74HC00", "U?", Some("A"), 40.0),
+            part("74HC00", "U?", Some("B"), 20.0),
+            part("74HC04", "U?", Some("A"), 10.0),
+            part("resistor", "R1", None, 0.0),
+            part("resistor", "R?", None, 50.0),
+        ] {
+            first.add_component(component).unwrap();
+        }
+        let mut second = Schematic::new("Second".to_string()).unwrap();
+        for component in [part("74HC00", "U?", Some("B"), 0.0), part("74HC00", "U?", Some("C"), 10.0)] {
+            second.add_component(component).unwrap();
+        }
+        project.add_schematic(first).unwrap();
+        project.add_schematic(second).unwrap();
+
+        // Reading order is top to bottom; free units join U1 before a new package opens,
+        // and a different part never shares a package
+        let changes = AnnotationService::annotate_project(&mut project, false);
+        assert_eq!(changes.len(), 6);
+        assert_eq!(references(&project), ["U1A", "U3A", "U1B", "U2A", "R1", "R2", "U3B", "U1C"]);
+        assert_eq!(changes[0].old_reference, "U?A");
+        assert_eq!(changes[0].new_reference, "U2A");
+        assert_eq!(project.package_components().len(), 5);
+
+        // Nothing left to number
+        assert!(AnnotationService::annotate_project(&mut project, false).is_empty());
+
+        let changes = AnnotationService::annotate_project(&mut project, true);
+        assert_eq!(changes.len(), 8);
+        assert_eq!(references(&project), ["U1A", "U3A", "U1B", "U2A", "R1", "R2", "U3B", "U1C"]);
+    }
+}
@@ -0,0 +1,802 @@
+use crate::models::{Board, DesignRules, FootprintPoint, LayerType, Project, Track, Via};
+use crate::services::drc_service::{connectivity, copper_items, CopperItem, CopperKind};
+use crate::services::file_service::FileService;
+use crate::services::ratsnest_service::Ratsnest;
+use crate::utils::error::{AppError, Result};
+use crate::utils::geometry::{distance_point_segment, point_in_contours, Shape, Vec2};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::f64::consts::{FRAC_1_SQRT_2, SQRT_2};
+use std::path::{Path, PathBuf};
+
+// Cells per layer the router will allocate before asking for a coarser grid
+const MAX_GRID_CELLS: usize = 4_000_000;
+// Extra cost per 45° of direction change, in grid steps
+const BEND_COST: f64 = 0.5;
+// Cost per cell when probing through autorouted copper for nets to rip up, in grid steps
+const RIPUP_COST: f64 = 20.0;
+
+const FREE: u32 = 0;
+const FIXED: u32 = u32::MAX;
+const NO_DIRECTION: usize = 8;
+const DIRECTIONS: [(i64, i64); 8] = [(1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1)];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutorouteOptions {
+    #[serde(default = "default_grid_size")]
+    pub grid_size: f64,
+    #[serde(default)]
+    pub layers: Vec<LayerType>, // empty: top copper, plus bottom copper on multilayer boards
+    #[serde(default)]
+    pub net_ids: Vec<String>, // empty: every net
+    #[serde(default = "default_via_cost")]
+    pub via_cost: f64, // millimetres of track a via is worth
+    #[serde(default = "default_max_ripups")]
+    pub max_ripups: usize, // rip-up rounds a failing net may trigger
+}
+
+fn default_grid_size() -> f64 {
+    0.2
+}
+
+fn default_via_cost() -> f64 {
+    2.0
+}
+
+fn default_max_ripups() -> usize {
+    3
+}
+
+impl Default for AutorouteOptions {
+    fn default() -> Self {
+        Self {
+            grid_size: default_grid_size(),
+            layers: Vec::new(),
+            net_ids: Vec::new(),
+            via_cost: default_via_cost(),
+            max_ripups: default_max_ripups(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnroutedNet {
+    pub net_id: String,
+    pub net_name: String,
+    pub open_connections: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutorouteReport {
+    pub routed_nets: Vec<String>,
+    pub unroutable: Vec<UnroutedNet>,
+    pub tracks_added: usize,
+    pub vias_added: usize,
+    pub track_length: f64,
+    pub ripups: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutorouteResult {
+    pub project: Project,
+    pub report: AutorouteReport,
+}
+
+/// Outcome of one project in a batch run; `error` is set when the file couldn't be routed at all.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchRouteOutcome {
+    pub project_path: String,
+    pub output_path: Option<String>,
+    pub report: Option<AutorouteReport>,
+    pub error: Option<String>,
+}
+
+// Cell centres on a square grid over the board outline
+struct Grid {
+    origin: Vec2,
+    size: f64,
+    cols: usize,
+    rows: usize,
+}
+
+impl Grid {
+    fn cells(&self) -> usize {
+        self.cols * self.rows
+    }
+
+    fn point(&self, cell: usize) -> Vec2 {
+        let (col, row) = (cell % self.cols, cell / self.cols);
+        (self.origin.0 + col as f64 * self.size, self.origin.1 + row as f64 * self.size)
+    }
+
+    fn nearest(&self, point: Vec2) -> Option<usize> {
+        let col = ((point.0 - self.origin.0) / self.size).round();
+        let row = ((point.1 - self.origin.1) / self.size).round();
+        (col >= 0.0 && row >= 0.0 && (col as usize) < self.cols && (row as usize) < self.rows)
+            .then(|| row as usize * self.cols + col as usize)
+    }
+
+    // Cells whose centre lies in the box, which may be empty
+    fn cells_in(&self, (x0, y0, x1, y1): (f64, f64, f64, f64)) -> impl Iterator<Item = usize> + '_ {
+        let col = |x: f64| ((x - self.origin.0) / self.size).clamp(0.0, self.cols as f64 - 1.0);
+        let row = |y: f64| ((y - self.origin.1) / self.size).clamp(0.0, self.rows as f64 - 1.0);
+        let (c0, c1) = (col(x0).ceil() as usize, col(x1).floor() as usize);
+        let (r0, r1) = (row(y0).ceil() as usize, row(y1).floor() as usize);
+        (r0..=r1).flat_map(move |r| (c0..=c1).map(move |c| r * self.cols + c))
+    }
+
+    fn step(&self, cell: usize, direction: usize) -> Option<usize> {
+        let (dx, dy) = DIRECTIONS[direction];
+        let col = (cell % self.cols) as i64 + dx;
+        let row = (cell / self.cols) as i64 + dy;
+        (col >= 0 && row >= 0 && (col as usize) < self.cols && (row as usize) < self.rows)
+            .then(|| row as usize * self.cols + col as usize)
+    }
+}
+
+// What a net may not cross: FREE, FIXED, or 1 + the index of the autorouted net in the way
+struct Obstacles {
+    tracks: Vec<Vec<u32>>, // per routing layer
+    vias: Vec<u32>,
+}
+
+fn mark(cell: &mut u32, owner: u32) {
+    if *cell == FREE || owner == FIXED {
+        *cell = owner;
+    }
+}
+
+// A set of grid nodes (routing layer slot, cell) reached by one connected piece of copper
+#[derive(Default)]
+struct Terminal {
+    nodes: Vec<(usize, usize)>,
+    // Pad centres for nodes snapped from pads too small to contain a cell centre
+    anchors: HashMap<(usize, usize), Vec2>,
+    bounds: (usize, usize, usize, usize), // col/row box
+}
+
+struct SearchResult {
+    path: Vec<(usize, usize)>,
+    target: usize,
+}
+
+#[derive(PartialEq)]
+struct Open {
+    cost: f64,
+    state: usize,
+}
+
+impl Eq for Open {}
+
+impl Ord for Open {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost).then(other.state.cmp(&self.state))
+    }
+}
+
+impl PartialOrd for Open {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Grid-based A* router with rip-up and retry. Routes the open ratsnest connections on up
+/// to two copper layers with net-class widths, clearances and vias, keeping out of keepouts
+/// and away from the board edge. Tracks it adds may be ripped up to let a blocked net
+/// through; copper that was on the board before is never moved.
+pub struct Autorouter {
+    board: Board,
+    rules: DesignRules,
+    options: AutorouteOptions,
+    layers: Vec<LayerType>,
+    grid: Grid,
+    inside: Vec<bool>,
+    edge_distance: Vec<f64>,
+    net_names: HashMap<String, String>,
+    // Autorouted track or via id -> 1 + net index
+    routed: HashMap<String, u32>,
+    ripups: usize,
+}
+
+impl Autorouter {
+    pub fn new(board: Board, rules: DesignRules, options: AutorouteOptions) -> Result<Self> {
+        if options.grid_size.is_nan() || options.grid_size <= 0.0 {
+            return Err(AppError::InvalidInput("Grid size must be positive".to_string()));
+        }
+        let copper = board.stackup.copper_layers();
+        let layers = if options.layers.is_empty() {
+            copper.iter().take(1).chain(copper.iter().skip(1).last()).cloned().collect()
+        } else {
+            options.layers.clone()
+        };
+        if layers.is_empty() || layers.len() > 2 {
+            return Err(AppError::InvalidInput("The autorouter routes on one or two copper layers".to_string()));
+        }
+        if let Some(layer) = layers.iter().find(|l| !copper.contains(l)) {
+            return Err(AppError::InvalidInput(format!("{:?} is not a copper layer of this board", layer)));
+        }
+
+        let outline = board.outline_contours()
+            .ok_or_else(|| AppError::InvalidOperation("The board needs a closed outline before autorouting".to_string()))?;
+        let (x0, y0, x1, y1) = board.outline_bounds().unwrap_or((0.0, 0.0, 0.0, 0.0));
+        let size = options.grid_size;
+        let grid = Grid {
+            origin: ((x0 / size).ceil() * size, (y0 / size).ceil() * size),
+            size,
+            cols: ((x1 - x0) / size) as usize + 1,
+            rows: ((y1 - y0) / size) as usize + 1,
+        };
+        if grid.cells() > MAX_GRID_CELLS {
+            return Err(AppError::InvalidInput(format!(
+                "A {} mm grid needs {} cells for this board; use a coarser grid", size, grid.cells()
+            )));
+        }
+
+        let segments = board.outline_segments();
+        let mut inside = Vec::with_capacity(grid.cells());
+        let mut edge_distance = Vec::with_capacity(grid.cells());
+        for cell in 0..grid.cells() {
+            let point = grid.point(cell);
+            inside.push(point_in_contours(point, &outline));
+            edge_distance.push(segments.iter().map(|&(a, b)| distance_point_segment(point, a, b)).fold(f64::INFINITY, f64::min));
+        }
+
+        let net_names = board.nets.iter().map(|n| (n.id.clone(), n.name.clone())).collect();
+        Ok(Self { board, rules, options, layers, grid, inside, edge_distance, net_names, routed: HashMap::new(), ripups: 0 })
+    }
+
+    pub fn run(mut self) -> Result<(Board, AutorouteReport)> {
+        let net_ids: Vec<String> = self.board.nets.iter().map(|n| n.id.clone()).collect();
+        let targets: Vec<String> = if self.options.net_ids.is_empty() {
+            net_ids.clone()
+        } else {
+            self.options.net_ids.iter().filter(|n| net_ids.contains(n)).cloned().collect()
+        };
+
+        // Shortest nets first; they have the fewest ways around each other
+        let ratsnest = Ratsnest::build(&self.board);
+        let mut order: Vec<(f64, String)> = targets.iter()
+            .filter_map(|n| ratsnest.nets.get(n).map(|lines| (lines.iter().map(|l| l.length).sum(), n.clone())))
+            .collect();
+        order.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut queue: VecDeque<String> = order.into_iter().map(|(_, n)| n).collect();
+
+        let mut ripup_rounds: HashMap<String, usize> = HashMap::new();
+        while let Some(net) = queue.pop_front() {
+            let owner = net_ids.iter().position(|n| *n == net).unwrap_or(0) as u32 + 1;
+            if self.route_net(&net, owner, false)?.is_empty() {
+                continue;
+            }
+            let rounds = ripup_rounds.entry(net.clone()).or_default();
+            if *rounds >= self.options.max_ripups {
+                continue;
+            }
+            *rounds += 1;
+
+            // Find a way through autorouted copper and clear the nets on it
+            let blockers = self.route_net(&net, owner, true)?;
+            if blockers.contains(&FIXED) {
+                continue;
+            }
+            for blocker in &blockers {
+                let blocker_net = &net_ids[*blocker as usize - 1];
+                self.rip_up(*blocker);
+                if !queue.contains(blocker_net) {
+                    queue.push_back(blocker_net.clone());
+                }
+            }
+            queue.push_front(net);
+        }
+
+        let mut report = AutorouteReport { ripups: self.ripups, ..Default::default() };
+        for track in self.board.tracks.iter().filter(|t| self.routed.contains_key(&t.id)) {
+            report.tracks_added += 1;
+            report.track_length += track.length();
+        }
+        report.vias_added = self.board.vias.iter().filter(|v| self.routed.contains_key(&v.id)).count();
+
+        let ratsnest = Ratsnest::build(&self.board);
+        for net in &targets {
+            let name = self.net_name(net).to_string();
+            match ratsnest.nets.get(net) {
+                Some(lines) => report.unroutable.push(UnroutedNet { net_id: net.clone(), net_name: name, open_connections: lines.len() }),
+                None => report.routed_nets.push(name),
+            }
+        }
+        Ok((self.board, report))
+    }
+
+    fn net_name<'a>(&'a self, net_id: &'a str) -> &'a str {
+        self.net_names.get(net_id).map(|n| n.as_str()).unwrap_or(net_id)
+    }
+
+    fn rip_up(&mut self, owner: u32) {
+        let routed = &self.routed;
+        self.board.tracks.retain(|t| routed.get(&t.id) != Some(&owner));
+        self.board.vias.retain(|v| routed.get(&v.id) != Some(&owner));
+        self.routed.retain(|_, o| *o != owner);
+        self.ripups += 1;
+    }
+
+    /// Connects the net's copper islands one at a time. Returns the owners of the obstacles
+    /// on a probe path, or while routing for real, a non-empty set when the net stays open.
+    fn route_net(&mut self, net: &str, owner: u32, probe: bool) -> Result<HashSet<u32>> {
+        let items = copper_items(&self.board);
+        let net_items: Vec<CopperItem> = items.iter().filter(|i| i.net_id.as_deref() == Some(net)).cloned().collect();
+        let roots = connectivity(&net_items);
+        let mut islands: Vec<usize> = Vec::new();
+        for (index, item) in net_items.iter().enumerate() {
+            if item.kind == CopperKind::Pad && !islands.contains(&roots[index]) {
+                islands.push(roots[index]);
+            }
+        }
+        if islands.len() < 2 {
+            return Ok(HashSet::new());
+        }
+
+        let net_name = self.net_name(net).to_string();
+        let width = self.rules.route_width_for(Some(&net_name));
+        let (via_diameter, via_drill) = self.rules.via_for(Some(&net_name));
+        let obstacles = self.obstacles(&items, net, &net_name, width, via_diameter);
+
+        let mut terminals: Vec<Terminal> = islands.iter()
+            .map(|root| self.terminal(net_items.iter().enumerate().filter(|(i, _)| roots[*i] == *root).map(|(_, item)| item), &obstacles, probe))
+            .collect();
+        let mut tree = terminals.remove(0);
+
+        while !terminals.is_empty() {
+            let Some(found) = self.search(&tree, &terminals, &obstacles, probe) else {
+                return Ok(HashSet::from([FIXED]));
+            };
+            if probe {
+                let mut owners: HashSet<u32> = HashSet::new();
+                for window in found.path.windows(2) {
+                    let (layer, cell) = window[1];
+                    let blocker = if window[0].1 == cell { obstacles.vias[cell] } else { obstacles.tracks[layer][cell] };
+                    if blocker != FREE {
+                        owners.insert(blocker);
+                    }
+                }
+                if let Some(&(layer, cell)) = found.path.first() {
+                    if obstacles.tracks[layer][cell] != FREE {
+                        owners.insert(obstacles.tracks[layer][cell]);
+                    }
+                }
+                owners.remove(&owner);
+                return Ok(owners);
+            }
+
+            let reached = terminals.remove(found.target);
+            self.add_route(net, owner, &found.path, &tree, &reached, width, via_diameter, via_drill)?;
+            tree.nodes.extend(found.path);
+            tree.nodes.extend(reached.nodes);
+            tree.anchors.extend(reached.anchors);
+        }
+        Ok(HashSet::new())
+    }
+
+    fn obstacles(&self, items: &[CopperItem], net: &str, net_name: &str, width: f64, via_diameter: f64) -> Obstacles {
+        let cells = self.grid.cells();
+        // Track centrelines run between cell centres, so a cell must also cover the diagonal half-step
+        let margin = self.grid.size * FRAC_1_SQRT_2;
+        let (track_reach, via_reach) = (width / 2.0 + margin, via_diameter / 2.0 + margin);
+        let mut obstacles = Obstacles { tracks: vec![vec![FREE; cells]; self.layers.len()], vias: vec![FREE; cells] };
+
+        for cell in 0..cells {
+            if !self.inside[cell] {
+                obstacles.vias[cell] = FIXED;
+                obstacles.tracks.iter_mut().for_each(|t| t[cell] = FIXED);
+                continue;
+            }
+            if self.edge_distance[cell] < self.rules.edge_clearance + via_reach {
+                obstacles.vias[cell] = FIXED;
+            }
+            if self.edge_distance[cell] < self.rules.edge_clearance + track_reach {
+                obstacles.tracks.iter_mut().for_each(|t| t[cell] = FIXED);
+            }
+        }
+
+        for item in items.iter().filter(|i| i.net_id.as_deref() != Some(net)) {
+            let clearance = self.rules.clearance_between(Some(net_name), item.net_id.as_deref().map(|n| self.net_name(n)));
+            let owner = self.routed.get(&item.id).copied().unwrap_or(FIXED);
+            for (slot, layer) in self.layers.iter().enumerate() {
+                if item.layers.contains(layer) {
+                    self.rasterize(&item.shape, clearance + track_reach, owner, &mut obstacles.tracks[slot]);
+                }
+            }
+            // Through vias meet copper on every layer
+            self.rasterize(&item.shape, clearance + via_reach, owner, &mut obstacles.vias);
+        }
+
+        let keepouts = self.board.keepouts.iter().cloned().chain(self.board.footprints.iter().filter_map(|f| f.keepout()));
+        for keepout in keepouts.filter(|k| k.area.len() >= 3) {
+            let area = Shape::polygon(keepout.area.iter().map(|p| (p.x, p.y)).collect());
+            let restrictions = &keepout.restrictions;
+            if restrictions.no_copper {
+                for (slot, layer) in self.layers.iter().enumerate() {
+                    if keepout.layers.contains(layer) {
+                        self.rasterize(&area, track_reach, FIXED, &mut obstacles.tracks[slot]);
+                    }
+                }
+            }
+            if restrictions.no_vias || (restrictions.no_copper && keepout.layers.iter().any(|l| l.is_copper())) {
+                self.rasterize(&area, via_reach, FIXED, &mut obstacles.vias);
+            }
+        }
+        obstacles
+    }
+
+    // Marks every cell closer than `reach` to the shape
+    fn rasterize(&self, shape: &Shape, reach: f64, owner: u32, map: &mut [u32]) {
+        let (x0, y0, x1, y1) = shape.bounds();
+        for cell in self.grid.cells_in((x0 - reach, y0 - reach, x1 + reach, y1 + reach)) {
+            if map[cell] != FIXED && Shape::circle(self.grid.point(cell), 0.0).distance(shape) < reach {
+                mark(&mut map[cell], owner);
+            }
+        }
+    }
+
+    fn terminal<'a>(&self, items: impl Iterator<Item = &'a CopperItem>, obstacles: &Obstacles, probe: bool) -> Terminal {
+        let usable = |slot: usize, cell: usize| {
+            let blocker = obstacles.tracks[slot][cell];
+            blocker == FREE || (probe && blocker != FIXED)
+        };
+        let mut terminal = Terminal::default();
+        for item in items {
+            for (slot, layer) in self.layers.iter().enumerate() {
+                if !item.layers.contains(layer) {
+                    continue;
+                }
+                let before = terminal.nodes.len();
+                for cell in self.grid.cells_in(item.shape.bounds()) {
+                    if item.shape.contains(self.grid.point(cell)) && usable(slot, cell) {
+                        terminal.nodes.push((slot, cell));
+                    }
+                }
+                // Pads smaller than a grid cell are reached through a short stub
+                if terminal.nodes.len() == before && item.kind == CopperKind::Pad {
+                    let center = item.shape.center();
+                    if let Some(cell) = self.grid.nearest(center).filter(|&c| usable(slot, c)) {
+                        terminal.nodes.push((slot, cell));
+                        terminal.anchors.insert((slot, cell), center);
+                    }
+                }
+            }
+        }
+        let cols = terminal.nodes.iter().map(|&(_, c)| c % self.grid.cols);
+        let rows = terminal.nodes.iter().map(|&(_, c)| c / self.grid.cols);
+        terminal.bounds = (
+            cols.clone().min().unwrap_or(0), rows.clone().min().unwrap_or(0),
+            cols.max().unwrap_or(0), rows.max().unwrap_or(0),
+        );
+        terminal
+    }
+
+    /// A* over (layer, cell, arrival direction) from the tree to the nearest other terminal.
+    fn search(&self, tree: &Terminal, terminals: &[Terminal], obstacles: &Obstacles, probe: bool) -> Option<SearchResult> {
+        let cells = self.grid.cells();
+        let size = self.grid.size;
+        let state = |slot: usize, cell: usize, direction: usize| (slot * cells + cell) * 9 + direction;
+        let goals: HashMap<(usize, usize), usize> = terminals.iter().enumerate()
+            .flat_map(|(i, t)| t.nodes.iter().map(move |&node| (node, i)))
+            .collect();
+        if goals.is_empty() || tree.nodes.is_empty() {
+            return None;
+        }
+
+        let heuristic = |cell: usize| {
+            let (col, row) = (cell % self.grid.cols, cell / self.grid.cols);
+            terminals.iter().filter(|t| !t.nodes.is_empty()).map(|t| {
+                let (c0, r0, c1, r1) = t.bounds;
+                let dx = c0.saturating_sub(col).max(col.saturating_sub(c1)) as f64;
+                let dy = r0.saturating_sub(row).max(row.saturating_sub(r1)) as f64;
+                size * (dx.max(dy) + (SQRT_2 - 1.0) * dx.min(dy))
+            }).fold(f64::INFINITY, f64::min)
+        };
+        // Cost of entering a cell, or None when it's blocked
+        let entry = |blocker: u32| match blocker {
+            FREE => Some(0.0),
+            FIXED => None,
+            _ if probe => Some(RIPUP_COST * size),
+            _ => None,
+        };
+
+        let mut cost = vec![f64::INFINITY; self.layers.len() * cells * 9];
+        let mut parent = vec![usize::MAX; cost.len()];
+        let mut open = BinaryHeap::new();
+        for &(slot, cell) in &tree.nodes {
+            let start = state(slot, cell, NO_DIRECTION);
+            cost[start] = 0.0;
+            open.push(Open { cost: heuristic(cell), state: start });
+        }
+
+        while let Some(Open { cost: estimate, state: current }) = open.pop() {
+            let (node, direction) = (current / 9, current % 9);
+            let (slot, cell) = (node / cells, node % cells);
+            let spent = cost[current];
+            if estimate > spent + heuristic(cell) + 1e-9 {
+                continue; // stale entry
+            }
+            if let Some(&target) = goals.get(&(slot, cell)) {
+                let mut path = Vec::new();
+                let mut at = current;
+                while at != usize::MAX {
+                    let node = at / 9;
+                    path.push((node / cells, node % cells));
+                    at = parent[at];
+                }
+                path.reverse();
+                return Some(SearchResult { path, target });
+            }
+
+            let mut relax = |next: usize, step: f64, open: &mut BinaryHeap<Open>, next_cell: usize| {
+                let total = spent + step;
+                if total < cost[next] {
+                    cost[next] = total;
+                    parent[next] = current;
+                    open.push(Open { cost: total + heuristic(next_cell), state: next });
+                }
+            };
+
+            for (heading, (dx, dy)) in DIRECTIONS.iter().enumerate() {
+                let bend = if direction == NO_DIRECTION {
+                    0
+                } else {
+                    let turn = (heading as i64 - direction as i64).rem_euclid(8);
+                    turn.min(8 - turn)
+                };
+                if bend > 2 {
+                    continue; // no acute corners
+                }
+                let Some(next_cell) = self.grid.step(cell, heading) else { continue };
+                let Some(penalty) = entry(obstacles.tracks[slot][next_cell]) else { continue };
+                let length = if *dx != 0 && *dy != 0 { size * SQRT_2 } else { size };
+                relax(state(slot, next_cell, heading), length + penalty + bend as f64 * BEND_COST * size, &mut open, next_cell);
+            }
+
+            if self.layers.len() > 1 && direction != NO_DIRECTION {
+                let other = 1 - slot;
+                if let (Some(via), Some(landing)) = (entry(obstacles.vias[cell]), entry(obstacles.tracks[other][cell])) {
+                    relax(state(other, cell, NO_DIRECTION), self.options.via_cost + via + landing, &mut open, cell);
+                }
+            }
+        }
+        None
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn add_route(
+        &mut self,
+        net: &str,
+        owner: u32,
+        path: &[(usize, usize)],
+        from: &Terminal,
+        to: &Terminal,
+        width: f64,
+        via_diameter: f64,
+        via_drill: f64,
+    ) -> Result<()> {
+        let point = |p: Vec2| FootprintPoint { x: p.0, y: p.1 };
+        let net_id = Some(net.to_string());
+
+        // Split the path into runs on one layer, joined by vias
+        let mut runs: Vec<(usize, Vec<Vec2>)> = Vec::new();
+        for (index, &(slot, cell)) in path.iter().enumerate() {
+            let here = self.grid.point(cell);
+            match runs.last_mut() {
+                Some((layer, points)) if *layer == slot => points.push(here),
+                Some(_) => {
+                    let via = Via::new(net_id.clone(), point(here), via_diameter, via_drill);
+                    self.routed.insert(via.id.clone(), owner);
+                    self.board.add_via(via)?;
+                    runs.push((slot, vec![here]));
+                }
+                None => {
+                    let mut points = vec![here];
+                    if let Some(&anchor) = from.anchors.get(&(slot, cell)) {
+                        points.insert(0, anchor);
+                    }
+                    runs.push((slot, points));
+                }
+            }
+            if index == path.len() - 1 {
+                if let (Some(&anchor), Some((_, points))) = (to.anchors.get(&(slot, cell)), runs.last_mut()) {
+                    points.push(anchor);
+                }
+            }
+        }
+
+        for (slot, points) in runs {
+            let layer = self.layers[slot].clone();
+            for (start, end) in simplify(&points) {
+                let track = Track::new(net_id.clone(), layer.clone(), point(start), point(end), width);
+                self.routed.insert(track.id.clone(), owner);
+                self.board.add_track(track)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Merges collinear steps into straight segments
+fn simplify(points: &[Vec2]) -> Vec<(Vec2, Vec2)> {
+    let mut corners: Vec<Vec2> = Vec::new();
+    for &p in points {
+        if corners.last() == Some(&p) {
+            continue;
+        }
+        if corners.len() >= 2 {
+            let (a, b) = (corners[corners.len() - 2], corners[corners.len() - 1]);
+            let cross = (b.0 - a.0) * (p.1 - b.1) - (b.1 - a.1) * (p.0 - b.0);
+            let forward = (b.0 - a.0) * (p.0 - b.0) + (b.1 - a.1) * (p.1 - b.1) > 0.0;
+            if cross.abs() < 1e-9 && forward {
+                corners.pop();
+            }
+        }
+        corners.push(p);
+    }
+    corners.windows(2).map(|w| (w[0], w[1])).collect()
+}
+
+pub struct AutorouterService;
+
+impl AutorouterService {
+    /// Routes the project's board in place.
+    pub fn route_project(project: &mut Project, options: AutorouteOptions) -> Result<AutorouteReport> {
+        let board = project.board.take()
+            .ok_or_else(|| AppError::InvalidOperation("Project has no board".to_string()))?;
+        let router = match Autorouter::new(board.clone(), project.settings.design_rules.clone(), options) {
+            Ok(router) => router,
+            Err(e) => {
+                project.board = Some(board);
+                return Err(e);
+            }
+        };
+        let (board, report) = router.run()?;
+        project.board = Some(board);
+        project.update_modified();
+        Ok(report)
+    }
+
+    /// Headless batch run: routes each project file and writes it next to the original as
+    /// `<name>-routed.<ext>`, or into `output_directory`. One failing file doesn't stop the rest.
+    pub fn route_files(paths: &[PathBuf], output_directory: Option<&Path>, options: &AutorouteOptions) -> Vec<BatchRouteOutcome> {
+        paths.iter().map(|path| {
+            let mut outcome = BatchRouteOutcome {
+                project_path: path.to_string_lossy().to_string(),
+                output_path: None,
+                report: None,
+                error: None,
+            };
+            match Self::route_file(path, output_directory, options) {
+                Ok((output, report)) => {
+                    outcome.output_path = Some(output.to_string_lossy().to_string());
+                    outcome.report = Some(report);
+                }
+                Err(e) => outcome.error = Some(e.to_string()),
+            }
+            outcome
+        }).collect()
+    }
+
+    fn route_file(path: &Path, output_directory: Option<&Path>, options: &AutorouteOptions) -> Result<(PathBuf, AutorouteReport)> {
+        let content = std::fs::read_to_string(path)?;
+        let mut project = Project::from_json(&content)?;
+        let report = Self::route_project(&mut project, options.clone())?;
+
+        let stem = FileService::get_file_stem(path).unwrap_or_else(|| "project".to_string());
+        let file_name = match FileService::get_file_extension(path) {
+            Some(extension) => format!("{}-routed.{}", stem, extension),
+            None => format!("{}-routed", stem),
+        };
+        let directory = output_directory.map(Path::to_path_buf)
+            .unwrap_or_else(|| path.parent().map(Path::to_path_buf).unwrap_or_default());
+        let output = directory.join(file_name);
+        FileService::write_atomic(&output, project.to_json()?.as_bytes())?;
+        Ok((output, report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BoardNet, KeepoutArea, KeepoutRestrictions, NetClass};
+    use crate::services::drc_service::DRCChecker;
+    use crate::services::test_fixtures::place;
+
+    #[test]
+    fn test_autoroute_two_layer_board() {
+        let mut board = Board::new("Router".to_string()).unwrap();
+        board.set_rectangular_outline(40.0, 30.0).unwrap();
+        board.nets = ["vcc", "sig", "gnd"].iter().map(|n| BoardNet { id: n.to_string(), name: n.to_uppercase() }).collect();
+        place(&mut board, "DIP-8", "U1", 12.0, 15.0, &[("1", "sig"), ("4", "gnd"), ("8", "vcc")]);
+        place(&mut board, "R_0805", "R1", 30.0, 8.0, &[("1", "sig"), ("2", "vcc")]);
+        place(&mut board, "C_0805", "C1", 30.0, 22.0, &[("1", "vcc"), ("2", "gnd")]);
+        // A wall of copper keepout between the parts with a gap only on the bottom layer
+        board.keepouts.push(KeepoutArea {
+            area: vec![FootprintPoint { x: 21.0, y: 0.0 }, FootprintPoint { x: 22.0, y: 0.0 }, FootprintPoint { x: 22.0, y: 30.0 }, FootprintPoint { x: 21.0, y: 30.0 }],
+            layers: vec![LayerType::TopCopper],
+            restrictions: KeepoutRestrictions { no_copper: true, no_vias: false, no_components: false },
+        });
+
+        let mut rules = DesignRules::default();
+        let mut power = NetClass::new("Power".to_string());
+        power.patterns = vec!["VCC".to_string(), "GND".to_string()];
+        power.track_width = Some(0.4);
+        rules.net_classes.push(power);
+
+        let options = AutorouteOptions { grid_size: 0.25, ..Default::default() };
+        let (board, report) = Autorouter::new(board, rules.clone(), options).unwrap().run().unwrap();
+        assert_eq!(report.routed_nets.len(), 3, "{:?}", report.unroutable);
+        assert!(report.unroutable.is_empty());
+        assert!(Ratsnest::build(&board).nets.is_empty());
+        assert!(report.vias_added >= 2, "SMD parts past the top-layer keepout need vias");
+        assert!(board.tracks.iter().filter(|t| t.net_id.as_deref() == Some("gnd")).all(|t| t.width == 0.4));
+
+        let drc = DRCChecker::new(board, rules).run_check().unwrap();
+        assert!(drc.errors.is_empty(), "{:?}", drc.errors.iter().map(|e| &e.message).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_unroutable_net_is_reported() {
+        let mut board = Board::new("Blocked".to_string()).unwrap();
+        board.set_rectangular_outline(30.0, 20.0).unwrap();
+        board.nets = vec![BoardNet { id: "sig".to_string(), name: "SIG".to_string() }];
+        place(&mut board, "R_0805", "R1", 5.0, 10.0, &[("2", "sig")]);
+        place(&mut board, "R_0805", "R2", 25.0, 10.0, &[("1", "sig")]);
+        board.keepouts.push(KeepoutArea {
+            area: vec![FootprintPoint { x: 14.0, y: -1.0 }, FootprintPoint { x: 16.0, y: -1.0 }, FootprintPoint { x: 16.0, y: 21.0 }, FootprintPoint { x: 14.0, y: 21.0 }],
+            layers: vec![LayerType::TopCopper, LayerType::BottomCopper],
+            restrictions: KeepoutRestrictions { no_copper: true, no_vias: true, no_components: false },
+        });
+
+        let (board, report) = Autorouter::new(board, DesignRules::default(), AutorouteOptions::default()).unwrap().run().unwrap();
+        assert!(report.routed_nets.is_empty());
+        assert_eq!(report.unroutable.len(), 1);
+        assert_eq!(report.unroutable[0].net_name, "SIG");
+        assert!(board.tracks.iter().all(|t| t.start.x < 15.0 && t.end.x < 15.0 || t.start.x > 15.0 && t.end.x > 15.0));
+    }
+
+    #[test]
+    fn test_route_files() {
+        let directory = std::env::temp_dir().join(format!("rust-eda-batch-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let mut board = Board::new("Batch".to_string()).unwrap();
+        board.set_rectangular_outline(30.0, 20.0).unwrap();
+        board.nets = vec![BoardNet { id: "sig".to_string(), name: "SIG".to_string() }];
+        place(&mut board, "R_0805", "R1", 8.0, 10.0, &[("2", "sig")]);
+        place(&mut board, "R_0805", "R2", 22.0, 10.0, &[("1", "sig")]);
+        let mut project = Project::new("Batch".to_string()).unwrap();
+        project.board = Some(board);
+        let good = directory.join("board.json");
+        std::fs::write(&good, project.to_json().unwrap()).unwrap();
+        let broken = directory.join("broken.json");
+        std::fs::write(&broken, "{ not a project").unwrap();
+        let load = |path: &Path| Project::from_json(&std::fs::read_to_string(path).unwrap()).unwrap().board.unwrap();
+
+        // Files that fail come first and don't stop the one after them
+        let options = AutorouteOptions::default();
+        let paths = [broken, directory.join("missing.json"), good.clone()];
+        let outcomes = AutorouterService::route_files(&paths, None, &options);
+        assert_eq!(outcomes.len(), 3);
+        assert!(outcomes[..2].iter().all(|o| o.error.is_some() && o.report.is_none() && o.output_path.is_none()));
+        let routed = directory.join("board-routed.json");
+        assert_eq!(outcomes[2].output_path.as_deref(), Some(routed.to_string_lossy().as_ref()));
+        assert_eq!(outcomes[2].report.as_ref().unwrap().routed_nets.len(), 1);
+        assert!(!load(&routed).tracks.is_empty());
+        assert!(load(&good).tracks.is_empty(), "the original is left alone");
+
+        let output_directory = directory.join("out");
+        let outcomes = AutorouterService::route_files(&[good], Some(&output_directory), &options);
+        assert!(outcomes[0].error.is_none());
+        assert!(!load(&output_directory.join("board-routed.json")).tracks.is_empty());
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
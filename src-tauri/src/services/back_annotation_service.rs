@@ -0,0 +1,498 @@
+use crate::models::{Component, ComponentFootprintMap, FootprintInstance, Project};
+use crate::services::board_service::schematic_nets;
+use crate::services::footprint_service::{validate_pin_mapping, FootprintService};
+use crate::utils::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A pin that takes over another pin's number (and with it its pad) within one unit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PinSwap {
+    pub component_id: String,
+    pub pin_id: String,
+    pub old_number: String,
+    pub new_number: String,
+}
+
+/// Two units of one package trading places, pins matched by their order in the unit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GateSwap {
+    pub first_component_id: String,
+    pub first_unit: String,
+    pub second_component_id: String,
+    pub second_unit: String,
+}
+
+/// What back-annotation would change for one package, keyed by the component its board
+/// footprint belongs to. Pin swaps are numbered as they are after the gate swaps.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackAnnotationChange {
+    pub component_id: String,
+    pub reference: String,
+    pub new_reference: Option<String>,
+    pub footprint_id: String,
+    pub new_footprint_id: Option<String>,
+    pub gate_swaps: Vec<GateSwap>,
+    pub pin_swaps: Vec<PinSwap>,
+}
+
+impl BackAnnotationChange {
+    pub fn is_empty(&self) -> bool {
+        self.new_reference.is_none() && self.new_footprint_id.is_none() && self.gate_swaps.is_empty() && self.pin_swaps.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackAnnotationReport {
+    pub changes: Vec<BackAnnotationChange>,
+    pub unresolved: Vec<String>, // board differences the schematic can't take over
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackAnnotationResult {
+    pub project: Project,
+    pub applied: Vec<String>,
+}
+
+pub struct BackAnnotationService;
+
+impl BackAnnotationService {
+    /// Compares the board with the schematic and lists what back-annotation would change:
+    /// references renamed on the board, footprints exchanged, and pad nets traded between
+    /// units of a package (gate swap) or between pins of a unit (pin swap). Pad net edits
+    /// that aren't such a permutation, renames that would clash, and footprints some pins
+    /// can't be mapped onto are only reported.
+    pub fn preview(project: &Project, footprints: &FootprintService) -> Result<BackAnnotationReport> {
+        let board = project.board.as_ref()
+            .ok_or_else(|| AppError::InvalidOperation("Project has no board".to_string()))?;
+        let (pin_nets, nets) = schematic_nets(project);
+        let net_name = |id: Option<&str>| {
+            id.map(|id| nets.iter().find(|n| n.id == id).map_or(id, |n| n.name.as_str()))
+                .unwrap_or("no net")
+                .to_string()
+        };
+
+        let mut report = BackAnnotationReport::default();
+        for instance in board.footprints.iter().filter(|f| !f.component_id.is_empty()) {
+            let Some(component) = project.find_component(&instance.component_id) else {
+                report.unresolved.push(format!("{} has no schematic component", instance.reference));
+                continue;
+            };
+            let Some(assignment) = project.get_footprint_assignment(&component.id) else {
+                report.unresolved.push(format!("{} has no footprint assignment", component.reference));
+                continue;
+            };
+            let units: Vec<&Component> = project.schematics.iter()
+                .flat_map(|s| s.components.iter())
+                .filter(|c| c.id == component.id || c.same_package(component))
+                .collect();
+            let package = Package { instance, assignment, units: &units, pin_nets: &pin_nets };
+
+            // Pin numbers each unit ends up with, starting from the schematic's
+            let mut numbers: Vec<Vec<String>> = units.iter()
+                .map(|u| u.pins.iter().map(|p| p.number.clone()).collect())
+                .collect();
+
+            let mut gate_swaps = Vec::new();
+            let mut swapped = vec![false; units.len()];
+            for a in 0..units.len() {
+                if swapped[a] || package.mismatched(a, &numbers[a]).is_empty() {
+                    continue;
+                }
+                let Some(b) = (a + 1..units.len()).find(|&b| !swapped[b] && package.gates_crossed(a, b)) else {
+                    continue;
+                };
+                let (Some(first_unit), Some(second_unit)) = (&units[a].unit, &units[b].unit) else {
+                    continue;
+                };
+                numbers.swap(a, b);
+                swapped[a] = true;
+                swapped[b] = true;
+                gate_swaps.push(GateSwap {
+                    first_component_id: units[a].id.clone(),
+                    first_unit: first_unit.clone(),
+                    second_component_id: units[b].id.clone(),
+                    second_unit: second_unit.clone(),
+                });
+            }
+
+            let mut pin_swaps = Vec::new();
+            for (u, unit) in units.iter().enumerate() {
+                let wrong = package.mismatched(u, &numbers[u]);
+                let mut taken = vec![false; unit.pins.len()];
+                let mut plan: Vec<(usize, usize)> = Vec::new();
+                for &p in &wrong {
+                    let wanted = package.schematic_net(u, p);
+                    let fits = |q: usize| !taken[q] && package.board_net(&numbers[u][q]) == wanted;
+                    // A straight exchange first, then any pin whose pad carries this pin's net
+                    let q = wrong.iter().copied()
+                        .find(|&q| fits(q) && package.board_net(&numbers[u][p]) == package.schematic_net(u, q))
+                        .or_else(|| wrong.iter().copied().find(|&q| fits(q)));
+                    match q {
+                        Some(q) => {
+                            taken[q] = true;
+                            plan.push((p, q));
+                        }
+                        None => break,
+                    }
+                }
+                if plan.len() < wrong.len() {
+                    for &p in &wrong {
+                        report.unresolved.push(format!(
+                            "{} pin {}: board net {} differs from schematic net {}",
+                            unit.display_reference(),
+                            numbers[u][p],
+                            net_name(package.board_net(&numbers[u][p])),
+                            net_name(package.schematic_net(u, p)),
+                        ));
+                    }
+                    continue;
+                }
+                pin_swaps.extend(plan.into_iter().map(|(p, q)| PinSwap {
+                    component_id: unit.id.clone(),
+                    pin_id: unit.pins[p].id.clone(),
+                    old_number: numbers[u][p].clone(),
+                    new_number: numbers[u][q].clone(),
+                }));
+            }
+
+            let mut new_footprint_id = None;
+            if instance.footprint_id != assignment.footprint_id {
+                match check_footprint_change(project, footprints, assignment, &instance.footprint_id) {
+                    Ok(_) => new_footprint_id = Some(instance.footprint_id.clone()),
+                    Err(reason) => report.unresolved.push(format!(
+                        "{} can't take footprint {}: {}", component.reference, instance.footprint_id, reason
+                    )),
+                }
+            }
+
+            let change = BackAnnotationChange {
+                component_id: component.id.clone(),
+                reference: component.reference.clone(),
+                new_reference: (instance.reference != component.reference).then(|| instance.reference.clone()),
+                footprint_id: assignment.footprint_id.clone(),
+                new_footprint_id,
+                gate_swaps,
+                pin_swaps,
+            };
+            if !change.is_empty() {
+                report.changes.push(change);
+            }
+        }
+
+        // Renames that would leave two parts with one reference stay on the board
+        let renames = renames(&report.changes);
+        let clashes = duplicate_references(project, &renames);
+        for change in &mut report.changes {
+            if change.new_reference.as_ref().is_some_and(|r| clashes.contains(r)) {
+                let reference = change.new_reference.take().unwrap_or_default();
+                report.unresolved.push(format!("{} can't become {}: the reference is taken", change.reference, reference));
+            }
+        }
+        report.changes.retain(|c| !c.is_empty());
+        Ok(report)
+    }
+
+    /// Applies the previewed changes of the given packages to the schematic and footprint
+    /// assignments, returning the component ids that were changed.
+    pub fn apply(project: &mut Project, footprints: &FootprintService, component_ids: &[String]) -> Result<Vec<String>> {
+        let changes: Vec<BackAnnotationChange> = Self::preview(project, footprints)?.changes.into_iter()
+            .filter(|c| component_ids.contains(&c.component_id))
+            .collect();
+        let clashes = duplicate_references(project, &renames(&changes));
+        if !clashes.is_empty() {
+            return Err(AppError::InvalidOperation(format!(
+                "Back-annotation would give {} to more than one part", clashes.join(", ")
+            )));
+        }
+
+        // Units are looked up before any reference changes, since renames can be swaps
+        let unit_ids: Vec<Vec<String>> = changes.iter().map(|change| {
+            let component = project.find_component(&change.component_id);
+            project.schematics.iter()
+                .flat_map(|s| s.components.iter())
+                .filter(|c| c.id == change.component_id || component.is_some_and(|p| c.same_package(p)))
+                .map(|c| c.id.clone())
+                .collect()
+        }).collect();
+
+        for (change, units) in changes.iter().zip(&unit_ids) {
+            if let Some(footprint_id) = &change.new_footprint_id {
+                if let Some(assignment) = project.get_footprint_assignment(&change.component_id).cloned() {
+                    let variant_id = carried_variant(footprints, &assignment, footprint_id);
+                    footprints.assign_footprint(project, &assignment.component_id, footprint_id, variant_id.as_deref())?;
+                }
+            }
+
+            for swap in &change.gate_swaps {
+                let (Some(mut first), Some(mut second)) = (
+                    project.find_component(&swap.first_component_id).cloned(),
+                    project.find_component(&swap.second_component_id).cloned(),
+                ) else {
+                    continue;
+                };
+                std::mem::swap(&mut first.unit, &mut second.unit);
+                for (a, b) in first.pins.iter_mut().zip(second.pins.iter_mut()) {
+                    std::mem::swap(&mut a.number, &mut b.number);
+                    std::mem::swap(&mut a.name, &mut b.name);
+                    std::mem::swap(&mut a.electrical, &mut b.electrical);
+                }
+                for unit in [first, second] {
+                    if let Some(component) = component_mut(project, &unit.id) {
+                        *component = unit;
+                    }
+                }
+            }
+
+            for unit_id in units {
+                let Some(component) = component_mut(project, unit_id) else { continue };
+                let before = component.pins.clone();
+                for swap in change.pin_swaps.iter().filter(|s| &s.component_id == unit_id) {
+                    let source = before.iter().find(|p| p.number == swap.new_number);
+                    let pin = component.pins.iter_mut().find(|p| p.id == swap.pin_id);
+                    if let (Some(source), Some(pin)) = (source, pin) {
+                        pin.number = source.number.clone();
+                        pin.name = source.name.clone();
+                        pin.electrical = source.electrical.clone();
+                    }
+                }
+                if let Some(reference) = &change.new_reference {
+                    component.reference = reference.clone();
+                }
+            }
+        }
+
+        if !changes.is_empty() {
+            project.update_modified();
+        }
+        Ok(changes.into_iter().map(|c| c.component_id).collect())
+    }
+}
+
+// The pinout variant survives a footprint change only if the new footprint has one by that name
+fn carried_variant(footprints: &FootprintService, assignment: &ComponentFootprintMap, footprint_id: &str) -> Option<String> {
+    let variant_id = assignment.variant_id.as_deref()?;
+    footprints.get_footprint(footprint_id)?.get_pin_map_variant(variant_id).map(|v| v.id.clone())
+}
+
+// Whether the package's pins still all land on pads after taking the board's footprint
+fn check_footprint_change(
+    project: &Project,
+    footprints: &FootprintService,
+    assignment: &ComponentFootprintMap,
+    footprint_id: &str,
+) -> std::result::Result<(), String> {
+    let footprint = footprints.get_footprint(footprint_id)
+        .ok_or_else(|| "it isn't in the footprint library".to_string())?;
+    let package = project.find_package(&assignment.component_id)
+        .ok_or_else(|| "the component is missing".to_string())?;
+    let variant_id = carried_variant(footprints, assignment, footprint_id);
+    let new = footprints.footprint_assignment(project, &assignment.component_id, footprint_id, variant_id.as_deref())
+        .map_err(|e| e.to_string())?;
+    let check = validate_pin_mapping(&package, Some(&footprint), &new);
+    if !check.unmapped_pins.is_empty() {
+        return Err(format!("pins {} have no pad", check.unmapped_pins.join(", ")));
+    }
+    if !check.missing_pads.is_empty() {
+        return Err(format!("pads {} don't exist", check.missing_pads.join(", ")));
+    }
+    Ok(())
+}
+
+// One board footprint with the schematic units it was made from
+struct Package<'a> {
+    instance: &'a FootprintInstance,
+    assignment: &'a ComponentFootprintMap,
+    units: &'a [&'a Component],
+    pin_nets: &'a HashMap<(String, String), String>,
+}
+
+impl Package<'_> {
+    fn board_net(&self, number: &str) -> Option<&str> {
+        self.assignment.pad_for_pin(number).and_then(|pad| self.instance.pad_net(pad))
+    }
+
+    fn schematic_net(&self, unit: usize, pin: usize) -> Option<&str> {
+        let unit = self.units[unit];
+        self.pin_nets.get(&(unit.id.clone(), unit.pins[pin].id.clone())).map(|n| n.as_str())
+    }
+
+    // Pins of the unit whose pad, under the given numbering, carries a different net
+    fn mismatched(&self, unit: usize, numbers: &[String]) -> Vec<usize> {
+        (0..numbers.len())
+            .filter(|&p| self.assignment.pad_for_pin(&numbers[p]).is_some())
+            .filter(|&p| self.board_net(&numbers[p]) != self.schematic_net(unit, p))
+            .collect()
+    }
+
+    // Each unit's pads carry what the other unit's pins are wired to
+    fn gates_crossed(&self, a: usize, b: usize) -> bool {
+        let (first, second) = (self.units[a], self.units[b]);
+        first.pins.len() == second.pins.len()
+            && (0..first.pins.len()).all(|i| {
+                self.board_net(&second.pins[i].number) == self.schematic_net(a, i)
+                    && self.board_net(&first.pins[i].number) == self.schematic_net(b, i)
+            })
+    }
+}
+
+fn component_mut<'a>(project: &'a mut Project, component_id: &str) -> Option<&'a mut Component> {
+    project.schematics.iter_mut().find_map(|s| s.get_component_mut(component_id))
+}
+
+fn renames(changes: &[BackAnnotationChange]) -> HashMap<String, String> {
+    changes.iter()
+        .filter_map(|c| c.new_reference.clone().map(|r| (c.component_id.clone(), r)))
+        .collect()
+}
+
+// References held by more than one package once the renames are done
+fn duplicate_references(project: &Project, renames: &HashMap<String, String>) -> Vec<String> {
+    let renamed: Vec<(&Component, &String)> = renames.iter()
+        .filter_map(|(id, reference)| project.find_component(id).map(|c| (c, reference)))
+        .collect();
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for package in project.package_components() {
+        let reference = renamed.iter()
+            .find(|(c, _)| c.id == package.id || c.same_package(package))
+            .map_or(&package.reference, |(_, r)| *r);
+        if !reference.ends_with('?') {
+            *counts.entry(reference.clone()).or_default() += 1;
+        }
+    }
+    let mut clashes: Vec<String> = counts.into_iter().filter(|(_, n)| *n > 1).map(|(r, _)| r).collect();
+    clashes.sort();
+    clashes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ElectricalType, Net, Pin, PinConnection, PinType, Schematic};
+    use crate::services::{BoardService, FootprintService};
+
+    #[test]
+    fn test_back_annotation() {
+        let pin = |id: &str, number: &str| Pin {
+            id: id.to_string(),
+            name: format!("P{}", number),
+            number: number.to_string(),
+            x: 0.0,
+            y: 0.0,
+            pin_type: PinType::Passive,
+            electrical: ElectricalType { voltage: None, current: None, impedance: None },
+            connected: false,
+        };
+        let mut schematic = Schematic::new("Main".to_string()).unwrap();
+        // A dual op-amp in SOIC-8 (out, in-, in+ per unit) and two resistors
+        let parts = [
+            ("U1", Some("A"), vec![pin("a-out", "1"), pin("a-inn", "2"), pin("a-inp", "3")]),
+            ("U1", Some("B"), vec![pin("b-out", "7"), pin("b-inn", "6"), pin("b-inp", "5")]),
+            ("R1", None, vec![pin("r1-a", "1"), pin("r1-b", "2")]),
+            ("R2", None, vec![pin("r2-a", "1"), pin("r2-b", "2")]),
+        ];
+        for (i, (reference, unit, pins)) in parts.into_iter().enumerate() {
+            let mut component = Component::new("part".to_string(), 100.0 * i as f64, 0.0).unwrap();
+            component.reference = reference.to_string();
+            component.unit = unit.map(|u| u.to_string());
+            component.pins = pins;
+            schematic.add_component(component).unwrap();
+        }
+        let ids: Vec<String> = schematic.components.iter().map(|c| c.id.clone()).collect();
+        let connect = |name: &str, pins: &[(usize, &str)]| Net {
+            id: format!("net-{}", name),
+            name: name.to_string(),
+            pins: pins.iter().map(|(c, p)| PinConnection { component_id: ids[*c].clone(), pin_id: p.to_string() }).collect(),
+        };
+        schematic.nets = vec![
+            connect("OUTA", &[(0, "a-out"), (2, "r1-a")]),
+            connect("FBA", &[(0, "a-inn"), (2, "r1-b")]),
+            connect("INA", &[(0, "a-inp")]),
+            connect("OUTB", &[(1, "b-out"), (3, "r2-a")]),
+            connect("FBB", &[(1, "b-inn"), (3, "r2-b")]),
+            connect("INB", &[(1, "b-inp")]),
+        ];
+
+        let mut project = Project::new("Test".to_string()).unwrap();
+        project.add_schematic(schematic).unwrap();
+        let footprints = FootprintService::new();
+        footprints.assign_footprint(&mut project, &ids[0], "SOIC-8", None).unwrap();
+        footprints.assign_footprint(&mut project, &ids[2], "R_0805", None).unwrap();
+        footprints.assign_footprint(&mut project, &ids[3], "R_0805", None).unwrap();
+        BoardService::update_from_schematic(&mut project, &footprints).unwrap();
+        assert!(BackAnnotationService::preview(&project, &footprints).unwrap().changes.is_empty());
+
+        // Layout: gates of U1 swapped, R1's pins swapped and its footprint changed, R2 renamed
+        let board = project.board.as_mut().unwrap();
+        let swap_pads = |instance: &mut FootprintInstance, a: &str, b: &str| {
+            let (na, nb) = (instance.pad_nets.remove(a), instance.pad_nets.remove(b));
+            if let Some(n) = na { instance.pad_nets.insert(b.to_string(), n); }
+            if let Some(n) = nb { instance.pad_nets.insert(a.to_string(), n); }
+        };
+        let u1 = board.footprints.iter_mut().find(|f| f.reference == "U1").unwrap();
+        for (a, b) in [("1", "7"), ("2", "6"), ("3", "5")] {
+            swap_pads(u1, a, b);
+        }
+        let r1 = board.footprints.iter_mut().find(|f| f.reference == "R1").unwrap();
+        swap_pads(r1, "1", "2");
+        r1.footprint = footprints.get_footprint("R_0603").unwrap();
+        r1.footprint_id = "R_0603".to_string();
+        board.footprints.iter_mut().find(|f| f.reference == "R2").unwrap().reference = "R7".to_string();
+        // A pinout variant the new footprint doesn't have must not carry over
+        let mut assignment = project.get_footprint_assignment(&ids[2]).unwrap().clone();
+        assignment.variant_id = Some("legacy".to_string());
+        assignment.pin_mapping.insert("3".to_string(), "3".to_string());
+        project.set_footprint_assignment(assignment);
+
+        let report = BackAnnotationService::preview(&project, &footprints).unwrap();
+        assert!(report.unresolved.is_empty(), "{:?}", report.unresolved);
+        assert_eq!(report.changes.len(), 3);
+        let u1 = report.changes.iter().find(|c| c.reference == "U1").unwrap();
+        assert_eq!(u1.gate_swaps.len(), 1);
+        assert!(u1.pin_swaps.is_empty());
+        let r1 = report.changes.iter().find(|c| c.reference == "R1").unwrap();
+        assert_eq!(r1.new_footprint_id.as_deref(), Some("R_0603"));
+        assert_eq!(r1.pin_swaps.len(), 2);
+
+        let all: Vec<String> = report.changes.iter().map(|c| c.component_id.clone()).collect();
+        assert_eq!(BackAnnotationService::apply(&mut project, &footprints, &all).unwrap().len(), 3);
+        let component = |id: &str| project.find_component(id).unwrap();
+        assert_eq!(component(&ids[0]).unit.as_deref(), Some("B"));
+        assert_eq!(component(&ids[0]).get_pin_by_number("7").map(|p| p.id.as_str()), Some("a-out"));
+        assert_eq!(component(&ids[1]).unit.as_deref(), Some("A"));
+        assert_eq!(component(&ids[2]).get_pin_by_number("2").map(|p| p.id.as_str()), Some("r1-a"));
+        assert_eq!(component(&ids[3]).reference, "R7");
+        let assignment = project.get_footprint_assignment(&ids[2]).unwrap();
+        assert_eq!(assignment.footprint_id, "R_0603");
+        assert_eq!(assignment.variant_id, None);
+        assert_eq!(assignment.pin_mapping, footprints.footprint_assignment(&project, &ids[2], "R_0603", None).unwrap().pin_mapping);
+        assert!(BackAnnotationService::preview(&project, &footprints).unwrap().changes.is_empty());
+
+        // Footprints the pins don't fit, or that aren't in the library, stay on the board
+        let board = project.board.as_mut().unwrap();
+        board.footprints.iter_mut().find(|f| f.reference == "U1").unwrap().footprint_id = "R_0805".to_string();
+        board.footprints.iter_mut().find(|f| f.reference == "R1").unwrap().footprint_id = "R_custom".to_string();
+        let report = BackAnnotationService::preview(&project, &footprints).unwrap();
+        assert!(report.changes.is_empty());
+        assert_eq!(report.unresolved, vec![
+            "U1 can't take footprint R_0805: pins 3, 5, 6, 7 have no pad",
+            "R1 can't take footprint R_custom: it isn't in the footprint library",
+        ]);
+        let board = project.board.as_mut().unwrap();
+        board.footprints.iter_mut().find(|f| f.reference == "U1").unwrap().footprint_id = "SOIC-8".to_string();
+        board.footprints.iter_mut().find(|f| f.reference == "R1").unwrap().footprint_id = "R_0603".to_string();
+
+        // A rename onto a reference still in use is left on the board
+        let board = project.board.as_mut().unwrap();
+        board.footprints.iter_mut().find(|f| f.reference == "R7").unwrap().reference = "R1".to_string();
+        let report = BackAnnotationService::preview(&project, &footprints).unwrap();
+        assert!(report.changes.is_empty());
+        assert_eq!(report.unresolved.len(), 1);
+        assert!(BackAnnotationService::apply(&mut project, &footprints, &[ids[3].clone()]).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,271 @@
+use crate::models::{
+    Board, BoardNet, BoardSide, Component, FootprintInstance, FootprintPoint, LayerStackup, PlacementSide, Project,
+};
+use crate::services::FootprintService;
+use crate::utils::error::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+// Spacing and row width for parts staged beside the board outline
+const STAGING_GAP: f64 = 2.0;
+const STAGING_ROW_WIDTH: f64 = 100.0;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BoardUpdateReport {
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub removed: Vec<String>,
+    pub unassigned: Vec<String>,       // packages without a footprint assignment
+    pub missing_footprints: Vec<String>, // assignments whose footprint isn't in any library
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BoardUpdateResult {
+    pub project: Project,
+    pub report: BoardUpdateReport,
+}
+
+/// The net class a schematic net resolves to under the project's design rules.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetClassAssignment {
+    pub net_id: String,
+    pub net_name: String,
+    pub net_class: Option<String>,
+}
+
+/// Net per (component, pin) over all sheets, and the nets sorted by name. Label nets with
+/// the same id on different sheets merge.
+pub(crate) fn schematic_nets(project: &Project) -> (HashMap<(String, String), String>, Vec<BoardNet>) {
+    let mut pin_nets: HashMap<(String, String), String> = HashMap::new();
+    let mut nets: Vec<BoardNet> = Vec::new();
+    for schematic in &project.schematics {
+        for net in schematic.generate_netlist() {
+            for pin in &net.pins {
+                pin_nets.insert((pin.component_id.clone(), pin.pin_id.clone()), net.id.clone());
+            }
+            if !nets.iter().any(|n| n.id == net.id) {
+                nets.push(BoardNet { id: net.id, name: net.name });
+            }
+        }
+    }
+    nets.sort_by(|a, b| a.name.cmp(&b.name));
+    (pin_nets, nets)
+}
+
+pub struct BoardService;
+
+impl BoardService {
+    pub fn create_board(project: &mut Project, copper_layers: usize, board_thickness: f64, width: f64, height: f64) -> Result<()> {
+        let mut board = Board::new(project.name.clone())?;
+        board.stackup = LayerStackup::new(copper_layers, board_thickness)?;
+        board.set_rectangular_outline(width, height)?;
+        project.board = Some(board);
+        project.update_modified();
+        Ok(())
+    }
+
+    /// Brings the board in line with the schematic ("update PCB"): every package with a
+    /// footprint assignment gets a footprint instance, references, values and pad nets are
+    /// refreshed, and instances whose component is gone are removed. New parts are staged
+    /// beside the board outline; placed parts keep their position.
+    pub fn update_from_schematic(project: &mut Project, footprints: &FootprintService) -> Result<BoardUpdateReport> {
+        let mut board = match project.board.take() {
+            Some(board) => board,
+            None => Board::new(project.name.clone())?,
+        };
+        let mut report = BoardUpdateReport::default();
+
+        let (pin_nets, nets) = schematic_nets(project);
+        board.nets = nets;
+
+        let mut staging = Staging::new(&board);
+        let mut kept: HashSet<String> = HashSet::new();
+        for component in project.package_components() {
+            let Some(assignment) = project.get_footprint_assignment(&component.id) else {
+                report.unassigned.push(component.reference.clone());
+                continue;
+            };
+            let Some(footprint) = footprints.get_footprint(&assignment.footprint_id) else {
+                report.missing_footprints.push(format!("{} ({})", component.reference, assignment.footprint_id));
+                continue;
+            };
+
+            let units: Vec<&Component> = project.schematics.iter()
+                .flat_map(|s| s.components.iter())
+                .filter(|c| c.id == component.id || c.same_package(component))
+                .collect();
+            let mut pad_nets = HashMap::new();
+            for unit in &units {
+                for pin in &unit.pins {
+                    let pad = assignment.pad_for_pin(&pin.number);
+                    let net = pin_nets.get(&(unit.id.clone(), pin.id.clone()));
+                    if let (Some(pad), Some(net)) = (pad, net) {
+                        pad_nets.insert(pad.to_string(), net.clone());
+                    }
+                }
+            }
+
+            let existing = board.footprints.iter_mut()
+                .find(|f| units.iter().any(|u| u.id == f.component_id));
+            match existing {
+                Some(instance) => {
+                    let changed = instance.reference != component.reference
+                        || instance.value != component.value
+                        || instance.footprint_id != footprint.id
+                        || instance.pad_nets != pad_nets;
+                    instance.component_id = component.id.clone();
+                    instance.reference = component.reference.clone();
+                    instance.value = component.value.clone();
+                    instance.footprint_id = footprint.id.clone();
+                    instance.footprint = footprint;
+                    instance.pad_nets = pad_nets;
+                    if changed {
+                        report.updated.push(component.reference.clone());
+                    }
+                    kept.insert(instance.id.clone());
+                }
+                None => {
+                    let position = staging.next(footprint.dimensions.courtyard_width, footprint.dimensions.courtyard_height);
+                    let mut instance = FootprintInstance::new(
+                        component.id.clone(),
+                        component.reference.clone(),
+                        component.value.clone(),
+                        footprint,
+                        position,
+                    );
+                    instance.rotation = assignment.placement_hints.rotation;
+                    if matches!(assignment.placement_hints.preferred_side, BoardSide::Bottom) {
+                        instance.side = PlacementSide::Bottom;
+                    }
+                    instance.pad_nets = pad_nets;
+                    report.added.push(component.reference.clone());
+                    kept.insert(instance.id.clone());
+                    board.footprints.push(instance);
+                }
+            }
+        }
+
+        // Board-only parts such as imported mounting holes have no component to follow
+        board.footprints.retain(|f| {
+            let keep = kept.contains(&f.id) || f.component_id.is_empty();
+            if !keep {
+                report.removed.push(f.reference.clone());
+            }
+            keep
+        });
+
+        project.board = Some(board);
+        project.update_modified();
+        Ok(report)
+    }
+
+    /// Resolves every schematic net to its net class, by explicit membership or name pattern.
+    pub fn net_class_assignments(project: &Project) -> Vec<NetClassAssignment> {
+        let rules = &project.settings.design_rules;
+        let mut assignments: Vec<NetClassAssignment> = Vec::new();
+        for schematic in &project.schematics {
+            for net in schematic.generate_netlist() {
+                if assignments.iter().any(|a| a.net_id == net.id) {
+                    continue;
+                }
+                assignments.push(NetClassAssignment {
+                    net_class: rules.net_class(&net.name).map(|c| c.name.clone()),
+                    net_id: net.id,
+                    net_name: net.name,
+                });
+            }
+        }
+        assignments.sort_by(|a, b| a.net_name.cmp(&b.net_name));
+        assignments
+    }
+}
+
+// Lays new footprints out in rows to the right of the board outline
+struct Staging {
+    origin_x: f64,
+    x: f64,
+    y: f64,
+    row_height: f64,
+}
+
+impl Staging {
+    fn new(board: &Board) -> Self {
+        let (_, min_y, max_x, _) = board.outline_bounds().unwrap_or((0.0, 0.0, 0.0, 0.0));
+        let origin_x = max_x + 10.0;
+        Self { origin_x, x: origin_x, y: min_y, row_height: 0.0 }
+    }
+
+    fn next(&mut self, width: f64, height: f64) -> FootprintPoint {
+        if self.x > self.origin_x && self.x + width > self.origin_x + STAGING_ROW_WIDTH {
+            self.x = self.origin_x;
+            self.y += self.row_height + STAGING_GAP;
+            self.row_height = 0.0;
+        }
+        let position = FootprintPoint { x: self.x + width / 2.0, y: self.y + height / 2.0 };
+        self.x += width + STAGING_GAP;
+        self.row_height = self.row_height.max(height);
+        position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Component, ElectricalType, Pin, PinType, Point, Schematic, Wire};
+
+    #[test]
+    fn test_update_pcb_from_assignments() {
+        let pin = |id: &str, x: f64| Pin {
+            id: id.to_string(),
+            name: id.to_string(),
+            number: id.to_string(),
+            x,
+            y: 0.0,
+            pin_type: PinType::Passive,
+            electrical: ElectricalType { voltage: None, current: None, impedance: None },
+            connected: false,
+        };
+        let mut schematic = Schematic::new("Main".to_string()).unwrap();
+        for (reference, x) in [("R1", 0.0), ("R2", 100.0), ("R3", 300.0)] {
+            let mut resistor = Component::new("resistor".to_string(), x, 0.0).unwrap();
+            resistor.reference = reference.to_string();
+            resistor.value = "10k".to_string();
+            resistor.pins = vec![pin("1", -30.0), pin("2", 30.0)];
+            schematic.add_component(resistor).unwrap();
+        }
+        schematic.add_wire(Wire::new(Point::new(30.0, 0.0), Point::new(70.0, 0.0))).unwrap();
+        let ids: Vec<String> = schematic.components.iter().map(|c| c.id.clone()).collect();
+
+        let mut project = Project::new("Test".to_string()).unwrap();
+        project.add_schematic(schematic).unwrap();
+        let footprints = FootprintService::new();
+        footprints.assign_footprint(&mut project, &ids[0], "R_0603", None).unwrap();
+        footprints.assign_footprint(&mut project, &ids[1], "R_0805", None).unwrap();
+
+        let report = BoardService::update_from_schematic(&mut project, &footprints).unwrap();
+        assert_eq!(report.added, vec!["R1", "R2"]);
+        assert_eq!(report.unassigned, vec!["R3"]);
+
+        let board = project.board.as_ref().unwrap();
+        let r1 = board.footprint_for_component(&ids[0]).unwrap();
+        let r2 = board.footprint_for_component(&ids[1]).unwrap();
+        assert_eq!(r1.pad_net("2"), r2.pad_net("1"));
+        assert!(r1.pad_net("2").is_some() && r1.pad_net("1").is_none());
+        assert!(r1.position.x > 100.0, "new parts are staged beside the outline");
+
+        // Moved parts stay put; instances of deleted components go
+        let moved = FootprintPoint { x: 20.0, y: 20.0 };
+        project.board.as_mut().unwrap().footprints[0].position = moved;
+        project.schematics[0].remove_component(&ids[1]);
+        let report = BoardService::update_from_schematic(&mut project, &footprints).unwrap();
+        assert!(report.added.is_empty());
+        assert_eq!(report.removed, vec!["R2"]);
+        assert_eq!(project.board.as_ref().unwrap().footprints[0].position, moved);
+
+        let restored = Project::from_json(&project.to_json().unwrap()).unwrap();
+        assert_eq!(restored.board.unwrap().footprints.len(), 1);
+    }
+}
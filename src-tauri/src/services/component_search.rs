@@ -0,0 +1,402 @@
+use crate::models::component::{ComponentLibrary, ComponentTemplate};
+use crate::utils::units::{parse_si_unit, parse_si_value};
+use std::collections::HashMap;
+
+// Field weights: a hit in the name outranks the same hit in a description
+const NAME_WEIGHT: f64 = 10.0;
+const KEYWORD_WEIGHT: f64 = 6.0;
+const VALUE_WEIGHT: f64 = 5.0;
+const CATEGORY_WEIGHT: f64 = 4.0;
+const DESCRIPTION_WEIGHT: f64 = 2.0;
+
+const KNOWN_UNITS: [&str; 7] = ["F", "V", "A", "Ω", "H", "W", "Hz"];
+
+// Relative tolerance for `=` filters, so "10µF" matches a stored 1e-5
+const EQUALITY_TOLERANCE: f64 = 1e-6;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FilterOp {
+    Less,
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    Greater,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParametricFilter {
+    pub op: FilterOp,
+    pub value: f64,
+    pub unit: Option<String>,
+}
+
+impl ParametricFilter {
+    /// True when some value in `min..=max` satisfies the filter; a fixed value has
+    /// `min == max`.
+    fn accepts(&self, min: f64, max: f64) -> bool {
+        let tolerance = EQUALITY_TOLERANCE * self.value.abs().max(f64::MIN_POSITIVE);
+        match self.op {
+            FilterOp::Less => min < self.value - tolerance,
+            FilterOp::LessOrEqual => min <= self.value + tolerance,
+            FilterOp::Equal => min <= self.value + tolerance && max >= self.value - tolerance,
+            FilterOp::GreaterOrEqual => max >= self.value - tolerance,
+            FilterOp::Greater => max > self.value + tolerance,
+        }
+    }
+}
+
+/// A parsed search string. Clauses are separated by commas; a clause with a comparison
+/// operator or a value carrying a unit ("≥ 25V", "10µF") becomes a parametric filter,
+/// everything else is split into words for fuzzy text matching.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchQuery {
+    pub terms: Vec<String>,
+    pub filters: Vec<ParametricFilter>,
+}
+
+impl SearchQuery {
+    pub fn parse(query: &str) -> Self {
+        let mut parsed = Self::default();
+
+        for clause in query.split([',', ';']).map(str::trim).filter(|c| !c.is_empty()) {
+            if let Some(filter) = parse_filter(clause) {
+                parsed.filters.push(filter);
+                continue;
+            }
+            for word in clause.split_whitespace() {
+                match parse_filter(word) {
+                    Some(filter) => parsed.filters.push(filter),
+                    None => parsed.terms.push(word.to_lowercase()),
+                }
+            }
+        }
+
+        parsed
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty() && self.filters.is_empty()
+    }
+}
+
+fn parse_filter(clause: &str) -> Option<ParametricFilter> {
+    const OPERATORS: [(&str, FilterOp); 8] = [
+        ("≥", FilterOp::GreaterOrEqual),
+        (">=", FilterOp::GreaterOrEqual),
+        ("≤", FilterOp::LessOrEqual),
+        ("<=", FilterOp::LessOrEqual),
+        (">", FilterOp::Greater),
+        ("<", FilterOp::Less),
+        ("==", FilterOp::Equal),
+        ("=", FilterOp::Equal),
+    ];
+
+    let (op, rest) = OPERATORS.iter()
+        .find_map(|(symbol, op)| clause.strip_prefix(symbol).map(|rest| (*op, rest)))
+        .unwrap_or((FilterOp::Equal, clause));
+
+    let value = parse_si_value(rest)?;
+    let unit = parse_si_unit(rest);
+
+    // Without an operator only a real unit makes it a value: "0805" and "74HC00" are names
+    let has_operator = rest.len() != clause.len();
+    if !has_operator && !unit.as_deref().is_some_and(|u| KNOWN_UNITS.contains(&u)) {
+        return None;
+    }
+
+    Some(ParametricFilter { op, value, unit })
+}
+
+// Quantities a property or parameter key implies when its value carries no unit
+fn unit_for_key(key: &str) -> Option<&'static str> {
+    let key = key.to_lowercase();
+    let table = [
+        ("capacit", "F"),
+        ("resist", "Ω"),
+        ("induct", "H"),
+        ("volt", "V"),
+        ("current", "A"),
+        ("power", "W"),
+        ("freq", "Hz"),
+    ];
+    table.iter().find(|(needle, _)| key.contains(needle)).map(|(_, unit)| *unit)
+}
+
+// A fixed value, or the range a configurable parameter may take
+#[derive(Debug, Clone)]
+struct IndexedValue {
+    min: f64,
+    max: f64,
+    unit: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct IndexEntry {
+    library_id: String,
+    component_id: String,
+    fields: Vec<(String, f64)>,
+    values: Vec<IndexedValue>,
+}
+
+impl IndexEntry {
+    fn new(library_id: &str, template: &ComponentTemplate, categories: &HashMap<&str, &str>) -> Self {
+        let mut fields = vec![(template.name.to_lowercase(), NAME_WEIGHT)];
+        fields.extend(template.keywords.iter().map(|k| (k.to_lowercase(), KEYWORD_WEIGHT)));
+        if let Some(category) = categories.get(template.category_id.as_str()) {
+            fields.push((category.to_lowercase(), CATEGORY_WEIGHT));
+        }
+        if let Some(description) = &template.description {
+            fields.push((description.to_lowercase(), DESCRIPTION_WEIGHT));
+        }
+        if let Some(footprint) = &template.footprint {
+            fields.push((footprint.to_lowercase(), VALUE_WEIGHT));
+        }
+
+        let mut values = Vec::new();
+        for (key, property) in &template.default_properties {
+            let text = match &property.value {
+                serde_json::Value::String(s) => s.clone(),
+                serde_json::Value::Number(n) => n.to_string(),
+                _ => continue,
+            };
+            fields.push((text.to_lowercase(), VALUE_WEIGHT));
+            if let Some(value) = parse_si_value(&text) {
+                let unit = parse_si_unit(&text).or_else(|| unit_for_key(key).map(str::to_string));
+                values.push(IndexedValue { min: value, max: value, unit });
+            }
+        }
+        for (key, parameter) in &template.parameters {
+            let unit = parameter.unit.clone().or_else(|| unit_for_key(key).map(str::to_string));
+            let default = match &parameter.default_value {
+                serde_json::Value::Number(n) => n.as_f64(),
+                serde_json::Value::String(s) => {
+                    fields.push((s.to_lowercase(), VALUE_WEIGHT));
+                    parse_si_value(s)
+                }
+                _ => None,
+            };
+            // A configurable range can satisfy any filter within its bounds; a missing bound
+            // is taken from the default
+            let min = parameter.min_value.or(default);
+            let max = parameter.max_value.or(default);
+            if let Some((min, max)) = min.zip(max).or(min.map(|v| (v, v))).or(max.map(|v| (v, v))) {
+                values.push(IndexedValue { min, max, unit });
+            }
+        }
+
+        Self {
+            library_id: library_id.to_string(),
+            component_id: template.id.clone(),
+            fields,
+            values,
+        }
+    }
+
+    fn score(&self, query: &SearchQuery) -> Option<f64> {
+        let passes_filters = query.filters.iter().all(|filter| {
+            self.values.iter().any(|value| {
+                let unit_matches = match (&filter.unit, &value.unit) {
+                    (Some(wanted), Some(unit)) => wanted == unit,
+                    (Some(_), None) => false,
+                    (None, _) => true,
+                };
+                unit_matches && filter.accepts(value.min, value.max)
+            })
+        });
+        if !passes_filters {
+            return None;
+        }
+
+        // Every word has to match somewhere; the best field decides its contribution
+        let mut score = 0.0;
+        for term in &query.terms {
+            let best = self.fields.iter()
+                .map(|(text, weight)| match_quality(term, text) * weight)
+                .fold(0.0, f64::max);
+            if best == 0.0 {
+                return None;
+            }
+            score += best;
+        }
+
+        Some(score)
+    }
+}
+
+// 1.0 for a whole word, down to ~0.2 for a scattered subsequence, 0.0 for no match
+fn match_quality(term: &str, text: &str) -> f64 {
+    let words = || text.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty());
+
+    if words().any(|w| w == term) {
+        return 1.0;
+    }
+    if words().any(|w| w.starts_with(term)) {
+        return 0.8;
+    }
+    if text.contains(term) {
+        return 0.6;
+    }
+    if term.chars().count() >= 4 && words().any(|w| within_one_edit(term, w)) {
+        return 0.5;
+    }
+    subsequence_quality(term, text)
+}
+
+// Typo tolerance: one insertion, deletion or substitution
+fn within_one_edit(a: &str, b: &str) -> bool {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > 1 {
+        return false;
+    }
+
+    let prefix = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let (ra, rb) = (&a[prefix..], &b[prefix..]);
+    match ra.len().cmp(&rb.len()) {
+        std::cmp::Ordering::Equal => ra.get(1..) == rb.get(1..),
+        std::cmp::Ordering::Less => ra == &rb[1..],
+        std::cmp::Ordering::Greater => &ra[1..] == rb,
+    }
+}
+
+fn subsequence_quality(term: &str, text: &str) -> f64 {
+    let mut text_chars = text.char_indices();
+    let mut first = None;
+    let mut last = 0;
+    for wanted in term.chars() {
+        match text_chars.find(|(_, c)| *c == wanted) {
+            Some((i, _)) => {
+                first.get_or_insert(i);
+                last = i;
+            }
+            None => return 0.0,
+        }
+    }
+
+    // Tighter clusters of matched characters score higher
+    let span = (last - first.unwrap_or(0) + 1) as f64;
+    0.4 * (term.len() as f64 / span).min(1.0)
+}
+
+/// In-memory search index over every loaded library, rebuilt by `LibraryService`
+/// whenever a library is added, edited or removed.
+#[derive(Debug, Clone, Default)]
+pub struct ComponentSearchIndex {
+    entries: Vec<IndexEntry>,
+}
+
+impl ComponentSearchIndex {
+    pub fn build<'a>(libraries: impl IntoIterator<Item = &'a ComponentLibrary>) -> Self {
+        let mut entries = Vec::new();
+        for library in libraries {
+            let categories: HashMap<&str, &str> = library.categories.iter()
+                .map(|c| (c.id.as_str(), c.name.as_str()))
+                .collect();
+            entries.extend(library.components.values().map(|t| IndexEntry::new(&library.id, t, &categories)));
+        }
+        Self { entries }
+    }
+
+    /// Ranked `(library id, component id, score)` matches, best first. An empty query
+    /// matches everything, in component id order.
+    pub fn search(&self, query: &SearchQuery, limit: usize) -> Vec<(String, String, f64)> {
+        let mut hits: Vec<(String, String, f64)> = self.entries.iter()
+            .filter_map(|entry| {
+                entry.score(query).map(|score| (entry.library_id.clone(), entry.component_id.clone(), score))
+            })
+            .collect();
+
+        hits.sort_by(|a, b| b.2.total_cmp(&a.2).then_with(|| a.1.cmp(&b.1)));
+        hits.truncate(limit);
+        hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::component::{ComponentCategory, ComponentSymbol, ParameterTemplate, ParameterType};
+
+    fn capacitor(name: &str, capacitance: f64, footprint: &str) -> ComponentTemplate {
+        capacitor_range(name, capacitance, None, footprint)
+    }
+
+    // A part whose capacitance can be set anywhere in `range`
+    fn capacitor_range(name: &str, capacitance: f64, range: Option<(f64, f64)>, footprint: &str) -> ComponentTemplate {
+        let symbol = ComponentSymbol { width: 20.0, height: 20.0, draw_commands: Vec::new(), graphics: None };
+        let mut template = ComponentTemplate::new(name.to_string(), "passive".to_string(), symbol).unwrap();
+        template.keywords = vec!["capacitor".to_string()];
+        template.footprint = Some(footprint.to_string());
+        template.add_parameter("capacitance".to_string(), ParameterTemplate {
+            name: "Capacitance".to_string(),
+            parameter_type: ParameterType::Number,
+            default_value: serde_json::json!(capacitance),
+            min_value: range.map(|r| r.0),
+            max_value: range.map(|r| r.1),
+            unit: Some("F".to_string()),
+            description: None,
+            required: true,
+        }).unwrap();
+        template
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let query = SearchQuery::parse("capacitor, ≥ 10µF, >= 25V, 0805");
+        assert_eq!(query.terms, vec!["capacitor".to_string(), "0805".to_string()]);
+        assert_eq!(query.filters.len(), 2);
+        assert_eq!(query.filters[0].op, FilterOp::GreaterOrEqual);
+        assert_eq!(query.filters[1].unit.as_deref(), Some("V"));
+        assert!(SearchQuery::parse("74HC00").filters.is_empty());
+    }
+
+    #[test]
+    fn test_search_ranking_and_filters() {
+        let mut library = ComponentLibrary::new("Caps".to_string()).unwrap();
+        library.add_category(ComponentCategory {
+            id: "passive".to_string(),
+            name: "Passive".to_string(),
+            parent_id: None,
+            description: None,
+            color: None,
+        }).unwrap();
+        library.add_component_template(capacitor("Bulk Capacitor", 22e-6, "C_0805")).unwrap();
+        library.add_component_template(capacitor("Decoupling Capacitor", 100e-9, "C_0603")).unwrap();
+        let index = ComponentSearchIndex::build([&library]);
+
+        let hits = index.search(&SearchQuery::parse("capacitor, ≥ 10µF"), 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(library.components[&hits[0].1].name, "Bulk Capacitor");
+
+        // Typos still find the part
+        assert_eq!(index.search(&SearchQuery::parse("decoupling capasitor"), 10).len(), 1);
+        assert!(index.search(&SearchQuery::parse("inductor"), 10).is_empty());
+    }
+
+    #[test]
+    fn test_parameter_ranges_and_tolerance() {
+        let mut library = ComponentLibrary::new("Caps".to_string()).unwrap();
+        library.add_category(ComponentCategory {
+            id: "passive".to_string(),
+            name: "Passive".to_string(),
+            parent_id: None,
+            description: None,
+            color: None,
+        }).unwrap();
+        library.add_component_template(capacitor_range("Trimmer", 1e-6, Some((1e-6, 10e-6)), "C_0805")).unwrap();
+        let index = ComponentSearchIndex::build([&library]);
+        for (query, hits) in [("4.7µF", 1), ("= 10µF", 1), ("≤ 2µF", 1), ("> 10µF", 0), ("22µF", 0), ("< 1µF", 0)] {
+            assert_eq!(index.search(&SearchQuery::parse(query), 10).len(), hits, "{}", query);
+        }
+        // An empty query lists everything
+        assert_eq!(index.search(&SearchQuery::parse(""), 10).len(), 1);
+
+        // Zero and negative targets get a tolerance the right way round
+        let filter = |op, value| ParametricFilter { op, value, unit: None };
+        assert!(filter(FilterOp::Equal, 0.0).accepts(0.0, 0.0));
+        assert!(filter(FilterOp::GreaterOrEqual, -5.0).accepts(-5.0 - 1e-9, -5.0 - 1e-9));
+        assert!(!filter(FilterOp::GreaterOrEqual, -5.0).accepts(-5.1, -5.1));
+        assert!(filter(FilterOp::LessOrEqual, -5.0).accepts(-5.0 + 1e-9, -5.0 + 1e-9));
+        assert!(!filter(FilterOp::LessOrEqual, -5.0).accepts(-4.9, -4.9));
+    }
+}
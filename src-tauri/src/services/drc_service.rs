@@ -0,0 +1,693 @@
+use crate::models::{Board, DesignRules, DrillShape, KeepoutArea, LayerType, PadType};
+use crate::services::erc_service::ERCSeverity;
+use crate::utils::error::Result;
+use crate::utils::geometry::{bounds_near, point_in_contours, polygons_overlap, Shape};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// Items closer than this are treated as touching when tracing connectivity
+const CONTACT_TOLERANCE: f64 = 1e-6;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DRCReport {
+    pub errors: Vec<DRCError>,
+    pub warnings: Vec<DRCWarning>,
+    pub passed: bool,
+    pub timestamp: i64,
+    pub statistics: DRCStatistics,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DRCError {
+    pub error_type: DRCErrorType,
+    pub message: String,
+    pub location: Option<DRCLocation>,
+    pub severity: ERCSeverity,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DRCWarning {
+    pub warning_type: DRCWarningType,
+    pub message: String,
+    pub location: Option<DRCLocation>,
+    pub severity: ERCSeverity,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DRCLocation {
+    pub x: f64,
+    pub y: f64,
+    pub layer: Option<LayerType>,
+    pub footprint_id: Option<String>,
+    pub pad_number: Option<String>,
+    pub track_id: Option<String>,
+    pub via_id: Option<String>,
+    pub zone_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DRCErrorType {
+    Clearance,
+    ShortCircuit,
+    EdgeClearance,
+    TrackWidth,
+    AnnularRing,
+    DrillSize,
+    CourtyardOverlap,
+    KeepoutViolation,
+    UnroutedConnection,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DRCWarningType {
+    MissingOutline,
+    MissingCourtyard,
+    FootprintOutsideBoard,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DRCStatistics {
+    pub total_footprints: usize,
+    pub total_pads: usize,
+    pub total_tracks: usize,
+    pub total_vias: usize,
+    pub total_nets: usize,
+    pub routed_connections: usize,
+    pub unrouted_connections: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum CopperKind {
+    Pad,
+    Track,
+    Via,
+    Zone,
+}
+
+/// A piece of copper on one or more layers: a pad, a track segment, a via or one polygon
+/// of a zone fill.
+#[derive(Debug, Clone)]
+pub(crate) struct CopperItem {
+    pub kind: CopperKind,
+    // Footprint instance, track or via id
+    pub id: String,
+    pub pad_number: Option<String>,
+    pub net_id: Option<String>,
+    pub layers: Vec<LayerType>,
+    pub shape: Shape,
+    pub label: String,
+}
+
+impl CopperItem {
+    fn location(&self, layer: Option<LayerType>) -> DRCLocation {
+        let (x, y) = self.shape.center();
+        DRCLocation {
+            x,
+            y,
+            layer,
+            footprint_id: (self.kind == CopperKind::Pad).then(|| self.id.clone()),
+            pad_number: self.pad_number.clone(),
+            track_id: (self.kind == CopperKind::Track).then(|| self.id.clone()),
+            via_id: (self.kind == CopperKind::Via).then(|| self.id.clone()),
+            zone_id: (self.kind == CopperKind::Zone).then(|| self.id.clone()),
+        }
+    }
+}
+
+pub(crate) fn copper_items(board: &Board) -> Vec<CopperItem> {
+    let copper = board.stackup.copper_layers();
+    let mut items = Vec::new();
+
+    for instance in &board.footprints {
+        for pad in &instance.footprint.pads {
+            let layers = instance.pad_copper_layers(pad, &board.stackup);
+            if layers.is_empty() {
+                continue;
+            }
+            items.push(CopperItem {
+                kind: CopperKind::Pad,
+                id: instance.id.clone(),
+                pad_number: Some(pad.pad_number.clone()),
+                net_id: instance.pad_net(&pad.pad_number).map(|n| n.to_string()),
+                layers,
+                shape: instance.pad_shape(pad),
+                label: format!("{} pad {}", instance.reference, pad.pad_number),
+            });
+        }
+    }
+
+    for track in &board.tracks {
+        items.push(CopperItem {
+            kind: CopperKind::Track,
+            id: track.id.clone(),
+            pad_number: None,
+            net_id: track.net_id.clone(),
+            layers: vec![track.layer.clone()],
+            shape: Shape::segment((track.start.x, track.start.y), (track.end.x, track.end.y), track.width / 2.0),
+            label: "Track".to_string(),
+        });
+    }
+
+    for via in &board.vias {
+        let position = |layer: &LayerType| copper.iter().position(|l| l == layer).unwrap_or(0);
+        let (a, b) = (position(&via.start_layer), position(&via.end_layer));
+        items.push(CopperItem {
+            kind: CopperKind::Via,
+            id: via.id.clone(),
+            pad_number: None,
+            net_id: via.net_id.clone(),
+            layers: copper[a.min(b)..=a.max(b)].to_vec(),
+            shape: Shape::circle((via.position.x, via.position.y), via.diameter / 2.0),
+            label: "Via".to_string(),
+        });
+    }
+
+    for zone in &board.zones {
+        for polygon in &zone.fill {
+            items.push(CopperItem {
+                kind: CopperKind::Zone,
+                id: zone.id.clone(),
+                pad_number: None,
+                net_id: zone.net_id.clone(),
+                layers: vec![zone.layer.clone()],
+                shape: polygon.shape(),
+                label: "Zone fill".to_string(),
+            });
+        }
+    }
+
+    items
+}
+
+fn share_layer(a: &CopperItem, b: &CopperItem) -> Option<LayerType> {
+    a.layers.iter().find(|l| b.layers.contains(l)).cloned()
+}
+
+/// Connected copper: the root item index for every item. Only items of the same net join;
+/// touching items of different nets are shorts, which the clearance check reports.
+pub(crate) fn connectivity(items: &[CopperItem]) -> Vec<usize> {
+    let mut parent: Vec<usize> = (0..items.len()).collect();
+    fn find(parent: &mut [usize], mut x: usize) -> usize {
+        while parent[x] != x {
+            parent[x] = parent[parent[x]];
+            x = parent[x];
+        }
+        x
+    }
+
+    let bounds: Vec<_> = items.iter().map(|i| i.shape.bounds()).collect();
+    for i in 0..items.len() {
+        for j in i + 1..items.len() {
+            let (a, b) = (&items[i], &items[j]);
+            if a.net_id.is_none() || a.net_id != b.net_id || !bounds_near(bounds[i], bounds[j], CONTACT_TOLERANCE) {
+                continue;
+            }
+            if share_layer(a, b).is_some() && a.shape.distance(&b.shape) <= CONTACT_TOLERANCE {
+                let (ra, rb) = (find(&mut parent, i), find(&mut parent, j));
+                parent[rb] = ra;
+            }
+        }
+    }
+    (0..items.len()).map(|i| find(&mut parent, i)).collect()
+}
+
+pub struct DRCChecker {
+    board: Board,
+    rules: DesignRules,
+    items: Vec<CopperItem>,
+    net_names: HashMap<String, String>,
+    errors: Vec<DRCError>,
+    warnings: Vec<DRCWarning>,
+    routed_connections: usize,
+    unrouted_connections: usize,
+}
+
+impl DRCChecker {
+    pub fn new(board: Board, rules: DesignRules) -> Self {
+        let items = copper_items(&board);
+        let net_names = board.nets.iter().map(|n| (n.id.clone(), n.name.clone())).collect();
+        Self {
+            board,
+            rules,
+            items,
+            net_names,
+            errors: Vec::new(),
+            warnings: Vec::new(),
+            routed_connections: 0,
+            unrouted_connections: 0,
+        }
+    }
+
+    pub fn run_check(&mut self) -> Result<DRCReport> {
+        self.check_track_widths();
+        self.check_clearances();
+        self.check_edge_clearance();
+        self.check_holes();
+        self.check_courtyards();
+        self.check_keepouts();
+        self.check_unrouted();
+
+        let statistics = self.calculate_statistics();
+
+        Ok(DRCReport {
+            errors: self.errors.clone(),
+            warnings: self.warnings.clone(),
+            passed: self.errors.is_empty(),
+            timestamp: chrono::Utc::now().timestamp(),
+            statistics,
+        })
+    }
+
+    fn net_name(&self, net_id: Option<&String>) -> Option<&str> {
+        net_id.and_then(|id| self.net_names.get(id)).map(|n| n.as_str())
+    }
+
+    fn error(&mut self, error_type: DRCErrorType, severity: ERCSeverity, message: String, location: Option<DRCLocation>) {
+        self.errors.push(DRCError { error_type, message, location, severity });
+    }
+
+    fn warning(&mut self, warning_type: DRCWarningType, message: String, location: Option<DRCLocation>) {
+        self.warnings.push(DRCWarning { warning_type, message, location, severity: ERCSeverity::Low });
+    }
+
+    fn check_track_widths(&mut self) {
+        let tracks = self.board.tracks.clone();
+        for track in tracks {
+            let net = self.net_name(track.net_id.as_ref()).map(|n| n.to_string());
+            let minimum = self.rules.track_width_for(net.as_deref());
+            if track.width < minimum - 1e-9 {
+                let location = DRCLocation {
+                    x: (track.start.x + track.end.x) / 2.0,
+                    y: (track.start.y + track.end.y) / 2.0,
+                    layer: Some(track.layer.clone()),
+                    footprint_id: None,
+                    pad_number: None,
+                    track_id: Some(track.id.clone()),
+                    via_id: None,
+                    zone_id: None,
+                };
+                self.error(
+                    DRCErrorType::TrackWidth,
+                    ERCSeverity::Medium,
+                    format!("Track width {:.3} mm is below the {:.3} mm minimum{}", track.width, minimum, net_suffix(net.as_deref())),
+                    Some(location),
+                );
+            }
+        }
+    }
+
+    fn check_clearances(&mut self) {
+        let max_clearance = self.rules.net_classes.iter()
+            .filter_map(|c| c.clearance)
+            .fold(self.rules.clearance, f64::max);
+        let bounds: Vec<_> = self.items.iter().map(|i| i.shape.bounds()).collect();
+        let mut violations = Vec::new();
+
+        for i in 0..self.items.len() {
+            for j in i + 1..self.items.len() {
+                let (a, b) = (&self.items[i], &self.items[j]);
+                if a.net_id.is_some() && a.net_id == b.net_id {
+                    continue;
+                }
+                // Pads sharing a number on one footprint are the same terminal, as is one zone's fill
+                if a.kind == b.kind && a.id == b.id && matches!(a.kind, CopperKind::Pad | CopperKind::Zone) && a.pad_number == b.pad_number {
+                    continue;
+                }
+                if !bounds_near(bounds[i], bounds[j], max_clearance) {
+                    continue;
+                }
+                let Some(layer) = share_layer(a, b) else { continue };
+                let (net_a, net_b) = (self.net_name(a.net_id.as_ref()), self.net_name(b.net_id.as_ref()));
+                let required = self.rules.clearance_between(net_a, net_b);
+                let gap = a.shape.distance(&b.shape);
+                if gap < required - 1e-9 {
+                    violations.push((i, j, layer, gap, required));
+                }
+            }
+        }
+
+        for (i, j, layer, gap, required) in violations {
+            let (a, b) = (&self.items[i], &self.items[j]);
+            let nets = format!(
+                "{} / {}",
+                self.net_name(a.net_id.as_ref()).unwrap_or("no net"),
+                self.net_name(b.net_id.as_ref()).unwrap_or("no net"),
+            );
+            let location = Some(a.location(Some(layer)));
+            if gap <= 0.0 && a.net_id.is_some() && b.net_id.is_some() {
+                let message = format!("{} shorts {} ({})", a.label, b.label.to_lowercase(), nets);
+                self.error(DRCErrorType::ShortCircuit, ERCSeverity::Critical, message, location);
+            } else {
+                let message = format!(
+                    "{} to {} clearance {:.3} mm is below {:.3} mm ({})",
+                    a.label, b.label.to_lowercase(), gap.max(0.0), required, nets
+                );
+                self.error(DRCErrorType::Clearance, ERCSeverity::High, message, location);
+            }
+        }
+    }
+
+    fn check_edge_clearance(&mut self) {
+        let segments = self.board.outline_segments();
+        if segments.is_empty() {
+            self.warning(DRCWarningType::MissingOutline, "Board has no outline on EdgeCuts".to_string(), None);
+            return;
+        }
+        // Cutouts count as outside the board
+        let contours = self.board.outline_contours();
+        let edges: Vec<Shape> = segments.iter().map(|&(a, b)| Shape::segment(a, b, 0.0)).collect();
+
+        let mut violations = Vec::new();
+        for (index, item) in self.items.iter().enumerate() {
+            let gap = edges.iter().map(|e| item.shape.distance(e)).fold(f64::INFINITY, f64::min);
+            let outside = contours.as_ref().is_some_and(|c| !point_in_contours(item.shape.center(), c));
+            if outside || gap < self.rules.edge_clearance - 1e-9 {
+                violations.push((index, gap, outside));
+            }
+        }
+        for (index, gap, outside) in violations {
+            let item = &self.items[index];
+            let message = if outside {
+                format!("{} is outside the board outline", item.label)
+            } else {
+                format!("{} is {:.3} mm from the board edge; minimum is {:.3} mm", item.label, gap.max(0.0), self.rules.edge_clearance)
+            };
+            let location = Some(item.location(item.layers.first().cloned()));
+            self.error(DRCErrorType::EdgeClearance, ERCSeverity::High, message, location);
+        }
+
+        if let Some(contours) = contours {
+            let outside: Vec<_> = self.board.footprints.iter()
+                .filter(|f| !point_in_contours((f.position.x, f.position.y), &contours))
+                .map(|f| (f.reference.clone(), footprint_location(f.id.clone(), f.position.x, f.position.y)))
+                .collect();
+            for (reference, location) in outside {
+                self.warning(DRCWarningType::FootprintOutsideBoard, format!("{} is placed outside the board", reference), Some(location));
+            }
+        }
+    }
+
+    // Drill size limits and annular rings for pad holes and vias
+    fn check_holes(&mut self) {
+        let mut findings = Vec::new();
+        for instance in &self.board.footprints {
+            for pad in &instance.footprint.pads {
+                let Some(drill) = &pad.drill else { continue };
+                let (hole_width, hole_height, tool) = match &drill.shape {
+                    DrillShape::Circle => (drill.diameter, drill.diameter, drill.diameter),
+                    DrillShape::Oval { width, height } => (*width, *height, width.min(*height)),
+                    DrillShape::Slot { start, end, width } => {
+                        let length = ((end.x - start.x).powi(2) + (end.y - start.y).powi(2)).sqrt();
+                        (length + width, *width, *width)
+                    }
+                };
+                let position = instance.pad_position(pad);
+                let location = DRCLocation {
+                    x: position.x,
+                    y: position.y,
+                    layer: None,
+                    footprint_id: Some(instance.id.clone()),
+                    pad_number: Some(pad.pad_number.clone()),
+                    track_id: None,
+                    via_id: None,
+                    zone_id: None,
+                };
+                let label = format!("{} pad {}", instance.reference, pad.pad_number);
+                let plated = !matches!(pad.pad_type, PadType::NPTH);
+                let ring = ((pad.size.width - hole_width) / 2.0).min((pad.size.height - hole_height) / 2.0);
+                findings.push((label, location, tool, plated.then_some(ring)));
+            }
+        }
+        for via in &self.board.vias {
+            let location = DRCLocation {
+                x: via.position.x,
+                y: via.position.y,
+                layer: None,
+                footprint_id: None,
+                pad_number: None,
+                track_id: None,
+                via_id: Some(via.id.clone()),
+                zone_id: None,
+            };
+            findings.push(("Via".to_string(), location, via.drill, Some((via.diameter - via.drill) / 2.0)));
+        }
+
+        for (label, location, tool, ring) in findings {
+            if tool < self.rules.min_drill - 1e-9 || tool > self.rules.max_drill + 1e-9 {
+                self.error(
+                    DRCErrorType::DrillSize,
+                    ERCSeverity::High,
+                    format!("{} drill {:.3} mm is outside {:.3}-{:.3} mm", label, tool, self.rules.min_drill, self.rules.max_drill),
+                    Some(location.clone()),
+                );
+            }
+            if let Some(ring) = ring.filter(|r| *r < self.rules.min_annular_ring - 1e-9) {
+                self.error(
+                    DRCErrorType::AnnularRing,
+                    ERCSeverity::Medium,
+                    format!("{} annular ring {:.3} mm is below {:.3} mm", label, ring.max(0.0), self.rules.min_annular_ring),
+                    Some(location),
+                );
+            }
+        }
+    }
+
+    fn check_courtyards(&mut self) {
+        let courtyards: Vec<Vec<Shape>> = self.board.footprints.iter().map(|f| f.courtyard()).collect();
+        let mut overlaps = Vec::new();
+        let mut missing = Vec::new();
+
+        for (i, a) in self.board.footprints.iter().enumerate() {
+            if courtyards[i].is_empty() {
+                missing.push(i);
+                continue;
+            }
+            for (j, b) in self.board.footprints.iter().enumerate().skip(i + 1) {
+                if a.side != b.side {
+                    continue;
+                }
+                let overlap = courtyards[i].iter().any(|ca| courtyards[j].iter().any(|cb| shapes_overlap(ca, cb)));
+                if overlap {
+                    overlaps.push((i, j));
+                }
+            }
+        }
+
+        for index in missing {
+            let f = &self.board.footprints[index];
+            let location = footprint_location(f.id.clone(), f.position.x, f.position.y);
+            self.warning(DRCWarningType::MissingCourtyard, format!("{} has no courtyard", f.reference), Some(location));
+        }
+        for (i, j) in overlaps {
+            let (a, b) = (&self.board.footprints[i], &self.board.footprints[j]);
+            let location = footprint_location(a.id.clone(), a.position.x, a.position.y);
+            let message = format!("Courtyards of {} and {} overlap", a.reference, b.reference);
+            self.error(DRCErrorType::CourtyardOverlap, ERCSeverity::Medium, message, Some(location));
+        }
+    }
+
+    fn check_keepouts(&mut self) {
+        // Board keepouts, then each footprint's own, which doesn't apply to that footprint
+        let mut keepouts: Vec<(KeepoutArea, Option<String>)> = self.board.keepouts.iter().map(|k| (k.clone(), None)).collect();
+        keepouts.extend(self.board.footprints.iter().filter_map(|f| f.keepout().map(|k| (k, Some(f.id.clone())))));
+
+        let mut violations = Vec::new();
+        for (keepout, owner) in &keepouts {
+            if keepout.area.len() < 3 {
+                continue;
+            }
+            let area = Shape::polygon(keepout.area.iter().map(|p| (p.x, p.y)).collect());
+            let restrictions = &keepout.restrictions;
+
+            for item in &self.items {
+                if item.kind == CopperKind::Pad && owner.as_ref() == Some(&item.id) {
+                    continue;
+                }
+                let applies = match item.kind {
+                    CopperKind::Via => restrictions.no_vias || restrictions.no_copper,
+                    _ => restrictions.no_copper,
+                };
+                let layer = item.layers.iter().find(|l| keepout.layers.contains(l));
+                if applies && layer.is_some() && item.shape.distance(&area) < 0.0 {
+                    let what = if item.kind == CopperKind::Via && restrictions.no_vias { "vias" } else { "copper" };
+                    violations.push((format!("{} is inside a keepout that forbids {}", item.label, what), item.location(layer.cloned())));
+                }
+            }
+
+            if restrictions.no_components {
+                for footprint in &self.board.footprints {
+                    if owner.as_ref() == Some(&footprint.id) {
+                        continue;
+                    }
+                    let courtyard_layer = footprint.board_layer(&LayerType::TopCourtyard);
+                    let copper_layer = footprint.board_layer(&LayerType::TopCopper);
+                    if !keepout.layers.contains(&courtyard_layer) && !keepout.layers.contains(&copper_layer) {
+                        continue;
+                    }
+                    let position = Shape::circle((footprint.position.x, footprint.position.y), 0.0);
+                    let inside = footprint.courtyard().iter().chain(std::iter::once(&position))
+                        .any(|c| shapes_overlap(c, &area));
+                    if inside {
+                        violations.push((
+                            format!("{} is inside a keepout that forbids components", footprint.reference),
+                            footprint_location(footprint.id.clone(), footprint.position.x, footprint.position.y),
+                        ));
+                    }
+                }
+            }
+        }
+
+        for (message, location) in violations {
+            self.error(DRCErrorType::KeepoutViolation, ERCSeverity::High, message, Some(location));
+        }
+    }
+
+    fn check_unrouted(&mut self) {
+        let roots = connectivity(&self.items);
+        // net -> pad clusters, each with its first pad
+        let mut clusters: HashMap<&str, Vec<(usize, usize)>> = HashMap::new();
+        for (index, item) in self.items.iter().enumerate() {
+            let (CopperKind::Pad, Some(net)) = (item.kind, item.net_id.as_deref()) else { continue };
+            let entry = clusters.entry(net).or_default();
+            if !entry.iter().any(|(root, _)| *root == roots[index]) {
+                entry.push((roots[index], index));
+            }
+        }
+
+        let pads_per_net = self.items.iter()
+            .filter(|i| i.kind == CopperKind::Pad)
+            .filter_map(|i| i.net_id.as_deref())
+            .fold(HashMap::<&str, usize>::new(), |mut counts, net| {
+                *counts.entry(net).or_default() += 1;
+                counts
+            });
+        let connections: usize = pads_per_net.values().map(|n| n - 1).sum();
+
+        let mut findings: Vec<(String, DRCLocation)> = Vec::new();
+        let mut nets: Vec<_> = clusters.into_iter().collect();
+        nets.sort_by(|a, b| a.0.cmp(b.0));
+        for (net, islands) in nets {
+            for &(_, index) in islands.iter().skip(1) {
+                let item = &self.items[index];
+                let name = self.net_names.get(net).map(|n| n.as_str()).unwrap_or(net);
+                findings.push((
+                    format!("{} is not connected to the rest of net {}", item.label, name),
+                    item.location(item.layers.first().cloned()),
+                ));
+            }
+        }
+
+        self.unrouted_connections = findings.len();
+        self.routed_connections = connections.saturating_sub(findings.len());
+        for (message, location) in findings {
+            self.error(DRCErrorType::UnroutedConnection, ERCSeverity::High, message, Some(location));
+        }
+    }
+
+    fn calculate_statistics(&self) -> DRCStatistics {
+        DRCStatistics {
+            total_footprints: self.board.footprints.len(),
+            total_pads: self.board.footprints.iter().map(|f| f.footprint.pads.len()).sum(),
+            total_tracks: self.board.tracks.len(),
+            total_vias: self.board.vias.len(),
+            total_nets: self.board.nets.len(),
+            routed_connections: self.routed_connections,
+            unrouted_connections: self.unrouted_connections,
+        }
+    }
+}
+
+fn net_suffix(net: Option<&str>) -> String {
+    net.map(|n| format!(" for net {}", n)).unwrap_or_default()
+}
+
+fn footprint_location(footprint_id: String, x: f64, y: f64) -> DRCLocation {
+    DRCLocation { x, y, layer: None, footprint_id: Some(footprint_id), pad_number: None, track_id: None, via_id: None, zone_id: None }
+}
+
+// Areas overlap when their interiors meet; polygons that only share an edge don't
+fn shapes_overlap(a: &Shape, b: &Shape) -> bool {
+    if a.radius == 0.0 && b.radius == 0.0 && a.points.len() >= 3 && b.points.len() >= 3 {
+        return polygons_overlap(&a.points, &b.points);
+    }
+    a.distance(b) < 0.0 || (a.points.len() == 1 && b.contains(a.points[0]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BoardNet, FootprintPoint, KeepoutRestrictions, NetClass, Track, Via};
+    use crate::services::test_fixtures::place;
+
+    #[test]
+    fn test_board_design_rules() {
+        let mut board = Board::new("Test".to_string()).unwrap();
+        for (id, name) in [("gnd", "GND"), ("sig", "SIG")] {
+            board.nets.push(BoardNet { id: id.to_string(), name: name.to_string() });
+        }
+        let nets = [("1", "sig"), ("2", "gnd")];
+        place(&mut board, "R_0805", "R1", 20.0, 20.0, &nets);
+        place(&mut board, "R_0805", "R2", 30.0, 20.0, &nets);
+        place(&mut board, "R_0805", "R3", 31.0, 20.5, &nets); // courtyard overlaps R2
+        let point = |x: f64, y: f64| FootprintPoint { x, y };
+        // R1.1 -> R2.1 routed around R1.2; R3.1 left unrouted; a thin GND stub passes close to R1.1
+        let route = [point(19.05, 20.0), point(19.05, 18.0), point(29.05, 18.0), point(29.05, 20.0)];
+        for segment in route.windows(2) {
+            board.tracks.push(Track::new(Some("sig".to_string()), LayerType::TopCopper, segment[0], segment[1], 0.25));
+        }
+        board.tracks.push(Track::new(Some("gnd".to_string()), LayerType::TopCopper, point(18.0, 20.85), point(20.0, 20.85), 0.1));
+        // On the board edge, with a small drill and a thin ring
+        board.vias.push(Via::new(Some("gnd".to_string()), point(50.0, 0.1), 0.45, 0.2));
+        board.keepouts.push(KeepoutArea {
+            area: vec![point(60.0, 60.0), point(70.0, 60.0), point(70.0, 70.0), point(60.0, 70.0)],
+            layers: vec![LayerType::TopCopper, LayerType::BottomCopper],
+            restrictions: KeepoutRestrictions { no_copper: false, no_vias: true, no_components: false },
+        });
+        board.vias.push(Via::new(Some("gnd".to_string()), point(65.0, 65.0), 0.6, 0.3));
+
+        let mut rules = DesignRules::default();
+        let mut power = NetClass::new("Power".to_string());
+        power.nets.push("GND".to_string());
+        power.track_width = Some(0.3);
+        rules.net_classes.push(power);
+        let report = DRCChecker::new(board.clone(), rules.clone()).run_check().unwrap();
+        let count = |kind: &str| report.errors.iter().filter(|e| format!("{:?}", e.error_type) == kind).count();
+
+        assert_eq!(count("TrackWidth"), 1, "GND class needs 0.3 mm");
+        assert!(count("Clearance") >= 1);
+        assert!(count("ShortCircuit") >= 1, "R3 pads overlap R2's");
+        assert_eq!(count("EdgeClearance"), 1);
+        assert_eq!(count("DrillSize"), 1);
+        assert_eq!(count("AnnularRing"), 1);
+        assert_eq!(count("CourtyardOverlap"), 1);
+        assert_eq!(count("KeepoutViolation"), 1);
+        assert!(count("UnroutedConnection") >= 1);
+        assert!(!report.passed);
+
+        // Moving R3 clear of everything leaves only routing and the deliberate faults
+        board.footprints[2].position = point(40.0, 40.0);
+        let report = DRCChecker::new(board, rules).run_check().unwrap();
+        assert_eq!(report.errors.iter().filter(|e| matches!(e.error_type, DRCErrorType::CourtyardOverlap | DRCErrorType::ShortCircuit)).count(), 0);
+        assert_eq!(report.statistics.total_footprints, 3);
+        // SIG: R3.1 apart; GND: R1.2, R2.2 and R3.2 all apart
+        assert_eq!(report.statistics.unrouted_connections, 3);
+        assert_eq!(report.statistics.routed_connections, 1);
+
+        // Copper inside a cutout is off the board
+        let mut board = Board::new("Cutout".to_string()).unwrap();
+        board.outline.push(crate::models::GraphicElement {
+            element_type: crate::models::GraphicType::Circle { center: point(50.0, 40.0), radius: 5.0 },
+            layer: LayerType::EdgeCuts,
+            width: 0.1,
+        });
+        board.vias.push(Via::new(None, point(50.0, 40.0), 0.6, 0.3));
+        board.vias.push(Via::new(None, point(20.0, 20.0), 0.6, 0.3));
+        let report = DRCChecker::new(board, DesignRules::default()).run_check().unwrap();
+        let outside: Vec<&str> = report.errors.iter()
+            .filter(|e| matches!(e.error_type, DRCErrorType::EdgeClearance))
+            .map(|e| e.message.as_str())
+            .collect();
+        assert_eq!(outside.len(), 1);
+        assert!(outside[0].ends_with("is outside the board outline"), "{}", outside[0]);
+    }
+}
@@ -0,0 +1,726 @@
+use crate::models::{
+    Board, FootprintInstance, FootprintPoint, GraphicElement, GraphicType, LayerType,
+    PlacementSide, Project,
+};
+use crate::services::fabrication_service::{decimal, layer_name};
+use crate::services::footprint_service::mounting_hole_footprint;
+use crate::utils::error::{AppError, Result};
+use crate::utils::geometry::point_in_polygon;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// Endpoints closer than this join into one contour, as in Board::outline_polygon
+const CHAIN_TOLERANCE: f64 = 1e-3;
+// Pen width given to imported outline graphics
+const OUTLINE_WIDTH: f64 = 0.1;
+// Points per full turn when estimating the extent of arcs
+const ARC_SAMPLES: usize = 72;
+// Instance ids of imported mounting holes start with this, so a later import can replace them
+const IMPORTED_HOLE_PREFIX: &str = "dxf-hole-";
+
+/// Drawing units, from the `$INSUNITS` header variable.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DxfUnits {
+    Inches,
+    Feet,
+    Millimeters,
+    Centimeters,
+    Meters,
+    Microinches,
+    Mils,
+    Microns,
+}
+
+impl DxfUnits {
+    fn from_code(code: i64) -> Option<Self> {
+        match code {
+            1 => Some(Self::Inches),
+            2 => Some(Self::Feet),
+            4 => Some(Self::Millimeters),
+            5 => Some(Self::Centimeters),
+            6 => Some(Self::Meters),
+            8 => Some(Self::Microinches),
+            9 => Some(Self::Mils),
+            13 => Some(Self::Microns),
+            _ => None,
+        }
+    }
+
+    fn millimeters(self) -> f64 {
+        match self {
+            Self::Inches => 25.4,
+            Self::Feet => 304.8,
+            Self::Millimeters => 1.0,
+            Self::Centimeters => 10.0,
+            Self::Meters => 1000.0,
+            Self::Microinches => 25.4e-6,
+            Self::Mils => 0.0254,
+            Self::Microns => 0.001,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DxfImportOptions {
+    #[serde(default)]
+    pub units: Option<DxfUnits>, // overrides the units in the file
+    #[serde(default)]
+    pub layers: Vec<String>, // DXF layers to read; empty: all of them
+    #[serde(default = "default_import_holes")]
+    pub import_holes: bool,
+}
+
+fn default_import_holes() -> bool {
+    true
+}
+
+impl Default for DxfImportOptions {
+    fn default() -> Self {
+        Self {
+            units: None,
+            layers: Vec::new(),
+            import_holes: default_import_holes(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DxfImportReport {
+    pub units: DxfUnits,
+    pub units_detected: bool,
+    pub outline_elements: usize,
+    pub holes: Vec<String>, // references of the mounting holes added
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DxfImportResult {
+    pub project: Project,
+    pub report: DxfImportReport,
+}
+
+type Point = (f64, f64);
+
+// Drawing geometry in millimetres, still in the DXF's y-up frame
+#[derive(Debug, Clone, Copy)]
+enum Curve {
+    Line(Point, Point),
+    Arc { center: Point, radius: f64, start: f64, sweep: f64 }, // degrees, counter-clockwise positive
+    Circle { center: Point, radius: f64 },
+}
+
+impl Curve {
+    fn ends(&self) -> Option<(Point, Point)> {
+        match *self {
+            Curve::Line(a, b) => Some((a, b)),
+            Curve::Arc { center, radius, start, sweep } => Some((polar(center, radius, start), polar(center, radius, start + sweep))),
+            Curve::Circle { .. } => None,
+        }
+    }
+
+    fn samples(&self) -> Vec<Point> {
+        let (center, radius, start, sweep) = match *self {
+            Curve::Line(a, b) => return vec![a, b],
+            Curve::Arc { center, radius, start, sweep } => (center, radius, start, sweep),
+            Curve::Circle { center, radius } => (center, radius, 0.0, 360.0),
+        };
+        let steps = ((sweep.abs() / 360.0 * ARC_SAMPLES as f64).ceil() as usize).max(1);
+        (0..=steps).map(|i| polar(center, radius, start + sweep * i as f64 / steps as f64)).collect()
+    }
+}
+
+struct Contour {
+    curves: Vec<Curve>,
+    closed: bool,
+    bounds: (f64, f64, f64, f64),
+}
+
+impl Contour {
+    fn new(curves: Vec<Curve>, closed: bool) -> Self {
+        let points: Vec<Point> = curves.iter().flat_map(Curve::samples).collect();
+        let bounds = points.iter().fold((f64::MAX, f64::MAX, f64::MIN, f64::MIN), |(x0, y0, x1, y1), p| {
+            (x0.min(p.0), y0.min(p.1), x1.max(p.0), y1.max(p.1))
+        });
+        Self { curves, closed, bounds }
+    }
+
+    fn area(&self) -> f64 {
+        (self.bounds.2 - self.bounds.0) * (self.bounds.3 - self.bounds.1)
+    }
+}
+
+struct Entity {
+    kind: String,
+    layer: String,
+    codes: Vec<(i32, String)>,
+}
+
+impl Entity {
+    fn value(&self, code: i32) -> Option<f64> {
+        self.codes.iter().find(|(c, _)| *c == code).and_then(|(_, v)| v.trim().parse().ok())
+    }
+
+    fn number(&self, code: i32) -> Result<f64> {
+        self.value(code)
+            .ok_or_else(|| AppError::InvalidFormat(format!("{} on layer {} is missing group code {}", self.kind, self.layer, code)))
+    }
+
+    // Entities drawn with a negative extrusion direction have their object x axis reversed
+    fn mirrored(&self) -> bool {
+        self.value(230).is_some_and(|z| z < 0.0)
+    }
+}
+
+pub struct DxfService;
+
+impl DxfService {
+    /// Replaces the board outline with the largest closed contour in the drawing, with the
+    /// closed contours inside it as cutouts. Unless turned off, circles centred inside it
+    /// become unplated mounting holes instead, in place of the holes an earlier import added;
+    /// otherwise they are cut out too. LINE, ARC,
+    /// CIRCLE and LWPOLYLINE entities are read; the outline's top-left corner lands on the
+    /// board origin.
+    pub fn import(project: &mut Project, text: &str, options: &DxfImportOptions) -> Result<DxfImportReport> {
+        let board = project.board.as_mut()
+            .ok_or_else(|| AppError::InvalidOperation("Project has no board".to_string()))?;
+        let (header, entities) = parse(text)?;
+
+        let mut warnings = Vec::new();
+        let detected = header.get("$INSUNITS").and_then(|c| DxfUnits::from_code(*c))
+            .or_else(|| header.get("$MEASUREMENT").map(|m| if *m == 0 { DxfUnits::Inches } else { DxfUnits::Millimeters }));
+        let units = match (options.units, detected) {
+            (Some(units), _) | (None, Some(units)) => units,
+            (None, None) => {
+                warnings.push("The drawing doesn't state its units; millimetres assumed".to_string());
+                DxfUnits::Millimeters
+            }
+        };
+        let scale = units.millimeters();
+
+        let mut curves = Vec::new();
+        let mut skipped: BTreeMap<String, usize> = BTreeMap::new();
+        for entity in &entities {
+            if !options.layers.is_empty() && !options.layers.iter().any(|l| l.eq_ignore_ascii_case(&entity.layer)) {
+                continue;
+            }
+            match entity.kind.as_str() {
+                "LINE" => curves.push(Curve::Line(
+                    (entity.number(10)? * scale, entity.number(20)? * scale),
+                    (entity.number(11)? * scale, entity.number(21)? * scale),
+                )),
+                "CIRCLE" => {
+                    let x = entity.number(10)? * if entity.mirrored() { -scale } else { scale };
+                    curves.push(Curve::Circle { center: (x, entity.number(20)? * scale), radius: entity.number(40)? * scale });
+                }
+                "ARC" => {
+                    let (mut start, mut end) = (entity.number(50)?, entity.number(51)?);
+                    let mut x = entity.number(10)? * scale;
+                    if entity.mirrored() {
+                        // Reflected in the y axis, the arc runs the other way round
+                        (start, end) = (180.0 - end, 180.0 - start);
+                        x = -x;
+                    }
+                    let sweep = (end - start).rem_euclid(360.0);
+                    curves.push(Curve::Arc {
+                        center: (x, entity.number(20)? * scale),
+                        radius: entity.number(40)? * scale,
+                        start,
+                        sweep: if sweep == 0.0 { 360.0 } else { sweep },
+                    });
+                }
+                "LWPOLYLINE" => curves.extend(polyline(entity, scale)),
+                other => *skipped.entry(other.to_string()).or_default() += 1,
+            }
+        }
+        for (kind, count) in skipped {
+            warnings.push(format!("Skipped {} {} entit{}", count, kind, if count == 1 { "y" } else { "ies" }));
+        }
+
+        let mut contours = chain(curves);
+        let Some(outline) = contours.iter().enumerate()
+            .filter(|(_, c)| c.closed)
+            .max_by(|a, b| a.1.area().total_cmp(&b.1.area()))
+            .map(|(i, _)| i)
+        else {
+            return Err(AppError::InvalidFormat("The drawing has no closed contour to use as the board outline".to_string()));
+        };
+        let outline = contours.swap_remove(outline);
+        let (min_x, max_y) = (outline.bounds.0, outline.bounds.3);
+        let to_board = |p: Point| FootprintPoint { x: p.0 - min_x, y: max_y - p.1 };
+
+        let edge = |curve: &Curve| GraphicElement {
+            element_type: match *curve {
+                Curve::Line(a, b) => GraphicType::Line { start: to_board(a), end: to_board(b) },
+                // Counter-clockwise with y up is a negative angle on the y-down board
+                Curve::Arc { center, radius, start, sweep } => GraphicType::Arc {
+                    center: to_board(center),
+                    start: to_board(polar(center, radius, start)),
+                    angle: -sweep,
+                },
+                Curve::Circle { center, radius } => GraphicType::Circle { center: to_board(center), radius },
+            },
+            layer: LayerType::EdgeCuts,
+            width: OUTLINE_WIDTH,
+        };
+        let previous = std::mem::replace(&mut board.outline, outline.curves.iter().map(edge).collect());
+        let Some(polygon) = board.outline_polygon() else {
+            board.outline = previous;
+            return Err(AppError::InvalidFormat("The outline contour doesn't close on the board".to_string()));
+        };
+
+        let mut holes = Vec::new();
+        let mut cutouts = Vec::new();
+        for contour in &contours {
+            let (x, y) = (contour.bounds.0, contour.bounds.3);
+            let location = format!("({}, {})", decimal(x - min_x), decimal(max_y - y));
+            // Circles count as inside by their centre, anything else by a point on it
+            let probe = match contour.curves.as_slice() {
+                [Curve::Circle { center, .. }] => Some(*center),
+                curves => curves.first().and_then(|c| c.samples().first().copied()),
+            };
+            let inside = probe.map(to_board).is_some_and(|p| point_in_polygon((p.x, p.y), &polygon));
+            match contour.curves.as_slice() {
+                [Curve::Circle { center, radius }] if inside && options.import_holes => {
+                    holes.push((to_board(*center), 2.0 * radius));
+                }
+                _ if !contour.closed => warnings.push(format!("Open contour near {} ignored", location)),
+                _ if inside => cutouts.extend(contour.curves.iter().map(edge)),
+                _ => warnings.push(format!("Contour near {} lies outside the outline and was ignored", location)),
+            }
+        }
+        board.outline.extend(cutouts);
+        if board.outline_contours().is_none() {
+            board.outline = previous;
+            return Err(AppError::InvalidFormat("A cutout contour doesn't close on the board".to_string()));
+        }
+
+        let mut references = Vec::new();
+        if options.import_holes {
+            // A new drawing replaces the holes of the last one, leaving holes placed by hand
+            board.footprints.retain(|f| !f.id.starts_with(IMPORTED_HOLE_PREFIX));
+            let mut next = board.footprints.iter()
+                .filter_map(|f| f.reference.strip_prefix('H').and_then(|n| n.parse::<usize>().ok()))
+                .max()
+                .unwrap_or(0);
+            for (position, diameter) in holes {
+                next += 1;
+                let footprint = mounting_hole_footprint(diameter);
+                let mut instance = FootprintInstance::new(
+                    String::new(), format!("H{}", next), footprint.id.clone(), footprint, position,
+                );
+                instance.id = format!("{}{}", IMPORTED_HOLE_PREFIX, instance.id);
+                instance.locked = true;
+                references.push(instance.reference.clone());
+                board.footprints.push(instance);
+            }
+        }
+
+        let outline_elements = board.outline.len();
+        project.update_modified();
+        Ok(DxfImportReport {
+            units,
+            units_detected: options.units.is_none() && detected.is_some(),
+            outline_elements,
+            holes: references,
+            warnings,
+        })
+    }
+
+    /// DXF (R12, millimetres, y up) of the board outline on `Edge_Cuts` and the component
+    /// courtyards on `F_Courtyard` and `B_Courtyard`.
+    pub fn export(board: &Board) -> Result<String> {
+        if board.outline.is_empty() {
+            return Err(AppError::InvalidOperation("Board has no outline on EdgeCuts".to_string()));
+        }
+        let layers = [
+            (LayerType::EdgeCuts, 7),
+            (LayerType::TopCourtyard, 6),
+            (LayerType::BottomCourtyard, 4),
+        ];
+
+        let mut out = DxfWriter::default();
+        out.pair(0, "SECTION");
+        out.pair(2, "HEADER");
+        out.pair(9, "$ACADVER");
+        out.pair(1, "AC1009");
+        out.pair(9, "$INSUNITS");
+        out.pair(70, "4");
+        out.pair(9, "$MEASUREMENT");
+        out.pair(70, "1");
+        out.pair(0, "ENDSEC");
+
+        out.pair(0, "SECTION");
+        out.pair(2, "TABLES");
+        out.pair(0, "TABLE");
+        out.pair(2, "LAYER");
+        out.pair(70, &layers.len().to_string());
+        for (layer, color) in &layers {
+            out.pair(0, "LAYER");
+            out.pair(2, &layer_name(layer));
+            out.pair(70, "0");
+            out.pair(62, &color.to_string());
+            out.pair(6, "CONTINUOUS");
+        }
+        out.pair(0, "ENDTAB");
+        out.pair(0, "ENDSEC");
+
+        out.pair(0, "SECTION");
+        out.pair(2, "ENTITIES");
+        let edge = layer_name(&LayerType::EdgeCuts);
+        for graphic in &board.outline {
+            out.graphic(&edge, &graphic.element_type, |p| p, false);
+        }
+        for instance in &board.footprints {
+            let mirrored = instance.side == PlacementSide::Bottom;
+            for graphic in &instance.footprint.courtyard {
+                let layer = layer_name(&instance.board_layer(&graphic.layer));
+                out.graphic(&layer, &graphic.element_type, |p| instance.transform(p), mirrored);
+            }
+        }
+        out.pair(0, "ENDSEC");
+        out.pair(0, "EOF");
+        Ok(out.text)
+    }
+}
+
+#[derive(Default)]
+struct DxfWriter {
+    text: String,
+}
+
+impl DxfWriter {
+    fn pair(&mut self, code: i32, value: &str) {
+        self.text.push_str(&format!("{:>3}\n{}\n", code, value));
+    }
+
+    // Board points are y-down; the drawing is y-up
+    fn point(&mut self, code: i32, point: FootprintPoint) {
+        self.pair(code, &decimal(point.x));
+        self.pair(code + 10, &decimal(-point.y));
+    }
+
+    fn line(&mut self, layer: &str, start: FootprintPoint, end: FootprintPoint) {
+        self.pair(0, "LINE");
+        self.pair(8, layer);
+        self.point(10, start);
+        self.point(11, end);
+    }
+
+    fn circle(&mut self, layer: &str, center: FootprintPoint, radius: f64) {
+        self.pair(0, "CIRCLE");
+        self.pair(8, layer);
+        self.point(10, center);
+        self.pair(40, &decimal(radius));
+    }
+
+    fn graphic(&mut self, layer: &str, graphic: &GraphicType, map: impl Fn(FootprintPoint) -> FootprintPoint, mirrored: bool) {
+        match graphic {
+            GraphicType::Line { start, end } => self.line(layer, map(*start), map(*end)),
+            GraphicType::Rectangle { top_left, bottom_right } => {
+                let corners = [
+                    *top_left,
+                    FootprintPoint { x: bottom_right.x, y: top_left.y },
+                    *bottom_right,
+                    FootprintPoint { x: top_left.x, y: bottom_right.y },
+                ];
+                for (i, corner) in corners.iter().enumerate() {
+                    self.line(layer, map(*corner), map(corners[(i + 1) % 4]));
+                }
+            }
+            GraphicType::Polygon { points } => {
+                for (i, point) in points.iter().enumerate() {
+                    self.line(layer, map(*point), map(points[(i + 1) % points.len()]));
+                }
+            }
+            GraphicType::Circle { center, radius } => self.circle(layer, map(*center), *radius),
+            GraphicType::Arc { center, start, angle } => {
+                let (center, start) = (map(*center), map(*start));
+                let radius = ((start.x - center.x).powi(2) + (start.y - center.y).powi(2)).sqrt();
+                if angle.abs() >= 360.0 {
+                    self.circle(layer, center, radius);
+                    return;
+                }
+                // A positive board angle turns clockwise once y points up, unless mirrored
+                let sweep = if mirrored { *angle } else { -angle };
+                let from = (-(start.y - center.y)).atan2(start.x - center.x).to_degrees();
+                let (first, last) = if sweep >= 0.0 { (from, from + sweep) } else { (from + sweep, from) };
+                self.pair(0, "ARC");
+                self.pair(8, layer);
+                self.point(10, center);
+                self.pair(40, &decimal(radius));
+                self.pair(50, &decimal(first.rem_euclid(360.0)));
+                self.pair(51, &decimal(last.rem_euclid(360.0)));
+            }
+            GraphicType::Text { .. } => {}
+        }
+    }
+}
+
+fn polar(center: Point, radius: f64, degrees: f64) -> Point {
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    (center.0 + radius * cos, center.1 + radius * sin)
+}
+
+// Header variables with integer values, and the entities of the ENTITIES section
+fn parse(text: &str) -> Result<(BTreeMap<String, i64>, Vec<Entity>)> {
+    let lines: Vec<&str> = text.lines().map(str::trim).collect();
+    let mut pairs = Vec::with_capacity(lines.len() / 2);
+    for chunk in lines.chunks(2) {
+        let [code, value] = chunk else { break };
+        let code: i32 = code.parse()
+            .map_err(|_| AppError::InvalidFormat(format!("Not an ASCII DXF file: bad group code '{}'", code)))?;
+        pairs.push((code, value.to_string()));
+    }
+
+    let mut header = BTreeMap::new();
+    let mut entities: Vec<Entity> = Vec::new();
+    let mut section = String::new();
+    let mut variable = None;
+    let mut iter = pairs.into_iter().peekable();
+    while let Some((code, value)) = iter.next() {
+        match (code, value.as_str()) {
+            (0, "SECTION") => {
+                if let Some((2, name)) = iter.next() {
+                    section = name;
+                }
+            }
+            (0, "ENDSEC") => section.clear(),
+            (0, "EOF") => break,
+            (9, name) if section == "HEADER" => variable = Some(name.to_string()),
+            (_, value) if section == "HEADER" => {
+                if let (Some(name), Ok(number)) = (variable.take(), value.parse::<i64>()) {
+                    header.insert(name, number);
+                }
+            }
+            (0, kind) if section == "ENTITIES" => {
+                let mut entity = Entity { kind: kind.to_string(), layer: "0".to_string(), codes: Vec::new() };
+                while let Some((code, value)) = iter.next_if(|(c, _)| *c != 0) {
+                    if code == 8 {
+                        entity.layer = value.clone();
+                    }
+                    entity.codes.push((code, value));
+                }
+                entities.push(entity);
+            }
+            _ => {}
+        }
+    }
+    if entities.is_empty() {
+        return Err(AppError::InvalidFormat("DXF file has no entities".to_string()));
+    }
+    Ok((header, entities))
+}
+
+// Vertices come as 10/20 pairs, each optionally followed by the bulge of the segment it starts
+fn polyline(entity: &Entity, scale: f64) -> Vec<Curve> {
+    let mut vertices: Vec<(f64, f64, f64)> = Vec::new();
+    for (code, value) in &entity.codes {
+        let Ok(number) = value.trim().parse::<f64>() else { continue };
+        match code {
+            10 => vertices.push((number * scale, 0.0, 0.0)),
+            20 => if let Some(v) = vertices.last_mut() { v.1 = number * scale },
+            42 => if let Some(v) = vertices.last_mut() { v.2 = number },
+            _ => {}
+        }
+    }
+    if entity.mirrored() {
+        for v in &mut vertices {
+            v.0 = -v.0;
+            v.2 = -v.2;
+        }
+    }
+    let closed = entity.value(70).is_some_and(|flags| flags as i64 & 1 == 1);
+    let count = if closed { vertices.len() } else { vertices.len().saturating_sub(1) };
+
+    (0..count).filter_map(|i| {
+        let (x0, y0, bulge) = vertices[i];
+        let (x1, y1, _) = vertices[(i + 1) % vertices.len()];
+        if (x1 - x0).abs() < CHAIN_TOLERANCE && (y1 - y0).abs() < CHAIN_TOLERANCE {
+            return None;
+        }
+        if bulge.abs() < 1e-9 {
+            return Some(Curve::Line((x0, y0), (x1, y1)));
+        }
+        // The bulge is the tangent of a quarter of the included angle
+        let theta = 4.0 * bulge.atan();
+        let chord = ((x1 - x0).powi(2) + (y1 - y0).powi(2)).sqrt();
+        let offset = chord / 2.0 / (theta / 2.0).tan();
+        let center = (
+            (x0 + x1) / 2.0 - (y1 - y0) / chord * offset,
+            (y0 + y1) / 2.0 + (x1 - x0) / chord * offset,
+        );
+        Some(Curve::Arc {
+            center,
+            radius: (chord / 2.0 / (theta / 2.0).sin()).abs(),
+            start: (y0 - center.1).atan2(x0 - center.0).to_degrees(),
+            sweep: theta.to_degrees(),
+        })
+    }).collect()
+}
+
+// Groups curves that share endpoints; a group is closed when none of its ends is loose
+fn chain(curves: Vec<Curve>) -> Vec<Contour> {
+    let near = |a: Point, b: Point| (a.0 - b.0).abs() < CHAIN_TOLERANCE && (a.1 - b.1).abs() < CHAIN_TOLERANCE;
+    let ends: Vec<Option<(Point, Point)>> = curves.iter().map(Curve::ends).collect();
+
+    let mut group: Vec<usize> = (0..curves.len()).collect();
+    for i in 0..curves.len() {
+        let Some((a0, a1)) = ends[i] else { continue };
+        for (j, other) in ends.iter().enumerate().skip(i + 1) {
+            let Some((b0, b1)) = *other else { continue };
+            if near(a0, b0) || near(a0, b1) || near(a1, b0) || near(a1, b1) {
+                let (ri, rj) = (root(&mut group, i), root(&mut group, j));
+                group[ri] = rj;
+            }
+        }
+    }
+
+    let mut members: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for i in 0..curves.len() {
+        let r = root(&mut group, i);
+        members.entry(r).or_default().push(i);
+    }
+    members.into_values().map(|indices| {
+        let endpoints: Vec<Point> = indices.iter()
+            .filter_map(|&i| ends[i])
+            .flat_map(|(a, b)| [a, b])
+            .collect();
+        // Every end meets another one; a lone arc closes on itself
+        let closed = endpoints.iter().enumerate()
+            .all(|(i, p)| endpoints.iter().enumerate().any(|(j, q)| i != j && near(*p, *q)));
+        Contour::new(indices.iter().map(|&i| curves[i]).collect(), closed)
+    }).collect()
+}
+
+// Union-find representative of curve `i`
+fn root(group: &mut [usize], mut i: usize) -> usize {
+    while group[i] != i {
+        group[i] = group[group[i]];
+        i = group[i];
+    }
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dxf_round_trip() {
+        // 2 x 1.5 inch board with one rounded corner and two mounting holes, the left edge
+        // drawn on its own, plus a stray open line
+        let dxf = [
+            "0", "SECTION", "2", "HEADER", "9", "$INSUNITS", "70", "1", "0", "ENDSEC",
+            "0", "SECTION", "2", "ENTITIES",
+            "0", "LWPOLYLINE", "8", "OUTLINE", "90", "4", "70", "0",
+            "10", "0", "20", "0",
+            "10", "1.75", "20", "0", "42", "0.41421356",
+            "10", "2", "20", "0.25",
+            "10", "2", "20", "1.5",
+            "0", "LINE", "8", "OUTLINE", "10", "2", "20", "1.5", "11", "0", "21", "1.5",
+            "0", "LINE", "8", "OUTLINE", "10", "0", "20", "1.5", "11", "0", "21", "0",
+            "0", "CIRCLE", "8", "HOLES", "10", "0.25", "20", "0.25", "40", "0.0625",
+            "0", "CIRCLE", "8", "HOLES", "10", "1.75", "20", "1.25", "40", "0.0625",
+            "0", "LINE", "8", "NOTES", "10", "3", "20", "3", "11", "4", "21", "3",
+            "0", "TEXT", "8", "NOTES", "10", "3", "20", "3", "1", "REV A",
+            "0", "ENDSEC", "0", "EOF",
+        ].join("\n");
+
+        let mut project = Project::new("Test".to_string()).unwrap();
+        project.board = Some(Board::new("Test Board".to_string()).unwrap());
+        let report = DxfService::import(&mut project, &dxf, &DxfImportOptions::default()).unwrap();
+        assert_eq!(report.units, DxfUnits::Inches);
+        assert!(report.units_detected);
+        assert_eq!(report.outline_elements, 5);
+        assert_eq!(report.holes, vec!["H1".to_string(), "H2".to_string()]);
+        assert_eq!(report.warnings.len(), 2, "{:?}", report.warnings);
+
+        let board = project.board.as_ref().unwrap();
+        let (x0, y0, x1, y1) = board.outline_bounds().unwrap();
+        assert!(x0.abs() < 1e-6 && y0.abs() < 1e-6);
+        assert!((x1 - 50.8).abs() < 1e-6 && (y1 - 38.1).abs() < 1e-6);
+        assert!(board.outline_polygon().is_some());
+        // The corner arc turns clockwise on screen from the bottom edge up the right one
+        let arc = board.outline.iter().find_map(|g| match g.element_type {
+            GraphicType::Arc { center, start, angle } => Some((center, start, angle)),
+            _ => None,
+        }).unwrap();
+        assert!((arc.0.x - 44.45).abs() < 1e-6 && (arc.0.y - 31.75).abs() < 1e-6);
+        assert!((arc.1.x - 44.45).abs() < 1e-6 && (arc.1.y - 38.1).abs() < 1e-6);
+        assert!((arc.2 + 90.0).abs() < 1e-6);
+        let hole = board.footprints.iter().find(|f| f.reference == "H1").unwrap();
+        assert!((hole.position.x - 6.35).abs() < 1e-6 && (hole.position.y - 31.75).abs() < 1e-6);
+        assert!(hole.locked && hole.component_id.is_empty());
+        assert!((hole.footprint.pads[0].drill.as_ref().unwrap().diameter - 3.175).abs() < 1e-6);
+
+        // Only the outline layer of our own export comes back, reproducing the outline
+        let exported = DxfService::export(board).unwrap();
+        assert!(exported.contains("F_Courtyard") && exported.contains("\nARC\n"));
+        let mut copy = Project::new("Copy".to_string()).unwrap();
+        copy.board = Some(Board::new("Copy".to_string()).unwrap());
+        let options = DxfImportOptions { layers: vec!["Edge_Cuts".to_string()], ..Default::default() };
+        let report = DxfService::import(&mut copy, &exported, &options).unwrap();
+        assert_eq!(report.units, DxfUnits::Millimeters);
+        assert!(report.holes.is_empty() && report.warnings.is_empty(), "{:?}", report);
+        let copy = copy.board.as_ref().unwrap();
+        assert_eq!(copy.outline_polygon().unwrap().len(), board.outline_polygon().unwrap().len());
+        let bounds = copy.outline_bounds().unwrap();
+        assert!((bounds.2 - 50.8).abs() < 1e-6 && (bounds.3 - 38.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_dxf_holes() {
+        // L-shaped board in millimetres with one hole and a slot in it, and a circle in the notch
+        let dxf = [
+            "0", "SECTION", "2", "HEADER", "9", "$INSUNITS", "70", "4", "0", "ENDSEC",
+            "0", "SECTION", "2", "ENTITIES",
+            "0", "LWPOLYLINE", "8", "OUTLINE", "90", "6", "70", "1",
+            "10", "0", "20", "0", "10", "20", "20", "0", "10", "20", "20", "10",
+            "10", "10", "20", "10", "10", "10", "20", "20", "10", "0", "20", "20",
+            "0", "CIRCLE", "8", "HOLES", "10", "5", "20", "5", "40", "1",
+            "0", "CIRCLE", "8", "HOLES", "10", "15", "20", "15", "40", "1",
+            "0", "LWPOLYLINE", "8", "SLOTS", "90", "4", "70", "1",
+            "10", "2", "20", "12", "10", "6", "20", "12", "10", "6", "20", "13", "10", "2", "20", "13",
+            "0", "ENDSEC", "0", "EOF",
+        ].join("\n");
+
+        let mut project = Project::new("Test".to_string()).unwrap();
+        let mut board = Board::new("Test Board".to_string()).unwrap();
+        // A hole placed by hand isn't the drawing's to replace
+        let footprint = mounting_hole_footprint(3.0);
+        board.footprints.push(FootprintInstance::new(
+            String::new(), "H1".to_string(), footprint.id.clone(), footprint, FootprintPoint { x: 2.0, y: 2.0 },
+        ));
+        project.board = Some(board);
+
+        let report = DxfService::import(&mut project, &dxf, &DxfImportOptions::default()).unwrap();
+        assert_eq!(report.holes, vec!["H2".to_string()]);
+        assert_eq!(report.warnings, vec!["Contour near (14, 4) lies outside the outline and was ignored".to_string()]);
+        let board = project.board.as_ref().unwrap();
+        let hole = board.footprints.iter().find(|f| f.reference == "H2").unwrap();
+        assert!((hole.position.x - 5.0).abs() < 1e-6 && (hole.position.y - 15.0).abs() < 1e-6);
+        // The slot is cut out of the board
+        let contours = board.outline_contours().unwrap();
+        assert_eq!(contours.len(), 2);
+        let (x0, y0, x1, y1) = contours[1].iter().fold((f64::MAX, f64::MAX, f64::MIN, f64::MIN), |b, p| (b.0.min(p.0), b.1.min(p.1), b.2.max(p.0), b.3.max(p.1)));
+        assert!((x0 - 2.0).abs() < 1e-6 && (y0 - 7.0).abs() < 1e-6 && (x1 - 6.0).abs() < 1e-6 && (y1 - 8.0).abs() < 1e-6);
+        assert!(!crate::utils::geometry::point_in_contours((4.0, 7.5), &contours));
+        assert!(crate::utils::geometry::point_in_contours((4.0, 5.0), &contours));
+
+        // Importing again swaps the drawing's holes and keeps the hand-placed one
+        let report = DxfService::import(&mut project, &dxf, &DxfImportOptions::default()).unwrap();
+        assert_eq!(report.holes, vec!["H2".to_string()]);
+        let references: Vec<&str> = project.board.as_ref().unwrap().footprints.iter().map(|f| f.reference.as_str()).collect();
+        assert_eq!(references, vec!["H1", "H2"]);
+
+        // Without holes the circle is cut out like the slot
+        let options = DxfImportOptions { import_holes: false, ..Default::default() };
+        let report = DxfService::import(&mut project, &dxf, &options).unwrap();
+        assert!(report.holes.is_empty());
+        assert_eq!(report.warnings.len(), 1, "{:?}", report.warnings);
+        assert_eq!(project.board.as_ref().unwrap().outline_contours().unwrap().len(), 3);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::models::schematic::Schematic;
-use crate::models::component::{Component as SchematicComponent, Pin};
+use crate::models::component::{Component as SchematicComponent, Pin, PinType as ComponentPinType};
 use crate::models::wire::Wire as SchematicWire;
 use crate::utils::error::Result;
 use serde::{Deserialize, Serialize};
@@ -63,6 +63,7 @@ pub enum ERCWarningType {
     MissingPullResistor,
     UnlabeledNet,
     SinglePinNet,
+    MissingPartNumber,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -147,6 +148,7 @@ impl ERCChecker {
         self.check_decoupling_capacitors();
         self.check_pull_resistors();
         self.check_net_labels();
+        self.check_part_numbers();
         
         // Calculate statistics
         let statistics = self.calculate_statistics();
@@ -450,12 +452,12 @@ impl ERCChecker {
         let mut references = HashSet::new();
         
         for component in &self.schematic.components {
-            if !references.insert(component.reference.clone()) {
+            if !references.insert(component.display_reference()) {
                 self.errors.push(ERCError {
                     error_type: ERCErrorType::DuplicateReference,
                     message: format!(
                         "Duplicate component reference: {}",
-                        component.reference
+                        component.display_reference()
                     ),
                     location: Some(ERCLocation {
                         x: component.x,
@@ -470,6 +472,30 @@ impl ERCChecker {
         }
     }
 
+    // A BOM line without an MPN can't be ordered
+    fn check_part_numbers(&mut self) {
+        for component in self.schematic.package_components() {
+            // Power and ground symbols aren't physical parts
+            let symbol_only = !component.pins.is_empty() && component.pins.iter()
+                .all(|p| matches!(p.pin_type, ComponentPinType::Power | ComponentPinType::Ground));
+            if symbol_only || component.part_info.has_mpn() {
+                continue;
+            }
+            self.warnings.push(ERCWarning {
+                warning_type: ERCWarningType::MissingPartNumber,
+                message: format!("{} has no manufacturer part number", component.reference),
+                location: Some(ERCLocation {
+                    x: component.x,
+                    y: component.y,
+                    component_id: Some(component.id.clone()),
+                    wire_id: None,
+                    pin_id: None,
+                }),
+                severity: ERCSeverity::Low,
+            });
+        }
+    }
+
     fn check_decoupling_capacitors(&mut self) {
         // Check if ICs have nearby decoupling capacitors
         for component in &self.schematic.components {
@@ -649,4 +675,56 @@ pub struct ERCRule {
     pub description: String,
     pub enabled: bool,
     pub severity: ERCSeverity,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::component::ElectricalType;
+
+    fn part(reference: &str, unit: Option<&str>, mpn: Option<&str>, pin_types: &[ComponentPinType]) -> SchematicComponent {
+        let mut component = SchematicComponent::new("part".to_string(), 0.0, 0.0).unwrap();
+        component.reference = reference.to_string();
+        component.unit = unit.map(str::to_string);
+        component.part_info.mpn = mpn.map(str::to_string);
+        for (i, pin_type) in pin_types.iter().enumerate() {
+            component.pins.push(Pin {
+                id: format!("{}-{}", reference, i),
+                name: format!("P{}", i + 1),
+                number: (i + 1).to_string(),
+                x: 0.0,
+                y: 0.0,
+                pin_type: pin_type.clone(),
+                electrical: ElectricalType { voltage: None, current: None, impedance: None },
+                connected: false,
+            });
+        }
+        component
+    }
+
+    #[test]
+    fn test_missing_part_numbers() {
+        let passive = [ComponentPinType::Passive, ComponentPinType::Passive];
+        let mut schematic = Schematic::new("Main".to_string()).unwrap();
+        for component in [
+            part("R1", None, None, &passive),
+            part("R2", None, Some("RC0603-10K"), &passive),
+            part("C1", None, Some("  "), &passive),
+            // Two units of one package are one BOM line
+            part("U1", Some("A"), None, &[ComponentPinType::Input, ComponentPinType::Output]),
+            part("U1", Some("B"), None, &[ComponentPinType::Input, ComponentPinType::Output]),
+            part("#PWR1", None, None, &[ComponentPinType::Ground]),
+        ] {
+            schematic.add_component(component).unwrap();
+        }
+
+        let mut checker = ERCChecker::new(schematic);
+        checker.check_part_numbers();
+        let flagged: Vec<&str> = checker.warnings.iter()
+            .filter(|w| matches!(w.warning_type, ERCWarningType::MissingPartNumber))
+            .map(|w| w.message.split(' ').next().unwrap())
+            .collect();
+        assert_eq!(flagged, vec!["R1", "C1", "U1"]);
+        assert!(checker.errors.is_empty());
+    }
+}
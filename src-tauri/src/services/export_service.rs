@@ -1,5 +1,13 @@
-use crate::models::{Project, Schematic};
+use crate::models::{Board, ComponentFootprintMap, DigitalWaveforms, Project, Schematic, Waveforms};
+use crate::models::component::{ComponentSymbol, DrawCommand, DrawCommandType, LifecycleStatus, PartInfo};
+use super::fabrication_service::FabricationService;
+use super::ipc_export_service::IpcExportService;
+use super::dxf_service::DxfService;
+use super::part_provider::BomPricing;
+use super::simulation::circuit::{Circuit, ElementKind, Polarity};
+use std::collections::HashMap;
 use crate::utils::error::{AppError, Result};
+use crate::utils::validators::{escape_markup, validate_web_url};
 use std::path::Path;
 use serde::{Deserialize, Serialize};
 use image::{ImageBuffer, Rgba, RgbaImage};
@@ -145,7 +153,9 @@ impl ExportService {
         Ok(())
     }
 
-    pub async fn export_to_svg(schematic: &Schematic, path: &Path) -> Result<String> {
+    /// `symbols` maps component ids to the symbol to draw; components without one
+    /// are drawn as a plain box.
+    pub async fn export_to_svg(schematic: &Schematic, symbols: &HashMap<String, ComponentSymbol>, path: &Path) -> Result<String> {
         let mut svg = String::new();
         
         // SVG header with proper namespace and viewport
@@ -174,15 +184,24 @@ impl ExportService {
             ));
             svg.push_str("\n");
             
-            // Component body with proper styling
-            svg.push_str(r#"      <rect x="-20" y="-10" width="40" height="20" "#);
-            svg.push_str(r#"fill="white" stroke="black" stroke-width="1.5"/>"#);
-            svg.push_str("\n");
+            // Component body from the library symbol, or a placeholder box
+            match symbols.get(&component.id) {
+                Some(symbol) => {
+                    for command in &symbol.draw_commands {
+                        svg.push_str(&Self::draw_command_svg(command));
+                    }
+                }
+                None => {
+                    svg.push_str(r#"      <rect x="-20" y="-10" width="40" height="20" "#);
+                    svg.push_str(r#"fill="white" stroke="black" stroke-width="1.5"/>"#);
+                    svg.push_str("\n");
+                }
+            }
             
             // Reference text
             svg.push_str(&format!(
                 r#"      <text x="0" y="-15" text-anchor="middle" font-family="Arial" font-size="10" fill="black">{}</text>"#,
-                component.reference
+                component.display_reference()
             ));
             svg.push_str("\n");
             
@@ -197,7 +216,7 @@ impl ExportService {
             for pin in &component.pins {
                 svg.push_str(&format!(
                     r#"      <circle cx="{}" cy="{}" r="2" fill="red" stroke="darkred" stroke-width="0.5"/>"#,
-                    pin.x - component.x, pin.y - component.y
+                    pin.x, pin.y
                 ));
                 svg.push_str("\n");
             }
@@ -244,6 +263,46 @@ impl ExportService {
         Ok(svg)
     }
 
+    fn draw_command_svg(command: &DrawCommand) -> String {
+        if !command.command_type.accepts_parameter_count(command.parameters.len()) {
+            return String::new();
+        }
+        let (stroke, stroke_width, fill) = match &command.style {
+            Some(style) => (style.stroke_color.as_str(), style.stroke_width, style.fill_color.as_deref().unwrap_or("none")),
+            None => ("black", 1.5, "none"),
+        };
+        let paint = format!(r#"fill="{}" stroke="{}" stroke-width="{}""#, fill, stroke, stroke_width);
+        let p = &command.parameters;
+
+        let element = match command.command_type {
+            DrawCommandType::Rectangle => format!(
+                r#"<rect x="{}" y="{}" width="{}" height="{}" {}/>"#,
+                p[0].min(p[0] + p[2]), p[1].min(p[1] + p[3]), p[2].abs(), p[3].abs(), paint
+            ),
+            DrawCommandType::Circle => format!(r#"<circle cx="{}" cy="{}" r="{}" {}/>"#, p[0], p[1], p[2], paint),
+            DrawCommandType::Line => format!(r#"<line x1="{}" y1="{}" x2="{}" y2="{}" {}/>"#, p[0], p[1], p[2], p[3], paint),
+            DrawCommandType::Arc => {
+                // Parameters are centre, radius and start/end angles in degrees
+                let (cx, cy, r) = (p[0], p[1], p[2]);
+                let (start, end) = (p[3].to_radians(), p[4].to_radians());
+                let sweep = (p[4] - p[3]).rem_euclid(360.0);
+                format!(
+                    r#"<path d="M {} {} A {} {} 0 {} 1 {} {}" {}/>"#,
+                    cx + r * start.cos(), cy + r * start.sin(), r, r,
+                    if sweep > 180.0 { 1 } else { 0 },
+                    cx + r * end.cos(), cy + r * end.sin(), paint
+                )
+            }
+            DrawCommandType::Polygon => {
+                let points: Vec<String> = p.chunks(2).map(|pt| format!("{},{}", pt[0], pt[1])).collect();
+                format!(r#"<polygon points="{}" {}/>"#, points.join(" "), paint)
+            }
+            // Symbol text carries no string of its own; references and values are drawn separately
+            DrawCommandType::Text => return String::new(),
+        };
+        format!("      {}\n", element)
+    }
+
     fn generate_grid_svg(grid_size: u32) -> String {
         let mut grid = String::new();
         grid.push_str("  <defs>\n");
@@ -260,11 +319,16 @@ impl ExportService {
         grid
     }
 
-    pub async fn export_netlist(schematic: &Schematic, path: &Path, format: NetlistFormat) -> Result<()> {
+    pub async fn export_netlist(
+        schematic: &Schematic,
+        path: &Path,
+        format: NetlistFormat,
+        assignments: &[ComponentFootprintMap],
+    ) -> Result<()> {
         let netlist = match format {
             NetlistFormat::Spice => Self::generate_spice_netlist(schematic),
             NetlistFormat::Verilog => Self::generate_verilog_netlist(schematic),
-            NetlistFormat::KiCad => Self::generate_kicad_netlist(schematic),
+            NetlistFormat::KiCad => Self::generate_kicad_netlist(schematic, assignments),
         };
         
         tokio::fs::write(path, netlist)
@@ -298,7 +362,7 @@ impl ExportService {
         }
         
         // Add components with proper SPICE syntax
-        for component in &schematic.components {
+        for component in schematic.package_components() {
             let comp_type = component.reference.chars().next().unwrap_or('X');
             
             match comp_type {
@@ -384,6 +448,129 @@ impl ExportService {
         netlist
     }
 
+    /// SPICE netlist of the parts the simulator has models for, with the node name chosen
+    /// for every schematic net, as handed to ngspice. Ground nets become node `0`; parts
+    /// without a model are listed as comments. Unlike the exported netlist this needs a
+    /// ground and parseable values.
+    pub fn spice_netlist(schematic: &Schematic) -> Result<SpiceNetlist> {
+        let circuit = Circuit::from_schematic(schematic)?;
+
+        // One SPICE node name per MNA node, taken from the first net on it
+        let mut node_names: Vec<Option<String>> = vec![None; circuit.node_count];
+        let mut used = std::collections::HashSet::new();
+        used.insert("0".to_string());
+        let mut nets = Vec::new();
+        for net in &circuit.nets {
+            let name = match net.node {
+                None => "0".to_string(),
+                Some(node) => node_names[node]
+                    .get_or_insert_with(|| unique_spice_name(&net.name, &mut used))
+                    .clone(),
+            };
+            nets.push(SpiceNode { net_id: net.id.clone(), net_name: net.name.clone(), node: name });
+        }
+        let mut node_name = |node: Option<usize>| match node {
+            None => "0".to_string(),
+            Some(node) => node_names[node]
+                .get_or_insert_with(|| unique_spice_name(&format!("nc{}", node), &mut used))
+                .clone(),
+        };
+
+        let mut body = String::new();
+        let mut models = String::new();
+        let mut branches = Vec::new();
+        let mut element_totals: HashMap<&str, usize> = HashMap::new();
+        for element in &circuit.elements {
+            *element_totals.entry(element.component_id.as_str()).or_insert(0) += 1;
+        }
+        let mut element_counts: HashMap<&str, usize> = HashMap::new();
+        for element in &circuit.elements {
+            let letter = match &element.kind {
+                ElementKind::Resistor { .. } => 'R',
+                ElementKind::Capacitor { .. } => 'C',
+                ElementKind::Inductor { .. } => 'L',
+                ElementKind::VoltageSource(_) => 'V',
+                ElementKind::CurrentSource(_) => 'I',
+                ElementKind::Diode(_) => 'D',
+                ElementKind::Bjt(_) => 'Q',
+                ElementKind::Mosfet(_) => 'M',
+            };
+            // SPICE infers the element type from the first letter; split parts get a suffix
+            let count = element_counts.entry(element.component_id.as_str()).or_insert(0);
+            *count += 1;
+            let mut name = element.reference.replace(|c: char| !c.is_ascii_alphanumeric() && c != '_', "_");
+            if !name.to_uppercase().starts_with(letter) {
+                name = format!("{}{}", letter, name);
+            }
+            if element_totals[element.component_id.as_str()] > 1 {
+                name = format!("{}_{}", name, count);
+            }
+            let nodes: Vec<String> = element.nodes.iter().map(|n| node_name(*n)).collect();
+
+            let line = match &element.kind {
+                ElementKind::Resistor { resistance } => format!("{} {} {} {}", name, nodes[0], nodes[1], resistance),
+                ElementKind::Capacitor { capacitance } => format!("{} {} {} {}", name, nodes[0], nodes[1], capacitance),
+                ElementKind::Inductor { inductance } => format!("{} {} {} {}", name, nodes[0], nodes[1], inductance),
+                ElementKind::VoltageSource(source) | ElementKind::CurrentSource(source) => {
+                    let mut line = format!("{} {} {} DC {}", name, nodes[0], nodes[1], source.dc);
+                    if source.ac_magnitude != 0.0 {
+                        line.push_str(&format!(" AC {} {}", source.ac_magnitude, source.ac_phase));
+                    }
+                    if let Some(waveform) = &source.waveform {
+                        line.push_str(&format!(" {}", waveform.to_spice()));
+                    }
+                    line
+                }
+                ElementKind::Diode(model) => {
+                    let mut parameters = format!("IS={} N={}", model.saturation_current, model.emission);
+                    if let Some(breakdown) = model.breakdown {
+                        parameters.push_str(&format!(" BV={}", breakdown));
+                    }
+                    models.push_str(&format!(".model D_{} D({})\n", name, parameters));
+                    format!("{} {} {} D_{}", name, nodes[0], nodes[1], name)
+                }
+                ElementKind::Bjt(model) => {
+                    let kind = if model.polarity == Polarity::P { "PNP" } else { "NPN" };
+                    models.push_str(&format!(".model Q_{} {}(IS={} BF={} BR={})\n",
+                        name, kind, model.saturation_current, model.beta_forward, model.beta_reverse));
+                    format!("{} {} {} {} Q_{}", name, nodes[0], nodes[1], nodes[2], name)
+                }
+                ElementKind::Mosfet(model) => {
+                    let kind = if model.polarity == Polarity::P { "PMOS" } else { "NMOS" };
+                    models.push_str(&format!(".model M_{} {}(LEVEL=1 VTO={} KP={} LAMBDA={})\n",
+                        name, kind, model.polarity.sign() * model.threshold, model.transconductance, model.lambda));
+                    // Bulk tied to source
+                    format!("{} {} {} {} {} M_{}", name, nodes[0], nodes[1], nodes[2], nodes[2], name)
+                }
+            };
+            body.push_str(&line);
+            body.push('\n');
+
+            if element.has_branch() {
+                branches.push(SpiceBranch {
+                    element: name.to_lowercase(),
+                    component_id: element.component_id.clone(),
+                    pin_id: element.pin_ids[0].clone(),
+                });
+            }
+        }
+        for reference in &circuit.unsupported {
+            body.push_str(&format!("* {}: no simulation model\n", reference));
+        }
+        if !models.is_empty() {
+            body.push('\n');
+            body.push_str(&models);
+        }
+
+        Ok(SpiceNetlist {
+            title: schematic.name.clone(),
+            body,
+            nets,
+            branches,
+            unsupported: circuit.unsupported,
+        })
+    }
+
     fn generate_verilog_netlist(schematic: &Schematic) -> String {
         let mut netlist = String::new();
         
@@ -405,7 +592,7 @@ impl ExportService {
         
         netlist.push_str("\n  // Component instantiations\n");
         for component in &schematic.components {
-            netlist.push_str(&format!("  // {} - {}\n", component.reference, component.value));
+            netlist.push_str(&format!("  // {} - {}\n", component.display_reference(), component.value));
         }
         
         netlist.push_str("\nendmodule\n");
@@ -413,7 +600,12 @@ impl ExportService {
         netlist
     }
 
-    fn generate_kicad_netlist(schematic: &Schematic) -> String {
+    fn generate_kicad_netlist(schematic: &Schematic, assignments: &[ComponentFootprintMap]) -> String {
+        // Any unit of a multi-unit package resolves to the package's assignment
+        let assignment_for = |component: &crate::models::Component| assignments.iter().find(|a| {
+            a.component_id == component.id
+                || schematic.get_component(&a.component_id).is_some_and(|c| c.same_package(component))
+        });
         let mut netlist = String::new();
         
         netlist.push_str("(export (version D)\n");
@@ -424,18 +616,39 @@ impl ExportService {
         netlist.push_str("  )\n");
         
         netlist.push_str("  (components\n");
-        for component in &schematic.components {
+        for component in schematic.package_components() {
             netlist.push_str(&format!("    (comp (ref {})\n", component.reference));
             netlist.push_str(&format!("      (value {})\n", component.value));
+            if let Some(assignment) = assignment_for(component) {
+                netlist.push_str(&format!("      (footprint {})\n", assignment.footprint_id));
+            }
             netlist.push_str("    )\n");
         }
         netlist.push_str("  )\n");
         
+        // Nodes reference footprint pads, so symbol pin numbers go through the pin map
         netlist.push_str("  (nets\n");
-        for (i, _wire) in schematic.wires.iter().enumerate() {
-            // Generate net name
-            let net_name = format!("Net-{}", i);
-            netlist.push_str(&format!("    (net (code {}) (name \"{}\")\n", i + 1, net_name));
+        for (i, net) in schematic.generate_netlist().iter().enumerate() {
+            netlist.push_str(&format!("    (net (code {}) (name \"{}\")\n", i + 1, net.name));
+            let mut nodes: Vec<(&str, &str)> = Vec::new();
+            for connection in &net.pins {
+                let component = match schematic.get_component(&connection.component_id) {
+                    Some(c) => c,
+                    None => continue,
+                };
+                let pin_number = component.pins.iter()
+                    .find(|p| p.id == connection.pin_id)
+                    .map(|p| p.number.as_str())
+                    .unwrap_or(connection.pin_id.as_str());
+                let pad = assignment_for(component)
+                    .and_then(|a| a.pad_for_pin(pin_number))
+                    .unwrap_or(pin_number);
+                // Shared pins show up once per unit but are a single pad
+                if !nodes.contains(&(component.reference.as_str(), pad)) {
+                    nodes.push((component.reference.as_str(), pad));
+                    netlist.push_str(&format!("      (node (ref {}) (pin {}))\n", component.reference, pad));
+                }
+            }
             netlist.push_str("    )\n");
         }
         netlist.push_str("  )\n");
@@ -445,11 +658,46 @@ impl ExportService {
         netlist
     }
 
+    pub async fn export_pick_and_place(board: &Board, path: &Path) -> Result<()> {
+        tokio::fs::write(path, Self::generate_pick_and_place(board))
+            .await
+            .map_err(|e| AppError::IoError(e.to_string()))?;
+        
+        Ok(())
+    }
+
+    // One row per placed footprint, at its board position
+    fn generate_pick_and_place(board: &Board) -> String {
+        let mut csv = String::new();
+        csv.push_str("Ref,Val,Package,PosX,PosY,Rot,Side\n");
+
+        let mut instances: Vec<_> = board.footprints.iter().collect();
+        instances.sort_by(|a, b| a.reference.cmp(&b.reference));
+
+        for instance in instances {
+            let side = match instance.side {
+                crate::models::PlacementSide::Top => "top",
+                crate::models::PlacementSide::Bottom => "bottom",
+            };
+            csv.push_str(&format!("\"{}\",\"{}\",\"{}\",{:.4},{:.4},{:.1},{}\n",
+                instance.reference,
+                instance.value,
+                instance.footprint_id,
+                instance.position.x,
+                instance.position.y,
+                instance.rotation.rem_euclid(360.0),
+                side
+            ));
+        }
+
+        csv
+    }
+
     pub async fn export_bom(project: &Project, path: &Path, format: BomFormat) -> Result<()> {
         let bom = match format {
             BomFormat::Csv => Self::generate_csv_bom(project),
             BomFormat::Json => Self::generate_json_bom(project)?,
-            BomFormat::Html => Self::generate_html_bom(project),
+            BomFormat::Html => Self::generate_html_bom(project, None),
         };
         
         tokio::fs::write(path, bom)
@@ -459,38 +707,62 @@ impl ExportService {
         Ok(())
     }
 
-    fn generate_csv_bom(project: &Project) -> String {
-        let mut csv = String::new();
-        csv.push_str("Reference,Value,Footprint,Quantity,Description\n");
-        
-        // Collect all components from all schematics
-        let mut component_map = std::collections::HashMap::new();
-        
-        for schematic in &project.schematics {
-            for component in &schematic.components {
-                let key = format!("{}_{}", component.value, component.type_id);
-                let entry = component_map.entry(key).or_insert((
-                    component.value.clone(),
-                    component.type_id.clone(),
-                    Vec::new(),
-                    0
-                ));
-                entry.2.push(component.reference.clone());
-                entry.3 += 1;
+    // Groups packages into BOM lines; parts only share a line when they share an MPN too
+    fn bom_lines(project: &Project) -> Vec<BomLine> {
+        let mut lines: Vec<BomLine> = Vec::new();
+
+        for component in project.package_components() {
+            let existing = lines.iter_mut().find(|line| {
+                line.value == component.value
+                    && line.footprint == component.type_id
+                    && line.part_info.mpn == component.part_info.mpn
+            });
+            match existing {
+                Some(line) => {
+                    line.references.push(component.reference.clone());
+                    line.quantity += 1;
+                }
+                None => lines.push(BomLine {
+                    references: vec![component.reference.clone()],
+                    value: component.value.clone(),
+                    footprint: component.type_id.clone(),
+                    quantity: 1,
+                    part_info: component.part_info.clone(),
+                }),
             }
         }
+
+        lines.sort_by(|a, b| a.value.cmp(&b.value));
+        lines
+    }
+
+    /// HTML BOM with unit price, extended cost and stock for `pricing.build_quantity` boards.
+    pub async fn export_costed_bom(project: &Project, path: &Path, pricing: &BomPricing) -> Result<()> {
+        tokio::fs::write(path, Self::generate_html_bom(project, Some(pricing)))
+            .await
+            .map_err(|e| AppError::IoError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn generate_csv_bom(project: &Project) -> String {
+        let mut csv = String::new();
+        csv.push_str("Reference,Value,Footprint,Quantity,Description,Manufacturer,MPN,Suppliers,Lifecycle,Datasheet,Alternates\n");
         
-        // Sort by reference
-        let mut sorted_components: Vec<_> = component_map.into_iter().collect();
-        sorted_components.sort_by_key(|k| k.0.clone());
-        
-        for (_key, (value, type_id, refs, count)) in sorted_components {
-            csv.push_str(&format!("\"{}\",\"{}\",\"{}\",{},\"{}\"\n", 
-                refs.join(", "), 
-                value, 
-                type_id,
-                count,
-                "Component" // Description placeholder
+        for line in Self::bom_lines(project) {
+            let info = &line.part_info;
+            csv.push_str(&format!("\"{}\",\"{}\",\"{}\",{},\"{}\",\"{}\",\"{}\",\"{}\",\"{:?}\",\"{}\",\"{}\"\n", 
+                line.references.join(", "), 
+                line.value, 
+                line.footprint,
+                line.quantity,
+                "Component", // Description placeholder
+                info.manufacturer.as_deref().unwrap_or(""),
+                info.mpn.as_deref().unwrap_or(""),
+                info.supplier_summary(),
+                info.lifecycle,
+                info.datasheet.as_deref().unwrap_or(""),
+                info.alternates.iter().map(|a| a.mpn.as_str()).collect::<Vec<_>>().join("; "),
             ));
         }
         
@@ -505,6 +777,7 @@ impl ExportService {
             footprint: String,
             quantity: usize,
             description: String,
+            part_info: PartInfo,
         }
         
         #[derive(Serialize)]
@@ -515,24 +788,16 @@ impl ExportService {
             entries: Vec<BomEntry>,
         }
         
-        let mut component_map = std::collections::HashMap::new();
-        
-        for schematic in &project.schematics {
-            for component in &schematic.components {
-                let key = format!("{}_{}", component.value, component.type_id);
-                let entry = component_map.entry(key).or_insert(BomEntry {
-                    references: Vec::new(),
-                    value: component.value.clone(),
-                    footprint: component.type_id.clone(),
-                    quantity: 0,
-                    description: "Component".to_string(),
-                });
-                entry.references.push(component.reference.clone());
-                entry.quantity += 1;
-            }
-        }
-        
-        let entries: Vec<BomEntry> = component_map.into_values().collect();
+        let entries: Vec<BomEntry> = Self::bom_lines(project).into_iter()
+            .map(|line| BomEntry {
+                references: line.references,
+                value: line.value,
+                footprint: line.footprint,
+                quantity: line.quantity,
+                description: "Component".to_string(),
+                part_info: line.part_info,
+            })
+            .collect();
         let total_components: usize = entries.iter().map(|e| e.quantity).sum();
         
         let bom = Bom {
@@ -546,7 +811,7 @@ impl ExportService {
             .map_err(|e| AppError::SerializationError(e.to_string()))
     }
 
-    fn generate_html_bom(project: &Project) -> String {
+    fn generate_html_bom(project: &Project, pricing: Option<&BomPricing>) -> String {
         let mut html = String::new();
         
         html.push_str("<!DOCTYPE html>\n");
@@ -555,7 +820,7 @@ impl ExportService {
         html.push_str("  <meta charset=\"UTF-8\">\n");
         html.push_str("  <meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">\n");
         html.push_str("  <title>Bill of Materials - ");
-        html.push_str(&project.name);
+        html.push_str(&escape_markup(&project.name));
         html.push_str("</title>\n");
         html.push_str("  <style>\n");
         html.push_str("    body { font-family: Arial, sans-serif; margin: 20px; }\n");
@@ -566,16 +831,23 @@ impl ExportService {
         html.push_str("    tr:nth-child(even) { background-color: #f2f2f2; }\n");
         html.push_str("    .metadata { margin: 20px 0; color: #666; }\n");
         html.push_str("    .total { font-weight: bold; background-color: #e0e0e0; }\n");
+        html.push_str("    .short { color: #c62828; }\n");
         html.push_str("  </style>\n");
         html.push_str("</head>\n");
         html.push_str("<body>\n");
         
-        html.push_str(&format!("  <h1>Bill of Materials: {}</h1>\n", project.name));
+        html.push_str(&format!("  <h1>Bill of Materials: {}</h1>\n", escape_markup(&project.name)));
         
         html.push_str("  <div class=\"metadata\">\n");
         html.push_str(&format!("    <p>Generated: {}</p>\n", chrono::Utc::now().format("%Y-%m-%d %H:%M:%S")));
         html.push_str(&format!("    <p>Version: {}</p>\n", project.version));
-        html.push_str(&format!("    <p>Author: {}</p>\n", project.author.as_deref().unwrap_or("Unknown")));
+        html.push_str(&format!("    <p>Author: {}</p>\n", escape_markup(project.author.as_deref().unwrap_or("Unknown"))));
+        if let Some(pricing) = pricing {
+            html.push_str(&format!(
+                "    <p>Pricing: {} for {} board(s), prices in {}</p>\n",
+                escape_markup(&pricing.provider), pricing.build_quantity, escape_markup(&pricing.currency)
+            ));
+        }
         html.push_str("  </div>\n");
         
         html.push_str("  <table>\n");
@@ -586,40 +858,70 @@ impl ExportService {
         html.push_str("        <th>Footprint</th>\n");
         html.push_str("        <th>Quantity</th>\n");
         html.push_str("        <th>Description</th>\n");
+        html.push_str("        <th>Manufacturer</th>\n");
+        html.push_str("        <th>MPN</th>\n");
+        html.push_str("        <th>Suppliers</th>\n");
+        html.push_str("        <th>Lifecycle</th>\n");
+        if pricing.is_some() {
+            html.push_str("        <th>Build Qty</th>\n");
+            html.push_str("        <th>Unit Price</th>\n");
+            html.push_str("        <th>Extended Cost</th>\n");
+            html.push_str("        <th>Stock</th>\n");
+        }
         html.push_str("      </tr>\n");
         html.push_str("    </thead>\n");
         html.push_str("    <tbody>\n");
         
-        // Collect and process components
-        let mut component_map = std::collections::HashMap::new();
-        let mut total_quantity = 0;
-        
-        for schematic in &project.schematics {
-            for component in &schematic.components {
-                let key = format!("{}_{}", component.value, component.type_id);
-                let entry = component_map.entry(key).or_insert((
-                    component.value.clone(),
-                    component.type_id.clone(),
-                    Vec::new(),
-                    0
-                ));
-                entry.2.push(component.reference.clone());
-                entry.3 += 1;
-                total_quantity += 1;
-            }
-        }
-        
-        // Sort and add rows
-        let mut sorted_components: Vec<_> = component_map.into_iter().collect();
-        sorted_components.sort_by_key(|k| k.0.clone());
+        let lines = Self::bom_lines(project);
+        let total_quantity: usize = lines.iter().map(|line| line.quantity).sum();
+        let mut extended_total = 0.0;
         
-        for (_key, (value, footprint, refs, quantity)) in sorted_components {
+        for line in lines {
+            let info = &line.part_info;
             html.push_str("      <tr>\n");
-            html.push_str(&format!("        <td>{}</td>\n", refs.join(", ")));
-            html.push_str(&format!("        <td>{}</td>\n", value));
-            html.push_str(&format!("        <td>{}</td>\n", footprint));
-            html.push_str(&format!("        <td>{}</td>\n", quantity));
+            html.push_str(&format!("        <td>{}</td>\n", escape_markup(&line.references.join(", "))));
+            html.push_str(&format!("        <td>{}</td>\n", escape_markup(&line.value)));
+            html.push_str(&format!("        <td>{}</td>\n", escape_markup(&line.footprint)));
+            html.push_str(&format!("        <td>{}</td>\n", line.quantity));
             html.push_str("        <td>Component</td>\n");
+            html.push_str(&format!("        <td>{}</td>\n", escape_markup(info.manufacturer.as_deref().unwrap_or(""))));
+            // Only web links become anchors; anything else (javascript:, file:) stays out of the page
+            match info.datasheet.as_deref().filter(|url| validate_web_url(url)) {
+                Some(datasheet) => html.push_str(&format!(
+                    "        <td><a href=\"{}\">{}</a></td>\n",
+                    escape_markup(datasheet.trim()), escape_markup(info.mpn.as_deref().unwrap_or("Datasheet"))
+                )),
+                None => html.push_str(&format!("        <td>{}</td>\n", escape_markup(info.mpn.as_deref().unwrap_or("")))),
+            }
+            html.push_str(&format!("        <td>{}</td>\n", escape_markup(&info.supplier_summary())));
+            let offer = pricing.zip(info.mpn.as_deref()).and_then(|(pricing, mpn)| pricing.get(mpn));
+            // The price list knows the lifecycle when the part info doesn't
+            let lifecycle = match (info.lifecycle, offer) {
+                (LifecycleStatus::Unknown, Some(offer)) => offer.lifecycle,
+                (lifecycle, _) => lifecycle,
+            };
+            html.push_str(&format!("        <td>{:?}</td>\n", lifecycle));
+            if let Some(pricing) = pricing {
+                let build_quantity = line.quantity * pricing.build_quantity as usize;
+                html.push_str(&format!("        <td>{}</td>\n", build_quantity));
+                match offer {
+                    Some(offer) => {
+                        extended_total += offer.unit_price * build_quantity as f64;
+                        html.push_str(&format!("        <td>{:.4}</td>\n", offer.unit_price));
+                        html.push_str(&format!("        <td>{:.2}</td>\n", offer.unit_price * build_quantity as f64));
+                        let stock = offer.stock.map(|s| s.to_string()).unwrap_or_else(|| "?".to_string());
+                        if offer.in_stock {
+                            html.push_str(&format!("        <td>{} ({})</td>\n", stock, escape_markup(&offer.supplier)));
+                        } else {
+                            html.push_str(&format!("        <td class=\"short\">{} ({})</td>\n", stock, escape_markup(&offer.supplier)));
+                        }
+                    }
+                    None if info.mpn.as_ref().is_some_and(|mpn| pricing.foreign_currency.contains(mpn)) => html.push_str(&format!(
+                        "        <td colspan=\"3\" class=\"short\">Not quoted in {}</td>\n", escape_markup(&pricing.currency)
+                    )),
+                    None => html.push_str("        <td colspan=\"3\">No price</td>\n"),
+                }
+            }
             html.push_str("      </tr>\n");
         }
         
@@ -627,7 +929,13 @@ impl ExportService {
         html.push_str("      <tr class=\"total\">\n");
         html.push_str("        <td colspan=\"3\">Total Components</td>\n");
         html.push_str(&format!("        <td>{}</td>\n", total_quantity));
-        html.push_str("        <td></td>\n");
+        html.push_str("        <td colspan=\"5\"></td>\n");
+        if let Some(pricing) = pricing {
+            html.push_str(&format!("        <td>{}</td>\n", total_quantity * pricing.build_quantity as usize));
+            html.push_str("        <td></td>\n");
+            html.push_str(&format!("        <td>{:.2} {}</td>\n", extended_total, escape_markup(&pricing.currency)));
+            html.push_str("        <td></td>\n");
+        }
         html.push_str("      </tr>\n");
         
         html.push_str("    </tbody>\n");
@@ -638,12 +946,206 @@ impl ExportService {
         html
     }
     
-    pub async fn export_to_gerber(_schematic: &Schematic, _path: &Path) -> Result<()> {
-        // Gerber export is for PCB, not schematic
-        Err(AppError::InvalidOperation("Gerber export is only available for PCB layouts".to_string()))
+    /// Simulation waveforms as CSV, one column per trace with the unit in the header.
+    pub async fn export_waveforms_csv(waveforms: &Waveforms, path: &Path) -> Result<()> {
+        tokio::fs::write(path, Self::generate_waveform_csv(waveforms))
+            .await
+            .map_err(|e| AppError::IoError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn generate_waveform_csv(waveforms: &Waveforms) -> String {
+        let mut csv = String::new();
+        let header: Vec<String> = waveforms.columns.iter()
+            .map(|column| format!("\"{} ({})\"", column.name.replace('"', "\"\""), column.unit))
+            .collect();
+        csv.push_str(&header.join(","));
+        csv.push('\n');
+
+        for row in 0..waveforms.len() {
+            let values: Vec<String> = waveforms.columns.iter()
+                .map(|column| column.values.get(row).map(|v| v.to_string()).unwrap_or_default())
+                .collect();
+            csv.push_str(&values.join(","));
+            csv.push('\n');
+        }
+
+        csv
+    }
+
+    /// Digital simulation results as a Value Change Dump for GTKWave and similar viewers.
+    pub async fn export_vcd(waveforms: &DigitalWaveforms, path: &Path) -> Result<()> {
+        tokio::fs::write(path, Self::generate_vcd(waveforms))
+            .await
+            .map_err(|e| AppError::IoError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn generate_vcd(waveforms: &DigitalWaveforms) -> String {
+        // Identifier codes are base-94 numbers over the printable ASCII range
+        let identifier = |mut index: usize| {
+            let mut code = String::new();
+            loop {
+                code.push((b'!' + (index % 94) as u8) as char);
+                index /= 94;
+                if index == 0 {
+                    break code;
+                }
+                index -= 1;
+            }
+        };
+        let vcd_name = |name: &str| name.split_whitespace().collect::<Vec<_>>().join("_");
+
+        let mut vcd = String::new();
+        vcd.push_str(&format!("$date {} $end\n", waveforms.simulated_at.to_rfc2822()));
+        vcd.push_str(&format!("$version rust-eda {} $end\n", env!("CARGO_PKG_VERSION")));
+        vcd.push_str("$timescale 1ps $end\n");
+        vcd.push_str(&format!("$scope module {} $end\n", vcd_name(&waveforms.scope)));
+        for (index, signal) in waveforms.signals.iter().enumerate() {
+            vcd.push_str(&format!("$var wire 1 {} {} $end\n", identifier(index), vcd_name(&signal.name)));
+        }
+        vcd.push_str("$upscope $end\n");
+        vcd.push_str("$enddefinitions $end\n");
+
+        // Merge all signals' changes into time order
+        let mut changes: Vec<(u64, usize, char)> = waveforms.signals.iter()
+            .enumerate()
+            .flat_map(|(index, signal)| signal.changes.iter().map(move |c| (c.time, index, c.value.as_char())))
+            .collect();
+        changes.sort_by_key(|(time, index, _)| (*time, *index));
+
+        let mut current_time = None;
+        for (time, index, value) in changes {
+            if current_time != Some(time) {
+                if current_time == Some(0) {
+                    vcd.push_str("$end\n");
+                }
+                vcd.push_str(&format!("#{}\n", time));
+                if time == 0 {
+                    vcd.push_str("$dumpvars\n");
+                }
+                current_time = Some(time);
+            }
+            vcd.push_str(&format!("{}{}\n", value, identifier(index)));
+        }
+        if current_time == Some(0) {
+            vcd.push_str("$end\n");
+        }
+        if current_time.is_none_or(|time| time < waveforms.end_time) {
+            vcd.push_str(&format!("#{}\n", waveforms.end_time));
+        }
+
+        vcd
+    }
+
+    /// Writes the Gerber layers, drill files and job file for the board into `directory`
+    /// and returns the paths written.
+    pub async fn export_to_gerber(board: &Board, directory: &Path) -> Result<Vec<String>> {
+        let files = FabricationService::fabrication_outputs(board)?;
+        tokio::fs::create_dir_all(directory)
+            .await
+            .map_err(|e| AppError::IoError(e.to_string()))?;
+
+        let mut written = Vec::with_capacity(files.len());
+        for file in files {
+            let path = directory.join(&file.file_name);
+            tokio::fs::write(&path, file.content)
+                .await
+                .map_err(|e| AppError::IoError(e.to_string()))?;
+            written.push(path.to_string_lossy().to_string());
+        }
+
+        Ok(written)
+    }
+
+    /// Writes the board as a single IPC-2581 fabrication package.
+    pub async fn export_to_ipc2581(project: &Project, output_path: &Path) -> Result<()> {
+        let file = IpcExportService::ipc2581(project)?;
+        tokio::fs::write(output_path, file.content)
+            .await
+            .map_err(|e| AppError::IoError(e.to_string()))
+    }
+
+    /// Writes the board outline and component courtyards as a DXF drawing.
+    pub async fn export_to_dxf(board: &Board, output_path: &Path) -> Result<()> {
+        let content = DxfService::export(board)?;
+        tokio::fs::write(output_path, content)
+            .await
+            .map_err(|e| AppError::IoError(e.to_string()))
+    }
+
+    /// Writes the IPC-D-356A netlist for bare-board electrical test.
+    pub async fn export_to_ipc_d356(board: &Board, output_path: &Path) -> Result<()> {
+        let file = IpcExportService::ipc_d356(board)?;
+        tokio::fs::write(output_path, file.content)
+            .await
+            .map_err(|e| AppError::IoError(e.to_string()))
     }
 }
 
+/// SPICE deck body plus the mapping from SPICE node and branch names back to the schematic.
+#[derive(Debug, Clone)]
+pub struct SpiceNetlist {
+    pub title: String,
+    pub body: String,
+    pub nets: Vec<SpiceNode>,
+    // Voltage sources and inductors, whose branch currents SPICE reports
+    pub branches: Vec<SpiceBranch>,
+    pub unsupported: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SpiceNode {
+    pub net_id: String,
+    pub net_name: String,
+    pub node: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct SpiceBranch {
+    // Lowercase element name, as SPICE reports it
+    pub element: String,
+    pub component_id: String,
+    // Pin the branch current flows into
+    pub pin_id: String,
+}
+
+impl SpiceNetlist {
+    /// Complete deck with `control` (analysis cards or a `.control` block) before `.end`.
+    pub fn deck(&self, control: &str) -> String {
+        let mut deck = String::new();
+        deck.push_str(&format!("* {}\n", self.title));
+        deck.push_str("* SPICE Netlist Generated by Rust EDA\n");
+        deck.push_str(&format!("* Date: {}\n", chrono::Utc::now().format("%Y-%m-%d %H:%M:%S")));
+        deck.push_str(&self.body);
+        if !control.is_empty() {
+            deck.push('\n');
+            deck.push_str(control);
+        }
+        deck.push_str("\n.end\n");
+        deck
+    }
+}
+
+// Net name reduced to SPICE-safe characters, made unique among the names already used
+fn unique_spice_name(name: &str, used: &mut std::collections::HashSet<String>) -> String {
+    let mut base: String = name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    if base.is_empty() || base == "0" {
+        base = "n".to_string();
+    }
+    let mut candidate = base.clone();
+    let mut suffix = 2;
+    while !used.insert(candidate.clone()) {
+        candidate = format!("{}_{}", base, suffix);
+        suffix += 1;
+    }
+    candidate
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum NetlistFormat {
     Spice,
@@ -651,6 +1153,14 @@ pub enum NetlistFormat {
     KiCad,
 }
 
+struct BomLine {
+    references: Vec<String>,
+    value: String,
+    footprint: String,
+    quantity: usize,
+    part_info: PartInfo,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum BomFormat {
     Csv,
@@ -658,4 +1168,216 @@ pub enum BomFormat {
     Html,
 }
 
-// Note: Tauri commands are defined in commands/export.rs to avoid duplication
\ No newline at end of file
+// Note: Tauri commands are defined in commands/export.rs to avoid duplication
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Component;
+
+    fn part(reference: &str, value: &str, mpn: Option<&str>) -> Component {
+        let mut component = Component::new("resistor".to_string(), 0.0, 0.0).unwrap();
+        component.reference = reference.to_string();
+        component.value = value.to_string();
+        component.part_info.mpn = mpn.map(str::to_string);
+        component.part_info.manufacturer = mpn.map(|_| "Yageo & Co".to_string());
+        component
+    }
+
+    fn bom_project() -> Project {
+        let mut project = Project::new("Test <BOM>".to_string()).unwrap();
+        let mut schematic = Schematic::new("Main".to_string()).unwrap();
+        let mut linked = part("R4", "4k7", Some("RC0603-4K7"));
+        linked.part_info.datasheet = Some("https://example.com/rc0603.pdf?a=1&b=2".to_string());
+        let mut scripted = part("R5", "1k", Some("RC0603-1K"));
+        scripted.part_info.datasheet = Some("javascript:alert(1)".to_string());
+        for component in [
+            part("R1", "10k", Some("RC0603-10K")),
+            part("R2", "10k", Some("RC0603-10K")),
+            part("R3", "10k", Some("ERJ-3EKF1002")),
+            linked,
+            scripted,
+            part("R6", "<script>", None),
+        ] {
+            schematic.add_component(component).unwrap();
+        }
+        project.add_schematic(schematic).unwrap();
+        project
+    }
+
+    #[test]
+    fn test_bom_lines_group_by_mpn() {
+        let lines = ExportService::bom_lines(&bom_project());
+        let grouped: Vec<(&str, Option<&str>, usize)> = lines.iter()
+            .map(|l| (l.value.as_str(), l.part_info.mpn.as_deref(), l.quantity))
+            .collect();
+        assert_eq!(grouped, vec![
+            ("10k", Some("RC0603-10K"), 2),
+            ("10k", Some("ERJ-3EKF1002"), 1),
+            ("1k", Some("RC0603-1K"), 1),
+            ("4k7", Some("RC0603-4K7"), 1),
+            ("<script>", None, 1),
+        ]);
+        assert_eq!(lines[0].references, vec!["R1", "R2"]);
+    }
+
+    #[test]
+    fn test_bom_formats() {
+        let project = bom_project();
+
+        let csv = ExportService::generate_csv_bom(&project);
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows.len(), 6);
+        assert!(rows[0].starts_with("Reference,Value,Footprint,Quantity"));
+        assert!(rows[1].starts_with("\"R1, R2\",\"10k\",\"resistor\",2,"));
+        assert!(rows[1].contains("\"Yageo & Co\",\"RC0603-10K\""));
+
+        let json: serde_json::Value = serde_json::from_str(&ExportService::generate_json_bom(&project).unwrap()).unwrap();
+        assert_eq!(json["total_components"], 6);
+        assert_eq!(json["entries"].as_array().unwrap().len(), 5);
+        assert_eq!(json["entries"][0]["part_info"]["mpn"], "RC0603-10K");
+
+        let html = ExportService::generate_html_bom(&project, None);
+        assert!(html.contains("<title>Bill of Materials - Test &lt;BOM&gt;</title>"));
+        assert!(html.contains("<td>&lt;script&gt;</td>"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("<td>Yageo &amp; Co</td>"));
+        assert!(html.contains(r#"<a href="https://example.com/rc0603.pdf?a=1&amp;b=2">RC0603-4K7</a>"#));
+        // Non-web datasheet links are dropped, the part number stays
+        assert!(!html.contains("javascript:"));
+        assert!(html.contains("<td>RC0603-1K</td>"));
+        assert!(html.contains("<td colspan=\"3\">Total Components</td>\n        <td>6</td>"));
+    }
+
+    #[test]
+    fn test_costed_html_bom() {
+        use super::super::part_provider::PartPricing;
+        let pricing = BomPricing {
+            provider: "prices.csv".to_string(),
+            build_quantity: 10,
+            currency: "USD".to_string(),
+            parts: vec![PartPricing {
+                mpn: "RC0603-10K".to_string(),
+                quantity: 20,
+                supplier: "Stocked".to_string(),
+                unit_price: 0.02,
+                extended_cost: 0.4,
+                stock: Some(5),
+                in_stock: false,
+                lifecycle: LifecycleStatus::Obsolete,
+            }],
+            total_cost: 0.4,
+            unpriced: vec!["ERJ-3EKF1002".to_string()],
+            foreign_currency: vec!["ERJ-3EKF1002".to_string()],
+        };
+        let html = ExportService::generate_html_bom(&bom_project(), Some(&pricing));
+        assert!(html.contains("<p>Pricing: prices.csv for 10 board(s), prices in USD</p>"));
+        // The price list supplies the lifecycle the part info lacks
+        assert!(html.contains("<td>Obsolete</td>\n        <td>20</td>\n        <td>0.0200</td>\n        <td>0.40</td>\n        <td class=\"short\">5 (Stocked)</td>"));
+        assert!(html.contains("<td colspan=\"3\" class=\"short\">Not quoted in USD</td>"));
+        assert_eq!(html.matches("<td colspan=\"3\">No price</td>").count(), 3);
+        assert!(html.contains("<td>60</td>\n        <td></td>\n        <td>0.40 USD</td>"));
+
+        // Provider and currency come from outside and are escaped like everything else
+        let odd = BomPricing { provider: "<b>&prices</b>".to_string(), currency: "U<S>D".to_string(), ..pricing };
+        let html = ExportService::generate_html_bom(&bom_project(), Some(&odd));
+        assert!(html.contains("<p>Pricing: &lt;b&gt;&amp;prices&lt;/b&gt; for 10 board(s), prices in U&lt;S&gt;D</p>"));
+        assert!(html.contains("<td>0.40 U&lt;S&gt;D</td>"));
+        assert!(!html.contains("U<S>D"));
+    }
+
+    #[test]
+    fn test_exported_spice_netlist_is_tolerant() {
+        // No ground, an unparseable value and a part without a SPICE letter still export
+        let mut schematic = Schematic::new("Draft".to_string()).unwrap();
+        schematic.add_component(part("R1", "ten k", None)).unwrap();
+        let mut opamp = part("U1", "TL072", None);
+        opamp.type_id = "op-amp".to_string();
+        schematic.add_component(opamp).unwrap();
+
+        let netlist = ExportService::generate_spice_netlist(&schematic);
+        assert!(netlist.contains("* Schematic: Draft\n"));
+        assert!(netlist.contains("R1 ten k\n"));
+        assert!(netlist.contains("XU1 op-amp\n"));
+        assert!(netlist.ends_with(".end\n"));
+        assert!(ExportService::spice_netlist(&schematic).is_err());
+    }
+
+    #[test]
+    fn test_waveform_csv() {
+        use crate::models::{AnalysisType, ProbeTarget, WaveformColumn};
+        let column = |name: &str, unit: &str, values: &[f64]| WaveformColumn {
+            name: name.to_string(),
+            unit: unit.to_string(),
+            target: Some(ProbeTarget::Voltage { net_id: "net-OUT".to_string() }),
+            values: values.to_vec(),
+        };
+        let waveforms = Waveforms {
+            analysis: AnalysisType::Transient,
+            columns: vec![
+                column("time", "s", &[0.0, 0.5e-3, 1e-3]),
+                column("V(OUT)", "V", &[0.0, 2.5, 5.0]),
+                // A short column leaves its trailing cells empty
+                column("I(\"R1\".1)", "A", &[0.001]),
+            ],
+            unsupported: Vec::new(),
+            simulated_at: chrono::Utc::now(),
+        };
+
+        let csv = ExportService::generate_waveform_csv(&waveforms);
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows, vec![
+            "\"time (s)\",\"V(OUT) (V)\",\"I(\"\"R1\"\".1) (A)\"",
+            "0,0,0.001",
+            "0.0005,2.5,",
+            "0.001,5,",
+        ]);
+    }
+
+    #[test]
+    fn test_vcd() {
+        use crate::models::{DigitalSignal, LogicChange, LogicLevel};
+        let signal = |name: &str, changes: &[(u64, LogicLevel)]| DigitalSignal {
+            net_id: name.to_lowercase(),
+            name: name.to_string(),
+            changes: changes.iter().map(|&(time, value)| LogicChange { time, value }).collect(),
+        };
+        let mut signals = vec![
+            signal("CLK", &[(0, LogicLevel::Low), (500, LogicLevel::High)]),
+            signal("Data Out", &[(0, LogicLevel::HighZ), (500, LogicLevel::Unknown), (800, LogicLevel::High)]),
+        ];
+        // Identifier codes run out of single characters after 94 signals
+        for index in 2..96 {
+            signals.push(signal(&format!("N{}", index), &[]));
+        }
+        let waveforms = DigitalWaveforms {
+            scope: "Main Sheet".to_string(),
+            end_time: 1000,
+            signals,
+            unsupported: Vec::new(),
+            simulated_at: chrono::Utc::now(),
+        };
+
+        let vcd = ExportService::generate_vcd(&waveforms);
+        let body: Vec<&str> = vcd.lines().skip_while(|l| !l.starts_with("$timescale")).collect();
+        assert_eq!(body[..5], [
+            "$timescale 1ps $end",
+            "$scope module Main_Sheet $end",
+            "$var wire 1 ! CLK $end",
+            "$var wire 1 \" Data_Out $end",
+            "$var wire 1 # N2 $end",
+        ]);
+        assert!(vcd.contains("$var wire 1 ~ N93 $end\n$var wire 1 !! N94 $end\n$var wire 1 \"! N95 $end\n"));
+        let changes: Vec<&str> = body.iter().skip_while(|l| !l.starts_with("$enddefinitions")).skip(1).copied().collect();
+        assert_eq!(changes, [
+            "#0", "$dumpvars", "0!", "z\"", "$end",
+            "#500", "1!", "x\"",
+            "#800", "1\"",
+            "#1000",
+        ]);
+
+        // A last change at the end time isn't followed by a second timestamp
+        let mut ending = waveforms.clone();
+        ending.end_time = 800;
+        assert!(ExportService::generate_vcd(&ending).ends_with("#800\n1\"\n"));
+    }
+}
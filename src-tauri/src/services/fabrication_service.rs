@@ -0,0 +1,759 @@
+use crate::models::{
+    Board, DrillShape, FootprintInstance, FootprintPoint, GraphicElement, GraphicType, LayerType, Pad, PadShape,
+    PadType, PlacementSide, StackupLayerKind,
+};
+use crate::utils::error::{AppError, Result};
+use crate::utils::geometry::offset_polygon;
+use crate::utils::stroke_font::text_strokes;
+use crate::utils::validators::sanitize_filename;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+pub(crate) const APPLICATION: &str = "Rust EDA";
+pub(crate) const VERSION: &str = env!("CARGO_PKG_VERSION");
+// Gerber coordinates are written as 4.6 fixed point millimetres
+const COORDINATE_SCALE: f64 = 1_000_000.0;
+
+/// One generated manufacturing file with the X2 attributes the job file lists for it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FabricationFile {
+    pub file_name: String,
+    pub file_function: String,
+    pub polarity: String,
+    pub content: String,
+}
+
+pub struct FabricationService;
+
+impl FabricationService {
+    /// Gerber X2 layers, Excellon drill files and the Gerber job file tying them together.
+    pub fn fabrication_outputs(board: &Board) -> Result<Vec<FabricationFile>> {
+        if board.outline.is_empty() {
+            return Err(AppError::InvalidOperation("Board has no outline on EdgeCuts".to_string()));
+        }
+
+        let mut files = Self::gerber_layers(board);
+        files.extend(Self::drill_files(board));
+        let job = Self::job_file(board, &files)?;
+        files.push(job);
+        Ok(files)
+    }
+
+    pub fn gerber_layers(board: &Board) -> Vec<FabricationFile> {
+        let base = base_name(board);
+        let copper = board.stackup.copper_layers();
+        let count = copper.len();
+        let mut files = Vec::new();
+
+        for (index, layer) in copper.iter().enumerate() {
+            let position = match layer {
+                LayerType::TopCopper => "Top",
+                LayerType::BottomCopper => "Bot",
+                LayerType::InnerCopper(_) => "Inr",
+                _ => continue,
+            };
+            let function = format!("Copper,L{},{}", index + 1, position);
+            let mut gerber = GerberWriter::new(board, &function, "Positive");
+            gerber.copper(board, layer, index);
+            files.push(gerber.finish(format!("{}-{}.gbr", base, layer_name(layer))));
+        }
+
+        let technical = [
+            (LayerType::TopSolderMask, "Soldermask,Top", "Negative"),
+            (LayerType::BottomSolderMask, "Soldermask,Bot", "Negative"),
+            (LayerType::TopPaste, "Paste,Top", "Positive"),
+            (LayerType::BottomPaste, "Paste,Bot", "Positive"),
+            (LayerType::TopSilkscreen, "Legend,Top", "Positive"),
+            (LayerType::BottomSilkscreen, "Legend,Bot", "Positive"),
+        ];
+        for (layer, function, polarity) in technical {
+            // Single-sided boards have nothing on the bottom
+            if count < 2 && matches!(layer, LayerType::BottomSolderMask | LayerType::BottomPaste | LayerType::BottomSilkscreen) {
+                continue;
+            }
+            let mut gerber = GerberWriter::new(board, function, polarity);
+            match layer {
+                LayerType::TopSolderMask | LayerType::BottomSolderMask => {
+                    gerber.pads(board, &layer, |pad| pad.solder_mask_expansion);
+                }
+                LayerType::TopPaste | LayerType::BottomPaste => {
+                    gerber.pads(board, &layer, |pad| pad.solder_paste_margin);
+                }
+                _ => gerber.silkscreen(board, &layer),
+            }
+            files.push(gerber.finish(format!("{}-{}.gbr", base, layer_name(&layer))));
+        }
+
+        let mut outline = GerberWriter::new(board, "Profile,NP", "Positive");
+        for graphic in &board.outline {
+            outline.graphic(graphic, &|p| p, false, Some("Profile"));
+        }
+        files.push(outline.finish(format!("{}-{}.gbr", base, layer_name(&LayerType::EdgeCuts))));
+
+        files
+    }
+
+    /// Excellon drill files: one for plated through holes, one for non-plated holes and
+    /// one per blind or buried via span.
+    pub fn drill_files(board: &Board) -> Vec<FabricationFile> {
+        let base = base_name(board);
+        let last = board.stackup.copper_count().saturating_sub(1);
+
+        drill_groups(board).into_iter()
+            .map(|((plated, first, end), hits)| {
+                let (name, function) = if !plated {
+                    (format!("{}-NPTH.drl", base), format!("NonPlated,1,{},NPTH", last + 1))
+                } else if first == 0 && end == last {
+                    (format!("{}-PTH.drl", base), format!("Plated,1,{},PTH", last + 1))
+                } else {
+                    let kind = if first == 0 || end == last { "Blind" } else { "Buried" };
+                    (
+                        format!("{}-PTH-L{}-L{}.drl", base, first + 1, end + 1),
+                        format!("Plated,{},{},{}", first + 1, end + 1, kind),
+                    )
+                };
+                FabricationFile {
+                    content: excellon(&function, &hits),
+                    file_name: name,
+                    file_function: function,
+                    polarity: "Positive".to_string(),
+                }
+            })
+            .collect()
+    }
+
+    pub fn job_file(board: &Board, files: &[FabricationFile]) -> Result<FabricationFile> {
+        let (min_x, min_y, max_x, max_y) = board.outline_bounds().unwrap_or((0.0, 0.0, 0.0, 0.0));
+        let stackup: Vec<serde_json::Value> = board.stackup.layers.iter()
+            .map(|layer| {
+                let kind = match layer.kind {
+                    StackupLayerKind::SolderMask => "SolderMask",
+                    StackupLayerKind::Copper => "Copper",
+                    StackupLayerKind::Dielectric => "Dielectric",
+                };
+                let mut entry = serde_json::json!({
+                    "Type": kind,
+                    "Thickness": layer.thickness,
+                    "Name": layer.name,
+                });
+                if let Some(material) = &layer.material {
+                    entry["Material"] = serde_json::json!(material);
+                }
+                if let Some(constant) = layer.dielectric_constant {
+                    entry["DielectricConstant"] = serde_json::json!(constant);
+                }
+                entry
+            })
+            .collect();
+
+        let job = serde_json::json!({
+            "Header": {
+                "GenerationSoftware": { "Vendor": APPLICATION, "Application": APPLICATION, "Version": VERSION },
+                "CreationDate": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            },
+            "GeneralSpecs": {
+                "ProjectId": { "Name": board.name, "GUID": board.id, "Revision": "rev?" },
+                "Size": { "X": max_x - min_x, "Y": max_y - min_y },
+                "LayerNumber": board.stackup.copper_count(),
+                "BoardThickness": board.stackup.thickness(),
+            },
+            "FilesAttributes": files.iter()
+                .map(|f| serde_json::json!({ "Path": f.file_name, "FileFunction": f.file_function, "FilePolarity": f.polarity }))
+                .collect::<Vec<_>>(),
+            "MaterialStackup": stackup,
+        });
+
+        Ok(FabricationFile {
+            file_name: format!("{}-job.gbrjob", base_name(board)),
+            file_function: "JobFile".to_string(),
+            polarity: "Positive".to_string(),
+            content: serde_json::to_string_pretty(&job).map_err(|e| AppError::SerializationError(e.to_string()))?,
+        })
+    }
+}
+
+/// Holes grouped by (plated, first copper layer index, last copper layer index): through
+/// holes span every layer, blind and buried vias only theirs.
+pub(crate) fn drill_groups(board: &Board) -> BTreeMap<(bool, usize, usize), Vec<DrillHit>> {
+    let copper = board.stackup.copper_layers();
+    let last = copper.len().saturating_sub(1);
+    let layer_index = |layer: &LayerType| copper.iter().position(|l| l == layer).unwrap_or(0);
+
+    let mut groups: BTreeMap<(bool, usize, usize), Vec<DrillHit>> = BTreeMap::new();
+    for instance in &board.footprints {
+        for pad in &instance.footprint.pads {
+            let Some(drill) = &pad.drill else { continue };
+            let plated = match pad.pad_type {
+                PadType::ThroughHole | PadType::Castellated | PadType::Via => true,
+                PadType::NPTH => false,
+                PadType::SMD => continue,
+            };
+            let offset = drill.offset.as_ref().map_or((0.0, 0.0), |o| (o.x, o.y));
+            let local = |x: f64, y: f64| instance.pad_point(pad, x + offset.0, y + offset.1);
+            let net_id = instance.pad_net(&pad.pad_number).map(|n| n.to_string());
+            let hit = match &drill.shape {
+                DrillShape::Circle => DrillHit { diameter: drill.diameter, start: local(0.0, 0.0), end: None, net_id },
+                DrillShape::Oval { width, height } => {
+                    let half = (width - height).abs() / 2.0;
+                    let (start, end) = if width >= height {
+                        (local(-half, 0.0), local(half, 0.0))
+                    } else {
+                        (local(0.0, -half), local(0.0, half))
+                    };
+                    DrillHit { diameter: width.min(*height), start, end: (half > 0.0).then_some(end), net_id }
+                }
+                DrillShape::Slot { start, end, width } => DrillHit {
+                    diameter: *width,
+                    start: local(start.x, start.y),
+                    end: Some(local(end.x, end.y)),
+                    net_id,
+                },
+            };
+            groups.entry((plated, 0, last)).or_default().push(hit);
+        }
+    }
+    for via in &board.vias {
+        let (a, b) = (layer_index(&via.start_layer), layer_index(&via.end_layer));
+        groups.entry((true, a.min(b), a.max(b))).or_default()
+            .push(DrillHit { diameter: via.drill, start: via.position, end: None, net_id: via.net_id.clone() });
+    }
+    groups
+}
+
+/// Short layer name used in file names and fabrication data, e.g. "F_Cu" or "In2_Cu".
+pub(crate) fn layer_name(layer: &LayerType) -> String {
+    match layer {
+        LayerType::TopCopper => "F_Cu".to_string(),
+        LayerType::BottomCopper => "B_Cu".to_string(),
+        LayerType::InnerCopper(n) => format!("In{}_Cu", n),
+        LayerType::TopSolderMask => "F_Mask".to_string(),
+        LayerType::BottomSolderMask => "B_Mask".to_string(),
+        LayerType::TopSilkscreen => "F_Silkscreen".to_string(),
+        LayerType::BottomSilkscreen => "B_Silkscreen".to_string(),
+        LayerType::TopPaste => "F_Paste".to_string(),
+        LayerType::BottomPaste => "B_Paste".to_string(),
+        LayerType::TopCourtyard => "F_Courtyard".to_string(),
+        LayerType::BottomCourtyard => "B_Courtyard".to_string(),
+        LayerType::TopAssembly => "F_Fab".to_string(),
+        LayerType::BottomAssembly => "B_Fab".to_string(),
+        LayerType::EdgeCuts => "Edge_Cuts".to_string(),
+        LayerType::UserDrawing => "User_Drawings".to_string(),
+        LayerType::UserComments => "User_Comments".to_string(),
+    }
+}
+
+pub(crate) fn base_name(board: &Board) -> String {
+    sanitize_filename(&board.name).replace(' ', "_")
+}
+
+// Gerber and Excellon are y-up; the board is y-down
+fn coordinate(value: f64) -> i64 {
+    (value * COORDINATE_SCALE).round() as i64
+}
+
+pub(crate) fn decimal(value: f64) -> String {
+    let text = format!("{:.6}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" { "0".to_string() } else { text.to_string() }
+}
+
+// Attribute values may not contain the field separator or Gerber delimiters
+fn attribute_field(value: &str) -> String {
+    value.chars().map(|c| if matches!(c, ',' | '*' | '%') { '_' } else { c }).collect()
+}
+
+const ROUND_RECT_MACRO: &str = "%AMRoundRect*\n\
+0 Rounded rectangle: $1 width, $2 height, $3 corner radius, $4 rotation*\n\
+21,1,$1,$2-$3-$3,0,0,$4*\n\
+21,1,$1-$3-$3,$2,0,0,$4*\n\
+1,1,$3+$3,$1/2-$3,$2/2-$3,$4*\n\
+1,1,$3+$3,$3-$1/2,$2/2-$3,$4*\n\
+1,1,$3+$3,$3-$1/2,$3-$2/2,$4*\n\
+1,1,$3+$3,$1/2-$3,$3-$2/2,$4*%\n";
+
+const ROTATED_RECT_MACRO: &str = "%AMRotRect*\n\
+0 Rectangle: $1 width, $2 height, $3 rotation*\n\
+21,1,$1,$2,0,0,$3*%\n";
+
+const ROTATED_OVAL_MACRO: &str = "%AMRotOval*\n\
+0 Obround: $1 length, $2 width, $3 rotation*\n\
+21,1,$1-$2,$2,0,0,$3*\n\
+1,1,$2,$1/2-$2/2,0,$3*\n\
+1,1,$2,$2/2-$1/2,0,$3*%\n";
+
+struct GerberWriter {
+    header: String,
+    macros: Vec<String>,
+    apertures: String,
+    aperture_codes: HashMap<String, usize>,
+    body: String,
+    current_aperture: Option<usize>,
+    net_names: HashMap<String, String>,
+    function: String,
+    polarity: String,
+}
+
+impl GerberWriter {
+    fn new(board: &Board, function: &str, polarity: &str) -> Self {
+        let mut header = String::new();
+        header.push_str(&format!("%TF.GenerationSoftware,{},{},{}*%\n", APPLICATION, APPLICATION, VERSION));
+        header.push_str(&format!("%TF.CreationDate,{}*%\n", chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, false)));
+        header.push_str(&format!("%TF.ProjectId,{},{},rev?*%\n", attribute_field(&board.name), board.id));
+        header.push_str("%TF.SameCoordinates,Original*%\n");
+        header.push_str(&format!("%TF.FileFunction,{}*%\n", function));
+        header.push_str(&format!("%TF.FilePolarity,{}*%\n", polarity));
+        header.push_str("%FSLAX46Y46*%\n%MOMM*%\n%LPD*%\n");
+
+        Self {
+            header,
+            macros: Vec::new(),
+            apertures: String::new(),
+            aperture_codes: HashMap::new(),
+            body: String::new(),
+            current_aperture: None,
+            net_names: board.nets.iter().map(|n| (n.id.clone(), n.name.clone())).collect(),
+            function: function.to_string(),
+            polarity: polarity.to_string(),
+        }
+    }
+
+    fn finish(self, file_name: String) -> FabricationFile {
+        let mut content = self.header;
+        for definition in &self.macros {
+            content.push_str(definition);
+        }
+        content.push_str(&self.apertures);
+        content.push_str("G01*\nG75*\n");
+        content.push_str(&self.body);
+        content.push_str("M02*\n");
+        FabricationFile { file_name, file_function: self.function, polarity: self.polarity, content }
+    }
+
+    fn use_macro(&mut self, definition: &str) {
+        if !self.macros.iter().any(|m| m == definition) {
+            self.macros.push(definition.to_string());
+        }
+    }
+
+    // D code for an aperture template such as "C,0.25", defining it on first use
+    fn aperture(&mut self, template: String, function: Option<&str>) -> usize {
+        let key = format!("{}|{}", function.unwrap_or(""), template);
+        if let Some(&code) = self.aperture_codes.get(&key) {
+            return code;
+        }
+        let code = 10 + self.aperture_codes.len();
+        if let Some(function) = function {
+            self.apertures.push_str(&format!("%TA.AperFunction,{}*%\n", function));
+        }
+        self.apertures.push_str(&format!("%ADD{}{}*%\n", code, template));
+        if function.is_some() {
+            self.apertures.push_str("%TD*%\n");
+        }
+        self.aperture_codes.insert(key, code);
+        code
+    }
+
+    fn select(&mut self, code: usize) {
+        if self.current_aperture != Some(code) {
+            self.body.push_str(&format!("D{}*\n", code));
+            self.current_aperture = Some(code);
+        }
+    }
+
+    fn xy(point: FootprintPoint) -> String {
+        format!("X{}Y{}", coordinate(point.x), coordinate(-point.y))
+    }
+
+    fn flash(&mut self, code: usize, point: FootprintPoint) {
+        self.select(code);
+        self.body.push_str(&format!("{}D03*\n", Self::xy(point)));
+    }
+
+    fn stroke(&mut self, code: usize, points: &[FootprintPoint]) {
+        let Some((first, rest)) = points.split_first() else { return };
+        self.select(code);
+        self.body.push_str(&format!("{}D02*\n", Self::xy(*first)));
+        for point in rest {
+            self.body.push_str(&format!("{}D01*\n", Self::xy(*point)));
+        }
+    }
+
+    fn region(&mut self, points: &[FootprintPoint]) {
+        let Some((first, rest)) = points.split_first() else { return };
+        self.body.push_str(&format!("G36*\n{}D02*\n", Self::xy(*first)));
+        for point in rest {
+            self.body.push_str(&format!("{}D01*\n", Self::xy(*point)));
+        }
+        self.body.push_str(&format!("{}D01*\nG37*\n", Self::xy(*first)));
+    }
+
+    fn object_attributes(&mut self, attributes: &[String]) {
+        for attribute in attributes {
+            self.body.push_str(&format!("%TO.{}*%\n", attribute));
+        }
+    }
+
+    fn clear_attributes(&mut self) {
+        self.body.push_str("%TD*%\n");
+    }
+
+    fn net_attribute(&self, net_id: Option<&str>) -> String {
+        let name = net_id.and_then(|id| self.net_names.get(id)).map(|n| attribute_field(n)).unwrap_or_default();
+        format!("N,{}", name)
+    }
+
+    fn copper(&mut self, board: &Board, layer: &LayerType, index: usize) {
+        let copper = board.stackup.copper_layers();
+
+        for instance in &board.footprints {
+            for pad in &instance.footprint.pads {
+                if !instance.pad_copper_layers(pad, &board.stackup).contains(layer) {
+                    continue;
+                }
+                let function = match pad.pad_type {
+                    PadType::SMD => "SMDPad,CuDef",
+                    PadType::Via => "ViaPad",
+                    PadType::Castellated => "CastellatedPad",
+                    _ => "ComponentPad",
+                };
+                let attributes = [
+                    format!("P,{},{}", attribute_field(&instance.reference), attribute_field(&pad.pad_number)),
+                    self.net_attribute(instance.pad_net(&pad.pad_number)),
+                    format!("C,{}", attribute_field(&instance.reference)),
+                ];
+                self.object_attributes(&attributes);
+                self.pad(instance, pad, 0.0, Some(function));
+                self.clear_attributes();
+            }
+        }
+
+        for track in board.tracks.iter().filter(|t| &t.layer == layer) {
+            let code = self.aperture(format!("C,{}", decimal(track.width)), Some("Conductor"));
+            let net = self.net_attribute(track.net_id.as_deref());
+            self.object_attributes(&[net]);
+            self.stroke(code, &[track.start, track.end]);
+            self.clear_attributes();
+        }
+
+        for via in &board.vias {
+            let position = |l: &LayerType| copper.iter().position(|c| c == l).unwrap_or(0);
+            let (a, b) = (position(&via.start_layer), position(&via.end_layer));
+            if index < a.min(b) || index > a.max(b) {
+                continue;
+            }
+            let code = self.aperture(format!("C,{}", decimal(via.diameter)), Some("ViaPad"));
+            let net = self.net_attribute(via.net_id.as_deref());
+            self.object_attributes(&[net]);
+            self.flash(code, via.position);
+            self.clear_attributes();
+        }
+
+        for zone in board.zones.iter().filter(|z| &z.layer == layer) {
+            let net = self.net_attribute(zone.net_id.as_deref());
+            self.object_attributes(&[net]);
+            for polygon in &zone.fill {
+                let code = (polygon.width > 0.0).then(|| self.aperture(format!("C,{}", decimal(polygon.width)), Some("Conductor")));
+                if polygon.points.len() >= 3 {
+                    self.region(&polygon.points);
+                    // The pen width runs around the region's edge
+                    if let Some(code) = code {
+                        let mut outline = polygon.points.clone();
+                        outline.push(polygon.points[0]);
+                        self.stroke(code, &outline);
+                    }
+                } else if let Some(code) = code {
+                    self.stroke(code, &polygon.points);
+                }
+            }
+            self.clear_attributes();
+        }
+    }
+
+    // Mask openings and paste stencil apertures, grown by the pad's expansion on that layer
+    fn pads(&mut self, board: &Board, layer: &LayerType, expansion: impl Fn(&Pad) -> f64) {
+        for instance in &board.footprints {
+            for pad in &instance.footprint.pads {
+                if instance.pad_layers(pad).contains(layer) {
+                    self.pad(instance, pad, expansion(pad), None);
+                }
+            }
+        }
+    }
+
+    fn silkscreen(&mut self, board: &Board, layer: &LayerType) {
+        for instance in &board.footprints {
+            let mirrored = instance.side == PlacementSide::Bottom;
+            for graphic in &instance.footprint.silkscreen {
+                if &instance.board_layer(&graphic.layer) == layer {
+                    self.graphic(graphic, &|p| instance.transform(p), mirrored, None);
+                }
+            }
+        }
+    }
+
+    fn pad(&mut self, instance: &FootprintInstance, pad: &Pad, expansion: f64, function: Option<&str>) {
+        let width = pad.size.width + 2.0 * expansion;
+        let height = pad.size.height + 2.0 * expansion;
+        if width <= 0.0 || height <= 0.0 {
+            return;
+        }
+        let center = instance.pad_position(pad);
+        // Macro rotations are counterclockwise in the y-up Gerber frame
+        let rotation = (-instance.pad_rotation(pad)).rem_euclid(360.0);
+        let quarter_turns = (rotation / 90.0).round();
+        let axis_aligned = (rotation - quarter_turns * 90.0).abs() < 1e-6;
+        let (w, h) = if axis_aligned && quarter_turns as i64 % 2 == 1 { (height, width) } else { (width, height) };
+
+        let template = match &pad.shape {
+            PadShape::Circle => format!("C,{}", decimal(width)),
+            PadShape::Rectangle if axis_aligned => format!("R,{}X{}", decimal(w), decimal(h)),
+            PadShape::Oval if axis_aligned => format!("O,{}X{}", decimal(w), decimal(h)),
+            PadShape::Rectangle => {
+                self.use_macro(ROTATED_RECT_MACRO);
+                format!("RotRect,{}X{}X{}", decimal(width), decimal(height), decimal(rotation))
+            }
+            PadShape::Oval => {
+                self.use_macro(ROTATED_OVAL_MACRO);
+                let (length, across, rotation) = if width >= height {
+                    (width, height, rotation)
+                } else {
+                    (height, width, (rotation + 90.0).rem_euclid(360.0))
+                };
+                format!("RotOval,{}X{}X{}", decimal(length), decimal(across), decimal(rotation))
+            }
+            PadShape::RoundedRectangle { radius } => {
+                self.use_macro(ROUND_RECT_MACRO);
+                let radius = (radius + expansion).clamp(0.0, width.min(height) / 2.0);
+                format!("RoundRect,{}X{}X{}X{}", decimal(width), decimal(height), decimal(radius), decimal(rotation))
+            }
+            // Polygon pads grow square to every edge, so a trapezoid's slant stays the same
+            PadShape::Trapezoid { delta } => {
+                let (half_width, half_height) = (pad.size.width / 2.0, pad.size.height / 2.0);
+                let (top, bottom) = (half_width - delta / 2.0, half_width + delta / 2.0);
+                let corners = [(-bottom, -half_height), (bottom, -half_height), (top, half_height), (-top, half_height)];
+                self.outline_macro(instance, pad, center, &offset_polygon(&corners, expansion))
+            }
+            PadShape::Custom(points) => {
+                let corners: Vec<(f64, f64)> = points.iter().map(|p| (p.x, p.y)).collect();
+                self.outline_macro(instance, pad, center, &offset_polygon(&corners, expansion))
+            }
+        };
+        let code = self.aperture(template, function);
+        self.flash(code, center);
+    }
+
+    // Polygon pads get a macro of their own with the placement baked into the vertices
+    fn outline_macro(&mut self, instance: &FootprintInstance, pad: &Pad, center: FootprintPoint, corners: &[(f64, f64)]) -> String {
+        if corners.len() < 3 {
+            return "C,0.001".to_string();
+        }
+        let vertices: Vec<String> = corners.iter()
+            .chain(std::iter::once(&corners[0]))
+            .map(|&(x, y)| {
+                let point = instance.pad_point(pad, x, y);
+                format!("{},{}", decimal(point.x - center.x), decimal(center.y - point.y))
+            })
+            .collect();
+        let body = format!("4,1,{},{},0*", corners.len(), vertices.join(","));
+
+        let index = match self.macros.iter().position(|m| m.contains(&body)) {
+            Some(index) => index,
+            None => {
+                self.macros.push(format!("%AMOutline{}*\n{}%\n", self.macros.len(), body));
+                self.macros.len() - 1
+            }
+        };
+        format!("Outline{}", index)
+    }
+
+    fn graphic(&mut self, graphic: &GraphicElement, transform: &dyn Fn(FootprintPoint) -> FootprintPoint, mirrored: bool, function: Option<&str>) {
+        let code = self.aperture(format!("C,{}", decimal(graphic.width.max(0.001))), function);
+        let point = |x: f64, y: f64| transform(FootprintPoint { x, y });
+
+        match &graphic.element_type {
+            GraphicType::Line { start, end } => self.stroke(code, &[transform(*start), transform(*end)]),
+            GraphicType::Rectangle { top_left, bottom_right } => {
+                let corners = [
+                    point(top_left.x, top_left.y),
+                    point(bottom_right.x, top_left.y),
+                    point(bottom_right.x, bottom_right.y),
+                    point(top_left.x, bottom_right.y),
+                    point(top_left.x, top_left.y),
+                ];
+                self.stroke(code, &corners);
+            }
+            GraphicType::Polygon { points } => {
+                let mut closed: Vec<FootprintPoint> = points.iter().map(|p| transform(*p)).collect();
+                if let Some(&first) = closed.first() {
+                    closed.push(first);
+                }
+                self.stroke(code, &closed);
+            }
+            GraphicType::Circle { center, radius } => {
+                let center = transform(*center);
+                let start = FootprintPoint { x: center.x + radius, y: center.y };
+                self.stroke(code, &[start]);
+                self.body.push_str(&format!("G02{}I{}J0D01*\nG01*\n", Self::xy(start), coordinate(-radius)));
+            }
+            GraphicType::Arc { center, start, angle } => {
+                let (sin, cos) = angle.to_radians().sin_cos();
+                let (dx, dy) = (start.x - center.x, start.y - center.y);
+                let end = FootprintPoint { x: center.x + dx * cos - dy * sin, y: center.y + dx * sin + dy * cos };
+                let (center, start, end) = (transform(*center), transform(*start), transform(end));
+                // Positive board angles turn clockwise on the y-up Gerber image; mirroring reverses that
+                let clockwise = (*angle > 0.0) != mirrored;
+                self.stroke(code, &[start]);
+                self.body.push_str(&format!(
+                    "{}{}I{}J{}D01*\nG01*\n",
+                    if clockwise { "G02" } else { "G03" },
+                    Self::xy(end),
+                    coordinate(center.x - start.x),
+                    coordinate(start.y - center.y),
+                ));
+            }
+            GraphicType::Text { position, text, size, rotation } => {
+                let (sin, cos) = rotation.to_radians().sin_cos();
+                for stroke in text_strokes(text, *size) {
+                    let points: Vec<FootprintPoint> = stroke.iter()
+                        .map(|&(x, y)| point(position.x + x * cos - y * sin, position.y + x * sin + y * cos))
+                        .collect();
+                    self.stroke(code, &points);
+                }
+            }
+        }
+    }
+}
+
+pub(crate) struct DrillHit {
+    pub diameter: f64,
+    pub start: FootprintPoint,
+    // Slots are routed from start to end
+    pub end: Option<FootprintPoint>,
+    pub net_id: Option<String>,
+}
+
+fn excellon(function: &str, hits: &[DrillHit]) -> String {
+    let mut tools: Vec<f64> = Vec::new();
+    for hit in hits {
+        if !tools.iter().any(|t| (t - hit.diameter).abs() < 1e-6) {
+            tools.push(hit.diameter);
+        }
+    }
+    tools.sort_by(|a, b| a.total_cmp(b));
+
+    let mut out = String::new();
+    out.push_str("M48\n");
+    out.push_str(&format!("; DRILL file {{{} {}}} date {}\n", APPLICATION, VERSION, chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%z")));
+    out.push_str("; FORMAT={-:-/ absolute / metric / decimal}\n");
+    out.push_str(&format!("; #@! TF.GenerationSoftware,{},{},{}\n", APPLICATION, APPLICATION, VERSION));
+    out.push_str(&format!("; #@! TF.FileFunction,{}\n", function));
+    out.push_str("FMAT,2\nMETRIC\n");
+    for (index, tool) in tools.iter().enumerate() {
+        out.push_str(&format!("T{}C{:.3}\n", index + 1, tool));
+    }
+    out.push_str("%\nG90\nG05\n");
+
+    let xy = |p: FootprintPoint| format!("X{:.4}Y{:.4}", p.x, -p.y);
+    for (index, tool) in tools.iter().enumerate() {
+        out.push_str(&format!("T{}\n", index + 1));
+        for hit in hits.iter().filter(|h| (h.diameter - tool).abs() < 1e-6) {
+            match hit.end {
+                Some(end) => out.push_str(&format!("{}G85{}\n", xy(hit.start), xy(end))),
+                None => out.push_str(&format!("{}\n", xy(hit.start))),
+            }
+        }
+    }
+    out.push_str("T0\nM30\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BoardNet, DrillInfo, Track, Via};
+    use crate::services::FootprintService;
+
+    #[test]
+    fn test_fabrication_outputs() {
+        let library = FootprintService::new();
+        let mut board = Board::new("Test Board".to_string()).unwrap();
+        board.nets.push(BoardNet { id: "n1".to_string(), name: "VCC".to_string() });
+
+        let mut resistor = FootprintInstance::new("c1".to_string(), "R1".to_string(), "10k".to_string(),
+            library.get_footprint("R_0603").unwrap(), FootprintPoint { x: 10.0, y: 10.0 });
+        resistor.pad_nets.insert("1".to_string(), "n1".to_string());
+        let mut dip = FootprintInstance::new("c2".to_string(), "U1".to_string(), "NE555".to_string(),
+            library.get_footprint("DIP-8").unwrap(), FootprintPoint { x: 40.0, y: 30.0 });
+        dip.rotation = 30.0;
+        // A mounting hole with a slot
+        let mut hole = dip.footprint.pads[0].clone();
+        hole.pad_number = String::new();
+        hole.pad_type = PadType::NPTH;
+        hole.layers.clear();
+        hole.drill = Some(DrillInfo {
+            diameter: 1.0,
+            shape: DrillShape::Slot { start: FootprintPoint { x: -1.0, y: 0.0 }, end: FootprintPoint { x: 1.0, y: 0.0 }, width: 1.0 },
+            offset: None,
+        });
+        dip.footprint.pads.push(hole);
+        board.footprints = vec![resistor, dip];
+        board.add_track(Track::new(Some("n1".to_string()), LayerType::TopCopper,
+            FootprintPoint { x: 10.0, y: 10.0 }, FootprintPoint { x: 30.0, y: 10.0 }, 0.25)).unwrap();
+        board.add_via(Via::new(Some("n1".to_string()), FootprintPoint { x: 30.0, y: 10.0 }, 0.6, 0.3)).unwrap();
+
+        let files = FabricationService::fabrication_outputs(&board).unwrap();
+        let file = |name: &str| files.iter().find(|f| f.file_name == name).unwrap_or_else(|| panic!("missing {}", name));
+
+        let top = &file("Test_Board-F_Cu.gbr").content;
+        assert!(top.contains("%TF.FileFunction,Copper,L1,Top*%"));
+        assert!(top.contains("%AMRoundRect*"));
+        assert!(top.contains("%AMRotRect*"), "rotated square pin 1");
+        assert!(top.contains("%TO.P,R1,1*%\n%TO.N,VCC*%"));
+        assert!(top.contains("X10000000Y-10000000D02*\nX30000000Y-10000000D01*"));
+        assert!(top.ends_with("M02*\n"));
+        assert!(file("Test_Board-F_Mask.gbr").content.contains("%TF.FilePolarity,Negative*%"));
+        assert!(!file("Test_Board-B_Paste.gbr").content.contains("D03"));
+
+        let plated = &file("Test_Board-PTH.drl").content;
+        assert!(plated.contains("T1C0.300\nT2C0.800\n"));
+        assert_eq!(plated.matches("\nX").count(), 9);
+        let unplated = &file("Test_Board-NPTH.drl").content;
+        assert!(unplated.contains("G85"));
+
+        let job: serde_json::Value = serde_json::from_str(&file("Test_Board-job.gbrjob").content).unwrap();
+        assert_eq!(job["FilesAttributes"].as_array().unwrap().len(), files.len() - 1);
+        assert_eq!(job["GeneralSpecs"]["LayerNumber"], 2);
+    }
+
+    #[test]
+    fn test_polygon_pads_and_legend_text() {
+        let mut footprint = FootprintService::new().get_footprint("R_0603").unwrap();
+        footprint.pads.truncate(1);
+        let pad = &mut footprint.pads[0];
+        pad.position.x = 0.0;
+        pad.position.y = 0.0;
+        pad.size.width = 1.0;
+        pad.size.height = 1.0;
+        pad.shape = PadShape::Trapezoid { delta: 0.4 };
+        pad.solder_mask_expansion = 0.1;
+        footprint.silkscreen.push(GraphicElement {
+            element_type: GraphicType::Text { position: FootprintPoint { x: 0.0, y: -2.0 }, text: "1".to_string(), size: 1.2, rotation: 0.0 },
+            layer: LayerType::TopSilkscreen,
+            width: 0.15,
+        });
+        let mut board = Board::new("Test Board".to_string()).unwrap();
+        board.footprints.push(FootprintInstance::new("c1".to_string(), "R1".to_string(), "10k".to_string(),
+            footprint, FootprintPoint { x: 10.0, y: 10.0 }));
+
+        let files = FabricationService::gerber_layers(&board);
+        let file = |name: &str| &files.iter().find(|f| f.file_name == name).unwrap().content;
+        // The mask opening grows 0.1 mm square to each edge, slanted ones included
+        assert!(file("Test_Board-F_Cu.gbr").contains("4,1,4,-0.7,0.5,0.7,0.5,0.3,-0.5,-0.3,-0.5,-0.7,0.5,0*"));
+        assert!(file("Test_Board-F_Mask.gbr")
+            .contains("4,1,4,-0.847703,0.6,0.847703,0.6,0.367703,-0.6,-0.367703,-0.6,-0.847703,0.6,0*"));
+
+        // The "1" is plotted: its stem runs from the cap line down to the baseline
+        assert!(file("Test_Board-F_Silkscreen.gbr").contains("X10000000Y-7400000D01*\nX10000000Y-8600000D01*"));
+    }
+}
@@ -17,6 +17,45 @@ impl FileService {
             .map_err(|e| AppError::IoError(e.to_string()))
     }
 
+    /// Writes via a temporary sibling file that is synced and then renamed over the target,
+    /// so a crash mid-write leaves either the old or the new content, never a torn file.
+    pub fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
+        use std::io::Write;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // Concurrent saves of one file each get a temporary file of their own
+        static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let parent = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        std::fs::create_dir_all(parent)?;
+
+        let file_name = path.file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| AppError::InvalidInput("Invalid file name".to_string()))?;
+        let temp_path = path.with_file_name(format!(
+            ".{}.{}.{}.tmp", file_name, std::process::id(), TEMP_COUNTER.fetch_add(1, Ordering::Relaxed),
+        ));
+
+        let mut file = std::fs::File::create(&temp_path)?;
+        file.write_all(content)?;
+        file.sync_all()?;
+        drop(file);
+
+        std::fs::rename(&temp_path, path).inspect_err(|_| {
+            let _ = std::fs::remove_file(&temp_path);
+        })?;
+
+        // The rename itself is only durable once the directory entry is on disk; Windows
+        // can't open directories as files and commits the rename with the file
+        #[cfg(unix)]
+        std::fs::File::open(parent)?.sync_all()?;
+
+        Ok(())
+    }
+
     pub async fn read_binary_file(path: &Path) -> Result<Vec<u8>> {
         fs::read(path)
             .await
@@ -0,0 +1,653 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use crate::models::footprint::{
+    ComponentFootprintMap, DrillInfo, DrillShape, Footprint, FootprintAssignmentRule, FootprintCategory,
+    FootprintDimensions, FootprintLibrary, FootprintMetadata, FootprintPoint, GraphicElement, GraphicType,
+    LayerType, PackageType, Pad, PadShape, PadSize, PadType, PinMapVariant, PinMappingReport,
+    Position,
+};
+use crate::models::{Component, Project};
+use crate::utils::error::{AppError, Result};
+use crate::utils::units::parse_si_value;
+
+pub const STANDARD_FOOTPRINT_LIBRARY_ID: &str = "standard-footprints";
+
+pub struct FootprintService {
+    libraries: Arc<Mutex<HashMap<String, FootprintLibrary>>>,
+}
+
+impl FootprintService {
+    pub fn new() -> Self {
+        let service = Self {
+            libraries: Arc::new(Mutex::new(HashMap::new())),
+        };
+
+        if let Err(e) = service.create_standard_library() {
+            eprintln!("Failed to create standard footprint library: {:?}", e);
+        }
+
+        service
+    }
+
+    pub fn create_standard_library(&self) -> Result<()> {
+        let mut library = FootprintLibrary::new(
+            "Standard Footprints".to_string(),
+            "Generic IPC-7351 style footprints".to_string(),
+            "Rust EDA".to_string(),
+        );
+        library.id = STANDARD_FOOTPRINT_LIBRARY_ID.to_string();
+
+        // Two-terminal chip parts (pad width, pad height, pad center offset)
+        let chips = [
+            (PackageType::SMD0402, "0402", 0.6, 0.6, 0.5),
+            (PackageType::SMD0603, "0603", 0.9, 0.95, 0.8),
+            (PackageType::SMD0805, "0805", 1.0, 1.45, 0.95),
+            (PackageType::SMD1206, "1206", 1.15, 1.8, 1.45),
+        ];
+        for (package, size, pad_w, pad_h, offset) in chips {
+            library.add_footprint(chip_footprint("R", FootprintCategory::Resistor, package.clone(), size, pad_w, pad_h, offset));
+            library.add_footprint(chip_footprint("C", FootprintCategory::Capacitor, package.clone(), size, pad_w, pad_h, offset));
+            library.add_footprint(chip_footprint("L", FootprintCategory::Inductor, package.clone(), size, pad_w, pad_h, offset));
+            library.add_footprint(chip_footprint("LED", FootprintCategory::Diode, package, size, pad_w, pad_h, offset));
+        }
+
+        library.add_footprint(sot23_footprint());
+        library.add_footprint(to92_footprint());
+        for pins in [8, 14, 16] {
+            library.add_footprint(soic_footprint(pins));
+            library.add_footprint(dip_footprint(pins));
+        }
+        for pins in [2, 3, 4, 5, 6, 8, 10] {
+            library.add_footprint(pin_header_footprint(pins));
+        }
+
+        let mut libraries = self.libraries.lock()
+            .map_err(|_| AppError::ThreadError("Mutex poisoned in create_standard_library".to_string()))?;
+        libraries.insert(library.id.clone(), library);
+
+        Ok(())
+    }
+
+    pub fn get_all_libraries(&self) -> Vec<FootprintLibrary> {
+        match self.libraries.lock() {
+            Ok(libraries) => libraries.values().cloned().collect(),
+            Err(_) => {
+                eprintln!("Warning: Mutex poisoned in get_all_libraries, returning empty list");
+                Vec::new()
+            }
+        }
+    }
+
+    // Footprint ids are unique across libraries, so lookups don't need the library id
+    pub fn get_footprint(&self, footprint_id: &str) -> Option<Footprint> {
+        let libraries = match self.libraries.lock() {
+            Ok(guard) => guard,
+            Err(_) => {
+                eprintln!("Warning: Mutex poisoned in get_footprint, returning None");
+                return None;
+            }
+        };
+        libraries.values().find_map(|lib| lib.get_footprint(footprint_id).cloned())
+    }
+
+    /// Links a placed component to a footprint, deriving the pin map from the chosen
+    /// pinout variant or, without one, by matching pin numbers to pad numbers.
+    pub fn assign_footprint(
+        &self,
+        project: &mut Project,
+        component_id: &str,
+        footprint_id: &str,
+        variant_id: Option<&str>,
+    ) -> Result<ComponentFootprintMap> {
+        let assignment = self.footprint_assignment(project, component_id, footprint_id, variant_id)?;
+        project.set_footprint_assignment(assignment.clone());
+        Ok(assignment)
+    }
+
+    /// The assignment `assign_footprint` would make, without making it. Placement hints
+    /// carry over from the component's current assignment.
+    pub fn footprint_assignment(
+        &self,
+        project: &Project,
+        component_id: &str,
+        footprint_id: &str,
+        variant_id: Option<&str>,
+    ) -> Result<ComponentFootprintMap> {
+        let package = project.find_package(component_id)
+            .ok_or_else(|| AppError::ComponentNotFound(component_id.to_string()))?;
+        let footprint = self.get_footprint(footprint_id)
+            .ok_or_else(|| AppError::InvalidInput(format!("Footprint not found: {}", footprint_id)))?;
+
+        let pin_mapping = build_pin_mapping(&package, &footprint, variant_id)?;
+        let placement_hints = project.get_footprint_assignment(component_id)
+            .map(|a| a.placement_hints.clone())
+            .unwrap_or_default();
+
+        Ok(ComponentFootprintMap {
+            component_id: component_id.to_string(),
+            footprint_id: footprint_id.to_string(),
+            pin_mapping,
+            placement_hints,
+            variant_id: variant_id.map(|v| v.to_string()),
+        })
+    }
+
+    /// Applies rules in order; the first matching rule wins for each component.
+    /// Returns the ids of the components that were (re)assigned.
+    pub fn bulk_assign(&self, project: &mut Project, rules: &[FootprintAssignmentRule]) -> Result<Vec<String>> {
+        let candidates: Vec<(String, &FootprintAssignmentRule)> = project.package_components()
+            .into_iter()
+            .filter_map(|component| {
+                let already_assigned = project.get_footprint_assignment(&component.id).is_some();
+                rules.iter()
+                    .find(|rule| (rule.overwrite || !already_assigned) && rule_matches(rule, component))
+                    .map(|rule| (component.id.clone(), rule))
+            })
+            .collect();
+
+        let mut assigned = Vec::with_capacity(candidates.len());
+        for (component_id, rule) in candidates {
+            self.assign_footprint(project, &component_id, &rule.footprint_id, rule.variant_id.as_deref())?;
+            assigned.push(component_id);
+        }
+
+        Ok(assigned)
+    }
+
+    pub fn validate_assignments(&self, project: &Project) -> Vec<PinMappingReport> {
+        project.footprint_assignments.iter()
+            .filter_map(|assignment| {
+                let package = project.find_package(&assignment.component_id)?;
+                let footprint = self.get_footprint(&assignment.footprint_id);
+                Some(validate_pin_mapping(&package, footprint.as_ref(), assignment))
+            })
+            .collect()
+    }
+}
+
+impl Default for FootprintService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn build_pin_mapping(component: &Component, footprint: &Footprint, variant_id: Option<&str>) -> Result<HashMap<String, String>> {
+    let mut mapping = HashMap::new();
+
+    match variant_id {
+        Some(variant_id) => {
+            let variant = footprint.get_pin_map_variant(variant_id)
+                .ok_or_else(|| AppError::InvalidInput(format!("Pin map variant not found: {}", variant_id)))?;
+            for (pad_number, function) in &variant.pad_functions {
+                let pin = component.pins.iter().find(|p| {
+                    p.id.eq_ignore_ascii_case(function) || p.name.eq_ignore_ascii_case(function)
+                });
+                if let Some(pin) = pin {
+                    mapping.insert(pin.number.clone(), pad_number.clone());
+                }
+            }
+        }
+        None => {
+            for pin in &component.pins {
+                if footprint.get_pad(&pin.number).is_some() {
+                    mapping.insert(pin.number.clone(), pin.number.clone());
+                }
+            }
+        }
+    }
+
+    Ok(mapping)
+}
+
+pub fn validate_pin_mapping(component: &Component, footprint: Option<&Footprint>, assignment: &ComponentFootprintMap) -> PinMappingReport {
+    let mut unmapped_pins: Vec<String> = component.pins.iter()
+        .filter(|p| assignment.pad_for_pin(&p.number).is_none())
+        .map(|p| p.number.clone())
+        .collect();
+    unmapped_pins.sort();
+
+    let (missing_pads, unconnected_pads) = match footprint {
+        Some(footprint) => {
+            let mut missing: Vec<String> = assignment.pin_mapping.values()
+                .filter(|pad| footprint.get_pad(pad).is_none())
+                .cloned()
+                .collect();
+            missing.sort();
+            missing.dedup();
+
+            let mut unconnected: Vec<String> = footprint.electrical_pads()
+                .filter(|pad| !assignment.pin_mapping.values().any(|p| p == &pad.pad_number))
+                .map(|pad| pad.pad_number.clone())
+                .collect();
+            unconnected.sort();
+            unconnected.dedup();
+
+            (missing, unconnected)
+        }
+        None => (Vec::new(), Vec::new()),
+    };
+
+    PinMappingReport {
+        component_id: component.id.clone(),
+        reference: component.reference.clone(),
+        footprint_id: assignment.footprint_id.clone(),
+        footprint_found: footprint.is_some(),
+        valid: footprint.is_some() && unmapped_pins.is_empty() && missing_pads.is_empty(),
+        unmapped_pins,
+        missing_pads,
+        unconnected_pads,
+    }
+}
+
+fn rule_matches(rule: &FootprintAssignmentRule, component: &Component) -> bool {
+    if let Some(prefix) = &rule.reference_prefix {
+        let reference_prefix: String = component.reference.chars().take_while(|c| c.is_alphabetic()).collect();
+        if !reference_prefix.eq_ignore_ascii_case(prefix) {
+            return false;
+        }
+    }
+    if let Some(type_id) = &rule.type_id {
+        if &component.type_id != type_id {
+            return false;
+        }
+    }
+    if rule.value_below.is_some() || rule.value_at_least.is_some() {
+        let value = match parse_si_value(&component.value) {
+            Some(v) => v,
+            None => return false,
+        };
+        if rule.value_below.is_some_and(|limit| value >= limit) {
+            return false;
+        }
+        if rule.value_at_least.is_some_and(|limit| value < limit) {
+            return false;
+        }
+    }
+    true
+}
+
+// Footprint builders for the standard library. Dimensions are in millimetres.
+
+fn smd_pad(number: &str, x: f64, y: f64, width: f64, height: f64) -> Pad {
+    Pad {
+        id: format!("pad{}", number),
+        pad_number: number.to_string(),
+        pad_type: PadType::SMD,
+        shape: PadShape::RoundedRectangle { radius: width.min(height) * 0.25 },
+        position: Position { x, y, rotation: 0.0 },
+        size: PadSize { width, height },
+        drill: None,
+        layers: vec![LayerType::TopCopper, LayerType::TopSolderMask, LayerType::TopPaste],
+        solder_mask_expansion: 0.05,
+        solder_paste_margin: 0.0,
+        thermal_relief: None,
+    }
+}
+
+fn tht_pad(number: &str, x: f64, y: f64, size: f64, drill: f64, square: bool) -> Pad {
+    Pad {
+        id: format!("pad{}", number),
+        pad_number: number.to_string(),
+        pad_type: PadType::ThroughHole,
+        shape: if square { PadShape::Rectangle } else { PadShape::Circle },
+        position: Position { x, y, rotation: 0.0 },
+        size: PadSize { width: size, height: size },
+        drill: Some(DrillInfo { diameter: drill, shape: DrillShape::Circle, offset: None }),
+        layers: vec![
+            LayerType::TopCopper,
+            LayerType::BottomCopper,
+            LayerType::TopSolderMask,
+            LayerType::BottomSolderMask,
+        ],
+        solder_mask_expansion: 0.05,
+        solder_paste_margin: 0.0,
+        thermal_relief: None,
+    }
+}
+
+fn rectangle(layer: LayerType, width: f64, height: f64, line_width: f64) -> GraphicElement {
+    GraphicElement {
+        element_type: GraphicType::Rectangle {
+            top_left: FootprintPoint { x: -width / 2.0, y: -height / 2.0 },
+            bottom_right: FootprintPoint { x: width / 2.0, y: height / 2.0 },
+        },
+        layer,
+        width: line_width,
+    }
+}
+
+fn new_footprint(
+    id: String,
+    description: String,
+    category: FootprintCategory,
+    package_type: PackageType,
+    pads: Vec<Pad>,
+    body: (f64, f64),
+    pitch: (Option<f64>, Option<f64>),
+) -> Footprint {
+    // Courtyard is the pad/body extent plus the IPC-7351 nominal 0.25 mm excess
+    let pad_extent = pads.iter().fold((body.0, body.1), |(w, h), p| {
+        (w.max(2.0 * p.position.x.abs() + p.size.width), h.max(2.0 * p.position.y.abs() + p.size.height))
+    });
+    let courtyard = (pad_extent.0 + 0.5, pad_extent.1 + 0.5);
+    let now = chrono::Utc::now().format("%Y-%m-%d").to_string();
+
+    Footprint {
+        name: id.clone(),
+        id,
+        description,
+        category,
+        package_type,
+        pads,
+        silkscreen: vec![rectangle(LayerType::TopSilkscreen, body.0, body.1, 0.12)],
+        courtyard: vec![rectangle(LayerType::TopCourtyard, courtyard.0, courtyard.1, 0.05)],
+        assembly: vec![rectangle(LayerType::TopAssembly, body.0, body.1, 0.1)],
+        keepout: None,
+        dimensions: FootprintDimensions {
+            body_width: body.0,
+            body_height: body.1,
+            courtyard_width: courtyard.0,
+            courtyard_height: courtyard.1,
+            pitch_x: pitch.0,
+            pitch_y: pitch.1,
+        },
+        metadata: FootprintMetadata {
+            manufacturer: None,
+            manufacturer_part: None,
+            datasheet: None,
+            keywords: Vec::new(),
+            ipc_standard: Some("IPC-7351B".to_string()),
+            created_date: now.clone(),
+            modified_date: now,
+            author: "Rust EDA".to_string(),
+            license: Some("CC-BY-SA-4.0".to_string()),
+        },
+        pin_map_variants: Vec::new(),
+    }
+}
+
+fn chip_footprint(prefix: &str, category: FootprintCategory, package: PackageType, size: &str, pad_w: f64, pad_h: f64, offset: f64) -> Footprint {
+    let pads = vec![
+        smd_pad("1", -offset, 0.0, pad_w, pad_h),
+        smd_pad("2", offset, 0.0, pad_w, pad_h),
+    ];
+    let body = (2.0 * offset, pad_h * 0.8);
+    new_footprint(
+        format!("{}_{}", prefix, size),
+        format!("{} chip package {}", prefix, size),
+        category,
+        package,
+        pads,
+        body,
+        (Some(2.0 * offset), None),
+    )
+}
+
+fn pad_functions(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs.iter().map(|(pad, function)| (pad.to_string(), function.to_string())).collect()
+}
+
+// Three-lead transistor pinouts as (variant id, functions of pads 1, 2 and 3)
+const TRANSISTOR_PINOUTS: [(&str, [&str; 3]); 6] = [
+    ("BEC", ["B", "E", "C"]),
+    ("BCE", ["B", "C", "E"]),
+    ("EBC", ["E", "B", "C"]),
+    ("ECB", ["E", "C", "B"]),
+    ("GSD", ["G", "S", "D"]),
+    ("GDS", ["G", "D", "S"]),
+];
+
+fn transistor_variants() -> Vec<PinMapVariant> {
+    TRANSISTOR_PINOUTS.iter()
+        .map(|(id, functions)| PinMapVariant {
+            id: id.to_string(),
+            name: format!("{} ({}-{}-{})", id, functions[0], functions[1], functions[2]),
+            pad_functions: pad_functions(&[("1", functions[0]), ("2", functions[1]), ("3", functions[2])]),
+        })
+        .collect()
+}
+
+fn sot23_footprint() -> Footprint {
+    let pads = vec![
+        smd_pad("1", -0.95, 1.1, 0.6, 0.7),
+        smd_pad("2", 0.95, 1.1, 0.6, 0.7),
+        smd_pad("3", 0.0, -1.1, 0.6, 0.7),
+    ];
+    let mut footprint = new_footprint(
+        "SOT-23".to_string(),
+        "SOT-23 3-lead small outline transistor".to_string(),
+        FootprintCategory::Transistor,
+        PackageType::SOT23,
+        pads,
+        (2.9, 1.3),
+        (Some(1.9), None),
+    );
+    footprint.pin_map_variants = transistor_variants();
+    footprint
+}
+
+fn to92_footprint() -> Footprint {
+    let pads = vec![
+        tht_pad("1", -1.27, 0.0, 1.05, 0.75, true),
+        tht_pad("2", 0.0, 0.0, 1.05, 0.75, false),
+        tht_pad("3", 1.27, 0.0, 1.05, 0.75, false),
+    ];
+    let mut footprint = new_footprint(
+        "TO-92".to_string(),
+        "TO-92 inline 3-lead through-hole".to_string(),
+        FootprintCategory::Transistor,
+        PackageType::TO92,
+        pads,
+        (4.8, 3.8),
+        (Some(1.27), None),
+    );
+    footprint.pin_map_variants = transistor_variants();
+    footprint
+}
+
+// Dual-row packages number pins down the left side and back up the right
+fn dual_row_pads(pins: usize, pitch: f64, row_spacing: f64, pad: impl Fn(&str, f64, f64) -> Pad) -> Vec<Pad> {
+    let per_side = pins / 2;
+    let top = -(per_side as f64 - 1.0) * pitch / 2.0;
+    (0..pins)
+        .map(|i| {
+            let (x, row) = if i < per_side { (-row_spacing / 2.0, i) } else { (row_spacing / 2.0, pins - 1 - i) };
+            pad(&(i + 1).to_string(), x, top + row as f64 * pitch)
+        })
+        .collect()
+}
+
+fn soic_footprint(pins: usize) -> Footprint {
+    let package = match pins {
+        8 => PackageType::SOIC8,
+        14 => PackageType::SOIC14,
+        _ => PackageType::SOIC16,
+    };
+    let pads = dual_row_pads(pins, 1.27, 5.4, |n, x, y| smd_pad(n, x, y, 1.55, 0.6));
+    let body_height = (pins / 2) as f64 * 1.27;
+    new_footprint(
+        format!("SOIC-{}", pins),
+        format!("SOIC-{} 3.9 mm body, 1.27 mm pitch", pins),
+        FootprintCategory::IC,
+        package,
+        pads,
+        (3.9, body_height),
+        (Some(5.4), Some(1.27)),
+    )
+}
+
+fn dip_footprint(pins: usize) -> Footprint {
+    let package = match pins {
+        8 => PackageType::DIP8,
+        14 => PackageType::DIP14,
+        _ => PackageType::DIP16,
+    };
+    let pads = dual_row_pads(pins, 2.54, 7.62, |n, x, y| tht_pad(n, x, y, 1.6, 0.8, n == "1"));
+    let body_height = (pins / 2) as f64 * 2.54;
+    new_footprint(
+        format!("DIP-{}", pins),
+        format!("DIP-{} 7.62 mm row spacing", pins),
+        FootprintCategory::IC,
+        package,
+        pads,
+        (6.35, body_height),
+        (Some(7.62), Some(2.54)),
+    )
+}
+
+fn pin_header_footprint(pins: usize) -> Footprint {
+    let package = match pins {
+        2 => PackageType::PinHeader1x2,
+        3 => PackageType::PinHeader1x3,
+        4 => PackageType::PinHeader1x4,
+        5 => PackageType::PinHeader1x5,
+        6 => PackageType::PinHeader1x6,
+        8 => PackageType::PinHeader1x8,
+        _ => PackageType::PinHeader1x10,
+    };
+    let top = -(pins as f64 - 1.0) * 2.54 / 2.0;
+    let pads = (0..pins)
+        .map(|i| tht_pad(&(i + 1).to_string(), 0.0, top + i as f64 * 2.54, 1.7, 1.0, i == 0))
+        .collect();
+    new_footprint(
+        format!("PinHeader_1x{:02}", pins),
+        format!("Pin header 1x{} 2.54 mm pitch", pins),
+        FootprintCategory::Connector,
+        package,
+        pads,
+        (2.54, pins as f64 * 2.54),
+        (None, Some(2.54)),
+    )
+}
+
+/// Unplated mounting hole of the given drill diameter, with a mask opening on both sides
+/// and a courtyard 0.25 mm clear of the hole.
+pub fn mounting_hole_footprint(diameter: f64) -> Footprint {
+    let size = format!("{}", (diameter * 1000.0).round() / 1000.0);
+    let hole = Pad {
+        id: "pad1".to_string(),
+        pad_number: String::new(),
+        pad_type: PadType::NPTH,
+        shape: PadShape::Circle,
+        position: Position { x: 0.0, y: 0.0, rotation: 0.0 },
+        size: PadSize { width: diameter, height: diameter },
+        drill: Some(DrillInfo { diameter, shape: DrillShape::Circle, offset: None }),
+        layers: vec![LayerType::TopSolderMask, LayerType::BottomSolderMask],
+        solder_mask_expansion: 0.05,
+        solder_paste_margin: 0.0,
+        thermal_relief: None,
+    };
+    let mut footprint = new_footprint(
+        format!("MountingHole_{}mm", size),
+        format!("Mounting hole {} mm, unplated", size),
+        FootprintCategory::MechanicalHole,
+        PackageType::Custom("MountingHole".to_string()),
+        vec![hole],
+        (diameter, diameter),
+        (None, None),
+    );
+    footprint.silkscreen.clear();
+    footprint.courtyard = vec![GraphicElement {
+        element_type: GraphicType::Circle { center: FootprintPoint { x: 0.0, y: 0.0 }, radius: diameter / 2.0 + 0.25 },
+        layer: LayerType::TopCourtyard,
+        width: 0.05,
+    }];
+    footprint
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ElectricalType, Pin, PinType, Schematic};
+
+    fn part(reference: &str, value: &str, pins: &[(&str, &str, &str)]) -> Component {
+        let mut component = Component::new("part".to_string(), 0.0, 0.0).unwrap();
+        component.reference = reference.to_string();
+        component.value = value.to_string();
+        component.pins = pins.iter().map(|(id, name, number)| Pin {
+            id: id.to_string(),
+            name: name.to_string(),
+            number: number.to_string(),
+            x: 0.0,
+            y: 0.0,
+            pin_type: PinType::Passive,
+            electrical: ElectricalType { voltage: None, current: None, impedance: None },
+            connected: false,
+        }).collect();
+        component
+    }
+
+    #[test]
+    fn test_footprint_assignment() {
+        let service = FootprintService::new();
+        let mut schematic = Schematic::new("Main".to_string()).unwrap();
+        let two_pins = [("a", "A", "1"), ("b", "B", "2")];
+        let parts = [
+            part("Q1", "BC547", &[("base", "B", "1"), ("emitter", "E", "2"), ("collector", "C", "3")]),
+            part("R1", "10k", &two_pins),
+            part("R2", "100", &two_pins),
+            part("C1", "100n", &two_pins),
+        ];
+        let ids: Vec<String> = parts.iter().map(|c| c.id.clone()).collect();
+        for component in parts {
+            schematic.add_component(component).unwrap();
+        }
+        let mut project = Project::new("Test".to_string()).unwrap();
+        project.add_schematic(schematic).unwrap();
+
+        // A pinout variant maps pins by function; without one pin numbers become pad numbers
+        let assignment = service.assign_footprint(&mut project, &ids[0], "SOT-23", Some("EBC")).unwrap();
+        assert_eq!(assignment.pad_for_pin("2"), Some("1"));
+        assert_eq!(assignment.pad_for_pin("1"), Some("2"));
+        assert_eq!(assignment.pad_for_pin("3"), Some("3"));
+        assert_eq!(assignment.variant_id.as_deref(), Some("EBC"));
+        assert!(service.assign_footprint(&mut project, &ids[0], "SOT-23", Some("XYZ")).is_err());
+        assert!(service.assign_footprint(&mut project, &ids[0], "NO-SUCH", None).is_err());
+        assert!(service.assign_footprint(&mut project, "missing", "SOT-23", None).is_err());
+        let plain = service.assign_footprint(&mut project, &ids[1], "R_0805", None).unwrap();
+        assert_eq!(plain.pad_for_pin("1"), Some("1"));
+
+        // The first matching rule wins; existing assignments stay unless the rule overwrites
+        let rule = |footprint: &str, prefix: &str, below: Option<f64>, overwrite: bool| FootprintAssignmentRule {
+            footprint_id: footprint.to_string(),
+            variant_id: None,
+            reference_prefix: Some(prefix.to_string()),
+            type_id: None,
+            value_below: below,
+            value_at_least: None,
+            overwrite,
+        };
+        let rules = [rule("R_0603", "R", Some(1000.0), false), rule("R_1206", "R", None, false), rule("C_0402", "c", None, false)];
+        let mut assigned = service.bulk_assign(&mut project, &rules).unwrap();
+        assigned.sort();
+        let mut expected = vec![ids[2].clone(), ids[3].clone()];
+        expected.sort();
+        assert_eq!(assigned, expected);
+        let footprint_of = |project: &Project, id: &str| project.get_footprint_assignment(id).unwrap().footprint_id.clone();
+        assert_eq!(footprint_of(&project, &ids[1]), "R_0805");
+        assert_eq!(footprint_of(&project, &ids[2]), "R_0603");
+        assert_eq!(footprint_of(&project, &ids[3]), "C_0402");
+        service.bulk_assign(&mut project, &[rule("R_1206", "R", None, true)]).unwrap();
+        assert_eq!(footprint_of(&project, &ids[1]), "R_1206");
+
+        assert!(service.validate_assignments(&project).iter().all(|r| r.valid));
+
+        // Pins without pads, mapping targets the footprint lacks and pads left unused
+        let transistor = project.find_package(&ids[0]).unwrap();
+        let mut assignment = service.assign_footprint(&mut project, &ids[0], "R_0805", None).unwrap();
+        let report = validate_pin_mapping(&transistor, service.get_footprint("R_0805").as_ref(), &assignment);
+        assert_eq!(report.unmapped_pins, vec!["3".to_string()]);
+        assert!(report.missing_pads.is_empty() && report.unconnected_pads.is_empty());
+        assert!(!report.valid);
+
+        assignment.pin_mapping.insert("3".to_string(), "9".to_string());
+        assignment.pin_mapping.remove("2");
+        let report = validate_pin_mapping(&transistor, service.get_footprint("R_0805").as_ref(), &assignment);
+        assert_eq!(report.unmapped_pins, vec!["2".to_string()]);
+        assert_eq!(report.missing_pads, vec!["9".to_string()]);
+        assert_eq!(report.unconnected_pads, vec!["2".to_string()]);
+
+        let report = validate_pin_mapping(&transistor, None, &assignment);
+        assert!(!report.footprint_found && !report.valid);
+    }
+}
@@ -0,0 +1,1001 @@
+use crate::models::{
+    Board, FootprintInstance, FootprintPoint, GraphicElement, GraphicType, LayerType, Pad, PadShape, PadType,
+    PlacementSide, Project, StackupLayerKind,
+};
+use crate::services::fabrication_service::{base_name, decimal, drill_groups, layer_name, FabricationFile, APPLICATION, VERSION};
+use crate::utils::error::{AppError, Result};
+use crate::utils::geometry::offset_polygon;
+use crate::utils::validators::escape_markup;
+use std::collections::{BTreeMap, HashMap};
+
+// Line width for package outlines, which only document the courtyard
+const OUTLINE_WIDTH: f64 = 0.05;
+// IPC-D-356 net names are at most 14 characters; longer ones get an NNAME alias
+const D356_NET_WIDTH: usize = 14;
+
+pub struct IpcExportService;
+
+impl IpcExportService {
+    /// IPC-2581 (revision C) package for the board: stackup, every layer's features,
+    /// drills, packages and components, the logical netlist and a BOM of the placed parts.
+    /// Coordinates are millimetres in the y-up frame fabs expect.
+    pub fn ipc2581(project: &Project) -> Result<FabricationFile> {
+        let board = project.board.as_ref()
+            .ok_or_else(|| AppError::InvalidOperation("Project has no board".to_string()))?;
+        let profile = board.outline_contours()
+            .ok_or_else(|| AppError::InvalidOperation("Board outline on EdgeCuts must be closed".to_string()))?;
+
+        let step = base_name(board);
+        let mut writer = Ipc2581Writer::new(board);
+        let copper = board.stackup.copper_layers();
+        let single_sided = copper.len() < 2;
+
+        // Layers: the physical stackup from the top down, then paste, legend and drill spans
+        let mut layers = Xml::new(3);
+        let mut stackup = Xml::new(4);
+        let mut specs = Xml::new(3);
+        for (sequence, layer) in board.stackup.layers.iter().enumerate() {
+            let (name, function, side) = match (&layer.kind, &layer.layer) {
+                (StackupLayerKind::Copper, Some(l)) => (layer_name(l), "SIGNAL", copper_side(l)),
+                (StackupLayerKind::SolderMask, Some(l)) => (layer_name(l), "SOLDERMASK", copper_side(l)),
+                _ => {
+                    let prepreg = layer.material.as_deref().is_some_and(|m| m.to_lowercase().contains("prepreg"));
+                    (xml_name(&layer.name), if prepreg { "DIELPREG" } else { "DIELCORE" }, "INTERNAL")
+                }
+            };
+            layers.empty("Layer", &[("name", name.clone()), ("layerFunction", function.into()), ("side", side.into()), ("polarity", "POSITIVE".into())]);
+
+            let spec = (layer.material.is_some() || layer.dielectric_constant.is_some()).then(|| format!("SPEC_{}", name));
+            if let Some(id) = &spec {
+                specs.open("Spec", &[("name", id.clone())]);
+                if let Some(material) = &layer.material {
+                    specs.open("General", &[("type", "MATERIAL".into())]);
+                    specs.empty("Property", &[("text", material.clone())]);
+                    specs.close("General");
+                }
+                if let Some(constant) = layer.dielectric_constant {
+                    specs.open("Dielectric", &[("type", "DIELECTRIC_CONSTANT".into())]);
+                    specs.empty("Property", &[("value", decimal(constant))]);
+                    specs.close("Dielectric");
+                }
+                specs.close("Spec");
+            }
+            let attributes = [
+                ("layerOrGroupRef", name),
+                ("thickness", decimal(layer.thickness)),
+                ("tolPlus", "0".into()),
+                ("tolMinus", "0".into()),
+                ("sequence", (sequence + 1).to_string()),
+            ];
+            match spec {
+                Some(id) => {
+                    stackup.open("StackupLayer", &attributes);
+                    stackup.empty("SpecRef", &[("id", id)]);
+                    stackup.close("StackupLayer");
+                }
+                None => stackup.empty("StackupLayer", &attributes),
+            }
+        }
+        let mut technical = vec![
+            (LayerType::TopPaste, "PASTEMASK", "TOP"),
+            (LayerType::TopSilkscreen, "SILKSCREEN", "TOP"),
+        ];
+        if !single_sided {
+            technical.push((LayerType::BottomPaste, "PASTEMASK", "BOTTOM"));
+            technical.push((LayerType::BottomSilkscreen, "SILKSCREEN", "BOTTOM"));
+        }
+        for (layer, function, side) in &technical {
+            layers.empty("Layer", &[("name", layer_name(layer)), ("layerFunction", function.to_string()), ("side", side.to_string()), ("polarity", "POSITIVE".into())]);
+        }
+        let drills = drill_groups(board);
+        let drill_layer = |plated: bool, first: usize, last: usize| {
+            if plated { format!("Drill_{}_{}", first + 1, last + 1) } else { "Drill_NPTH".to_string() }
+        };
+        for &(plated, first, last) in drills.keys() {
+            layers.open("Layer", &[("name", drill_layer(plated, first, last)), ("layerFunction", "DRILL".into()), ("side", "ALL".into()), ("polarity", "POSITIVE".into())]);
+            layers.empty("Span", &[("fromLayer", layer_name(&copper[first])), ("toLayer", layer_name(&copper[last]))]);
+            layers.close("Layer");
+        }
+
+        // Step: profile, packages, components, nets and the features of every layer
+        let mut body = Xml::new(3);
+        body.open("Step", &[("name", step.clone())]);
+        body.empty("Datum", &[("x", "0".into()), ("y", "0".into())]);
+        body.open("Profile", &[]);
+        for (index, contour) in profile.iter().enumerate() {
+            let points: Vec<FootprintPoint> = contour.iter().map(|&(x, y)| FootprintPoint { x, y }).collect();
+            contour_element(&mut body, if index == 0 { "Polygon" } else { "Cutout" }, &points);
+        }
+        body.close("Profile");
+
+        let mut packages: BTreeMap<&str, &FootprintInstance> = BTreeMap::new();
+        for instance in &board.footprints {
+            packages.entry(instance.footprint_id.as_str()).or_insert(instance);
+        }
+        for (name, instance) in &packages {
+            let footprint = &instance.footprint;
+            let pin_one = footprint.electrical_pads().next().map(|p| p.pad_number.clone()).unwrap_or_default();
+            body.open("Package", &[("name", name.to_string()), ("type", "OTHER".into()), ("pinOne", pin_one)]);
+            let (w, h) = (footprint.dimensions.courtyard_width / 2.0, footprint.dimensions.courtyard_height / 2.0);
+            if w > 0.0 && h > 0.0 {
+                body.open("Outline", &[]);
+                polygon(&mut body, &[
+                    FootprintPoint { x: -w, y: -h }, FootprintPoint { x: w, y: -h },
+                    FootprintPoint { x: w, y: h }, FootprintPoint { x: -w, y: h },
+                ]);
+                let line = writer.line_desc(OUTLINE_WIDTH);
+                body.empty("LineDescRef", &[("id", line)]);
+                body.close("Outline");
+            }
+            for pad in &footprint.pads {
+                let kind = match pad.pad_type {
+                    PadType::SMD => "SURFACE",
+                    PadType::NPTH => "NONPLATED",
+                    _ => "THRU",
+                };
+                let electrical = if matches!(pad.pad_type, PadType::NPTH) { "MECHANICAL" } else { "ELECTRICAL" };
+                body.open("Pin", &[("number", pad.pad_number.clone()), ("type", kind.into()), ("electricalType", electrical.into())]);
+                body.empty("Xform", &[("rotation", decimal((-pad.position.rotation).rem_euclid(360.0)))]);
+                body.empty("Location", &[("x", decimal(pad.position.x)), ("y", decimal(-pad.position.y))]);
+                let primitive = writer.primitive(pad, 0.0);
+                body.empty("StandardPrimitiveRef", &[("id", primitive)]);
+                body.close("Pin");
+            }
+            body.close("Package");
+        }
+
+        let parts = bom_parts(project, board);
+        for instance in &board.footprints {
+            let through = instance.footprint.pads.iter().any(|p| matches!(p.pad_type, PadType::ThroughHole));
+            body.open("Component", &[
+                ("refDes", instance.reference.clone()),
+                ("packageRef", instance.footprint_id.clone()),
+                ("layerRef", layer_name(&outer_layer(board, instance.side))),
+                ("part", parts.get(&instance.id).cloned().unwrap_or_default()),
+                ("mountType", if through { "THMT" } else { "SMT" }.into()),
+            ]);
+            body.empty("Xform", &[
+                ("rotation", decimal((-instance.rotation).rem_euclid(360.0))),
+                ("mirror", (instance.side == PlacementSide::Bottom).to_string()),
+            ]);
+            body.empty("Location", &[("x", decimal(instance.position.x)), ("y", decimal(-instance.position.y))]);
+            body.close("Component");
+        }
+
+        for net in &board.nets {
+            let pins: Vec<(&str, &str)> = board.footprints.iter()
+                .flat_map(|f| f.pad_nets.iter().filter(|(_, n)| **n == net.id).map(move |(pad, _)| (f.reference.as_str(), pad.as_str())))
+                .collect::<std::collections::BTreeSet<_>>()
+                .into_iter()
+                .collect();
+            if pins.is_empty() {
+                continue;
+            }
+            body.open("LogicalNet", &[("name", net.name.clone())]);
+            for (component, pin) in pins {
+                body.empty("PinRef", &[("componentRef", component.to_string()), ("pin", pin.to_string())]);
+            }
+            body.close("LogicalNet");
+        }
+
+        for (index, layer) in copper.iter().enumerate() {
+            writer.copper_features(&mut body, board, layer, index);
+        }
+        let mut surfaces = vec![LayerType::TopSolderMask, LayerType::TopPaste, LayerType::TopSilkscreen];
+        if !single_sided {
+            surfaces.extend([LayerType::BottomSolderMask, LayerType::BottomPaste, LayerType::BottomSilkscreen]);
+        }
+        for layer in &surfaces {
+            writer.surface_features(&mut body, board, layer);
+        }
+        for (&(plated, first, last), hits) in &drills {
+            body.open("LayerFeature", &[("layerRef", drill_layer(plated, first, last))]);
+            for (index, hit) in hits.iter().enumerate() {
+                let status = if !plated {
+                    "NONPLATED"
+                } else if hit.end.is_none() && (first, last) != (0, copper.len().saturating_sub(1)) {
+                    "VIA"
+                } else {
+                    "PLATED"
+                };
+                writer.open_set(&mut body, hit.net_id.as_deref(), None);
+                let name = format!("H{}", index + 1);
+                match hit.end {
+                    None => body.empty("Hole", &[
+                        ("name", name),
+                        ("diameter", decimal(hit.diameter)),
+                        ("platingStatus", status.into()),
+                        ("plusTol", "0".into()),
+                        ("minusTol", "0".into()),
+                        ("x", decimal(hit.start.x)),
+                        ("y", decimal(-hit.start.y)),
+                    ]),
+                    Some(end) => {
+                        body.open("SlotCavity", &[("name", name), ("platingStatus", status.into()), ("plusTol", "0".into()), ("minusTol", "0".into())]);
+                        body.open("Outline", &[]);
+                        slot_outline(&mut body, hit.start, end, hit.diameter / 2.0);
+                        let line = writer.line_desc(0.0);
+                        body.empty("LineDescRef", &[("id", line)]);
+                        body.close("Outline");
+                        body.close("SlotCavity");
+                    }
+                }
+                body.close("Set");
+            }
+            body.close("LayerFeature");
+        }
+        body.close("Step");
+
+        // BOM, one item per part number
+        let mut bom = Xml::new(1);
+        let bom_name = format!("{}_BOM", step);
+        bom.open("Bom", &[("name", bom_name.clone())]);
+        bom.open("BomHeader", &[("assembly", step.clone()), ("revision", "1".into())]);
+        bom.empty("StepRef", &[("name", step.clone())]);
+        bom.close("BomHeader");
+        let mut items: BTreeMap<&str, Vec<&FootprintInstance>> = BTreeMap::new();
+        for instance in &board.footprints {
+            if let Some(part) = parts.get(&instance.id) {
+                items.entry(part.as_str()).or_default().push(instance);
+            }
+        }
+        for (part, instances) in &items {
+            let first = instances[0];
+            let pins = first.footprint.electrical_pads().count();
+            let category = if pins == 0 { "MECHANICAL" } else { "ELECTRICAL" };
+            bom.open("BomItem", &[
+                ("OEMDesignNumberRef", part.to_string()),
+                ("quantity", instances.len().to_string()),
+                ("pinCount", pins.to_string()),
+                ("category", category.into()),
+            ]);
+            for instance in instances {
+                bom.empty("RefDes", &[
+                    ("name", instance.reference.clone()),
+                    ("packageRef", instance.footprint_id.clone()),
+                    ("populate", "true".into()),
+                    ("layerRef", layer_name(&outer_layer(board, instance.side))),
+                ]);
+            }
+            bom.open("Characteristics", &[("category", category.into())]);
+            let info = project.find_component(&first.component_id).map(|c| c.part_info.clone()).unwrap_or_default();
+            let mut textual = vec![("Value", Some(first.value.clone())), ("Manufacturer", info.manufacturer), ("MPN", info.mpn)];
+            textual.retain(|(_, v)| v.as_ref().is_some_and(|v| !v.is_empty()));
+            for (name, value) in textual {
+                bom.empty("Textual", &[
+                    ("definitionSource", APPLICATION.into()),
+                    ("textualCharacteristicName", name.into()),
+                    ("textualCharacteristicValue", value.unwrap_or_default()),
+                ]);
+            }
+            bom.close("Characteristics");
+            bom.close("BomItem");
+        }
+        bom.close("Bom");
+
+        // Content last, now that the dictionaries are complete
+        let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        let mut out = Xml::new(0);
+        out.text.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.open("IPC-2581", &[("revision", "C".into()), ("xmlns", "http://webstds.ipc.org/2581".into())]);
+        out.open("Content", &[("roleRef", "Owner".into())]);
+        out.empty("FunctionMode", &[("mode", "USERDEF".into())]);
+        out.empty("StepRef", &[("name", step.clone())]);
+        for layer in board.stackup.layers.iter() {
+            let name = match &layer.layer {
+                Some(l) => layer_name(l),
+                None => xml_name(&layer.name),
+            };
+            out.empty("LayerRef", &[("name", name)]);
+        }
+        for (layer, _, _) in &technical {
+            out.empty("LayerRef", &[("name", layer_name(layer))]);
+        }
+        for &(plated, first, last) in drills.keys() {
+            out.empty("LayerRef", &[("name", drill_layer(plated, first, last))]);
+        }
+        out.empty("BomRef", &[("name", bom_name)]);
+        out.open("DictionaryStandard", &[("units", "MILLIMETER".into())]);
+        for (id, primitive) in &writer.primitives {
+            out.open("EntryStandard", &[("id", id.clone())]);
+            out.text.push_str(primitive);
+            out.close("EntryStandard");
+        }
+        out.close("DictionaryStandard");
+        out.open("DictionaryLineDesc", &[("units", "MILLIMETER".into())]);
+        for (id, width) in &writer.lines {
+            out.open("EntryLineDesc", &[("id", id.clone())]);
+            out.empty("LineDesc", &[("lineEnd", "ROUND".into()), ("lineWidth", width.clone())]);
+            out.close("EntryLineDesc");
+        }
+        out.close("DictionaryLineDesc");
+        out.close("Content");
+
+        out.open("LogisticHeader", &[]);
+        out.empty("Role", &[("id", "Owner".into()), ("roleFunction", "SENDER".into())]);
+        out.empty("Enterprise", &[("id", APPLICATION.into()), ("code", "NONE".into())]);
+        out.empty("Person", &[("name", APPLICATION.into()), ("enterpriseRef", APPLICATION.into()), ("roleRef", "Owner".into())]);
+        out.close("LogisticHeader");
+        out.open("HistoryRecord", &[("number", "1".into()), ("origination", now.clone()), ("software", APPLICATION.into()), ("lastChange", now)]);
+        out.open("FileRevision", &[("fileRevisionId", "1".into()), ("comment", String::new())]);
+        out.open("SoftwarePackage", &[("name", APPLICATION.into()), ("revision", VERSION.into()), ("vendor", APPLICATION.into())]);
+        out.empty("Certification", &[("certificationStatus", "SELFTEST".into())]);
+        out.close("SoftwarePackage");
+        out.close("FileRevision");
+        out.close("HistoryRecord");
+        out.text.push_str(&bom.text);
+
+        out.open("Ecad", &[("name", step.clone())]);
+        out.open("CadHeader", &[("units", "MILLIMETER".into())]);
+        out.text.push_str(&specs.text);
+        out.close("CadHeader");
+        out.open("CadData", &[]);
+        out.text.push_str(&layers.text);
+        out.open("Stackup", &[
+            ("name", "Primary".into()),
+            ("overallThickness", decimal(board.stackup.thickness())),
+            ("whereMeasured", "METAL".into()),
+            ("tolPlus", "0".into()),
+            ("tolMinus", "0".into()),
+        ]);
+        out.open("StackupGroup", &[
+            ("name", "Primary".into()),
+            ("thickness", decimal(board.stackup.thickness())),
+            ("tolPlus", "0".into()),
+            ("tolMinus", "0".into()),
+        ]);
+        out.text.push_str(&stackup.text);
+        out.close("StackupGroup");
+        out.close("Stackup");
+        out.text.push_str(&body.text);
+        out.close("CadData");
+        out.close("Ecad");
+        out.close("IPC-2581");
+
+        Ok(FabricationFile {
+            file_name: format!("{}.xml", step),
+            file_function: "IPC-2581".to_string(),
+            polarity: "Positive".to_string(),
+            content: out.text,
+        })
+    }
+
+    /// IPC-D-356A bare-board test netlist: one record per pad and via with its net, test
+    /// access side and soldermask, in 0.001 mm units. Conductor (378) records are not written.
+    pub fn ipc_d356(board: &Board) -> Result<FabricationFile> {
+        let copper = board.stackup.copper_layers();
+        let bottom_access = copper.len().max(1);
+        let net_names: HashMap<&str, &str> = board.nets.iter().map(|n| (n.id.as_str(), n.name.as_str())).collect();
+
+        // Names that don't fit the column (or contain blanks) are aliased in the header
+        let mut aliases: BTreeMap<String, String> = BTreeMap::new();
+        let mut column = |net_id: Option<&str>| -> String {
+            let Some(name) = net_id.map(|id| net_names.get(id).copied().unwrap_or(id)) else {
+                return "N/C".to_string();
+            };
+            if name.len() <= D356_NET_WIDTH && !name.contains(' ') {
+                return name.to_string();
+            }
+            let next = aliases.len() + 1;
+            aliases.entry(name.to_string()).or_insert_with(|| format!("NNAME{}", next)).clone()
+        };
+
+        let mut records = Vec::new();
+        for instance in &board.footprints {
+            let bottom = instance.side == PlacementSide::Bottom;
+            for pad in &instance.footprint.pads {
+                let center = instance.pad_position(pad);
+                let rotation = (-instance.pad_rotation(pad)).rem_euclid(360.0);
+                let feature = Feature {
+                    width: pad.size.width,
+                    height: pad.size.height,
+                    rotation,
+                    position: center,
+                };
+                let record = match (&pad.pad_type, &pad.drill) {
+                    (PadType::SMD, _) => D356Record {
+                        code: 327,
+                        net: column(instance.pad_net(&pad.pad_number)),
+                        reference: instance.reference.clone(),
+                        pin: pad.pad_number.clone(),
+                        hole: None,
+                        access: if bottom { bottom_access } else { 1 },
+                        feature,
+                        mask: if bottom { 1 } else { 2 },
+                    },
+                    (PadType::NPTH, Some(drill)) => D356Record {
+                        code: 367,
+                        net: column(None),
+                        reference: instance.reference.clone(),
+                        pin: pad.pad_number.clone(),
+                        hole: Some((drill.diameter, false)),
+                        access: 0,
+                        feature,
+                        mask: 0,
+                    },
+                    (_, Some(drill)) => D356Record {
+                        code: 317,
+                        net: column(instance.pad_net(&pad.pad_number)),
+                        reference: instance.reference.clone(),
+                        pin: pad.pad_number.clone(),
+                        hole: Some((drill.diameter, true)),
+                        access: 0,
+                        feature,
+                        mask: 0,
+                    },
+                    (_, None) => continue,
+                };
+                records.push(record);
+            }
+        }
+        for via in &board.vias {
+            let through = copper.first() == Some(&via.start_layer) && copper.last() == Some(&via.end_layer);
+            let access = if through { 0 } else { copper.iter().position(|l| *l == via.start_layer).map_or(1, |i| i + 1) };
+            records.push(D356Record {
+                code: 317,
+                net: column(via.net_id.as_deref()),
+                reference: "VIA".to_string(),
+                pin: String::new(),
+                hole: Some((via.drill, true)),
+                access,
+                feature: Feature { width: via.diameter, height: via.diameter, rotation: 0.0, position: via.position },
+                mask: 3, // tented on both sides
+            });
+        }
+
+        let mut out = String::new();
+        out.push_str(&format!("C  IPC-D-356A netlist generated by {} {}\n", APPLICATION, VERSION));
+        out.push_str(&format!("C  {}\n", chrono::Utc::now().format("%Y-%m-%d %H:%M:%S")));
+        out.push_str(&format!("P  JOB   {}\n", board.name));
+        out.push_str("P  CODE  00\n");
+        out.push_str("P  UNITS CUST 1\n");
+        out.push_str("P  DIM   N\n");
+        for (name, alias) in &aliases {
+            out.push_str(&format!("P  {:<6}{}\n", alias, name));
+        }
+        for record in &records {
+            out.push_str(&record.line());
+            out.push('\n');
+        }
+        out.push_str("999\n");
+
+        Ok(FabricationFile {
+            file_name: format!("{}.ipc", base_name(board)),
+            file_function: "Netlist,IPC-D-356A".to_string(),
+            polarity: "Positive".to_string(),
+            content: out,
+        })
+    }
+}
+
+struct Feature {
+    width: f64,
+    height: f64,
+    rotation: f64,
+    position: FootprintPoint,
+}
+
+struct D356Record {
+    code: u16,
+    net: String,
+    reference: String,
+    pin: String,
+    hole: Option<(f64, bool)>, // diameter, plated
+    access: usize,             // 0 for both sides, else the copper layer number
+    feature: Feature,
+    mask: u8,                  // sides the soldermask covers: 0 neither, 1 primary, 2 secondary, 3 both
+}
+
+impl D356Record {
+    // Fixed columns of the IPC-D-356A test record
+    fn line(&self) -> String {
+        let micrometres = |value: f64| (value * 1000.0).round() as i64;
+        let size = |value: f64| micrometres(value).clamp(0, 9999);
+        let hole = match self.hole {
+            Some((diameter, plated)) => format!("D{:04}{}", size(diameter), if plated { 'P' } else { 'U' }),
+            None => " ".repeat(6),
+        };
+        format!(
+            "{:03}{:<14.14}   {:<6.6}-{:<4.4} {}A{:02}X{:+07}Y{:+07}X{:04}Y{:04}R{:03} S{}",
+            self.code,
+            self.net,
+            self.reference,
+            self.pin,
+            hole,
+            self.access.min(99),
+            micrometres(self.feature.position.x).clamp(-999_999, 999_999),
+            micrometres(-self.feature.position.y).clamp(-999_999, 999_999),
+            size(self.feature.width),
+            size(self.feature.height),
+            self.feature.rotation.round() as i64 % 360,
+            self.mask,
+        )
+    }
+}
+
+// Collects the dictionary entries features refer to while the step is written
+struct Ipc2581Writer {
+    net_names: HashMap<String, String>,
+    primitives: Vec<(String, String)>, // id -> primitive element
+    primitive_ids: HashMap<String, String>,
+    lines: Vec<(String, String)>, // id -> width
+}
+
+impl Ipc2581Writer {
+    fn new(board: &Board) -> Self {
+        Self {
+            net_names: board.nets.iter().map(|n| (n.id.clone(), n.name.clone())).collect(),
+            primitives: Vec::new(),
+            primitive_ids: HashMap::new(),
+            lines: Vec::new(),
+        }
+    }
+
+    fn line_desc(&mut self, width: f64) -> String {
+        let width = decimal(width);
+        let id = format!("LINE_{}", width);
+        if !self.lines.iter().any(|(existing, _)| *existing == id) {
+            self.lines.push((id.clone(), width));
+        }
+        id
+    }
+
+    // Dictionary id of the pad's shape in its own frame (y up), grown by `expansion`
+    fn primitive(&mut self, pad: &Pad, expansion: f64) -> String {
+        let (w, h) = (pad.size.width + 2.0 * expansion, pad.size.height + 2.0 * expansion);
+        let (key, element) = match &pad.shape {
+            PadShape::Circle => (format!("CIRCLE_{}", decimal(w)), format!("<Circle diameter=\"{}\"/>", decimal(w))),
+            PadShape::Rectangle => (
+                format!("RECT_{}x{}", decimal(w), decimal(h)),
+                format!("<RectCenter width=\"{}\" height=\"{}\"/>", decimal(w), decimal(h)),
+            ),
+            PadShape::Oval => (
+                format!("OVAL_{}x{}", decimal(w), decimal(h)),
+                format!("<Oval width=\"{}\" height=\"{}\"/>", decimal(w), decimal(h)),
+            ),
+            PadShape::RoundedRectangle { radius } => {
+                let radius = (radius + expansion).clamp(0.0, w.min(h) / 2.0);
+                (
+                    format!("RRECT_{}x{}_{}", decimal(w), decimal(h), decimal(radius)),
+                    format!(
+                        "<RectRound width=\"{}\" height=\"{}\" radius=\"{}\" upperRight=\"true\" upperLeft=\"true\" lowerRight=\"true\" lowerLeft=\"true\"/>",
+                        decimal(w), decimal(h), decimal(radius),
+                    ),
+                )
+            }
+            PadShape::Trapezoid { delta } => {
+                let (half_width, half_height) = (pad.size.width / 2.0, pad.size.height / 2.0);
+                let (top, bottom) = (half_width - delta / 2.0, half_width + delta / 2.0);
+                let corners = [(-bottom, -half_height), (bottom, -half_height), (top, half_height), (-top, half_height)];
+                contour(&offset_polygon(&corners, expansion))
+            }
+            PadShape::Custom(points) => {
+                let corners: Vec<(f64, f64)> = points.iter().map(|p| (p.x, p.y)).collect();
+                contour(&offset_polygon(&corners, expansion))
+            }
+        };
+        if let Some(id) = self.primitive_ids.get(&key) {
+            return id.clone();
+        }
+        let id = if key.starts_with("CONTOUR") { format!("CONTOUR_{}", self.primitives.len() + 1) } else { key.clone() };
+        self.primitives.push((id.clone(), element));
+        self.primitive_ids.insert(key, id.clone());
+        id
+    }
+
+    fn open_set(&self, xml: &mut Xml, net_id: Option<&str>, usage: Option<&str>) {
+        let mut attributes = Vec::new();
+        if let Some(name) = net_id.and_then(|id| self.net_names.get(id)) {
+            attributes.push(("net", name.clone()));
+        }
+        if let Some(usage) = usage {
+            attributes.push(("padUsage", usage.to_string()));
+        }
+        xml.open("Set", &attributes);
+    }
+
+    fn pad(&mut self, xml: &mut Xml, instance: &FootprintInstance, pad: &Pad, expansion: f64, pin: bool) {
+        if pad.size.width + 2.0 * expansion <= 0.0 || pad.size.height + 2.0 * expansion <= 0.0 {
+            return;
+        }
+        let center = instance.pad_position(pad);
+        let primitive = self.primitive(pad, expansion);
+        xml.open("Pad", &[]);
+        xml.empty("Xform", &[
+            ("rotation", decimal((-instance.pad_rotation(pad)).rem_euclid(360.0))),
+            ("mirror", (instance.side == PlacementSide::Bottom).to_string()),
+        ]);
+        xml.empty("Location", &[("x", decimal(center.x)), ("y", decimal(-center.y))]);
+        xml.empty("StandardPrimitiveRef", &[("id", primitive)]);
+        if pin {
+            xml.empty("PinRef", &[("componentRef", instance.reference.clone()), ("pin", pad.pad_number.clone())]);
+        }
+        xml.close("Pad");
+    }
+
+    fn copper_features(&mut self, xml: &mut Xml, board: &Board, layer: &LayerType, index: usize) {
+        let copper = board.stackup.copper_layers();
+        xml.open("LayerFeature", &[("layerRef", layer_name(layer))]);
+
+        for instance in &board.footprints {
+            for pad in &instance.footprint.pads {
+                if !instance.pad_copper_layers(pad, &board.stackup).contains(layer) {
+                    continue;
+                }
+                self.open_set(xml, instance.pad_net(&pad.pad_number), Some("TERMINATION"));
+                self.pad(xml, instance, pad, 0.0, true);
+                xml.close("Set");
+            }
+        }
+
+        for track in board.tracks.iter().filter(|t| &t.layer == layer) {
+            let line = self.line_desc(track.width);
+            self.open_set(xml, track.net_id.as_deref(), None);
+            xml.open("Features", &[]);
+            xml.open("Line", &[
+                ("startX", decimal(track.start.x)),
+                ("startY", decimal(-track.start.y)),
+                ("endX", decimal(track.end.x)),
+                ("endY", decimal(-track.end.y)),
+            ]);
+            xml.empty("LineDescRef", &[("id", line)]);
+            xml.close("Line");
+            xml.close("Features");
+            xml.close("Set");
+        }
+
+        for via in &board.vias {
+            let position = |l: &LayerType| copper.iter().position(|c| c == l).unwrap_or(0);
+            let (a, b) = (position(&via.start_layer), position(&via.end_layer));
+            if index < a.min(b) || index > a.max(b) {
+                continue;
+            }
+            let primitive = format!("CIRCLE_{}", decimal(via.diameter));
+            if !self.primitive_ids.contains_key(&primitive) {
+                self.primitives.push((primitive.clone(), format!("<Circle diameter=\"{}\"/>", decimal(via.diameter))));
+                self.primitive_ids.insert(primitive.clone(), primitive.clone());
+            }
+            self.open_set(xml, via.net_id.as_deref(), Some("VIA"));
+            xml.open("Pad", &[]);
+            xml.empty("Location", &[("x", decimal(via.position.x)), ("y", decimal(-via.position.y))]);
+            xml.empty("StandardPrimitiveRef", &[("id", primitive)]);
+            xml.close("Pad");
+            xml.close("Set");
+        }
+
+        for zone in board.zones.iter().filter(|z| &z.layer == layer && z.is_filled()) {
+            self.open_set(xml, zone.net_id.as_deref(), None);
+            xml.open("Features", &[]);
+            for fill in &zone.fill {
+                let line = self.line_desc(fill.width);
+                if fill.points.len() >= 3 {
+                    xml.open("Contour", &[]);
+                    polygon(xml, &fill.points);
+                    xml.close("Contour");
+                    // The pen width runs around the edge, as in the Gerber output
+                    if fill.width > 0.0 {
+                        let mut ring = fill.points.clone();
+                        ring.push(fill.points[0]);
+                        polyline(xml, &ring, &line);
+                    }
+                } else if fill.points.len() == 2 {
+                    polyline(xml, &fill.points, &line);
+                }
+            }
+            xml.close("Features");
+            xml.close("Set");
+        }
+
+        xml.close("LayerFeature");
+    }
+
+    // Mask and paste openings, and the legend
+    fn surface_features(&mut self, xml: &mut Xml, board: &Board, layer: &LayerType) {
+        xml.open("LayerFeature", &[("layerRef", layer_name(layer))]);
+        for instance in &board.footprints {
+            match layer {
+                LayerType::TopSilkscreen | LayerType::BottomSilkscreen => {
+                    let graphics: Vec<&GraphicElement> = instance.footprint.silkscreen.iter()
+                        .filter(|g| &instance.board_layer(&g.layer) == layer)
+                        .collect();
+                    if graphics.is_empty() {
+                        continue;
+                    }
+                    xml.open("Set", &[]);
+                    xml.open("Features", &[]);
+                    for graphic in graphics {
+                        self.graphic(xml, instance, graphic);
+                    }
+                    xml.close("Features");
+                    xml.close("Set");
+                }
+                _ => {
+                    for pad in &instance.footprint.pads {
+                        if !instance.pad_layers(pad).contains(layer) {
+                            continue;
+                        }
+                        let expansion = match layer {
+                            LayerType::TopPaste | LayerType::BottomPaste => pad.solder_paste_margin,
+                            _ => pad.solder_mask_expansion,
+                        };
+                        xml.open("Set", &[]);
+                        self.pad(xml, instance, pad, expansion, false);
+                        xml.close("Set");
+                    }
+                }
+            }
+        }
+        xml.close("LayerFeature");
+    }
+
+    fn graphic(&mut self, xml: &mut Xml, instance: &FootprintInstance, graphic: &GraphicElement) {
+        let line = self.line_desc(graphic.width.max(0.001));
+        let point = |x: f64, y: f64| instance.transform(FootprintPoint { x, y });
+        match &graphic.element_type {
+            GraphicType::Line { start, end } => {
+                polyline(xml, &[instance.transform(*start), instance.transform(*end)], &line);
+            }
+            GraphicType::Rectangle { top_left, bottom_right } => {
+                polyline(xml, &[
+                    point(top_left.x, top_left.y),
+                    point(bottom_right.x, top_left.y),
+                    point(bottom_right.x, bottom_right.y),
+                    point(top_left.x, bottom_right.y),
+                    point(top_left.x, top_left.y),
+                ], &line);
+            }
+            GraphicType::Polygon { points } => {
+                let mut ring: Vec<FootprintPoint> = points.iter().map(|p| instance.transform(*p)).collect();
+                if let Some(&first) = ring.first() {
+                    ring.push(first);
+                }
+                polyline(xml, &ring, &line);
+            }
+            GraphicType::Circle { center, radius } => {
+                // A bare Circle feature has no location of its own, so draw a closed arc
+                let center = instance.transform(*center);
+                let start = FootprintPoint { x: center.x + radius, y: center.y };
+                xml.open("Arc", &[
+                    ("startX", decimal(start.x)), ("startY", decimal(-start.y)),
+                    ("endX", decimal(start.x)), ("endY", decimal(-start.y)),
+                    ("centerX", decimal(center.x)), ("centerY", decimal(-center.y)),
+                    ("clockwise", "false".to_string()),
+                ]);
+                xml.empty("LineDescRef", &[("id", line)]);
+                xml.close("Arc");
+            }
+            GraphicType::Arc { center, start, angle } => {
+                let (sin, cos) = angle.to_radians().sin_cos();
+                let (dx, dy) = (start.x - center.x, start.y - center.y);
+                let end = FootprintPoint { x: center.x + dx * cos - dy * sin, y: center.y + dx * sin + dy * cos };
+                let (c, s, e) = (instance.transform(*center), instance.transform(*start), instance.transform(end));
+                // Positive board angles turn clockwise once y points up; mirroring reverses that
+                let clockwise = (*angle > 0.0) != (instance.side == PlacementSide::Bottom);
+                xml.open("Arc", &[
+                    ("startX", decimal(s.x)), ("startY", decimal(-s.y)),
+                    ("endX", decimal(e.x)), ("endY", decimal(-e.y)),
+                    ("centerX", decimal(c.x)), ("centerY", decimal(-c.y)),
+                    ("clockwise", clockwise.to_string()),
+                ]);
+                xml.empty("LineDescRef", &[("id", line)]);
+                xml.close("Arc");
+            }
+            GraphicType::Text { .. } => {}
+        }
+    }
+}
+
+// Minimal indenting XML writer; attribute values are escaped
+struct Xml {
+    text: String,
+    depth: usize,
+}
+
+impl Xml {
+    fn new(depth: usize) -> Self {
+        Self { text: String::new(), depth }
+    }
+
+    fn tag(&mut self, name: &str, attributes: &[(&str, String)], end: &str) {
+        self.text.push_str(&"  ".repeat(self.depth));
+        self.text.push('<');
+        self.text.push_str(name);
+        for (key, value) in attributes {
+            self.text.push_str(&format!(" {}=\"{}\"", key, escape_markup(value)));
+        }
+        self.text.push_str(end);
+        self.text.push('\n');
+    }
+
+    fn open(&mut self, name: &str, attributes: &[(&str, String)]) {
+        self.tag(name, attributes, ">");
+        self.depth += 1;
+    }
+
+    fn empty(&mut self, name: &str, attributes: &[(&str, String)]) {
+        self.tag(name, attributes, "/>");
+    }
+
+    fn close(&mut self, name: &str) {
+        self.depth = self.depth.saturating_sub(1);
+        self.text.push_str(&format!("{}</{}>\n", "  ".repeat(self.depth), name));
+    }
+}
+
+// Stackup layer names become identifiers: no blanks
+fn xml_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join("_")
+}
+
+fn copper_side(layer: &LayerType) -> &'static str {
+    match layer {
+        LayerType::TopCopper | LayerType::TopSolderMask => "TOP",
+        LayerType::BottomCopper | LayerType::BottomSolderMask => "BOTTOM",
+        _ => "INTERNAL",
+    }
+}
+
+fn outer_layer(board: &Board, side: PlacementSide) -> LayerType {
+    let copper = board.stackup.copper_layers();
+    let layer = match side {
+        PlacementSide::Top => copper.first(),
+        PlacementSide::Bottom => copper.last(),
+    };
+    layer.cloned().unwrap_or(LayerType::TopCopper)
+}
+
+// Part number per footprint id: the MPN where the schematic has one, else value and footprint
+fn bom_parts(project: &Project, board: &Board) -> HashMap<String, String> {
+    board.footprints.iter()
+        .map(|instance| {
+            let mpn = project.find_component(&instance.component_id)
+                .and_then(|c| c.part_info.mpn.clone())
+                .filter(|m| !m.is_empty());
+            let part = mpn.unwrap_or_else(|| format!("{}_{}", instance.value, instance.footprint_id));
+            (instance.id.clone(), xml_name(&part))
+        })
+        .collect()
+}
+
+fn polygon(xml: &mut Xml, points: &[FootprintPoint]) {
+    contour_element(xml, "Polygon", points);
+}
+
+// A closed contour as `element`, e.g. a profile's `Polygon` or one of its `Cutout`s
+fn contour_element(xml: &mut Xml, element: &str, points: &[FootprintPoint]) {
+    let Some((first, rest)) = points.split_first() else { return };
+    xml.open(element, &[]);
+    xml.empty("PolyBegin", &[("x", decimal(first.x)), ("y", decimal(-first.y))]);
+    for point in rest.iter().chain(std::iter::once(first)) {
+        xml.empty("PolyStepSegment", &[("x", decimal(point.x)), ("y", decimal(-point.y))]);
+    }
+    xml.close(element);
+}
+
+fn polyline(xml: &mut Xml, points: &[FootprintPoint], line: &str) {
+    let Some((first, rest)) = points.split_first() else { return };
+    xml.open("Polyline", &[]);
+    xml.empty("PolyBegin", &[("x", decimal(first.x)), ("y", decimal(-first.y))]);
+    for point in rest {
+        xml.empty("PolyStepSegment", &[("x", decimal(point.x)), ("y", decimal(-point.y))]);
+    }
+    xml.empty("LineDescRef", &[("id", line.to_string())]);
+    xml.close("Polyline");
+}
+
+// Dictionary contour for a polygon pad, keyed by its vertices
+fn contour(corners: &[(f64, f64)]) -> (String, String) {
+    let mut xml = Xml::new(0);
+    xml.open("Contour", &[]);
+    polygon(&mut xml, &corners.iter().map(|&(x, y)| FootprintPoint { x, y }).collect::<Vec<_>>());
+    xml.close("Contour");
+    let key = format!("CONTOUR_{}", corners.iter().map(|(x, y)| format!("{},{}", decimal(*x), decimal(*y))).collect::<Vec<_>>().join(";"));
+    (key, xml.text)
+}
+
+// Obround around a routed slot, traced clockwise in the y-up frame
+fn slot_outline(xml: &mut Xml, start: FootprintPoint, end: FootprintPoint, radius: f64) {
+    let (sx, sy, ex, ey) = (start.x, -start.y, end.x, -end.y);
+    let length = ((ex - sx).powi(2) + (ey - sy).powi(2)).sqrt().max(1e-9);
+    let (nx, ny) = (-(ey - sy) / length * radius, (ex - sx) / length * radius);
+    let point = |x: f64, y: f64| [("x", decimal(x)), ("y", decimal(y))];
+    xml.open("Polygon", &[]);
+    xml.empty("PolyBegin", &point(sx + nx, sy + ny));
+    xml.empty("PolyStepSegment", &point(ex + nx, ey + ny));
+    let mut curve = point(ex - nx, ey - ny).to_vec();
+    curve.extend([("centerX", decimal(ex)), ("centerY", decimal(ey)), ("clockwise", "true".to_string())]);
+    xml.empty("PolyStepCurve", &curve);
+    xml.empty("PolyStepSegment", &point(sx - nx, sy - ny));
+    let mut curve = point(sx + nx, sy + ny).to_vec();
+    curve.extend([("centerX", decimal(sx)), ("centerY", decimal(sy)), ("clockwise", "true".to_string())]);
+    xml.empty("PolyStepCurve", &curve);
+    xml.close("Polygon");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BoardNet, PadSize, Track, Via};
+    use crate::services::FootprintService;
+    use crate::services::test_fixtures::place;
+
+    #[test]
+    fn test_ipc2581_and_d356() {
+        let mut project = Project::new("Test".to_string()).unwrap();
+        let mut board = Board::new("Test Board".to_string()).unwrap();
+        board.set_rectangular_outline(50.0, 40.0).unwrap();
+        board.nets = vec![
+            BoardNet { id: "n1".to_string(), name: "VCC".to_string() },
+            BoardNet { id: "n2".to_string(), name: "A_VERY_LONG_SIGNAL_NAME".to_string() },
+        ];
+        place(&mut board, "R_0805", "R1", 10.0, 10.0, &[("1", "n1"), ("2", "n2")]).value = "10k".to_string();
+        let flipped = place(&mut board, "R_0805", "R2", 20.0, 10.0, &[("1", "n1"), ("2", "n2")]);
+        flipped.value = "10k".to_string();
+        flipped.side = PlacementSide::Bottom;
+        place(&mut board, "DIP-8", "U1", 30.0, 20.0, &[("8", "n1")]).value = "NE555".to_string();
+        board.add_track(Track::new(Some("n1".to_string()), LayerType::TopCopper,
+            FootprintPoint { x: 9.05, y: 10.0 }, FootprintPoint { x: 9.05, y: 25.0 }, 0.25)).unwrap();
+        board.add_via(Via::new(Some("n1".to_string()), FootprintPoint { x: 9.05, y: 25.0 }, 0.6, 0.3)).unwrap();
+        project.board = Some(board);
+
+        let xml = IpcExportService::ipc2581(&project).unwrap().content;
+        assert!(xml.starts_with("<?xml"));
+        assert!(xml.contains("<IPC-2581 revision=\"C\""));
+        assert!(xml.contains("<StackupLayer layerOrGroupRef=\"F_Cu\" thickness=\"0.035\""));
+        assert!(xml.contains("<Layer name=\"Drill_1_2\" layerFunction=\"DRILL\""));
+        assert!(xml.contains("<Component refDes=\"R2\" packageRef=\"R_0805\" layerRef=\"B_Cu\""));
+        assert!(xml.contains("<PinRef componentRef=\"U1\" pin=\"8\"/>"));
+        assert!(xml.contains("<Line startX=\"9.05\" startY=\"-10\" endX=\"9.05\" endY=\"-25\">"));
+        assert!(xml.contains("platingStatus=\"PLATED\""));
+        assert!(xml.contains("<BomItem OEMDesignNumberRef=\"10k_R_0805\" quantity=\"2\""));
+        // Every dictionary reference resolves
+        for id in xml.split("StandardPrimitiveRef id=\"").skip(1).map(|s| &s[..s.find('"').unwrap()]) {
+            assert!(xml.contains(&format!("<EntryStandard id=\"{}\">", id)), "missing primitive {}", id);
+        }
+        for id in xml.split("LineDescRef id=\"").skip(1).map(|s| &s[..s.find('"').unwrap()]) {
+            assert!(xml.contains(&format!("<EntryLineDesc id=\"{}\">", id)), "missing line {}", id);
+        }
+        assert_eq!(xml.matches('<').count() - xml.matches("/>").count() - 1, 2 * xml.matches("</").count(), "balanced tags");
+
+        let d356 = IpcExportService::ipc_d356(project.board.as_ref().unwrap()).unwrap().content;
+        let lines: Vec<&str> = d356.lines().collect();
+        assert!(lines.contains(&"P  NNAME1A_VERY_LONG_SIGNAL_NAME"));
+        let r1 = lines.iter().find(|l| l.starts_with("327VCC") && l[20..26].trim() == "R1").unwrap();
+        assert_eq!(&r1[26..31], "-1   ");
+        assert_eq!(&r1[38..57], "A01X+009050Y-010000");
+        // Top SMD pads are open on the primary side and covered on the secondary
+        assert!(r1.ends_with("S2"));
+        assert!(lines.iter().any(|l| l.starts_with("327NNAME1") && l[20..26].trim() == "R2" && &l[38..41] == "A02"));
+        assert!(lines.iter().any(|l| l.starts_with("317VCC") && l[20..26].trim() == "U1" && &l[32..38] == "D0800P"));
+        assert!(lines.iter().any(|l| l.starts_with("317VCC") && l[20..26].trim() == "VIA" && l.ends_with("S3")));
+        assert!(lines.iter().filter(|l| l.starts_with('3')).all(|l| l.len() == 74));
+        assert_eq!(lines.last(), Some(&"999"));
+    }
+
+    #[test]
+    fn test_polygon_pad_primitives() {
+        let board = Board::new("Test Board".to_string()).unwrap();
+        let mut writer = Ipc2581Writer::new(&board);
+        let element = |writer: &Ipc2581Writer, id: &str| writer.primitives.iter().find(|(i, _)| i == id).unwrap().1.clone();
+        let mut pad = FootprintService::new().get_footprint("R_0805").unwrap().pads[0].clone();
+
+        // Slanted sides move out by the expansion too, not just the width
+        pad.size = PadSize { width: 1.0, height: 1.45 };
+        pad.shape = PadShape::Trapezoid { delta: 0.4 };
+        let id = writer.primitive(&pad, 0.0);
+        assert!(element(&writer, &id).contains("x=\"0.7\" y=\"0.725\""));
+        let id = writer.primitive(&pad, 0.1);
+        let trapezoid = element(&writer, &id);
+        assert!(trapezoid.contains("x=\"0.831321\" y=\"0.825\""), "{}", trapezoid);
+        assert!(trapezoid.contains("x=\"-0.376149\" y=\"-0.825\""), "{}", trapezoid);
+
+        pad.shape = PadShape::Custom(vec![
+            FootprintPoint { x: -0.5, y: -0.5 }, FootprintPoint { x: 0.5, y: -0.5 },
+            FootprintPoint { x: 0.5, y: 0.5 }, FootprintPoint { x: -0.5, y: 0.5 },
+        ]);
+        let id = writer.primitive(&pad, 0.1);
+        let custom = element(&writer, &id);
+        assert!(custom.contains("x=\"0.6\" y=\"0.6\"") && custom.contains("x=\"-0.6\" y=\"-0.6\""), "{}", custom);
+        assert!(!custom.contains("0.5\""));
+    }
+}
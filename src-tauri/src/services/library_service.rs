@@ -1,22 +1,59 @@
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
-use crate::models::component::{ComponentLibrary, ComponentTemplate, ComponentCategory, ComponentSymbol, DrawCommand, DrawCommandType, DrawStyle, ParameterTemplate, ParameterType, PinTemplate, SymbolGraphics, GraphicsBounds};
-use crate::models::{PinType, ElectricalType};
-use crate::utils::error::Result;
+use crate::models::component::{ComponentLibrary, ComponentTemplate, ComponentCategory, ComponentSymbol};
+use crate::models::{CachedTemplate, Project, Schematic};
+use crate::utils::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use super::file_service::FileService;
+use super::component_search::{ComponentSearchIndex, SearchQuery};
+
+pub const STANDARD_LIBRARY_ID: &str = "standard";
+const LIBRARY_CONFIG_FILE: &str = "library_paths.json";
+const MAX_SEARCH_RESULTS: usize = 200;
+
+// Parts shipped with the application, kept as data so they can be edited without touching code
+const STANDARD_LIBRARY_JSON: &str = include_str!("../../library/standard.json");
+
+// Layout of the standard library file; components are a list so a repeated id is caught on load
+#[derive(Deserialize)]
+struct StandardLibraryFile {
+    name: String,
+    version: String,
+    description: Option<String>,
+    categories: Vec<ComponentCategory>,
+    components: Vec<ComponentTemplate>,
+}
+
+// Where an editable library lives
+#[derive(Debug, Clone)]
+enum LibraryOrigin {
+    UserFile(PathBuf),
+    Project(String),
+}
 
-use super::extended_components;
-use super::logic_gates;
-use super::integrated_circuits;
-use super::sensors_power;
+/// Project after a rescue, with the ids of the templates copied into its local library.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RescueResult {
+    pub project: Project,
+    pub rescued: Vec<String>,
+}
 
 pub struct LibraryService {
     libraries: Arc<Mutex<HashMap<String, ComponentLibrary>>>,
+    origins: Arc<Mutex<HashMap<String, LibraryOrigin>>>,
+    library_paths: Arc<Mutex<Vec<PathBuf>>>,
+    search_index: Arc<Mutex<ComponentSearchIndex>>,
 }
 
 impl LibraryService {
     pub fn new() -> Self {
         let service = Self {
             libraries: Arc::new(Mutex::new(HashMap::new())),
+            origins: Arc::new(Mutex::new(HashMap::new())),
+            library_paths: Arc::new(Mutex::new(Self::load_library_paths_config())),
+            search_index: Arc::new(Mutex::new(ComponentSearchIndex::default())),
         };
         
         // Initialize with standard library
@@ -24,631 +61,486 @@ impl LibraryService {
             eprintln!("Failed to create standard library: {:?}", e);
         }
         
+        if let Err(e) = service.load_user_libraries() {
+            eprintln!("Failed to load user libraries: {:?}", e);
+        }
+        
         service
     }
 
-    pub fn create_standard_library(&self) -> Result<()> {
-        let mut standard_lib = ComponentLibrary::new("Standard Electronics".to_string())?;
-        
-        // Add categories
-        let categories = vec![
-            ComponentCategory {
-                id: "passive".to_string(),
-                name: "Passive Components".to_string(),
-                parent_id: None,
-                description: Some("Resistors, capacitors, inductors".to_string()),
-                color: Some("#4CAF50".to_string()),
-            },
-            ComponentCategory {
-                id: "active".to_string(),
-                name: "Active Components".to_string(),
-                parent_id: None,
-                description: Some("Transistors, ICs, semiconductors".to_string()),
-                color: Some("#2196F3".to_string()),
-            },
-            ComponentCategory {
-                id: "connector".to_string(),
-                name: "Connectors".to_string(),
-                parent_id: None,
-                description: Some("Headers, terminals, connectors".to_string()),
-                color: Some("#FF9800".to_string()),
-            },
-            ComponentCategory {
-                id: "power".to_string(),
-                name: "Power Components".to_string(),
-                parent_id: None,
-                description: Some("Voltage regulators, power supplies".to_string()),
-                color: Some("#F44336".to_string()),
-            },
-            ComponentCategory {
-                id: "digital".to_string(),
-                name: "Digital Logic".to_string(),
-                parent_id: None,
-                description: Some("Logic gates, flip-flops, counters".to_string()),
-                color: Some("#9C27B0".to_string()),
-            },
-        ];
+    fn config_file() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("rust-eda").join(LIBRARY_CONFIG_FILE))
+    }
+
+    fn default_library_paths() -> Vec<PathBuf> {
+        dirs::data_dir()
+            .map(|dir| vec![dir.join("rust-eda").join("libraries")])
+            .unwrap_or_default()
+    }
+
+    fn load_library_paths_config() -> Vec<PathBuf> {
+        Self::config_file()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_else(Self::default_library_paths)
+    }
 
-        for category in categories {
-            standard_lib.add_category(category)?;
+    pub fn get_library_paths(&self) -> Vec<PathBuf> {
+        match self.library_paths.lock() {
+            Ok(paths) => paths.clone(),
+            Err(_) => {
+                eprintln!("Warning: Mutex poisoned in get_library_paths, returning empty list");
+                Vec::new()
+            }
         }
+    }
 
-        // Add basic components
-        self.add_resistor_template(&mut standard_lib)?;
-        self.add_capacitor_template(&mut standard_lib)?;
-        self.add_led_template(&mut standard_lib)?;
-        self.add_op_amp_template(&mut standard_lib)?;
-        self.add_connector_template(&mut standard_lib)?;
-        
-        // Add extended components (50+ total)
-        extended_components::add_all_extended_components(&mut standard_lib)?;
-        logic_gates::add_logic_gates(&mut standard_lib)?;
-        integrated_circuits::add_integrated_circuits(&mut standard_lib)?;
-        sensors_power::add_sensors(&mut standard_lib)?;
-        sensors_power::add_power_components(&mut standard_lib)?;
-        sensors_power::add_display_components(&mut standard_lib)?;
+    /// Replaces the library search path list, persists it and reloads user libraries from it.
+    pub fn set_library_paths(&self, paths: Vec<PathBuf>) -> Result<()> {
+        for path in &paths {
+            if !path.is_absolute() || path.to_string_lossy().contains('\0') {
+                return Err(AppError::InvalidInput(format!("Invalid library path: {}", path.display())));
+            }
+        }
 
-        // Store the library
+        if let Some(config) = Self::config_file() {
+            FileService::write_atomic(&config, serde_json::to_string_pretty(&paths)?.as_bytes())?;
+        }
+
+        *self.library_paths.lock()
+            .map_err(|_| AppError::ThreadError("Mutex poisoned in set_library_paths".to_string()))? = paths;
+        self.load_user_libraries()
+    }
+
+    /// Loads every `*.json` library file found in the library paths, replacing any
+    /// user libraries loaded earlier. Unreadable files are reported and skipped.
+    pub fn load_user_libraries(&self) -> Result<()> {
+        let paths = self.get_library_paths();
         let mut libraries = self.libraries.lock()
-            .map_err(|_| crate::utils::error::AppError::ThreadError("Mutex poisoned in initialize_standard_library".to_string()))?;
-        libraries.insert(standard_lib.id.clone(), standard_lib);
-        
+            .map_err(|_| AppError::ThreadError("Mutex poisoned in load_user_libraries".to_string()))?;
+        let mut origins = self.origins.lock()
+            .map_err(|_| AppError::ThreadError("Mutex poisoned in load_user_libraries".to_string()))?;
+
+        let stale: Vec<String> = origins.iter()
+            .filter(|(_, origin)| matches!(origin, LibraryOrigin::UserFile(_)))
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in stale {
+            origins.remove(&id);
+            libraries.remove(&id);
+        }
+
+        for dir in paths {
+            let entries = match std::fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(_) => continue,
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                    continue;
+                }
+                match Self::read_library_file(&path) {
+                    Ok(library) if libraries.contains_key(&library.id) => {
+                        eprintln!("Warning: duplicate library id {} in {}, skipping", library.id, path.display());
+                    }
+                    Ok(library) => {
+                        origins.insert(library.id.clone(), LibraryOrigin::UserFile(path));
+                        libraries.insert(library.id.clone(), library);
+                    }
+                    Err(e) => eprintln!("Warning: failed to load library {}: {}", path.display(), e),
+                }
+            }
+        }
+
+        drop(libraries);
+        drop(origins);
+        self.rebuild_search_index();
         Ok(())
     }
 
-    fn add_resistor_template(&self, library: &mut ComponentLibrary) -> Result<()> {
-        let symbol = ComponentSymbol {
-            width: 60.0,
-            height: 20.0,
-            draw_commands: vec![
-                // IEEE Standard Zigzag Resistor Symbol
-                DrawCommand {
-                    command_type: DrawCommandType::Line,
-                    parameters: vec![-30.0, 0.0, -15.0, 0.0],
-                    style: Some(DrawStyle {
-                        stroke_width: 2.0,
-                        stroke_color: "#000000".to_string(),
-                        fill_color: None,
-                    }),
-                },
-                DrawCommand {
-                    command_type: DrawCommandType::Line,
-                    parameters: vec![-15.0, 0.0, -12.0, -8.0],
-                    style: Some(DrawStyle {
-                        stroke_width: 2.0,
-                        stroke_color: "#000000".to_string(),
-                        fill_color: None,
-                    }),
-                },
-                DrawCommand {
-                    command_type: DrawCommandType::Line,
-                    parameters: vec![-12.0, -8.0, -6.0, 8.0],
-                    style: Some(DrawStyle {
-                        stroke_width: 2.0,
-                        stroke_color: "#000000".to_string(),
-                        fill_color: None,
-                    }),
-                },
-                DrawCommand {
-                    command_type: DrawCommandType::Line,
-                    parameters: vec![-6.0, 8.0, 0.0, -8.0],
-                    style: Some(DrawStyle {
-                        stroke_width: 2.0,
-                        stroke_color: "#000000".to_string(),
-                        fill_color: None,
-                    }),
-                },
-                DrawCommand {
-                    command_type: DrawCommandType::Line,
-                    parameters: vec![0.0, -8.0, 6.0, 8.0],
-                    style: Some(DrawStyle {
-                        stroke_width: 2.0,
-                        stroke_color: "#000000".to_string(),
-                        fill_color: None,
-                    }),
-                },
-                DrawCommand {
-                    command_type: DrawCommandType::Line,
-                    parameters: vec![6.0, 8.0, 12.0, -8.0],
-                    style: Some(DrawStyle {
-                        stroke_width: 2.0,
-                        stroke_color: "#000000".to_string(),
-                        fill_color: None,
-                    }),
-                },
-                DrawCommand {
-                    command_type: DrawCommandType::Line,
-                    parameters: vec![12.0, -8.0, 15.0, 0.0],
-                    style: Some(DrawStyle {
-                        stroke_width: 2.0,
-                        stroke_color: "#000000".to_string(),
-                        fill_color: None,
-                    }),
-                },
-                DrawCommand {
-                    command_type: DrawCommandType::Line,
-                    parameters: vec![15.0, 0.0, 30.0, 0.0],
-                    style: Some(DrawStyle {
-                        stroke_width: 2.0,
-                        stroke_color: "#000000".to_string(),
-                        fill_color: None,
-                    }),
-                },
-                // Value text
-                DrawCommand {
-                    command_type: DrawCommandType::Text,
-                    parameters: vec![0.0, 15.0, 10.0],
-                    style: Some(DrawStyle {
-                        stroke_width: 1.0,
-                        stroke_color: "#000000".to_string(),
-                        fill_color: None,
-                    }),
-                },
-            ],
-            graphics: Some(SymbolGraphics {
-                bounds: GraphicsBounds { width: 60.0, height: 20.0 }
-            }),
+    // Called after every change to the loaded libraries; never while `libraries` is locked
+    fn rebuild_search_index(&self) {
+        let index = match self.libraries.lock() {
+            Ok(libraries) => {
+                // Derived parts are searchable by what they inherit
+                let resolved: Vec<ComponentLibrary> = libraries.values()
+                    .map(|library| resolve_library(&libraries, library))
+                    .collect();
+                ComponentSearchIndex::build(resolved.iter())
+            }
+            Err(_) => {
+                eprintln!("Warning: Mutex poisoned in rebuild_search_index, keeping previous index");
+                return;
+            }
         };
+        match self.search_index.lock() {
+            Ok(mut search_index) => *search_index = index,
+            Err(_) => eprintln!("Warning: Mutex poisoned in rebuild_search_index, keeping previous index"),
+        }
+    }
 
-        let mut template = ComponentTemplate::new(
-            "Resistor".to_string(),
-            "passive".to_string(),
-            symbol,
-        )?;
-
-        // Add parameters
-        template.add_parameter("resistance".to_string(), ParameterTemplate {
-            name: "Resistance".to_string(),
-            parameter_type: ParameterType::Number,
-            default_value: serde_json::Value::Number(serde_json::Number::from(1000)),
-            min_value: Some(0.1),
-            max_value: Some(1e12),
-            unit: Some("Ω".to_string()),
-            description: Some("Resistance value in ohms".to_string()),
-            required: true,
-        })?;
+    fn read_library_file(path: &Path) -> Result<ComponentLibrary> {
+        let json = std::fs::read_to_string(path)?;
+        serde_json::from_str(&json).map_err(|e| AppError::InvalidFormat(e.to_string()))
+    }
 
-        template.add_parameter("tolerance".to_string(), ParameterTemplate {
-            name: "Tolerance".to_string(),
-            parameter_type: ParameterType::Choice(vec!["1%".to_string(), "5%".to_string(), "10%".to_string()]),
-            default_value: serde_json::Value::String("5%".to_string()),
-            min_value: None,
-            max_value: None,
-            unit: None,
-            description: Some("Resistance tolerance".to_string()),
-            required: false,
-        })?;
+    /// Makes a project's embedded library available for lookups and edits.
+    pub fn mount_project_library(&self, project: &Project) -> Result<()> {
+        let library = match &project.local_library {
+            Some(library) => library.clone(),
+            None => return Ok(()),
+        };
+        let mut libraries = self.libraries.lock()
+            .map_err(|_| AppError::ThreadError("Mutex poisoned in mount_project_library".to_string()))?;
+        let mut origins = self.origins.lock()
+            .map_err(|_| AppError::ThreadError("Mutex poisoned in mount_project_library".to_string()))?;
+        origins.insert(library.id.clone(), LibraryOrigin::Project(project.id.clone()));
+        libraries.insert(library.id.clone(), library);
+        drop(libraries);
+        drop(origins);
+        self.rebuild_search_index();
+        Ok(())
+    }
 
-        // Add pins
-        template.add_pin(PinTemplate {
-            id: "pin1".to_string(),
-            name: "1".to_string(),
-            number: "1".to_string(),
-            x: -30.0,
-            y: 0.0,
-            pin_type: PinType::Passive,
-            electrical: ElectricalType {
-                voltage: None,
-                current: None,
-                impedance: None,
-            },
-        })?;
+    /// Copies the in-memory project library back into the project before it is written out.
+    pub fn sync_project_library(&self, project: &mut Project) -> Result<()> {
+        let libraries = self.libraries.lock()
+            .map_err(|_| AppError::ThreadError("Mutex poisoned in sync_project_library".to_string()))?;
+        let origins = self.origins.lock()
+            .map_err(|_| AppError::ThreadError("Mutex poisoned in sync_project_library".to_string()))?;
+        // A library that was never mounted here (or failed to mount) stays as it was saved
+        let mounted = origins.iter()
+            .find(|(_, origin)| matches!(origin, LibraryOrigin::Project(id) if id == &project.id))
+            .and_then(|(library_id, _)| libraries.get(library_id).cloned());
+        if let Some(library) = mounted {
+            project.local_library = Some(library);
+        }
+        Ok(())
+    }
 
-        template.add_pin(PinTemplate {
-            id: "pin2".to_string(),
-            name: "2".to_string(),
-            number: "2".to_string(),
-            x: 30.0,
-            y: 0.0,
-            pin_type: PinType::Passive,
-            electrical: ElectricalType {
-                voltage: None,
-                current: None,
-                impedance: None,
-            },
-        })?;
+    /// Rebuilds the project's symbol cache from the templates its components use.
+    /// Entries for libraries that aren't available here are kept as they were.
+    pub fn refresh_symbol_cache(&self, project: &mut Project) {
+        project.symbol_cache = project.used_templates().into_iter()
+            .filter_map(|(library_id, component_id)| {
+                let template = self.get_component_template(&library_id, &component_id)
+                    .or_else(|| project.cached_template(&library_id, &component_id).cloned())?;
+                Some(CachedTemplate { library_id, template })
+            })
+            .collect();
+    }
 
-        template.keywords = vec!["resistor".to_string(), "R".to_string(), "ohm".to_string()];
-        
-        library.add_component_template(template)?;
-        Ok(())
+    /// Looks a template up in the loaded libraries, falling back to the project's cache.
+    pub fn get_project_template(&self, project: &Project, library_id: &str, component_id: &str) -> Option<ComponentTemplate> {
+        self.get_component_template(library_id, component_id)
+            .or_else(|| project.cached_template(library_id, component_id).cloned())
     }
 
-    fn add_capacitor_template(&self, library: &mut ComponentLibrary) -> Result<()> {
-        let symbol = ComponentSymbol {
-            width: 30.0,
-            height: 30.0,
-            draw_commands: vec![
-                DrawCommand {
-                    command_type: DrawCommandType::Line,
-                    parameters: vec![-5.0, -15.0, -5.0, 15.0],
-                    style: Some(DrawStyle {
-                        stroke_width: 3.0,
-                        stroke_color: "#000000".to_string(),
-                        fill_color: None,
-                    }),
-                },
-                DrawCommand {
-                    command_type: DrawCommandType::Line,
-                    parameters: vec![5.0, -15.0, 5.0, 15.0],
-                    style: Some(DrawStyle {
-                        stroke_width: 3.0,
-                        stroke_color: "#000000".to_string(),
-                        fill_color: None,
-                    }),
-                },
-                DrawCommand {
-                    command_type: DrawCommandType::Line,
-                    parameters: vec![-20.0, 0.0, -5.0, 0.0],
-                    style: Some(DrawStyle {
-                        stroke_width: 2.0,
-                        stroke_color: "#000000".to_string(),
-                        fill_color: None,
-                    }),
-                },
-                DrawCommand {
-                    command_type: DrawCommandType::Line,
-                    parameters: vec![5.0, 0.0, 20.0, 0.0],
-                    style: Some(DrawStyle {
-                        stroke_width: 2.0,
-                        stroke_color: "#000000".to_string(),
-                        fill_color: None,
-                    }),
-                },
-            ],
-            graphics: Some(SymbolGraphics {
-                bounds: GraphicsBounds { width: 40.0, height: 20.0 }
-            }),
-        };
+    /// Symbol to draw for each component of a schematic, keyed by component id;
+    /// multi-unit parts get their unit's symbol.
+    pub fn component_symbols(&self, project: &Project, schematic: &Schematic) -> HashMap<String, ComponentSymbol> {
+        schematic.components.iter()
+            .filter_map(|component| {
+                let template = self.get_project_template(project, component.library_id.as_ref()?, &component.type_id)?;
+                let symbol = component.unit.as_ref()
+                    .and_then(|unit| template.get_unit(unit))
+                    .map(|unit| unit.symbol.clone())
+                    .unwrap_or(template.symbol);
+                Some((component.id.clone(), symbol))
+            })
+            .collect()
+    }
 
-        let mut template = ComponentTemplate::new(
-            "Capacitor".to_string(),
-            "passive".to_string(),
-            symbol,
-        )?;
-
-        template.add_parameter("capacitance".to_string(), ParameterTemplate {
-            name: "Capacitance".to_string(),
-            parameter_type: ParameterType::Number,
-            default_value: serde_json::Value::Number(
-                serde_json::Number::from_f64(1e-6)
-                    .ok_or_else(|| crate::utils::error::AppError::InvalidInput("Invalid capacitance value".to_string()))?
-            ),
-            min_value: Some(1e-15),
-            max_value: Some(1.0),
-            unit: Some("F".to_string()),
-            description: Some("Capacitance value in farads".to_string()),
-            required: true,
-        })?;
+    /// Copies cached templates whose library is missing into the project-local library
+    /// (creating it if needed) and points the affected components at it.
+    /// Returns the ids of the rescued templates.
+    pub fn rescue_cached_templates(&self, project: &mut Project) -> Result<Vec<String>> {
+        let missing: Vec<CachedTemplate> = project.symbol_cache.iter()
+            .filter(|c| self.get_component_template(&c.library_id, &c.template.id).is_none())
+            .cloned()
+            .collect();
+        if missing.is_empty() {
+            return Ok(Vec::new());
+        }
 
-        template.add_parameter("voltage_rating".to_string(), ParameterTemplate {
-            name: "Voltage Rating".to_string(),
-            parameter_type: ParameterType::Number,
-            default_value: serde_json::Value::Number(serde_json::Number::from(16)),
-            min_value: Some(1.0),
-            max_value: Some(1000.0),
-            unit: Some("V".to_string()),
-            description: Some("Maximum voltage rating".to_string()),
-            required: false,
-        })?;
+        self.mount_project_library(project)?;
+        let local_id = match self.project_library_id(&project.id)? {
+            Some(id) => id,
+            None => self.create_library(format!("{} (rescued)", project.name), None, Some(project.id.clone()))?.id,
+        };
 
-        // Add pins
-        template.add_pin(PinTemplate {
-            id: "pin1".to_string(),
-            name: "1".to_string(),
-            number: "1".to_string(),
-            x: -20.0,
-            y: 0.0,
-            pin_type: PinType::Passive,
-            electrical: ElectricalType {
-                voltage: None,
-                current: None,
-                impedance: None,
-            },
-        })?;
+        let mut rescued = Vec::new();
+        for cached in &missing {
+            // Cached templates are already resolved, so they no longer need their base
+            let mut template = cached.template.clone();
+            template.extends = None;
+            if self.get_component_template(&local_id, &template.id).is_none() {
+                let template = template.clone();
+                self.edit_library(&local_id, |library| {
+                    // The original category may have lived only in the missing library
+                    if !library.categories.iter().any(|c| c.id == template.category_id) {
+                        library.add_category(ComponentCategory {
+                            id: template.category_id.clone(),
+                            name: template.category_id.clone(),
+                            parent_id: None,
+                            description: None,
+                            color: None,
+                        })?;
+                    }
+                    library.add_component_template(template)
+                })?;
+            }
+            for component in project.schematics.iter_mut().flat_map(|s| s.components.iter_mut()) {
+                if component.library_id.as_deref() == Some(cached.library_id.as_str()) && component.type_id == template.id {
+                    component.library_id = Some(local_id.clone());
+                }
+            }
+            rescued.push(template.id);
+        }
 
-        template.add_pin(PinTemplate {
-            id: "pin2".to_string(),
-            name: "2".to_string(),
-            number: "2".to_string(),
-            x: 20.0,
-            y: 0.0,
-            pin_type: PinType::Passive,
-            electrical: ElectricalType {
-                voltage: None,
-                current: None,
-                impedance: None,
-            },
-        })?;
+        self.sync_project_library(project)?;
+        self.refresh_symbol_cache(project);
+        project.update_modified();
+        Ok(rescued)
+    }
 
-        template.keywords = vec!["capacitor".to_string(), "C".to_string(), "cap".to_string()];
-        
-        library.add_component_template(template)?;
-        Ok(())
+    fn project_library_id(&self, project_id: &str) -> Result<Option<String>> {
+        let origins = self.origins.lock()
+            .map_err(|_| AppError::ThreadError("Mutex poisoned in project_library_id".to_string()))?;
+        Ok(origins.iter()
+            .find(|(_, origin)| matches!(origin, LibraryOrigin::Project(id) if id == project_id))
+            .map(|(library_id, _)| library_id.clone()))
     }
 
-    fn add_led_template(&self, library: &mut ComponentLibrary) -> Result<()> {
-        let symbol = ComponentSymbol {
-            width: 40.0,
-            height: 30.0,
-            draw_commands: vec![
-                // Triangle (diode)
-                DrawCommand {
-                    command_type: DrawCommandType::Polygon,
-                    parameters: vec![-5.0, -10.0, -5.0, 10.0, 5.0, 0.0],
-                    style: Some(DrawStyle {
-                        stroke_width: 2.0,
-                        stroke_color: "#000000".to_string(),
-                        fill_color: Some("#FF0000".to_string()),
-                    }),
-                },
-                // Cathode line
-                DrawCommand {
-                    command_type: DrawCommandType::Line,
-                    parameters: vec![5.0, -10.0, 5.0, 10.0],
-                    style: Some(DrawStyle {
-                        stroke_width: 2.0,
-                        stroke_color: "#000000".to_string(),
-                        fill_color: None,
-                    }),
-                },
-                // Anode line
-                DrawCommand {
-                    command_type: DrawCommandType::Line,
-                    parameters: vec![-20.0, 0.0, -5.0, 0.0],
-                    style: Some(DrawStyle {
-                        stroke_width: 2.0,
-                        stroke_color: "#000000".to_string(),
-                        fill_color: None,
-                    }),
-                },
-                // Cathode line
-                DrawCommand {
-                    command_type: DrawCommandType::Line,
-                    parameters: vec![5.0, 0.0, 20.0, 0.0],
-                    style: Some(DrawStyle {
-                        stroke_width: 2.0,
-                        stroke_color: "#000000".to_string(),
-                        fill_color: None,
-                    }),
-                },
-            ],
-            graphics: Some(SymbolGraphics {
-                bounds: GraphicsBounds { width: 40.0, height: 20.0 }
-            }),
+    /// Creates an empty library, either as a file in the first library path or,
+    /// when `project_id` is given, as the project-local library.
+    pub fn create_library(&self, name: String, description: Option<String>, project_id: Option<String>) -> Result<ComponentLibrary> {
+        let mut library = ComponentLibrary::new(name)?;
+        library.description = description;
+
+        // New libraries share the standard categories so parts can be filed right away
+        if let Some(standard) = self.get_library(STANDARD_LIBRARY_ID) {
+            for category in standard.categories {
+                library.add_category(category)?;
+            }
+        }
+
+        let origin = match project_id {
+            Some(project_id) => {
+                let origins = self.origins.lock()
+                    .map_err(|_| AppError::ThreadError("Mutex poisoned in create_library".to_string()))?;
+                if origins.values().any(|o| matches!(o, LibraryOrigin::Project(id) if id == &project_id)) {
+                    return Err(AppError::InvalidOperation("Project already has a local library".to_string()));
+                }
+                LibraryOrigin::Project(project_id)
+            }
+            None => {
+                let dir = self.get_library_paths().into_iter().next()
+                    .ok_or_else(|| AppError::InvalidOperation("No library path configured".to_string()))?;
+                LibraryOrigin::UserFile(dir.join(format!("{}.json", library.id)))
+            }
         };
 
-        let mut template = ComponentTemplate::new(
-            "LED".to_string(),
-            "active".to_string(),
-            symbol,
-        )?;
-
-        template.add_parameter("forward_voltage".to_string(), ParameterTemplate {
-            name: "Forward Voltage".to_string(),
-            parameter_type: ParameterType::Number,
-            default_value: serde_json::Value::Number(
-                serde_json::Number::from_f64(2.1)
-                    .ok_or_else(|| crate::utils::error::AppError::InvalidInput("Invalid voltage value".to_string()))?
-            ),
-            min_value: Some(1.0),
-            max_value: Some(5.0),
-            unit: Some("V".to_string()),
-            description: Some("Forward voltage drop".to_string()),
-            required: false,
-        })?;
+        self.origins.lock()
+            .map_err(|_| AppError::ThreadError("Mutex poisoned in create_library".to_string()))?
+            .insert(library.id.clone(), origin);
+        if let Err(e) = self.persist_library(&library) {
+            self.origins.lock()
+                .map_err(|_| AppError::ThreadError("Mutex poisoned in create_library".to_string()))?
+                .remove(&library.id);
+            return Err(e);
+        }
+        self.libraries.lock()
+            .map_err(|_| AppError::ThreadError("Mutex poisoned in create_library".to_string()))?
+            .insert(library.id.clone(), library.clone());
+        self.rebuild_search_index();
 
-        template.add_parameter("color".to_string(), ParameterTemplate {
-            name: "Color".to_string(),
-            parameter_type: ParameterType::Choice(vec![
-                "Red".to_string(), 
-                "Green".to_string(), 
-                "Blue".to_string(), 
-                "Yellow".to_string(), 
-                "White".to_string()
-            ]),
-            default_value: serde_json::Value::String("Red".to_string()),
-            min_value: None,
-            max_value: None,
-            unit: None,
-            description: Some("LED color".to_string()),
-            required: false,
-        })?;
+        Ok(library)
+    }
 
-        // Add pins
-        template.add_pin(PinTemplate {
-            id: "anode".to_string(),
-            name: "Anode".to_string(),
-            number: "1".to_string(),
-            x: -20.0,
-            y: 0.0,
-            pin_type: PinType::Input,
-            electrical: ElectricalType {
-                voltage: Some(3.3),
-                current: Some(0.02),
-                impedance: None,
-            },
-        })?;
+    pub fn rename_library(&self, library_id: &str, name: String) -> Result<ComponentLibrary> {
+        crate::utils::validators::validate_string_length_range(&name, 1, 100)?;
+        self.edit_library(library_id, |library| {
+            library.name = name;
+            Ok(())
+        })
+    }
 
-        template.add_pin(PinTemplate {
-            id: "cathode".to_string(),
-            name: "Cathode".to_string(),
-            number: "2".to_string(),
-            x: 20.0,
-            y: 0.0,
-            pin_type: PinType::Output,
-            electrical: ElectricalType {
-                voltage: Some(0.0),
-                current: Some(0.02),
-                impedance: None,
-            },
-        })?;
+    pub fn delete_library(&self, library_id: &str) -> Result<()> {
+        let origin = self.editable_origin(library_id)?;
+        if let LibraryOrigin::UserFile(path) = &origin {
+            if path.exists() {
+                std::fs::remove_file(path)?;
+            }
+        }
 
-        template.keywords = vec!["led".to_string(), "diode".to_string(), "light".to_string()];
-        
-        library.add_component_template(template)?;
+        self.origins.lock()
+            .map_err(|_| AppError::ThreadError("Mutex poisoned in delete_library".to_string()))?
+            .remove(library_id);
+        self.libraries.lock()
+            .map_err(|_| AppError::ThreadError("Mutex poisoned in delete_library".to_string()))?
+            .remove(library_id);
+        self.rebuild_search_index();
         Ok(())
     }
 
-    fn add_op_amp_template(&self, library: &mut ComponentLibrary) -> Result<()> {
-        let symbol = ComponentSymbol {
-            width: 60.0,
-            height: 40.0,
-            draw_commands: vec![
-                // Triangle body
-                DrawCommand {
-                    command_type: DrawCommandType::Polygon,
-                    parameters: vec![-20.0, -20.0, -20.0, 20.0, 20.0, 0.0],
-                    style: Some(DrawStyle {
-                        stroke_width: 2.0,
-                        stroke_color: "#000000".to_string(),
-                        fill_color: Some("#FFFFFF".to_string()),
-                    }),
-                },
-                // + symbol
-                DrawCommand {
-                    command_type: DrawCommandType::Text,
-                    parameters: vec![-10.0, -8.0],
-                    style: None,
-                },
-                // - symbol  
-                DrawCommand {
-                    command_type: DrawCommandType::Text,
-                    parameters: vec![-10.0, 8.0],
-                    style: None,
-                },
-            ],
-            graphics: Some(SymbolGraphics {
-                bounds: GraphicsBounds { width: 40.0, height: 20.0 }
-            }),
+    pub fn add_component_template(&self, library_id: &str, template: ComponentTemplate) -> Result<ComponentLibrary> {
+        self.check_inheritance(library_id, &template)?;
+        self.edit_library(library_id, |library| {
+            if library.components.contains_key(&template.id) {
+                return Err(AppError::InvalidOperation(format!("Component {} already exists", template.id)));
+            }
+            library.add_component_template(template)
+        })
+    }
+
+    pub fn update_component_template(&self, library_id: &str, template: ComponentTemplate) -> Result<ComponentLibrary> {
+        self.check_inheritance(library_id, &template)?;
+        self.edit_library(library_id, |library| library.update_component_template(template))
+    }
+
+    pub fn remove_component_template(&self, library_id: &str, component_id: &str) -> Result<ComponentLibrary> {
+        if let Some((dependent_library, dependent)) = self.find_derived_template(library_id, component_id)? {
+            return Err(AppError::InvalidOperation(format!(
+                "Component {} is the base of {} in library {}", component_id, dependent, dependent_library
+            )));
+        }
+        self.edit_library(library_id, |library| {
+            library.remove_component_template(component_id)
+                .map(|_| ())
+                .ok_or_else(|| AppError::ComponentNotFound(component_id.to_string()))
+        })
+    }
+
+    // Rejects a template whose base is missing or whose chain would loop back on itself
+    fn check_inheritance(&self, library_id: &str, template: &ComponentTemplate) -> Result<()> {
+        if template.extends.is_none() {
+            return Ok(());
+        }
+        let mut libraries = self.libraries.lock()
+            .map_err(|_| AppError::ThreadError("Mutex poisoned in check_inheritance".to_string()))?;
+        let library = libraries.get_mut(library_id)
+            .ok_or_else(|| AppError::InvalidInput(format!("Library not found: {}", library_id)))?;
+
+        // Resolve against the library as it would look after the edit, then put it back
+        let previous = library.components.insert(template.id.clone(), template.clone());
+        let result = resolve_template(&libraries, library_id, template).map(|_| ());
+        let library = libraries.get_mut(library_id)
+            .ok_or_else(|| AppError::InvalidInput(format!("Library not found: {}", library_id)))?;
+        match previous {
+            Some(previous) => library.components.insert(template.id.clone(), previous),
+            None => library.components.remove(&template.id),
         };
+        result
+    }
 
-        let mut template = ComponentTemplate::new(
-            "Op-Amp".to_string(),
-            "active".to_string(),
-            symbol,
-        )?;
-
-        template.add_parameter("gain".to_string(), ParameterTemplate {
-            name: "Gain".to_string(),
-            parameter_type: ParameterType::Number,
-            default_value: serde_json::Value::Number(serde_json::Number::from(100000)),
-            min_value: Some(1.0),
-            max_value: Some(1e9),
-            unit: Some("V/V".to_string()),
-            description: Some("Open loop gain".to_string()),
-            required: false,
-        })?;
+    fn find_derived_template(&self, library_id: &str, component_id: &str) -> Result<Option<(String, String)>> {
+        let libraries = self.libraries.lock()
+            .map_err(|_| AppError::ThreadError("Mutex poisoned in find_derived_template".to_string()))?;
+        Ok(libraries.iter()
+            .flat_map(|(id, library)| library.components.values().map(move |t| (id, t)))
+            .find(|(id, t)| t.extends.as_ref().is_some_and(|base| {
+                base.component_id == component_id && base.library_id.as_deref().unwrap_or(id.as_str()) == library_id
+            }))
+            .map(|(id, t)| (id.clone(), t.name.clone())))
+    }
 
-        // Add pins
-        template.add_pin(PinTemplate {
-            id: "in_pos".to_string(),
-            name: "V+".to_string(),
-            number: "3".to_string(),
-            x: -20.0,
-            y: -8.0,
-            pin_type: PinType::Input,
-            electrical: ElectricalType {
-                voltage: Some(5.0),
-                current: Some(0.001),
-                impedance: Some(1e6),
-            },
+    /// Edits one template of an editable library in place and returns the result.
+    pub fn edit_component_template<F>(&self, library_id: &str, component_id: &str, edit: F) -> Result<ComponentTemplate>
+    where
+        F: FnOnce(&mut ComponentTemplate) -> Result<()>,
+    {
+        let library = self.edit_library(library_id, |library| {
+            let template = library.components.get_mut(component_id)
+                .ok_or_else(|| AppError::ComponentNotFound(component_id.to_string()))?;
+            edit(template)
         })?;
+        library.get_component_template(component_id)
+            .cloned()
+            .ok_or_else(|| AppError::ComponentNotFound(component_id.to_string()))
+    }
 
-        template.add_pin(PinTemplate {
-            id: "in_neg".to_string(),
-            name: "V-".to_string(),
-            number: "2".to_string(),
-            x: -20.0,
-            y: 8.0,
-            pin_type: PinType::Input,
-            electrical: ElectricalType {
-                voltage: Some(5.0),
-                current: Some(0.001),
-                impedance: Some(1e6),
-            },
-        })?;
+    // Applies an edit to a user or project library and persists it; the in-memory copy
+    // is only replaced once the edit succeeds and has been written.
+    fn edit_library<F>(&self, library_id: &str, edit: F) -> Result<ComponentLibrary>
+    where
+        F: FnOnce(&mut ComponentLibrary) -> Result<()>,
+    {
+        self.editable_origin(library_id)?;
+
+        let updated = {
+            let mut libraries = self.libraries.lock()
+                .map_err(|_| AppError::ThreadError("Mutex poisoned in edit_library".to_string()))?;
+            let library = libraries.get_mut(library_id)
+                .ok_or_else(|| AppError::InvalidInput(format!("Library not found: {}", library_id)))?;
+            let mut updated = library.clone();
+            edit(&mut updated)?;
+            // Still holding the lock, so concurrent edits are written in the order they apply
+            self.persist_library(&updated)?;
+            *library = updated.clone();
+            updated
+        };
 
-        template.add_pin(PinTemplate {
-            id: "output".to_string(),
-            name: "Out".to_string(),
-            number: "6".to_string(),
-            x: 20.0,
-            y: 0.0,
-            pin_type: PinType::Output,
-            electrical: ElectricalType {
-                voltage: Some(5.0),
-                current: Some(0.025),
-                impedance: Some(75.0),
-            },
-        })?;
+        self.rebuild_search_index();
+        Ok(updated)
+    }
+
+    fn editable_origin(&self, library_id: &str) -> Result<LibraryOrigin> {
+        let origins = self.origins.lock()
+            .map_err(|_| AppError::ThreadError("Mutex poisoned in editable_origin".to_string()))?;
+        origins.get(library_id)
+            .cloned()
+            .ok_or_else(|| AppError::InvalidOperation(format!("Library {} is read-only", library_id)))
+    }
+
+    // Project libraries are written together with the project file
+    fn persist_library(&self, library: &ComponentLibrary) -> Result<()> {
+        let path = match self.editable_origin(&library.id)? {
+            LibraryOrigin::UserFile(path) => path,
+            LibraryOrigin::Project(_) => return Ok(()),
+        };
+        let json = serde_json::to_string_pretty(library)?;
+        FileService::write_atomic(&path, json.as_bytes())
+    }
+
+    pub fn create_standard_library(&self) -> Result<()> {
+        let standard_lib = Self::load_standard_library()?;
 
-        template.keywords = vec!["op-amp".to_string(), "amplifier".to_string(), "opamp".to_string()];
+        // Store the library
+        let mut libraries = self.libraries.lock()
+            .map_err(|_| crate::utils::error::AppError::ThreadError("Mutex poisoned in initialize_standard_library".to_string()))?;
+        libraries.insert(standard_lib.id.clone(), standard_lib);
+        drop(libraries);
+        self.rebuild_search_index();
         
-        library.add_component_template(template)?;
         Ok(())
     }
 
-    fn add_connector_template(&self, library: &mut ComponentLibrary) -> Result<()> {
-        let symbol = ComponentSymbol {
-            width: 30.0,
-            height: 60.0,
-            draw_commands: vec![
-                DrawCommand {
-                    command_type: DrawCommandType::Rectangle,
-                    parameters: vec![-10.0, -30.0, 20.0, 60.0],
-                    style: Some(DrawStyle {
-                        stroke_width: 2.0,
-                        stroke_color: "#000000".to_string(),
-                        fill_color: Some("#DDDDDD".to_string()),
-                    }),
-                },
-            ],
-            graphics: Some(SymbolGraphics {
-                bounds: GraphicsBounds { width: 40.0, height: 20.0 }
-            }),
-        };
+    fn load_standard_library() -> Result<ComponentLibrary> {
+        let file: StandardLibraryFile = serde_json::from_str(STANDARD_LIBRARY_JSON)
+            .map_err(|e| AppError::InvalidFormat(format!("Standard library: {}", e)))?;
 
-        let mut template = ComponentTemplate::new(
-            "Header 4x1".to_string(),
-            "connector".to_string(),
-            symbol,
-        )?;
-
-        template.add_parameter("pin_count".to_string(), ParameterTemplate {
-            name: "Pin Count".to_string(),
-            parameter_type: ParameterType::Choice(vec!["2".to_string(), "4".to_string(), "8".to_string(), "16".to_string()]),
-            default_value: serde_json::Value::String("4".to_string()),
-            min_value: None,
-            max_value: None,
-            unit: None,
-            description: Some("Number of pins".to_string()),
-            required: true,
-        })?;
+        let mut library = ComponentLibrary::new(file.name)?;
+        library.id = STANDARD_LIBRARY_ID.to_string();
+        library.version = file.version;
+        library.description = file.description;
 
-        // Add pins
-        for i in 1..=4 {
-            template.add_pin(PinTemplate {
-                id: format!("pin{}", i),
-                name: format!("{}", i),
-                number: format!("{}", i),
-                x: -20.0,
-                y: -22.5 + (i as f64 - 1.0) * 15.0,
-                pin_type: PinType::Bidirectional,
-                electrical: ElectricalType {
-                    voltage: None,
-                    current: None,
-                    impedance: None,
-                },
-            })?;
+        for category in file.categories {
+            library.add_category(category)?;
+        }
+        for template in file.components {
+            if library.components.contains_key(&template.id) {
+                return Err(AppError::InvalidFormat(format!("Duplicate component id in standard library: {}", template.id)));
+            }
+            library.add_component_template(template)?;
         }
 
-        template.keywords = vec!["header".to_string(), "connector".to_string(), "pin".to_string()];
-        
-        library.add_component_template(template)?;
-        Ok(())
+        Ok(library)
     }
 
+    /// A copy of the library with derived templates resolved; a template whose chain
+    /// can't be resolved is returned as stored.
     pub fn get_library(&self, id: &str) -> Option<ComponentLibrary> {
         let libraries = match self.libraries.lock() {
             Ok(guard) => guard,
@@ -657,12 +549,13 @@ impl LibraryService {
                 return None;
             }
         };
-        libraries.get(id).cloned()
+        libraries.get(id).map(|library| resolve_library(&libraries, library))
     }
 
+    /// Every loaded library, with derived templates resolved like [`Self::get_library`].
     pub fn get_all_libraries(&self) -> Vec<ComponentLibrary> {
         match self.libraries.lock() {
-            Ok(libraries) => libraries.values().cloned().collect(),
+            Ok(libraries) => libraries.values().map(|library| resolve_library(&libraries, library)).collect(),
             Err(_) => {
                 eprintln!("Warning: Mutex poisoned in get_all_libraries, returning empty list");
                 Vec::new()
@@ -670,6 +563,7 @@ impl LibraryService {
         }
     }
 
+    /// Returns the template with its inheritance chain resolved.
     pub fn get_component_template(&self, library_id: &str, component_id: &str) -> Option<ComponentTemplate> {
         let libraries = match self.libraries.lock() {
             Ok(guard) => guard,
@@ -678,12 +572,29 @@ impl LibraryService {
                 return None;
             }
         };
-        libraries.get(library_id)?
-            .get_component_template(component_id)
-            .cloned()
+        let template = libraries.get(library_id)?.get_component_template(component_id)?;
+        match resolve_template(&libraries, library_id, template) {
+            Ok(resolved) => Some(resolved),
+            Err(e) => {
+                eprintln!("Warning: failed to resolve template {}: {}", component_id, e);
+                None
+            }
+        }
     }
 
+    /// Ranked fuzzy search with parametric filters, e.g. `"capacitor, ≥ 10µF, ≥ 25V, 0805"`.
+    /// An empty query lists every component.
     pub fn search_all_components(&self, query: &str) -> Vec<(String, ComponentTemplate)> {
+        let query = SearchQuery::parse(query);
+        let limit = if query.is_empty() { usize::MAX } else { MAX_SEARCH_RESULTS };
+
+        let hits = match self.search_index.lock() {
+            Ok(index) => index.search(&query, limit),
+            Err(_) => {
+                eprintln!("Warning: Mutex poisoned in search_all_components, returning empty results");
+                return Vec::new();
+            }
+        };
         let libraries = match self.libraries.lock() {
             Ok(guard) => guard,
             Err(_) => {
@@ -691,16 +602,14 @@ impl LibraryService {
                 return Vec::new();
             }
         };
-        let mut results = Vec::new();
-        
-        for (lib_id, library) in libraries.iter() {
-            let components = library.search_components(query);
-            for component in components {
-                results.push((lib_id.clone(), component.clone()));
-            }
-        }
-        
-        results
+
+        hits.into_iter()
+            .filter_map(|(library_id, component_id, _)| {
+                let template = libraries.get(&library_id)?.get_component_template(&component_id)?;
+                let resolved = resolve_template(&libraries, &library_id, template).ok()?;
+                Some((library_id, resolved))
+            })
+            .collect()
     }
 
     pub fn get_components_by_category(&self, category_id: &str) -> Vec<(String, ComponentTemplate)> {
@@ -716,7 +625,10 @@ impl LibraryService {
         for (lib_id, library) in libraries.iter() {
             let components = library.get_components_by_category(category_id);
             for component in components {
-                results.push((lib_id.clone(), component.clone()));
+                match resolve_template(&libraries, lib_id, component) {
+                    Ok(resolved) => results.push((lib_id.clone(), resolved)),
+                    Err(e) => eprintln!("Warning: failed to resolve template {}: {}", component.id, e),
+                }
             }
         }
         
@@ -724,8 +636,338 @@ impl LibraryService {
     }
 }
 
+// Copy of `library` with every derived template replaced by its resolved form
+fn resolve_library(libraries: &HashMap<String, ComponentLibrary>, library: &ComponentLibrary) -> ComponentLibrary {
+    let mut resolved = library.clone();
+    for template in resolved.components.values_mut().filter(|t| t.extends.is_some()) {
+        match resolve_template(libraries, &library.id, template) {
+            Ok(r) => *template = r,
+            Err(e) => eprintln!("Warning: failed to resolve template {}: {}", template.id, e),
+        }
+    }
+    resolved
+}
+
+/// Walks a template's `extends` chain and applies it from the root down.
+/// Fails on a missing base or a cycle in the chain.
+fn resolve_template(
+    libraries: &HashMap<String, ComponentLibrary>,
+    library_id: &str,
+    template: &ComponentTemplate,
+) -> Result<ComponentTemplate> {
+    let mut chain = vec![format!("{}/{}", library_id, template.id)];
+    let mut bases: Vec<&ComponentTemplate> = Vec::new();
+    let mut current = template;
+    let mut current_library = library_id;
+
+    while let Some(base) = &current.extends {
+        let base_library = base.library_id.as_deref().unwrap_or(current_library);
+        let key = format!("{}/{}", base_library, base.component_id);
+        if chain.contains(&key) {
+            chain.push(key);
+            return Err(AppError::InvalidOperation(format!("Template inheritance cycle: {}", chain.join(" -> "))));
+        }
+        chain.push(key);
+
+        current = libraries.get(base_library)
+            .and_then(|library| library.get_component_template(&base.component_id))
+            .ok_or_else(|| AppError::ComponentNotFound(format!("{}/{}", base_library, base.component_id)))?;
+        current_library = base_library;
+        bases.push(current);
+    }
+
+    let resolved = match bases.pop() {
+        Some(root) => {
+            let mut resolved = root.clone();
+            while let Some(base) = bases.pop() {
+                resolved = base.inherit_from(&resolved);
+            }
+            template.inherit_from(&resolved)
+        }
+        None => template.clone(),
+    };
+    Ok(resolved)
+}
+
 impl Default for LibraryService {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::component::{Component, TemplateBase};
+    use crate::services::symbol_editor_service::SymbolEditor;
+
+    // A service reading user libraries from `dir` only, without touching the user's config
+    fn service_in(dir: &Path) -> LibraryService {
+        let service = LibraryService {
+            libraries: Arc::new(Mutex::new(HashMap::new())),
+            origins: Arc::new(Mutex::new(HashMap::new())),
+            library_paths: Arc::new(Mutex::new(vec![dir.to_path_buf()])),
+            search_index: Arc::new(Mutex::new(ComponentSearchIndex::default())),
+        };
+        service.create_standard_library().unwrap();
+        service.load_user_libraries().unwrap();
+        service
+    }
+
+    fn standard_template(service: &LibraryService, id: &str) -> ComponentTemplate {
+        let standard = service.get_library(STANDARD_LIBRARY_ID).unwrap();
+        let mut template = standard.components.values().min_by_key(|t| t.id.clone()).unwrap().clone();
+        template.id = id.to_string();
+        template
+    }
+
+    #[test]
+    fn test_user_library_round_trip() {
+        let dir = std::env::temp_dir().join(format!("rust-eda-libraries-{}", uuid::Uuid::new_v4()));
+        let service = service_in(&dir);
+        let library = service.create_library("Mine".to_string(), None, None).unwrap();
+        let path = dir.join(format!("{}.json", library.id));
+        assert!(path.exists());
+        let mut template = standard_template(&service, "my-part");
+        template.name = "Zebulon widget".to_string();
+        service.add_component_template(&library.id, template).unwrap();
+        service.rename_library(&library.id, "Renamed".to_string()).unwrap();
+        // No temporary files are left beside the library
+        let files: Vec<_> = std::fs::read_dir(&dir).unwrap().flatten().map(|e| e.file_name()).collect();
+        assert_eq!(files.len(), 1, "{:?}", files);
+
+        // Unreadable and non-library files are skipped
+        std::fs::write(dir.join("broken.json"), "{ not json").unwrap();
+        std::fs::write(dir.join("notes.txt"), "hello").unwrap();
+        let reloaded = service_in(&dir);
+        let loaded = reloaded.get_library(&library.id).unwrap();
+        assert_eq!(loaded.name, "Renamed");
+        assert!(reloaded.get_component_template(&library.id, "my-part").is_some());
+        assert!(reloaded.search_all_components("zebulon").iter().any(|(id, _)| id == &library.id));
+        let everything: usize = reloaded.get_all_libraries().iter().map(|l| l.components.len()).sum();
+        assert_eq!(reloaded.search_all_components("").len(), everything);
+
+        reloaded.delete_library(&library.id).unwrap();
+        assert!(!path.exists());
+        assert!(reloaded.get_library(&library.id).is_none());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_failed_write_keeps_library() {
+        let dir = std::env::temp_dir().join(format!("rust-eda-libraries-{}", uuid::Uuid::new_v4()));
+        let service = service_in(&dir);
+        let library = service.create_library("Mine".to_string(), None, None).unwrap();
+
+        // A directory where the file should be makes every write fail
+        let path = dir.join(format!("{}.json", library.id));
+        std::fs::remove_file(&path).unwrap();
+        std::fs::create_dir(&path).unwrap();
+        assert!(service.rename_library(&library.id, "Renamed".to_string()).is_err());
+        assert!(service.add_component_template(&library.id, standard_template(&service, "my-part")).is_err());
+        let unchanged = service.get_library(&library.id).unwrap();
+        assert_eq!(unchanged.name, "Mine");
+        assert!(!unchanged.components.contains_key("my-part"));
+        let leftovers: Vec<_> = std::fs::read_dir(&dir).unwrap().flatten()
+            .filter(|e| e.file_name().to_string_lossy().ends_with(".tmp"))
+            .collect();
+        assert!(leftovers.is_empty(), "{:?}", leftovers);
+
+        // A library that can't be written isn't created either
+        std::fs::remove_dir_all(&dir).unwrap();
+        std::fs::write(&dir, "a file, not a directory").unwrap();
+        let count = service.get_all_libraries().len();
+        assert!(service.create_library("Other".to_string(), None, None).is_err());
+        assert_eq!(service.get_all_libraries().len(), count);
+        std::fs::remove_file(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_project_library_mount_and_sync() {
+        let dir = std::env::temp_dir().join(format!("rust-eda-libraries-{}", uuid::Uuid::new_v4()));
+        let service = service_in(&dir);
+        let mut project = Project::new("Test".to_string()).unwrap();
+        let library = service.create_library("Local".to_string(), None, Some(project.id.clone())).unwrap();
+        assert!(service.create_library("Again".to_string(), None, Some(project.id.clone())).is_err());
+        service.add_component_template(&library.id, standard_template(&service, "local-part")).unwrap();
+        service.sync_project_library(&mut project).unwrap();
+        assert!(project.local_library.as_ref().unwrap().components.contains_key("local-part"));
+        // Project libraries live in the project file, not in the library path
+        assert!(!dir.exists());
+
+        // Another session mounts the library from the project it was saved with
+        let other = service_in(&dir);
+        assert!(other.get_library(&library.id).is_none());
+        other.mount_project_library(&project).unwrap();
+        assert!(other.get_component_template(&library.id, "local-part").is_some());
+        other.remove_component_template(&library.id, "local-part").unwrap();
+        other.sync_project_library(&mut project).unwrap();
+        assert!(project.local_library.as_ref().unwrap().components.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_save_keeps_unmounted_project_library() {
+        let dir = std::env::temp_dir().join(format!("rust-eda-libraries-{}", uuid::Uuid::new_v4()));
+        let service = service_in(&dir);
+        let mut project = Project::new("Test".to_string()).unwrap();
+        let library = service.create_library("Local".to_string(), None, Some(project.id.clone())).unwrap();
+        service.add_component_template(&library.id, standard_template(&service, "local-part")).unwrap();
+        service.sync_project_library(&mut project).unwrap();
+
+        // Saved from a session where the library isn't mounted, it still comes back
+        let other = service_in(&dir);
+        other.sync_project_library(&mut project).unwrap();
+        let path = dir.join("project.json");
+        let projects = crate::services::ProjectService::new();
+        projects.save_project(&project, path.to_str().unwrap()).await.unwrap();
+        let loaded = projects.load_project(path.to_str().unwrap()).await.unwrap();
+        let local = loaded.local_library.unwrap();
+        assert_eq!(local.id, library.id);
+        assert!(local.components.contains_key("local-part"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_symbol_cache_refresh_and_rescue() {
+        let dir = std::env::temp_dir().join(format!("rust-eda-libraries-{}", uuid::Uuid::new_v4()));
+        let service = service_in(&dir);
+        let library = service.create_library("Mine".to_string(), None, None).unwrap();
+        let mut template = standard_template(&service, "cached-part");
+        template.category_id = "sensors".to_string();
+        service.edit_library(&library.id, |l| {
+            l.add_category(ComponentCategory {
+                id: "sensors".to_string(),
+                name: "Sensors".to_string(),
+                parent_id: None,
+                description: None,
+                color: None,
+            })
+        }).unwrap();
+        service.add_component_template(&library.id, template.clone()).unwrap();
+        let standard = standard_template(&service, "unused");
+        let standard_id = service.get_library(STANDARD_LIBRARY_ID).unwrap().components.keys().min().unwrap().clone();
+
+        let mut project = Project::new("Cached".to_string()).unwrap();
+        let mut schematic = Schematic::new("Main".to_string()).unwrap();
+        for x in [0.0, 50.0] {
+            schematic.add_component(Component::from_template(&template, library.id.clone(), x, 0.0).unwrap()).unwrap();
+        }
+        let standard_part = service.get_component_template(STANDARD_LIBRARY_ID, &standard_id).unwrap();
+        schematic.add_component(Component::from_template(&standard_part, STANDARD_LIBRARY_ID.to_string(), 100.0, 0.0).unwrap()).unwrap();
+        project.add_schematic(schematic).unwrap();
+        // Stale entries for templates nothing uses any more are dropped
+        project.symbol_cache.push(CachedTemplate { library_id: STANDARD_LIBRARY_ID.to_string(), template: standard });
+
+        service.refresh_symbol_cache(&mut project);
+        assert_eq!(project.symbol_cache.len(), 2);
+        assert!(project.cached_template(&library.id, "cached-part").is_some());
+        assert!(project.cached_template(STANDARD_LIBRARY_ID, "unused").is_none());
+        assert!(service.rescue_cached_templates(&mut project).unwrap().is_empty());
+
+        // Elsewhere the user library is missing; the cache still draws the part and survives a refresh
+        let other_dir = std::env::temp_dir().join(format!("rust-eda-libraries-{}", uuid::Uuid::new_v4()));
+        let other = service_in(&other_dir);
+        other.refresh_symbol_cache(&mut project);
+        assert_eq!(project.symbol_cache.len(), 2);
+        assert!(other.get_project_template(&project, &library.id, "cached-part").is_some());
+        let symbols = other.component_symbols(&project, &project.schematics[0]);
+        assert_eq!(symbols.len(), 3);
+
+        let rescued = other.rescue_cached_templates(&mut project).unwrap();
+        assert_eq!(rescued, vec!["cached-part"]);
+        let local = project.local_library.clone().unwrap();
+        assert!(local.components.contains_key("cached-part"));
+        assert!(local.categories.iter().any(|c| c.id == "sensors"));
+        let library_ids: Vec<Option<String>> = project.schematics[0].components.iter().map(|c| c.library_id.clone()).collect();
+        assert_eq!(library_ids, vec![Some(local.id.clone()), Some(local.id.clone()), Some(STANDARD_LIBRARY_ID.to_string())]);
+        assert!(project.cached_template(&local.id, "cached-part").is_some());
+        assert!(project.cached_template(&library.id, "cached-part").is_none());
+        assert!(other.rescue_cached_templates(&mut project).unwrap().is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(!other_dir.exists());
+    }
+
+    #[test]
+    fn test_template_inheritance() {
+        let dir = std::env::temp_dir().join(format!("rust-eda-libraries-{}", uuid::Uuid::new_v4()));
+        let service = service_in(&dir);
+        let library = service.create_library("Mine".to_string(), None, None).unwrap();
+
+        let mut base = standard_template(&service, "base");
+        base.footprint = Some("SOT-23".to_string());
+        base.description = Some("Base part".to_string());
+        service.add_component_template(&library.id, base.clone()).unwrap();
+
+        let derive = |id: &str, from: &str| {
+            let symbol = ComponentSymbol { width: 0.0, height: 0.0, draw_commands: Vec::new(), graphics: None };
+            let mut template = ComponentTemplate::new(id.to_string(), base.category_id.clone(), symbol).unwrap();
+            template.id = id.to_string();
+            template.extends = Some(TemplateBase { library_id: None, component_id: from.to_string() });
+            template
+        };
+        let mut middle = derive("middle", "base");
+        middle.footprint = Some("SOT-23-5".to_string());
+        service.add_component_template(&library.id, middle).unwrap();
+        service.add_component_template(&library.id, derive("leaf", "middle")).unwrap();
+
+        // The leaf inherits through the chain, the nearest override winning
+        let leaf = service.get_component_template(&library.id, "leaf").unwrap();
+        assert_eq!(leaf.pins.len(), base.pins.len());
+        assert_eq!(leaf.symbol.draw_commands.len(), base.symbol.draw_commands.len());
+        assert_eq!(leaf.footprint.as_deref(), Some("SOT-23-5"));
+        assert_eq!(leaf.description.as_deref(), Some("Base part"));
+        assert_eq!(leaf.extends.as_ref().unwrap().component_id, "middle");
+
+        // Whole-library reads come back resolved too
+        let loaded = service.get_library(&library.id).unwrap();
+        assert_eq!(loaded.components["leaf"].pins.len(), base.pins.len());
+        let all = service.get_all_libraries();
+        let listed = all.iter().find(|l| l.id == library.id).unwrap();
+        assert_eq!(listed.components["middle"].pins.len(), base.pins.len());
+
+        // A base in another library is found by its library id
+        let standard_id = service.get_library(STANDARD_LIBRARY_ID).unwrap().components.keys().min().unwrap().clone();
+        let mut external = derive("external", &standard_id);
+        external.extends.as_mut().unwrap().library_id = Some(STANDARD_LIBRARY_ID.to_string());
+        service.add_component_template(&library.id, external).unwrap();
+        assert!(!service.get_component_template(&library.id, "external").unwrap().pins.is_empty());
+
+        // Missing bases and cycles are rejected, and a base can't be removed from under its parts
+        assert!(service.add_component_template(&library.id, derive("orphan", "nowhere")).is_err());
+        assert!(service.update_component_template(&library.id, derive("base", "leaf")).is_err());
+        assert!(service.update_component_template(&library.id, derive("middle", "middle")).is_err());
+        assert!(service.remove_component_template(&library.id, "base").is_err());
+        assert!(service.get_component_template(&library.id, "base").unwrap().extends.is_none());
+
+        // A cycle loaded from disk is reported instead of resolved
+        let mut libraries = HashMap::new();
+        let mut looped = ComponentLibrary::new("Looped".to_string()).unwrap();
+        looped.components.insert("a".to_string(), derive("a", "b"));
+        looped.components.insert("b".to_string(), derive("b", "a"));
+        libraries.insert(looped.id.clone(), looped.clone());
+        let error = resolve_template(&libraries, &looped.id, &looped.components["a"]).unwrap_err();
+        assert!(error.to_string().contains("cycle"), "{}", error);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_standard_library_templates_are_valid() {
+        let library = LibraryService::load_standard_library().unwrap();
+        assert!(!library.components.is_empty());
+
+        for (id, template) in &library.components {
+            assert_eq!(id, &template.id);
+            assert!(!template.pins.is_empty(), "{} has no pins", id);
+
+            let report = SymbolEditor::validate(template);
+            assert!(report.passed, "{} failed validation: {:?}", id, report.issues);
+
+            // Every shape and pin must sit inside the declared symbol size
+            let mut recomputed = template.symbol.clone();
+            recomputed.recompute_bounds(&template.pins);
+            assert!(recomputed.width <= template.symbol.width + 1e-6, "{} is wider than its symbol", id);
+            assert!(recomputed.height <= template.symbol.height + 1e-6, "{} is taller than its symbol", id);
+        }
+    }
+}
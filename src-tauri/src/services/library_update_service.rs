@@ -0,0 +1,246 @@
+use crate::models::component::{Component, ComponentTemplate, PinTemplate};
+use crate::models::Project;
+use crate::utils::error::Result;
+use super::library_service::LibraryService;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LibraryUpdateStatus {
+    Changed,
+    TemplateMissing,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PinChange {
+    pub number: String,
+    pub old_name: String,
+    pub new_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertyChange {
+    pub key: String,
+    pub old_value: serde_json::Value,
+    pub new_value: serde_json::Value,
+}
+
+/// Difference between a placed component and the current version of its template.
+/// Pins are matched by number, properties by key; properties the template doesn't
+/// define are listed but survive an update.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentUpdateReport {
+    pub component_id: String,
+    pub reference: String,
+    pub library_id: Option<String>,
+    pub type_id: String,
+    pub status: LibraryUpdateStatus,
+    pub placed_hash: Option<String>,
+    pub current_hash: Option<String>,
+    pub pins_added: Vec<String>,
+    pub pins_removed: Vec<String>,
+    pub pins_renamed: Vec<PinChange>,
+    pub properties_added: Vec<String>,
+    pub properties_only_on_instance: Vec<String>,
+    pub properties_changed: Vec<PropertyChange>,
+}
+
+pub struct LibraryUpdateService;
+
+impl LibraryUpdateService {
+    /// Lists every placed component whose template changed since placement or has disappeared.
+    pub fn check_for_updates(project: &Project, libraries: &LibraryService) -> Vec<ComponentUpdateReport> {
+        project.schematics.iter()
+            .flat_map(|s| s.components.iter())
+            .filter_map(|component| {
+                let library_id = component.library_id.as_ref()?;
+                let template = libraries.get_component_template(library_id, &component.type_id);
+                Self::diff_component(component, template.as_ref())
+            })
+            .collect()
+    }
+
+    /// Refreshes the given components from their current templates and returns the ids
+    /// that were actually updated. Missing templates are skipped.
+    pub fn apply_updates(project: &mut Project, libraries: &LibraryService, component_ids: &[String]) -> Result<Vec<String>> {
+        let mut updated = Vec::new();
+
+        for component in project.schematics.iter_mut().flat_map(|s| s.components.iter_mut()) {
+            if !component_ids.contains(&component.id) {
+                continue;
+            }
+            let template = match &component.library_id {
+                Some(library_id) => libraries.get_component_template(library_id, &component.type_id),
+                None => None,
+            };
+            if let Some(template) = template {
+                component.refresh_from_template(&template)?;
+                updated.push(component.id.clone());
+            }
+        }
+
+        if !updated.is_empty() {
+            project.update_modified();
+        }
+        Ok(updated)
+    }
+
+    fn diff_component(component: &Component, template: Option<&ComponentTemplate>) -> Option<ComponentUpdateReport> {
+        let mut report = ComponentUpdateReport {
+            component_id: component.id.clone(),
+            reference: component.display_reference(),
+            library_id: component.library_id.clone(),
+            type_id: component.type_id.clone(),
+            status: LibraryUpdateStatus::TemplateMissing,
+            placed_hash: component.template_hash.clone(),
+            current_hash: None,
+            pins_added: Vec::new(),
+            pins_removed: Vec::new(),
+            pins_renamed: Vec::new(),
+            properties_added: Vec::new(),
+            properties_only_on_instance: Vec::new(),
+            properties_changed: Vec::new(),
+        };
+
+        let template = match template {
+            Some(template) => template,
+            None => return Some(report),
+        };
+        let current_hash = template.content_hash();
+        if component.template_hash.as_ref() == Some(&current_hash) {
+            return None;
+        }
+        report.status = LibraryUpdateStatus::Changed;
+        report.current_hash = Some(current_hash);
+
+        let template_pins: Vec<&PinTemplate> = match &component.unit {
+            Some(unit) => template.unit_pins(unit).unwrap_or_default(),
+            None => template.pins.iter().collect(),
+        };
+        for pin in &template_pins {
+            match component.get_pin_by_number(&pin.number) {
+                None => report.pins_added.push(pin.number.clone()),
+                Some(placed) if placed.name != pin.name => report.pins_renamed.push(PinChange {
+                    number: pin.number.clone(),
+                    old_name: placed.name.clone(),
+                    new_name: pin.name.clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+        report.pins_removed = component.pins.iter()
+            .filter(|p| !template_pins.iter().any(|t| t.number == p.number))
+            .map(|p| p.number.clone())
+            .collect();
+
+        for (key, default) in &template.default_properties {
+            match component.properties.get(key) {
+                None => report.properties_added.push(key.clone()),
+                // Instance edits to editable properties are intentional, not drift
+                Some(placed) if !default.editable && placed.value != default.value => {
+                    report.properties_changed.push(PropertyChange {
+                        key: key.clone(),
+                        old_value: placed.value.clone(),
+                        new_value: default.value.clone(),
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+        report.properties_only_on_instance = component.properties.keys()
+            .filter(|key| !template.default_properties.contains_key(*key))
+            .cloned()
+            .collect();
+
+        report.pins_added.sort();
+        report.pins_removed.sort();
+        report.properties_added.sort();
+        report.properties_only_on_instance.sort();
+
+        // Components placed before hashes were recorded can only be compared by content
+        let differs = !report.pins_added.is_empty()
+            || !report.pins_removed.is_empty()
+            || !report.pins_renamed.is_empty()
+            || !report.properties_added.is_empty()
+            || !report.properties_changed.is_empty();
+        if component.template_hash.is_none() && !differs {
+            return None;
+        }
+        Some(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::component::{ComponentProperty, ComponentSymbol, ElectricalType, PinType};
+
+    fn template() -> ComponentTemplate {
+        let symbol = ComponentSymbol { width: 40.0, height: 20.0, draw_commands: Vec::new(), graphics: None };
+        let mut template = ComponentTemplate::new("Resistor".to_string(), "passive".to_string(), symbol).unwrap();
+        for (number, x) in [("1", -20.0), ("2", 20.0)] {
+            template.pins.push(PinTemplate {
+                id: format!("p{}", number),
+                name: format!("P{}", number),
+                number: number.to_string(),
+                x,
+                y: 0.0,
+                pin_type: PinType::Passive,
+                electrical: ElectricalType { voltage: None, current: None, impedance: None },
+            });
+        }
+        template.default_properties.insert("tolerance".to_string(), ComponentProperty {
+            value: serde_json::json!("5%"),
+            visible: false,
+            editable: false,
+        });
+        template
+    }
+
+    #[test]
+    fn test_diff_component() {
+        let template = template();
+        let mut component = Component::from_template(&template, "standard".to_string(), 0.0, 0.0).unwrap();
+        assert!(LibraryUpdateService::diff_component(&component, Some(&template)).is_none());
+
+        let missing = LibraryUpdateService::diff_component(&component, None).unwrap();
+        assert_eq!(missing.status, LibraryUpdateStatus::TemplateMissing);
+
+        let mut changed = template.clone();
+        changed.pins[0].name = "RENAMED".to_string();
+        changed.pins.remove(1);
+        changed.default_properties.get_mut("tolerance").unwrap().value = serde_json::json!("1%");
+        changed.default_properties.insert("power".to_string(), ComponentProperty {
+            value: serde_json::json!("0.25W"),
+            visible: false,
+            editable: true,
+        });
+        component.properties.insert("note".to_string(), ComponentProperty {
+            value: serde_json::json!("hand fit"),
+            visible: true,
+            editable: true,
+        });
+        let report = LibraryUpdateService::diff_component(&component, Some(&changed)).unwrap();
+        assert_eq!(report.status, LibraryUpdateStatus::Changed);
+        assert_eq!(report.current_hash, Some(changed.content_hash()));
+        assert_eq!(report.pins_renamed[0].new_name, "RENAMED");
+        assert_eq!(report.pins_removed, vec![template.pins[1].number.clone()]);
+        assert_eq!(report.properties_added, vec!["power"]);
+        assert_eq!(report.properties_changed[0].key, "tolerance");
+        assert_eq!(report.properties_only_on_instance, vec!["note"]);
+
+        // Without a recorded hash only a content difference counts
+        component.template_hash = None;
+        assert!(LibraryUpdateService::diff_component(&component, Some(&template)).is_none());
+        let report = LibraryUpdateService::diff_component(&component, Some(&changed)).unwrap();
+        assert_eq!(report.status, LibraryUpdateStatus::Changed);
+        assert!(report.placed_hash.is_none());
+
+        // Updating records the hash again
+        component.refresh_from_template(&changed).unwrap();
+        assert!(LibraryUpdateService::diff_component(&component, Some(&changed)).is_none());
+        assert_eq!(component.template_hash, Some(changed.content_hash()));
+    }
+}
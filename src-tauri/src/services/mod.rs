@@ -1,17 +1,45 @@
 pub mod file_service;
 pub mod library_service;
+pub mod component_search;
 pub mod project_service;
 pub mod export_service;
 pub mod erc_service;
-pub mod extended_components;
-pub mod logic_gates;
-pub mod logic_gates_impl;
-pub mod integrated_circuits;
-pub mod sensors_power;
-pub mod symbol_helper;
+pub mod footprint_service;
+pub mod annotation_service;
+pub mod library_update_service;
+pub mod symbol_editor_service;
+pub mod part_provider;
+pub mod simulation;
+pub mod board_service;
+pub mod fabrication_service;
+pub mod drc_service;
+pub mod ratsnest_service;
+pub mod autorouter_service;
+pub mod zone_fill_service;
+pub mod placement_service;
+pub mod back_annotation_service;
+pub mod ipc_export_service;
+pub mod dxf_service;
+#[cfg(test)]
+pub(crate) mod test_fixtures;
 
 pub use file_service::*;
 pub use library_service::*;
 pub use project_service::*;
 pub use export_service::*;
-pub use erc_service::*;
\ No newline at end of file
+pub use erc_service::*;
+pub use footprint_service::*;
+pub use annotation_service::*;
+pub use library_update_service::*;
+pub use symbol_editor_service::*;
+pub use part_provider::*;
+pub use board_service::*;
+pub use fabrication_service::*;
+pub use drc_service::*;
+pub use ratsnest_service::*;
+pub use autorouter_service::*;
+pub use zone_fill_service::*;
+pub use placement_service::*;
+pub use back_annotation_service::*;
+pub use ipc_export_service::*;
+pub use dxf_service::*;
\ No newline at end of file
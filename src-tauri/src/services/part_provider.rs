@@ -0,0 +1,392 @@
+use crate::models::component::LifecycleStatus;
+use crate::models::Project;
+use crate::utils::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+const DEFAULT_CURRENCY: &str = "USD";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceBreak {
+    pub quantity: u32,
+    pub unit_price: f64,
+}
+
+/// One supplier's offer for a manufacturer part.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartOffer {
+    pub mpn: String,
+    pub supplier: String,
+    #[serde(default = "default_currency")]
+    pub currency: String,
+    pub price_breaks: Vec<PriceBreak>,
+    pub stock: Option<u64>,
+    #[serde(default)]
+    pub lifecycle: LifecycleStatus,
+}
+
+fn default_currency() -> String {
+    DEFAULT_CURRENCY.to_string()
+}
+
+impl PartOffer {
+    /// Unit price when ordering `quantity`; below the first break the first break's price applies.
+    pub fn unit_price(&self, quantity: u32) -> Option<f64> {
+        let mut breaks: Vec<&PriceBreak> = self.price_breaks.iter().collect();
+        breaks.sort_by_key(|b| b.quantity);
+        breaks.iter()
+            .rev()
+            .find(|b| b.quantity <= quantity)
+            .or(breaks.first())
+            .map(|b| b.unit_price)
+    }
+
+    pub fn covers(&self, quantity: u32) -> bool {
+        self.stock.is_none_or(|stock| stock >= quantity as u64)
+    }
+}
+
+/// Source of pricing and availability. Implementations are looked up with the MPNs
+/// of a BOM and return whatever offers they know, keyed by the requested MPN.
+pub trait PartProvider: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn lookup(&self, mpns: &[String]) -> Result<HashMap<String, Vec<PartOffer>>>;
+}
+
+/// Provider backed by a local price list, either a JSON array of [`PartOffer`]s or a CSV
+/// with one price break per row:
+/// `mpn,supplier,quantity,unit_price[,stock][,lifecycle][,currency]`.
+pub struct FilePartProvider {
+    name: String,
+    offers: HashMap<String, Vec<PartOffer>>,
+}
+
+impl FilePartProvider {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        let name = path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "price list".to_string());
+
+        let is_json = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        let offers = if is_json { Self::parse_json(&text)? } else { Self::parse_csv(&text)? };
+        Ok(Self::from_offers(name, offers))
+    }
+
+    pub fn from_offers(name: String, offers: Vec<PartOffer>) -> Self {
+        let mut by_mpn: HashMap<String, Vec<PartOffer>> = HashMap::new();
+        for offer in offers {
+            by_mpn.entry(normalize_mpn(&offer.mpn)).or_default().push(offer);
+        }
+        Self { name, offers: by_mpn }
+    }
+
+    fn parse_json(text: &str) -> Result<Vec<PartOffer>> {
+        serde_json::from_str(text).map_err(|e| AppError::InvalidFormat(format!("Price list: {}", e)))
+    }
+
+    fn parse_csv(text: &str) -> Result<Vec<PartOffer>> {
+        let mut offers: Vec<PartOffer> = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let fields = split_csv_line(line);
+            if fields.iter().all(|f| f.is_empty()) || (index == 0 && fields[0].eq_ignore_ascii_case("mpn")) {
+                continue;
+            }
+            if fields.len() < 4 {
+                return Err(AppError::InvalidFormat(format!("Price list line {}: expected at least 4 columns", index + 1)));
+            }
+
+            let number_error = |column: &str| AppError::InvalidFormat(format!("Price list line {}: invalid {}", index + 1, column));
+            let price_break = PriceBreak {
+                quantity: fields[2].parse().map_err(|_| number_error("quantity"))?,
+                unit_price: fields[3].parse().map_err(|_| number_error("unit price"))?,
+            };
+            let stock = match fields.get(4).filter(|f| !f.is_empty()) {
+                Some(stock) => Some(stock.parse().map_err(|_| number_error("stock"))?),
+                None => None,
+            };
+            let lifecycle = fields.get(5).map(|f| parse_lifecycle(f)).unwrap_or_default();
+            let currency = fields.get(6).filter(|f| !f.is_empty()).cloned().unwrap_or_else(default_currency);
+
+            // Further rows for the same part and supplier add price breaks
+            match offers.iter_mut().find(|o| o.mpn == fields[0] && o.supplier == fields[1]) {
+                Some(offer) => {
+                    offer.price_breaks.push(price_break);
+                    offer.stock = offer.stock.or(stock);
+                    if offer.lifecycle == LifecycleStatus::Unknown {
+                        offer.lifecycle = lifecycle;
+                    }
+                }
+                None => offers.push(PartOffer {
+                    mpn: fields[0].clone(),
+                    supplier: fields[1].clone(),
+                    currency,
+                    price_breaks: vec![price_break],
+                    stock,
+                    lifecycle,
+                }),
+            }
+        }
+
+        Ok(offers)
+    }
+}
+
+impl PartProvider for FilePartProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn lookup(&self, mpns: &[String]) -> Result<HashMap<String, Vec<PartOffer>>> {
+        Ok(mpns.iter()
+            .filter_map(|mpn| Some((mpn.clone(), self.offers.get(&normalize_mpn(mpn))?.clone())))
+            .collect())
+    }
+}
+
+fn normalize_mpn(mpn: &str) -> String {
+    mpn.trim().to_uppercase()
+}
+
+fn parse_lifecycle(text: &str) -> LifecycleStatus {
+    match text.trim().to_lowercase().replace(['-', '_', ' '], "").as_str() {
+        "active" => LifecycleStatus::Active,
+        "nrnd" | "notrecommended" => LifecycleStatus::NotRecommended,
+        "ltb" | "lasttimebuy" => LifecycleStatus::LastTimeBuy,
+        "obsolete" | "eol" => LifecycleStatus::Obsolete,
+        _ => LifecycleStatus::Unknown,
+    }
+}
+
+// Comma split that honours double-quoted fields
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field).trim().to_string()),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
+}
+
+/// Chosen offer and cost for one MPN across the whole build.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartPricing {
+    pub mpn: String,
+    pub quantity: u32,
+    pub supplier: String,
+    pub unit_price: f64,
+    pub extended_cost: f64,
+    pub stock: Option<u64>,
+    pub in_stock: bool,
+    pub lifecycle: LifecycleStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BomPricing {
+    pub provider: String,
+    pub build_quantity: u32,
+    pub currency: String,
+    pub parts: Vec<PartPricing>,
+    pub total_cost: f64,
+    // MPNs without a usable offer, and packages without an MPN at all
+    pub unpriced: Vec<String>,
+    // MPNs left unpriced because every offer was quoted in another currency
+    #[serde(default)]
+    pub foreign_currency: Vec<String>,
+}
+
+impl BomPricing {
+    pub fn get(&self, mpn: &str) -> Option<&PartPricing> {
+        self.parts.iter().find(|p| p.mpn == mpn)
+    }
+}
+
+pub struct PartPricingService;
+
+impl PartPricingService {
+    /// Prices every MPN in the project for `build_quantity` boards in `currency` (USD when
+    /// `None`). Per MPN the cheapest offer with enough stock wins, falling back to the
+    /// cheapest offer overall; offers in other currencies are never mixed in.
+    pub fn price_project(project: &Project, provider: &dyn PartProvider, build_quantity: u32, currency: Option<&str>) -> Result<BomPricing> {
+        let build_quantity = build_quantity.max(1);
+        let currency = currency.map(str::trim).filter(|c| !c.is_empty()).unwrap_or(DEFAULT_CURRENCY).to_uppercase();
+
+        let mut per_board: Vec<(String, u32)> = Vec::new();
+        let mut unpriced = Vec::new();
+        for component in project.package_components() {
+            match component.part_info.mpn.as_deref().filter(|_| component.part_info.has_mpn()) {
+                Some(mpn) => match per_board.iter_mut().find(|(m, _)| m == mpn) {
+                    Some((_, count)) => *count += 1,
+                    None => per_board.push((mpn.to_string(), 1)),
+                },
+                None => unpriced.push(component.reference.clone()),
+            }
+        }
+
+        let mpns: Vec<String> = per_board.iter().map(|(mpn, _)| mpn.clone()).collect();
+        let offers = provider.lookup(&mpns)?;
+
+        let mut parts = Vec::new();
+        let mut foreign_currency = Vec::new();
+        for (mpn, count) in per_board {
+            let quantity = count.checked_mul(build_quantity).ok_or_else(|| AppError::InvalidInput(format!(
+                "Building {} boards needs more of {} than can be ordered", build_quantity, mpn
+            )))?;
+            let found = offers.get(&mpn).map(Vec::as_slice).unwrap_or_default();
+            let best = found.iter()
+                .filter(|o| o.currency.trim().eq_ignore_ascii_case(&currency))
+                .filter_map(|o| Some((o, o.unit_price(quantity)?)))
+                .min_by(|(a, a_price), (b, b_price)| {
+                    b.covers(quantity).cmp(&a.covers(quantity))
+                        .then(a_price.total_cmp(b_price))
+                });
+
+            match best {
+                Some((offer, unit_price)) => {
+                    parts.push(PartPricing {
+                        mpn,
+                        quantity,
+                        supplier: offer.supplier.clone(),
+                        unit_price,
+                        extended_cost: unit_price * quantity as f64,
+                        stock: offer.stock,
+                        in_stock: offer.covers(quantity),
+                        lifecycle: offer.lifecycle,
+                    });
+                }
+                None => {
+                    if !found.is_empty() {
+                        foreign_currency.push(mpn.clone());
+                    }
+                    unpriced.push(mpn);
+                }
+            }
+        }
+
+        Ok(BomPricing {
+            provider: provider.name().to_string(),
+            build_quantity,
+            currency,
+            total_cost: parts.iter().map(|p| p.extended_cost).sum(),
+            parts,
+            unpriced,
+            foreign_currency,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_price_list() {
+        let csv = "mpn,supplier,quantity,unit_price,stock,lifecycle\n\
+                   LM1117-3.3,Digi-Key,1,0.95,500,active\n\
+                   LM1117-3.3,Digi-Key,100,0.52,,\n\
+                   \"RC0603FR-0710KL\",Mouser,1,0.10,5,nrnd\n";
+        let offers = FilePartProvider::parse_csv(csv).unwrap();
+        assert_eq!(offers.len(), 2);
+
+        let provider = FilePartProvider::from_offers("test".to_string(), offers);
+        let found = provider.lookup(&["lm1117-3.3".to_string()]).unwrap();
+        let offer = &found["lm1117-3.3"][0];
+        assert_eq!(offer.unit_price(10), Some(0.95));
+        assert_eq!(offer.unit_price(250), Some(0.52));
+        assert_eq!(offer.stock, Some(500));
+        assert_eq!(offer.lifecycle, LifecycleStatus::Active);
+        assert!(offer.covers(500) && !offer.covers(501));
+    }
+
+    fn offer(mpn: &str, supplier: &str, currency: &str, price: f64, stock: Option<u64>) -> PartOffer {
+        PartOffer {
+            mpn: mpn.to_string(),
+            supplier: supplier.to_string(),
+            currency: currency.to_string(),
+            price_breaks: vec![PriceBreak { quantity: 1, unit_price: price }, PriceBreak { quantity: 100, unit_price: price / 2.0 }],
+            stock,
+            lifecycle: LifecycleStatus::Active,
+        }
+    }
+
+    fn project(parts: &[(&str, Option<&str>)]) -> Project {
+        let mut project = Project::new("Priced".to_string()).unwrap();
+        let mut schematic = crate::models::Schematic::new("Main".to_string()).unwrap();
+        for (reference, mpn) in parts {
+            let mut component = crate::models::Component::new("part".to_string(), 0.0, 0.0).unwrap();
+            component.reference = reference.to_string();
+            component.part_info.mpn = mpn.map(str::to_string);
+            schematic.add_component(component).unwrap();
+        }
+        project.add_schematic(schematic).unwrap();
+        project
+    }
+
+    #[test]
+    fn test_price_project() {
+        let project = project(&[
+            ("R1", Some("RES-10K")),
+            ("R2", Some("RES-10K")),
+            ("U1", Some("MCU-1")),
+            ("U2", Some("EURO-ONLY")),
+            ("U3", Some("UNKNOWN")),
+            ("J1", None),
+        ]);
+        let provider = FilePartProvider::from_offers("test".to_string(), vec![
+            // Cheapest, but can't cover 2 x 10 boards
+            offer("RES-10K", "Cheap", "USD", 0.01, Some(5)),
+            offer("RES-10K", "Stocked", "usd", 0.02, Some(1000)),
+            offer("RES-10K", "Euro", "EUR", 0.001, None),
+            // Nobody has enough, so the cheapest short offer is used
+            offer("MCU-1", "A", "USD", 3.0, Some(2)),
+            offer("MCU-1", "B", "USD", 2.5, Some(0)),
+            offer("EURO-ONLY", "Euro", "EUR", 1.0, None),
+        ]);
+
+        let pricing = PartPricingService::price_project(&project, &provider, 10, None).unwrap();
+        assert_eq!(pricing.currency, "USD");
+        assert_eq!(pricing.build_quantity, 10);
+
+        let resistor = pricing.get("RES-10K").unwrap();
+        assert_eq!((resistor.supplier.as_str(), resistor.quantity), ("Stocked", 20));
+        assert!(resistor.in_stock);
+        assert!((resistor.extended_cost - 0.4).abs() < 1e-9);
+        let mcu = pricing.get("MCU-1").unwrap();
+        assert_eq!(mcu.supplier, "B");
+        assert!(!mcu.in_stock);
+        assert!((pricing.total_cost - (0.4 + 25.0)).abs() < 1e-9);
+
+        assert!(pricing.get("EURO-ONLY").is_none());
+        assert_eq!(pricing.foreign_currency, vec!["EURO-ONLY"]);
+        assert_eq!(pricing.unpriced, vec!["J1", "EURO-ONLY", "UNKNOWN"]);
+
+        // Priced in euros only euro offers count; 20 resistors hit no break, 100 boards do
+        let euros = PartPricingService::price_project(&project, &provider, 100, Some("eur")).unwrap();
+        assert_eq!(euros.currency, "EUR");
+        assert_eq!(euros.get("RES-10K").unwrap().unit_price, 0.0005);
+        assert!(euros.get("MCU-1").is_none());
+        assert_eq!(euros.foreign_currency, vec!["MCU-1"]);
+
+        // Two resistors a board for u32::MAX boards don't fit in an order quantity
+        assert!(PartPricingService::price_project(&project, &provider, u32::MAX, None).is_err());
+    }
+}
@@ -0,0 +1,701 @@
+use crate::models::component::reference_prefix;
+use crate::models::{glob_matches, BoardSide, FootprintPoint, KeepoutArea, LayerType, PlacementSide, Project};
+use crate::services::ratsnest_service::Ratsnest;
+use crate::utils::error::{AppError, Result};
+use crate::utils::geometry::{distance, point_in_contours, segments_intersect, Shape, Vec2};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+// Net names treated as supply rails; they join nearly everything, so they don't form clusters
+const POWER_NETS: [&str; 9] = ["+*", "-*V*", "VCC*", "VDD*", "VIN*", "VBAT*", "*3V3*", "*5V*", "V+"];
+const GROUND_NETS: [&str; 5] = ["GND*", "*GND", "VSS*", "VEE*", "0V"];
+// Rails weigh less in the wirelength estimate; they usually end up on planes
+const RAIL_WEIGHT: f64 = 0.2;
+// Rings searched past the first one with a legal spot
+const EXTRA_RINGS: usize = 4;
+// Additional keep-away around parts with thermal considerations
+const THERMAL_SPACING: f64 = 2.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlacementOptions {
+    #[serde(default)]
+    pub footprint_ids: Vec<String>, // empty: every unlocked footprint
+    #[serde(default = "default_spacing")]
+    pub spacing: f64, // between courtyards
+    #[serde(default = "default_grid")]
+    pub grid: f64,
+    #[serde(default = "default_passes")]
+    pub improvement_passes: usize,
+}
+
+fn default_spacing() -> f64 {
+    0.5
+}
+
+fn default_grid() -> f64 {
+    0.5
+}
+
+fn default_passes() -> usize {
+    2
+}
+
+impl Default for PlacementOptions {
+    fn default() -> Self {
+        Self {
+            footprint_ids: Vec::new(),
+            spacing: default_spacing(),
+            grid: default_grid(),
+            improvement_passes: default_passes(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecouplingAssignment {
+    pub capacitor: String,
+    pub ic: String,
+    pub net_name: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlacementReport {
+    pub placed: Vec<String>,
+    pub unplaced: Vec<String>, // no legal spot left on the board
+    pub decoupling: Vec<DecouplingAssignment>,
+    pub ratsnest_before: f64,
+    pub ratsnest_after: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlacementResult {
+    pub project: crate::models::Project,
+    pub report: PlacementReport,
+}
+
+// A footprint being placed, in its own frame with the side already applied
+struct Part {
+    index: usize,
+    side: PlacementSide,
+    rotations: Vec<f64>,
+    bounds: (f64, f64, f64, f64), // courtyard grown by half the spacing
+    pads: Vec<(Vec2, Option<String>)>,
+    sheet: usize,
+}
+
+#[derive(Clone, Copy)]
+struct Spot {
+    position: Vec2,
+    rotation: f64,
+}
+
+struct Decoupling {
+    capacitor: usize,     // part index
+    ic: usize,            // footprint index
+    power_pad: Vec2,      // of the IC, on the board
+    power_net: String,
+}
+
+pub struct PlacementService;
+
+impl PlacementService {
+    /// Initial placement for the selected footprints: parts are clustered by schematic sheet
+    /// and signal connectivity and placed cluster by cluster where their ratsnest is shortest,
+    /// decoupling capacitors go next to the power pin of the IC they serve, and a few passes
+    /// then move parts wherever that shortens the ratsnest. Locked and unselected footprints
+    /// stay put and are placed around.
+    pub fn auto_place(project: &mut Project, options: &PlacementOptions) -> Result<PlacementReport> {
+        if options.grid.is_nan() || options.grid <= 0.0 {
+            return Err(AppError::InvalidInput("Placement grid must be positive".to_string()));
+        }
+        let mut board = project.board.take()
+            .ok_or_else(|| AppError::InvalidOperation("Project has no board".to_string()))?;
+        let Some(outline) = board.outline_contours() else {
+            project.board = Some(board);
+            return Err(AppError::InvalidOperation("The board needs a closed outline before placement".to_string()));
+        };
+
+        let mut report = PlacementReport { ratsnest_before: Ratsnest::build(&board).total_length(), ..Default::default() };
+        let net_names: HashMap<String, String> = board.nets.iter().map(|n| (n.id.clone(), n.name.clone())).collect();
+        let is_rail = |net: &str| net_names.get(net).is_some_and(|n| is_power(n) || is_ground(n));
+
+        // Parts to place, each measured at the origin on its chosen side
+        let mut parts: Vec<Part> = Vec::new();
+        for (index, instance) in board.footprints.iter().enumerate() {
+            let selected = options.footprint_ids.is_empty() || options.footprint_ids.contains(&instance.id);
+            if !selected || instance.locked {
+                continue;
+            }
+            let hints = project.get_footprint_assignment(&instance.component_id)
+                .map(|a| a.placement_hints.clone())
+                .unwrap_or_default();
+            let mut template = instance.clone();
+            template.position = FootprintPoint { x: 0.0, y: 0.0 };
+            template.rotation = 0.0;
+            template.side = match hints.preferred_side {
+                BoardSide::Top => PlacementSide::Top,
+                BoardSide::Bottom => PlacementSide::Bottom,
+                _ => instance.side,
+            };
+            let margin = options.spacing / 2.0 + if hints.thermal_considerations { THERMAL_SPACING } else { 0.0 };
+            let rotations = if hints.allow_rotation {
+                (0..4).map(|k| (hints.rotation + 90.0 * k as f64).rem_euclid(360.0)).collect()
+            } else {
+                vec![hints.rotation]
+            };
+            parts.push(Part {
+                index,
+                side: template.side,
+                rotations,
+                bounds: grow(footprint_bounds(&template), margin),
+                pads: template.footprint.electrical_pads()
+                    .map(|p| {
+                        let at = template.pad_position(p);
+                        ((at.x, at.y), template.pad_net(&p.pad_number).map(|n| n.to_string()))
+                    })
+                    .collect(),
+                sheet: project.schematics.iter()
+                    .position(|s| s.components.iter().any(|c| c.id == instance.component_id))
+                    .unwrap_or(0),
+            });
+        }
+        let moving: HashSet<usize> = parts.iter().map(|p| p.index).collect();
+
+        let rail_nets: HashSet<String> = board.nets.iter().filter(|n| is_rail(&n.id)).map(|n| n.id.clone()).collect();
+        let mut placer = Placer {
+            outline,
+            outline_segments: board.outline_segments(),
+            keepouts: board.keepouts.iter()
+                .filter(|k| k.restrictions.no_components && k.area.len() >= 3)
+                .flat_map(|k| {
+                    let area = Shape::polygon(k.area.iter().map(|p| (p.x, p.y)).collect());
+                    [PlacementSide::Top, PlacementSide::Bottom].into_iter()
+                        .filter(|side| keeps_side(k, *side))
+                        .map(move |side| (side, area.clone()))
+                })
+                .collect(),
+            grid: options.grid,
+            occupied: HashMap::new(),
+            pads: HashMap::new(),
+            rail_nets: rail_nets.clone(),
+        };
+        for (index, instance) in board.footprints.iter().enumerate() {
+            if moving.contains(&index) {
+                continue;
+            }
+            placer.occupied.insert(index, grow(footprint_bounds(instance), options.spacing / 2.0));
+            let pads = instance.footprint.electrical_pads()
+                .filter_map(|p| instance.pad_net(&p.pad_number).map(|n| (n.to_string(), instance.pad_position(p))))
+                .map(|(net, at)| (net, (at.x, at.y)));
+            placer.add_pads(index, pads);
+        }
+
+        // Decoupling capacitors and the IC power pins they belong to
+        let mut decoupling: Vec<Decoupling> = Vec::new();
+        let mut caps_per_ic: HashMap<usize, usize> = HashMap::new();
+        for (part_index, part) in parts.iter().enumerate() {
+            let instance = &board.footprints[part.index];
+            let nets: Vec<&str> = part.pads.iter().filter_map(|(_, n)| n.as_deref()).collect();
+            if reference_prefix(&instance.reference) != "C" || part.pads.len() != 2 || nets.len() != 2 {
+                continue;
+            }
+            let name = |id: &str| net_names.get(id).cloned().unwrap_or_default();
+            let Some(power) = nets.iter().find(|n| is_power(&name(n))).copied() else { continue };
+            if !nets.iter().any(|n| is_ground(&name(n))) {
+                continue;
+            }
+            let ic = board.footprints.iter().enumerate()
+                .filter(|(_, f)| f.footprint.electrical_pads().count() >= 3 && f.pad_nets.values().any(|n| n == power))
+                .min_by_key(|(i, _)| caps_per_ic.get(i).copied().unwrap_or(0));
+            if let Some((ic_index, ic)) = ic {
+                *caps_per_ic.entry(ic_index).or_default() += 1;
+                let pad = ic.footprint.electrical_pads().find(|p| ic.pad_net(&p.pad_number) == Some(power));
+                decoupling.push(Decoupling {
+                    capacitor: part_index,
+                    ic: ic_index,
+                    power_pad: pad.map(|p| ic.pad_position(p)).map(|p| (p.x, p.y)).unwrap_or((ic.position.x, ic.position.y)),
+                    power_net: power.to_string(),
+                });
+                report.decoupling.push(DecouplingAssignment {
+                    capacitor: instance.reference.clone(),
+                    ic: ic.reference.clone(),
+                    net_name: name(power),
+                });
+            }
+        }
+        let capacitors: HashSet<usize> = decoupling.iter().map(|d| d.capacitor).collect();
+
+        // Clusters: signal-connected parts on one sheet
+        let mut cluster_of: Vec<usize> = (0..parts.len()).collect();
+        fn find(parent: &mut [usize], mut x: usize) -> usize {
+            while parent[x] != x {
+                parent[x] = parent[parent[x]];
+                x = parent[x];
+            }
+            x
+        }
+        let mut by_net: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (i, part) in parts.iter().enumerate().filter(|(i, _)| !capacitors.contains(i)) {
+            for net in part.pads.iter().filter_map(|(_, n)| n.as_deref()).filter(|n| !rail_nets.contains(*n)) {
+                by_net.entry(net).or_default().push(i);
+            }
+        }
+        for members in by_net.values() {
+            for pair in members.windows(2) {
+                if parts[pair[0]].sheet == parts[pair[1]].sheet {
+                    let (a, b) = (find(&mut cluster_of, pair[0]), find(&mut cluster_of, pair[1]));
+                    cluster_of[b] = a;
+                }
+            }
+        }
+        let mut clusters: HashMap<usize, Vec<usize>> = HashMap::new();
+        for i in (0..parts.len()).filter(|i| !capacitors.contains(i)) {
+            clusters.entry(find(&mut cluster_of, i)).or_default().push(i);
+        }
+        let mut clusters: Vec<Vec<usize>> = clusters.into_values().collect();
+        clusters.sort_by_key(|c| (parts[c[0]].sheet, std::cmp::Reverse(c.len()), c[0]));
+
+        let signal_links = |a: &Part, b: &Part| {
+            let nets: HashSet<&str> = a.pads.iter().filter_map(|(_, n)| n.as_deref()).collect();
+            b.pads.iter().filter_map(|(_, n)| n.as_deref()).filter(|n| nets.contains(n) && !rail_nets.contains(*n)).count()
+        };
+
+        let (x0, y0, x1, y1) = board.outline_bounds().unwrap_or((0.0, 0.0, 0.0, 0.0));
+        let board_center = ((x0 + x1) / 2.0, (y0 + y1) / 2.0);
+        let mut spots: HashMap<usize, Spot> = HashMap::new();
+        for cluster in &clusters {
+            // Most connected part first, then whichever is most tied to what's placed
+            let mut remaining = cluster.clone();
+            let mut placed_here: Vec<usize> = Vec::new();
+            while !remaining.is_empty() {
+                let pick = (0..remaining.len()).max_by_key(|&r| {
+                    let part = &parts[remaining[r]];
+                    let links: usize = if placed_here.is_empty() {
+                        cluster.iter().map(|&o| signal_links(part, &parts[o])).sum()
+                    } else {
+                        placed_here.iter().map(|&o| signal_links(part, &parts[o])).sum()
+                    };
+                    (links, std::cmp::Reverse(remaining[r]))
+                }).unwrap_or(0);
+                let part_index = remaining.remove(pick);
+                let part = &parts[part_index];
+                let target = placer.centroid(part).unwrap_or(board_center);
+                let spot = placer.best_spot(part, target, |spot| placer.wirelength(part, spot));
+                match spot {
+                    Some(spot) => {
+                        placer.place(part, spot);
+                        spots.insert(part_index, spot);
+                        placed_here.push(part_index);
+                    }
+                    None => report.unplaced.push(board.footprints[part.index].reference.clone()),
+                }
+            }
+        }
+
+        // Decoupling capacitors hug their IC's power pin, wherever the IC ended up
+        for cap in &decoupling {
+            let part = &parts[cap.capacitor];
+            let power_pad = match parts.iter().position(|p| p.index == cap.ic).and_then(|i| spots.get(&i).map(|s| (i, s))) {
+                Some((ic_part, spot)) => {
+                    let ic = &parts[ic_part];
+                    ic.pads.iter().find(|(_, n)| n.as_deref() == Some(cap.power_net.as_str()))
+                        .map(|(at, _)| place_point(*at, *spot))
+                        .unwrap_or(spot.position)
+                }
+                None => cap.power_pad,
+            };
+            let cost = |spot: Spot| {
+                part.pads.iter().find(|(_, n)| n.as_deref() == Some(cap.power_net.as_str()))
+                    .map(|(at, _)| distance(place_point(*at, spot), power_pad))
+                    .unwrap_or(0.0)
+                    + placer.wirelength(part, spot) * RAIL_WEIGHT
+            };
+            match placer.best_spot(part, power_pad, cost) {
+                Some(spot) => {
+                    placer.place(part, spot);
+                    spots.insert(cap.capacitor, spot);
+                }
+                None => report.unplaced.push(board.footprints[part.index].reference.clone()),
+            }
+        }
+
+        // Move parts to shorter ratsnest where there's room. ICs with decoupling stay put so
+        // their capacitors stay beside them.
+        let anchored: HashSet<usize> = decoupling.iter().map(|d| d.ic).collect();
+        for _ in 0..options.improvement_passes {
+            let mut improved = false;
+            for (part_index, part) in parts.iter().enumerate() {
+                if capacitors.contains(&part_index) || anchored.contains(&part.index) {
+                    continue;
+                }
+                let Some(current) = spots.get(&part_index).copied() else { continue };
+                placer.remove(part);
+                let before = placer.net_length(part, current);
+                let target = placer.centroid(part).unwrap_or(current.position);
+                let better = placer.best_spot(part, target, |spot| placer.net_length(part, spot))
+                    .filter(|spot| placer.net_length(part, *spot) < before - 1e-6);
+                let spot = better.unwrap_or(current);
+                improved |= better.is_some();
+                placer.place(part, spot);
+                spots.insert(part_index, spot);
+            }
+            if !improved {
+                break;
+            }
+        }
+
+        for (part_index, spot) in &spots {
+            let part = &parts[*part_index];
+            let instance = &mut board.footprints[part.index];
+            instance.position = FootprintPoint { x: spot.position.0, y: spot.position.1 };
+            instance.rotation = spot.rotation;
+            instance.side = part.side;
+            report.placed.push(instance.reference.clone());
+        }
+        report.placed.sort();
+        report.ratsnest_after = Ratsnest::build(&board).total_length();
+
+        project.board = Some(board);
+        project.update_modified();
+        Ok(report)
+    }
+}
+
+// Keeps track of what's on the board while parts are added one at a time
+struct Placer {
+    outline: Vec<Vec<Vec2>>, // board edge, then cutouts
+    outline_segments: Vec<(Vec2, Vec2)>,
+    keepouts: Vec<(PlacementSide, Shape)>, // component keepouts and the side they hold parts off
+    grid: f64,
+    occupied: HashMap<usize, (f64, f64, f64, f64)>, // footprint index -> box
+    pads: HashMap<String, Vec<(usize, Vec2)>>,      // net -> footprint index and pad position
+    rail_nets: HashSet<String>,
+}
+
+impl Placer {
+    fn add_pads(&mut self, footprint: usize, pads: impl Iterator<Item = (String, Vec2)>) {
+        for (net, at) in pads {
+            self.pads.entry(net).or_default().push((footprint, at));
+        }
+    }
+
+    fn place(&mut self, part: &Part, spot: Spot) {
+        self.occupied.insert(part.index, place_box(part.bounds, spot));
+        let pads: Vec<(String, Vec2)> = part.pads.iter()
+            .filter_map(|(at, net)| net.clone().map(|n| (n, place_point(*at, spot))))
+            .collect();
+        self.add_pads(part.index, pads.into_iter());
+    }
+
+    fn remove(&mut self, part: &Part) {
+        self.occupied.remove(&part.index);
+        for pads in self.pads.values_mut() {
+            pads.retain(|(f, _)| *f != part.index);
+        }
+    }
+
+    fn weight(&self, net: &str) -> f64 {
+        if self.rail_nets.contains(net) { RAIL_WEIGHT } else { 1.0 }
+    }
+
+    // Mean position of the placed pads sharing a net with the part
+    fn centroid(&self, part: &Part) -> Option<Vec2> {
+        let mut sum = (0.0, 0.0, 0.0);
+        for net in part.pads.iter().filter_map(|(_, n)| n.as_deref()) {
+            let weight = self.weight(net);
+            for (_, at) in self.pads.get(net).into_iter().flatten().filter(|(f, _)| *f != part.index) {
+                sum = (sum.0 + at.0 * weight, sum.1 + at.1 * weight, sum.2 + weight);
+            }
+        }
+        (sum.2 > 0.0).then(|| (sum.0 / sum.2, sum.1 / sum.2))
+    }
+
+    // Each pad's distance to the nearest placed pad of its net: cheap while the board fills up
+    fn wirelength(&self, part: &Part, spot: Spot) -> f64 {
+        part.pads.iter().filter_map(|(at, net)| {
+            let net = net.as_deref()?;
+            let at = place_point(*at, spot);
+            let nearest = self.pads.get(net)?.iter()
+                .filter(|(f, _)| *f != part.index)
+                .map(|(_, p)| distance(at, *p))
+                .fold(f64::INFINITY, f64::min);
+            nearest.is_finite().then(|| nearest * self.weight(net))
+        }).sum()
+    }
+
+    // Spanning-tree length of the part's nets with the part at `spot`
+    fn net_length(&self, part: &Part, spot: Spot) -> f64 {
+        let nets: HashSet<&str> = part.pads.iter().filter_map(|(_, n)| n.as_deref()).collect();
+        nets.into_iter().map(|net| {
+            let mut points: Vec<Vec2> = self.pads.get(net).into_iter().flatten()
+                .filter(|(f, _)| *f != part.index)
+                .map(|(_, p)| *p)
+                .collect();
+            points.extend(part.pads.iter().filter(|(_, n)| n.as_deref() == Some(net)).map(|(at, _)| place_point(*at, spot)));
+            spanning_length(&points) * self.weight(net)
+        }).sum()
+    }
+
+    fn fits(&self, part: &Part, spot: Spot) -> bool {
+        let (x0, y0, x1, y1) = place_box(part.bounds, spot);
+        let corners = [(x0, y0), (x1, y0), (x1, y1), (x0, y1)];
+        if !corners.iter().all(|&c| point_in_contours(c, &self.outline)) {
+            return false;
+        }
+        // Notches in the outline can reach into a box whose corners are all inside
+        let edges = [(corners[0], corners[1]), (corners[1], corners[2]), (corners[2], corners[3]), (corners[3], corners[0])];
+        if self.outline_segments.iter().any(|&(a, b)| edges.iter().any(|&(c, d)| segments_intersect(a, b, c, d))) {
+            return false;
+        }
+        // ...or a cutout can lie wholly inside it
+        if self.outline[1..].iter().any(|cutout| cutout.iter().any(|p| x0 < p.0 && p.0 < x1 && y0 < p.1 && p.1 < y1)) {
+            return false;
+        }
+        if self.occupied.iter().any(|(&f, o)| f != part.index && x0 < o.2 && o.0 < x1 && y0 < o.3 && o.1 < y1) {
+            return false;
+        }
+        let area = Shape::polygon(corners.to_vec());
+        self.keepouts.iter().filter(|(side, _)| *side == part.side).all(|(_, k)| area.distance(k) > 0.0)
+    }
+
+    /// Cheapest legal spot in square rings around `target`, searching a few rings past the
+    /// first one that has any room.
+    fn best_spot(&self, part: &Part, target: Vec2, cost: impl Fn(Spot) -> f64) -> Option<Spot> {
+        let (x0, y0, x1, y1) = self.outline[0].iter()
+            .fold((f64::INFINITY, f64::INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY), |b, p| (b.0.min(p.0), b.1.min(p.1), b.2.max(p.0), b.3.max(p.1)));
+        let center = ((target.0 / self.grid).round() * self.grid, (target.1 / self.grid).round() * self.grid);
+        let reach = (center.0 - x0).abs().max((x1 - center.0).abs()).max((center.1 - y0).abs()).max((y1 - center.1).abs());
+        let rings = (reach / self.grid).ceil() as i64 + 1;
+
+        let mut best: Option<(f64, Spot)> = None;
+        let mut last_ring = rings;
+        for ring in 0..=rings {
+            if ring > last_ring {
+                break;
+            }
+            for (dx, dy) in ring_offsets(ring) {
+                let position = (center.0 + dx as f64 * self.grid, center.1 + dy as f64 * self.grid);
+                for &rotation in &part.rotations {
+                    let spot = Spot { position, rotation };
+                    if !self.fits(part, spot) {
+                        continue;
+                    }
+                    let value = cost(spot);
+                    if best.is_none_or(|(b, _)| value < b) {
+                        best = Some((value, spot));
+                    }
+                    last_ring = last_ring.min(ring + EXTRA_RINGS as i64);
+                }
+            }
+        }
+        best.map(|(_, spot)| spot)
+    }
+}
+
+fn ring_offsets(ring: i64) -> Vec<(i64, i64)> {
+    if ring == 0 {
+        return vec![(0, 0)];
+    }
+    let mut offsets = Vec::with_capacity(8 * ring as usize);
+    for i in -ring..ring {
+        offsets.push((i, -ring));
+        offsets.push((ring, i));
+        offsets.push((-i, ring));
+        offsets.push((-ring, -i));
+    }
+    offsets
+}
+
+// A point of the part's own frame on the board, as FootprintInstance::transform does after mirroring
+fn place_point(at: Vec2, spot: Spot) -> Vec2 {
+    let (sin, cos) = spot.rotation.to_radians().sin_cos();
+    (spot.position.0 + at.0 * cos - at.1 * sin, spot.position.1 + at.0 * sin + at.1 * cos)
+}
+
+// As in DRC, a keepout applies to the side whose courtyard or outer copper it covers
+fn keeps_side(keepout: &KeepoutArea, side: PlacementSide) -> bool {
+    let layers = match side {
+        PlacementSide::Top => [LayerType::TopCourtyard, LayerType::TopCopper],
+        PlacementSide::Bottom => [LayerType::BottomCourtyard, LayerType::BottomCopper],
+    };
+    layers.iter().any(|l| keepout.layers.contains(l))
+}
+
+fn place_box((x0, y0, x1, y1): (f64, f64, f64, f64), spot: Spot) -> (f64, f64, f64, f64) {
+    [(x0, y0), (x1, y0), (x1, y1), (x0, y1)].iter()
+        .map(|&c| place_point(c, spot))
+        .fold((f64::INFINITY, f64::INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY), |b, p| (b.0.min(p.0), b.1.min(p.1), b.2.max(p.0), b.3.max(p.1)))
+}
+
+fn grow((x0, y0, x1, y1): (f64, f64, f64, f64), margin: f64) -> (f64, f64, f64, f64) {
+    (x0 - margin, y0 - margin, x1 + margin, y1 + margin)
+}
+
+// Courtyard box, or the pads' when the footprint has no courtyard
+fn footprint_bounds(instance: &crate::models::FootprintInstance) -> (f64, f64, f64, f64) {
+    let mut shapes = instance.courtyard();
+    if shapes.is_empty() {
+        shapes = instance.footprint.pads.iter().map(|p| instance.pad_shape(p)).collect();
+    }
+    if shapes.is_empty() {
+        let (x, y) = (instance.position.x, instance.position.y);
+        return (x, y, x, y);
+    }
+    shapes.iter().map(|s| s.bounds())
+        .fold((f64::INFINITY, f64::INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY), |b, s| (b.0.min(s.0), b.1.min(s.1), b.2.max(s.2), b.3.max(s.3)))
+}
+
+// Prim's minimum spanning tree over the points
+fn spanning_length(points: &[Vec2]) -> f64 {
+    if points.len() < 2 {
+        return 0.0;
+    }
+    let mut best: Vec<f64> = points.iter().map(|p| distance(*p, points[0])).collect();
+    let mut joined = vec![false; points.len()];
+    joined[0] = true;
+    let mut total = 0.0;
+    for _ in 1..points.len() {
+        let Some(next) = (0..points.len()).filter(|&i| !joined[i]).min_by(|&a, &b| best[a].total_cmp(&best[b])) else { break };
+        joined[next] = true;
+        total += best[next];
+        for i in 0..points.len() {
+            if !joined[i] {
+                best[i] = best[i].min(distance(points[i], points[next]));
+            }
+        }
+    }
+    total
+}
+
+fn is_power(net_name: &str) -> bool {
+    !is_ground(net_name) && POWER_NETS.iter().any(|p| glob_matches(p, net_name))
+}
+
+fn is_ground(net_name: &str) -> bool {
+    GROUND_NETS.iter().any(|p| glob_matches(p, net_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Board, BoardNet, KeepoutRestrictions};
+    use crate::services::test_fixtures::place;
+
+    #[test]
+    fn test_auto_place_with_decoupling() {
+        let mut project = Project::new("Placement".to_string()).unwrap();
+        let mut board = Board::new("Placement".to_string()).unwrap();
+        board.set_rectangular_outline(40.0, 30.0).unwrap();
+        board.nets = ["vcc", "gnd", "a", "b", "c"].iter()
+            .map(|n| BoardNet { id: n.to_string(), name: if *n == "vcc" { "+3V3".to_string() } else { n.to_uppercase() } })
+            .collect();
+
+        type PartSpec<'a> = (&'a str, &'a str, &'a [(&'a str, &'a str)]);
+        let parts: [PartSpec; 6] = [
+            ("U1", "SOIC-8", &[("8", "vcc"), ("4", "gnd"), ("1", "a"), ("2", "b")]),
+            ("U2", "SOIC-8", &[("8", "vcc"), ("4", "gnd"), ("1", "c")]),
+            ("R1", "R_0805", &[("1", "a"), ("2", "c")]),
+            ("R2", "R_0805", &[("1", "b"), ("2", "gnd")]),
+            ("C1", "C_0805", &[("1", "vcc"), ("2", "gnd")]),
+            ("C2", "C_0805", &[("1", "vcc"), ("2", "gnd")]),
+        ];
+        // Staged in a row off the board, as an update from the schematic leaves them
+        for (i, (reference, footprint, nets)) in parts.iter().enumerate() {
+            place(&mut board, footprint, reference, 50.0 + 8.0 * i as f64, 5.0, nets);
+        }
+        board.footprints[1].locked = true;
+        board.footprints[1].position = FootprintPoint { x: 30.0, y: 20.0 };
+        project.board = Some(board);
+
+        let report = PlacementService::auto_place(&mut project, &PlacementOptions::default()).unwrap();
+        assert!(report.unplaced.is_empty());
+        assert_eq!(report.placed, vec!["C1", "C2", "R1", "R2", "U1"]);
+        assert!(report.ratsnest_after < report.ratsnest_before);
+        let mut pairs: Vec<(String, String)> = report.decoupling.iter().map(|d| (d.capacitor.clone(), d.ic.clone())).collect();
+        pairs.sort();
+        assert_eq!(pairs, vec![("C1".to_string(), "U1".to_string()), ("C2".to_string(), "U2".to_string())]);
+
+        let board = project.board.as_ref().unwrap();
+        let find = |reference: &str| board.footprints.iter().find(|f| f.reference == reference).unwrap();
+        assert_eq!((find("U2").position.x, find("U2").position.y), (30.0, 20.0), "locked parts stay");
+        let pin = |reference: &str, pad: &str| {
+            let f = find(reference);
+            let p = f.footprint.pads.iter().find(|p| p.pad_number == pad).unwrap();
+            let at = f.pad_position(p);
+            (at.x, at.y)
+        };
+        // Each capacitor sits right beside its IC, power pin towards the IC's
+        let options = PlacementOptions::default();
+        for (cap, ic) in [("C1", "U1"), ("C2", "U2")] {
+            let (c, u) = (footprint_bounds(find(cap)), footprint_bounds(find(ic)));
+            let gap = (c.0 - u.2).max(u.0 - c.2).max(c.1 - u.3).max(u.1 - c.3);
+            assert!(gap <= options.spacing + options.grid, "{} is {} mm from {}", cap, gap, ic);
+            assert!(distance(pin(cap, "1"), pin(ic, "8")) < distance(pin(cap, "2"), pin(ic, "8")));
+        }
+
+        // Everything is on the board and clear of each other
+        let boxes: Vec<_> = board.footprints.iter().map(footprint_bounds).collect();
+        for (i, a) in boxes.iter().enumerate() {
+            assert!(a.0 >= 0.0 && a.1 >= 0.0 && a.2 <= 40.0 && a.3 <= 30.0);
+            for b in &boxes[i + 1..] {
+                assert!(a.2 <= b.0 || b.2 <= a.0 || a.3 <= b.1 || b.3 <= a.1);
+            }
+        }
+
+        // A subset run only moves what was selected
+        let before: Vec<_> = board.footprints.iter().map(|f| (f.position.x, f.position.y)).collect();
+        let options = PlacementOptions { footprint_ids: vec!["R2".to_string()], ..Default::default() };
+        let report = PlacementService::auto_place(&mut project, &options).unwrap();
+        assert_eq!(report.placed, vec!["R2"]);
+        let board = project.board.as_ref().unwrap();
+        for (f, old) in board.footprints.iter().zip(before) {
+            if f.reference != "R2" {
+                assert_eq!((f.position.x, f.position.y), old);
+            }
+        }
+    }
+
+    #[test]
+    fn test_auto_place_avoids_component_keepouts() {
+        let mut project = Project::new("Keepout".to_string()).unwrap();
+        let mut board = Board::new("Keepout".to_string()).unwrap();
+        board.set_rectangular_outline(30.0, 10.0).unwrap();
+        board.nets = vec![BoardNet { id: "a".to_string(), name: "A".to_string() }];
+        // Everything left of x = 20 is off limits to parts, but not to copper
+        let point = |x, y| FootprintPoint { x, y };
+        board.keepouts.push(KeepoutArea {
+            area: vec![point(-1.0, -1.0), point(20.0, -1.0), point(20.0, 11.0), point(-1.0, 11.0)],
+            layers: vec![LayerType::TopCopper, LayerType::BottomCopper],
+            restrictions: KeepoutRestrictions { no_copper: false, no_vias: false, no_components: true },
+        });
+        place(&mut board, "R_0805", "R1", 40.0, 5.0, &[("1", "a")]);
+        place(&mut board, "R_0805", "R2", 45.0, 5.0, &[("1", "a")]);
+        project.board = Some(board);
+
+        let report = PlacementService::auto_place(&mut project, &PlacementOptions::default()).unwrap();
+        assert!(report.unplaced.is_empty());
+        for f in &project.board.as_ref().unwrap().footprints {
+            let (x0, _, x1, _) = footprint_bounds(f);
+            assert!(x0 > 20.0 && x1 <= 30.0, "{} spans {}..{}", f.reference, x0, x1);
+        }
+
+        // With the whole board kept out there is nowhere to go
+        let board = project.board.as_mut().unwrap();
+        board.keepouts[0].area[1].x = 31.0;
+        board.keepouts[0].area[2].x = 31.0;
+        let report = PlacementService::auto_place(&mut project, &PlacementOptions::default()).unwrap();
+        assert_eq!(report.unplaced, vec!["R1", "R2"]);
+
+        // Kept out of the bottom only, the top side is free and the bottom part has nowhere to go
+        let board = project.board.as_mut().unwrap();
+        board.keepouts[0].layers = vec![LayerType::BottomCopper];
+        board.footprints[1].side = PlacementSide::Bottom;
+        let report = PlacementService::auto_place(&mut project, &PlacementOptions::default()).unwrap();
+        assert_eq!(report.placed, vec!["R1"]);
+        assert_eq!(report.unplaced, vec!["R2"]);
+    }
+}
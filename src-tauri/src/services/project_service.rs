@@ -1,5 +1,6 @@
 use crate::models::{Project, Schematic};
 use crate::utils::error::{AppError, Result};
+use super::file_service::FileService;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -42,12 +43,12 @@ impl ProjectService {
 
     pub async fn save_project(&self, project: &Project, path: &str) -> Result<()> {
         let json = project.to_json()?;
+        let path = PathBuf::from(path);
         
-        fs::write(path, json)
+        // The project file carries the project-local library, so never leave it half-written
+        tokio::task::spawn_blocking(move || FileService::write_atomic(&path, json.as_bytes()))
             .await
-            .map_err(|e| AppError::IoError(e.to_string()))?;
-        
-        Ok(())
+            .map_err(|e| AppError::ThreadError(e.to_string()))?
     }
 
     pub async fn get_current_project(&self) -> Option<Project> {
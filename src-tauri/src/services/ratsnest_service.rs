@@ -0,0 +1,165 @@
+use crate::models::Board;
+use crate::services::drc_service::{connectivity, copper_items, CopperItem, CopperKind};
+use crate::utils::geometry::distance;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RatsnestEnd {
+    pub footprint_id: String,
+    pub pad_number: String,
+    pub x: f64,
+    pub y: f64,
+}
+
+/// An unrouted connection between two pads of the same net.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RatsnestLine {
+    pub net_id: String,
+    pub from: RatsnestEnd,
+    pub to: RatsnestEnd,
+    pub length: f64,
+}
+
+/// Airwires for every net: a minimum spanning tree over the net's pads in which pads
+/// already joined by copper count as one node, so only unrouted connections remain.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ratsnest {
+    pub nets: BTreeMap<String, Vec<RatsnestLine>>, // net id -> lines
+}
+
+impl Ratsnest {
+    pub fn build(board: &Board) -> Self {
+        let mut ratsnest = Self::default();
+        let nets: HashSet<String> = board.nets.iter().map(|n| n.id.clone()).collect();
+        ratsnest.recompute(board, &nets);
+        ratsnest
+    }
+
+    /// Refreshes only the nets reaching a pad of the given footprints, after they were moved.
+    pub fn update_footprints(&mut self, board: &Board, footprint_ids: &[String]) {
+        let nets: HashSet<String> = board.footprints.iter()
+            .filter(|f| footprint_ids.contains(&f.id))
+            .flat_map(|f| f.pad_nets.values().cloned())
+            .collect();
+        self.recompute(board, &nets);
+    }
+
+    /// Refreshes the given nets, e.g. after tracks or vias on them changed.
+    pub fn update_nets(&mut self, board: &Board, net_ids: &[String]) {
+        self.recompute(board, &net_ids.iter().cloned().collect());
+    }
+
+    fn recompute(&mut self, board: &Board, nets: &HashSet<String>) {
+        let mut items: BTreeMap<&str, Vec<CopperItem>> = nets.iter().map(|n| (n.as_str(), Vec::new())).collect();
+        for item in copper_items(board) {
+            if let Some(group) = item.net_id.as_deref().and_then(|n| items.get_mut(n)) {
+                group.push(item);
+            }
+        }
+        for (net, group) in items {
+            let lines = net_lines(net, &group);
+            if lines.is_empty() {
+                self.nets.remove(net);
+            } else {
+                self.nets.insert(net.to_string(), lines);
+            }
+        }
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &RatsnestLine> {
+        self.nets.values().flatten()
+    }
+
+    pub fn unrouted_count(&self) -> usize {
+        self.lines().count()
+    }
+
+    pub fn total_length(&self) -> f64 {
+        self.lines().map(|l| l.length).sum()
+    }
+}
+
+fn find(parent: &mut [usize], mut x: usize) -> usize {
+    while parent[x] != x {
+        parent[x] = parent[parent[x]];
+        x = parent[x];
+    }
+    x
+}
+
+// Kruskal over the net's pads, seeded with the copper that already connects them
+fn net_lines(net_id: &str, items: &[CopperItem]) -> Vec<RatsnestLine> {
+    let mut parent = connectivity(items);
+    let pads: Vec<usize> = (0..items.len()).filter(|&i| items[i].kind == CopperKind::Pad).collect();
+    let centers: Vec<_> = items.iter().map(|i| i.shape.center()).collect();
+
+    let mut edges: Vec<(f64, usize, usize)> = Vec::new();
+    for (n, &a) in pads.iter().enumerate() {
+        for &b in &pads[n + 1..] {
+            edges.push((distance(centers[a], centers[b]), a, b));
+        }
+    }
+    edges.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+    let end = |index: usize| RatsnestEnd {
+        footprint_id: items[index].id.clone(),
+        pad_number: items[index].pad_number.clone().unwrap_or_default(),
+        x: centers[index].0,
+        y: centers[index].1,
+    };
+    let mut lines = Vec::new();
+    for (length, a, b) in edges {
+        let (ra, rb) = (find(&mut parent, a), find(&mut parent, b));
+        if ra == rb {
+            continue;
+        }
+        parent[rb] = ra;
+        lines.push(RatsnestLine { net_id: net_id.to_string(), from: end(a), to: end(b), length });
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BoardNet, FootprintPoint, LayerType, Track};
+    use crate::services::test_fixtures::place;
+
+    #[test]
+    fn test_ratsnest_mst_and_incremental_update() {
+        let mut board = Board::new("Test".to_string()).unwrap();
+        board.nets = vec![
+            BoardNet { id: "a".to_string(), name: "A".to_string() },
+            BoardNet { id: "b".to_string(), name: "B".to_string() },
+        ];
+        // Four resistors on a 10 mm square, pin 1 on net A and pin 2 on net B
+        for (i, (x, y)) in [(10.0, 10.0), (20.0, 10.0), (20.0, 20.0), (10.0, 20.0)].into_iter().enumerate() {
+            place(&mut board, "R_0805", &format!("R{}", i + 1), x, y, &[("1", "a"), ("2", "b")]);
+        }
+
+        let ratsnest = Ratsnest::build(&board);
+        assert_eq!(ratsnest.nets["a"].len(), 3);
+        assert_eq!(ratsnest.unrouted_count(), 6);
+        assert!((ratsnest.total_length() - 60.0).abs() < 1e-9, "three sides per net");
+
+        // Routing R1 pin 1 to R4 pin 1 removes that airwire
+        board.add_track(Track::new(
+            Some("a".to_string()), LayerType::TopCopper, FootprintPoint { x: 9.05, y: 10.0 }, FootprintPoint { x: 9.05, y: 20.0 }, 0.25,
+        )).unwrap();
+        let mut ratsnest = Ratsnest::build(&board);
+        assert_eq!(ratsnest.nets["a"].len(), 2);
+        assert_eq!(ratsnest.nets["b"].len(), 3);
+
+        // Moving R3 only touches its nets and matches a full rebuild
+        board.move_footprint("R3", FootprintPoint { x: 40.0, y: 20.0 }, 90.0).unwrap();
+        ratsnest.update_footprints(&board, &["R3".to_string()]);
+        let rebuilt = Ratsnest::build(&board);
+        assert_eq!(ratsnest.unrouted_count(), rebuilt.unrouted_count());
+        assert!((ratsnest.total_length() - rebuilt.total_length()).abs() < 1e-9);
+        assert!(ratsnest.total_length() > 50.0);
+    }
+}
@@ -0,0 +1,136 @@
+use super::circuit::{Circuit, ElementKind, Node};
+use super::dc::{linearize, DcSolver};
+use super::mna::{Complex, Mna, Scalar};
+use super::transient::column;
+use crate::models::{AcSweepSettings, AnalysisType, ProbeTarget, Schematic, Waveforms};
+use crate::utils::error::{AppError, Result};
+
+const GMIN: f64 = 1e-12;
+
+/// Small-signal frequency sweep around the DC operating point. Sources are driven by their
+/// `ac` magnitude and phase; every other source is held at zero.
+pub struct AcSolver;
+
+impl AcSolver {
+    pub fn run(schematic: &Schematic, settings: &AcSweepSettings) -> Result<Waveforms> {
+        settings.validate()?;
+        let circuit = Circuit::from_schematic(schematic)?;
+        Self::sweep(&circuit, settings)
+    }
+
+    pub fn sweep(circuit: &Circuit, settings: &AcSweepSettings) -> Result<Waveforms> {
+        let has_stimulus = circuit.elements.iter().any(|e| match &e.kind {
+            ElementKind::VoltageSource(source) | ElementKind::CurrentSource(source) => source.ac_magnitude != 0.0,
+            _ => false,
+        });
+        if !has_stimulus {
+            return Err(AppError::InvalidOperation(
+                "No source has an AC stimulus; set the `ac` property on the input source".to_string()
+            ));
+        }
+
+        let (operating_point, _) = DcSolver::solve(circuit)?;
+        let linearized: Vec<_> = circuit.elements.iter().map(|e| linearize(e, &operating_point)).collect();
+
+        let mut columns = vec![column("frequency", "Hz", None)];
+        for net in &circuit.nets {
+            let target = Some(ProbeTarget::Voltage { net_id: net.id.clone() });
+            columns.push(column(&format!("|V({})|", net.name), "V", target.clone()));
+            columns.push(column(&format!("phase V({})", net.name), "deg", target));
+        }
+
+        for frequency in settings.frequencies() {
+            let omega = std::f64::consts::TAU * frequency;
+            let mut mna: Mna<Complex> = Mna::new(circuit.node_count, circuit.branch_count());
+            let mut branch = 0;
+            for (element, linearization) in circuit.elements.iter().zip(&linearized) {
+                let nodes = &element.nodes;
+                match &element.kind {
+                    ElementKind::Resistor { resistance } => mna.conductance(nodes[0], nodes[1], Complex::from(1.0 / resistance)),
+                    ElementKind::Capacitor { capacitance } => mna.conductance(nodes[0], nodes[1], Complex::new(0.0, omega * capacitance)),
+                    ElementKind::Inductor { inductance } => {
+                        mna.voltage_branch(branch, nodes[0], nodes[1], Complex::default());
+                        mna.branch_impedance(branch, Complex::new(0.0, omega * inductance));
+                        branch += 1;
+                    }
+                    ElementKind::VoltageSource(source) => {
+                        mna.voltage_branch(branch, nodes[0], nodes[1], Complex::from_polar(source.ac_magnitude, source.ac_phase));
+                        branch += 1;
+                    }
+                    ElementKind::CurrentSource(source) => {
+                        mna.current(nodes[0], nodes[1], Complex::from_polar(source.ac_magnitude, source.ac_phase));
+                    }
+                    _ => {
+                        if let Some(linearization) = linearization {
+                            mna.small_signal(nodes, linearization);
+                        }
+                    }
+                }
+            }
+            for node in 0..circuit.node_count {
+                mna.conductance(Some(node), None, Complex::from(GMIN));
+            }
+
+            let solution = mna.solve().ok_or_else(|| AppError::InvalidOperation(format!(
+                "Singular circuit matrix at {} Hz", frequency
+            )))?;
+
+            columns[0].values.push(frequency);
+            for (index, net) in circuit.nets.iter().enumerate() {
+                let phasor = phasor(&solution, net.node);
+                columns[1 + 2 * index].values.push(phasor.magnitude());
+                columns[2 + 2 * index].values.push(phasor.phase_degrees());
+            }
+        }
+
+        Ok(Waveforms {
+            analysis: AnalysisType::Ac,
+            columns,
+            unsupported: circuit.unsupported.clone(),
+            simulated_at: chrono::Utc::now(),
+        })
+    }
+}
+
+fn phasor(solution: &[Complex], node: Node) -> Complex {
+    node.map_or(Complex::default(), |n| solution[n])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::simulation::circuit::{CircuitNet, Element, Source};
+
+    #[test]
+    fn test_rc_low_pass_corner() {
+        let element = |reference: &str, nodes: Vec<Node>, kind: ElementKind| Element {
+            component_id: reference.to_string(),
+            reference: reference.to_string(),
+            pin_ids: vec!["1".to_string(), "2".to_string()],
+            nodes,
+            kind,
+        };
+        let circuit = Circuit {
+            node_count: 2,
+            nets: vec![CircuitNet { id: "out".to_string(), name: "OUT".to_string(), node: Some(1) }],
+            elements: vec![
+                element("V1", vec![Some(0), None], ElementKind::VoltageSource(Source { dc: 0.0, waveform: None, ac_magnitude: 1.0, ac_phase: 0.0 })),
+                element("R1", vec![Some(0), Some(1)], ElementKind::Resistor { resistance: 1000.0 }),
+                element("C1", vec![Some(1), None], ElementKind::Capacitor { capacitance: 1e-6 }),
+            ],
+            unsupported: Vec::new(),
+        };
+
+        let corner = 1.0 / (std::f64::consts::TAU * 1e-3);
+        let settings = AcSweepSettings { start_frequency: corner, stop_frequency: corner * 100.0, points_per_decade: 10 };
+        let waveforms = AcSolver::sweep(&circuit, &settings).unwrap();
+        assert_eq!(waveforms.len(), 21);
+
+        let magnitude = waveforms.column("|V(OUT)|").unwrap();
+        let phase = waveforms.column("phase V(OUT)").unwrap();
+        assert!((magnitude.values[0] - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-6);
+        assert!((phase.values[0] + 45.0).abs() < 1e-6);
+        // -20 dB per decade well above the corner
+        assert!((magnitude.values[20] - 0.01).abs() < 1e-4);
+    }
+}
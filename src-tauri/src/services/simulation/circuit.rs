@@ -0,0 +1,699 @@
+use crate::models::component::{reference_prefix, Component, Pin};
+use crate::models::Schematic;
+use crate::utils::error::{AppError, Result};
+use crate::utils::units::parse_si_value;
+use std::collections::HashMap;
+
+/// Index of a non-ground node; `None` is the ground reference.
+pub type Node = Option<usize>;
+
+pub const THERMAL_VOLTAGE: f64 = 0.025852;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Polarity {
+    N,
+    P,
+}
+
+impl Polarity {
+    pub fn sign(self) -> f64 {
+        match self {
+            Polarity::N => 1.0,
+            Polarity::P => -1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DiodeModel {
+    pub saturation_current: f64,
+    pub emission: f64,
+    // Reverse breakdown voltage (zener), as a positive number
+    pub breakdown: Option<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BjtModel {
+    pub polarity: Polarity,
+    pub saturation_current: f64,
+    pub beta_forward: f64,
+    pub beta_reverse: f64,
+}
+
+// Level-1 square-law model; the threshold is a magnitude for both polarities
+#[derive(Debug, Clone)]
+pub struct MosfetModel {
+    pub polarity: Polarity,
+    pub threshold: f64,
+    pub transconductance: f64,
+    pub lambda: f64,
+}
+
+/// Time-varying source shape, written in a source's `waveform` property with SPICE syntax:
+/// `PULSE(v1 v2 delay rise fall width period)`, `SIN(offset amplitude freq [delay [damping]])`
+/// or `PWL(t1 v1 t2 v2 ...)`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Waveform {
+    Pulse { initial: f64, pulsed: f64, delay: f64, rise: f64, fall: f64, width: f64, period: f64 },
+    Sine { offset: f64, amplitude: f64, frequency: f64, delay: f64, damping: f64 },
+    PiecewiseLinear(Vec<(f64, f64)>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Source {
+    pub dc: f64,
+    pub waveform: Option<Waveform>,
+    // AC sweep stimulus; sources with zero magnitude are quiet in the sweep
+    pub ac_magnitude: f64,
+    pub ac_phase: f64,
+}
+
+/// Terminal order per kind: two-terminal parts `[p, n]`, diodes `[anode, cathode]`,
+/// BJTs `[collector, base, emitter]` and MOSFETs `[drain, gate, source]`.
+#[derive(Debug, Clone)]
+pub enum ElementKind {
+    Resistor { resistance: f64 },
+    Capacitor { capacitance: f64 },
+    Inductor { inductance: f64 },
+    VoltageSource(Source),
+    // Current flows from `p` through the source to `n`
+    CurrentSource(Source),
+    Diode(DiodeModel),
+    Bjt(BjtModel),
+    Mosfet(MosfetModel),
+}
+
+#[derive(Debug, Clone)]
+pub struct Element {
+    pub component_id: String,
+    pub reference: String,
+    pub pin_ids: Vec<String>,
+    pub nodes: Vec<Node>,
+    pub kind: ElementKind,
+}
+
+#[derive(Debug, Clone)]
+pub struct CircuitNet {
+    pub id: String,
+    pub name: String,
+    pub node: Node,
+}
+
+/// Simulation view of a schematic: nets mapped to MNA nodes and components to elements.
+#[derive(Debug, Clone)]
+pub struct Circuit {
+    pub node_count: usize,
+    pub nets: Vec<CircuitNet>,
+    pub elements: Vec<Element>,
+    pub unsupported: Vec<String>,
+}
+
+impl Element {
+    /// Voltage sources and inductors carry their current as an extra MNA unknown.
+    pub fn has_branch(&self) -> bool {
+        matches!(self.kind, ElementKind::VoltageSource(_) | ElementKind::Inductor { .. })
+    }
+}
+
+impl Waveform {
+    pub fn parse(text: &str) -> Result<Self> {
+        let invalid = || AppError::InvalidInput(format!("Invalid source waveform \"{}\"", text));
+        let (name, rest) = text.split_once('(').ok_or_else(invalid)?;
+        let args = rest.trim_end().strip_suffix(')').ok_or_else(invalid)?
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|arg| !arg.is_empty())
+            .map(|arg| parse_si_value(arg).ok_or_else(invalid))
+            .collect::<Result<Vec<f64>>>()?;
+        let arg = |index: usize| args.get(index).copied().unwrap_or(0.0);
+
+        match name.trim().to_uppercase().as_str() {
+            "PULSE" if args.len() >= 2 => Ok(Waveform::Pulse {
+                initial: arg(0),
+                pulsed: arg(1),
+                delay: arg(2),
+                rise: arg(3),
+                fall: arg(4),
+                width: args.get(5).copied().unwrap_or(f64::INFINITY),
+                period: arg(6),
+            }),
+            "SIN" | "SINE" if args.len() >= 3 => Ok(Waveform::Sine {
+                offset: arg(0),
+                amplitude: arg(1),
+                frequency: arg(2),
+                delay: arg(3),
+                damping: arg(4),
+            }),
+            "PWL" if args.len() >= 2 && args.len().is_multiple_of(2) => {
+                let points: Vec<(f64, f64)> = args.chunks(2).map(|pair| (pair[0], pair[1])).collect();
+                if points.windows(2).any(|w| w[1].0 < w[0].0) {
+                    return Err(invalid());
+                }
+                Ok(Waveform::PiecewiseLinear(points))
+            }
+            _ => Err(invalid()),
+        }
+    }
+
+    /// SPICE source syntax, the inverse of [`Waveform::parse`].
+    pub fn to_spice(&self) -> String {
+        match self {
+            Waveform::Pulse { initial, pulsed, delay, rise, fall, width, period } => {
+                // An endless pulse leaves width and period to SPICE's defaults
+                if width.is_finite() {
+                    format!("PULSE({} {} {} {} {} {} {})", initial, pulsed, delay, rise, fall, width, period)
+                } else {
+                    format!("PULSE({} {} {} {} {})", initial, pulsed, delay, rise, fall)
+                }
+            }
+            Waveform::Sine { offset, amplitude, frequency, delay, damping } => {
+                format!("SIN({} {} {} {} {})", offset, amplitude, frequency, delay, damping)
+            }
+            Waveform::PiecewiseLinear(points) => {
+                let pairs: Vec<String> = points.iter().map(|(t, v)| format!("{} {}", t, v)).collect();
+                format!("PWL({})", pairs.join(" "))
+            }
+        }
+    }
+
+    pub fn value_at(&self, time: f64) -> f64 {
+        match self {
+            Waveform::Pulse { initial, pulsed, delay, rise, fall, width, period } => {
+                if time < *delay {
+                    return *initial;
+                }
+                let mut local = time - delay;
+                if *period > 0.0 {
+                    local %= period;
+                }
+                if local < *rise {
+                    initial + (pulsed - initial) * local / rise
+                } else if local < rise + width {
+                    *pulsed
+                } else if local < rise + width + fall {
+                    pulsed + (initial - pulsed) * (local - rise - width) / fall
+                } else {
+                    *initial
+                }
+            }
+            Waveform::Sine { offset, amplitude, frequency, delay, damping } => {
+                if time < *delay {
+                    return *offset;
+                }
+                let local = time - delay;
+                offset + amplitude * (-damping * local).exp() * (std::f64::consts::TAU * frequency * local).sin()
+            }
+            Waveform::PiecewiseLinear(points) => {
+                let after = points.iter().position(|(t, _)| *t > time);
+                match after {
+                    Some(0) => points[0].1,
+                    Some(i) => {
+                        let (t0, v0) = points[i - 1];
+                        let (t1, v1) = points[i];
+                        v0 + (v1 - v0) * (time - t0) / (t1 - t0)
+                    }
+                    None => points[points.len() - 1].1,
+                }
+            }
+        }
+    }
+}
+
+impl Source {
+    /// Value at `time` during a transient, or the DC value when `time` is `None`.
+    pub fn value(&self, time: Option<f64>) -> f64 {
+        match (time, &self.waveform) {
+            (Some(time), Some(waveform)) => waveform.value_at(time),
+            _ => self.dc,
+        }
+    }
+}
+
+impl Circuit {
+    pub fn from_schematic(schematic: &Schematic) -> Result<Self> {
+        let nets = schematic.generate_netlist();
+        let mut pin_nets: HashMap<(&str, &str), usize> = HashMap::new();
+        for (index, net) in nets.iter().enumerate() {
+            for pin in &net.pins {
+                pin_nets.insert((pin.component_id.as_str(), pin.pin_id.as_str()), index);
+            }
+        }
+
+        // Nets touching a ground symbol, or named like ground, are the reference node
+        let grounded: Vec<bool> = nets.iter()
+            .map(|net| {
+                matches!(net.name.to_lowercase().as_str(), "gnd" | "0")
+                    || net.pins.iter().any(|p| {
+                        schematic.get_component(&p.component_id)
+                            .is_some_and(|c| matches!(classify(c), Some(DeviceClass::Ground)))
+                    })
+            })
+            .collect();
+        if !grounded.contains(&true) {
+            return Err(AppError::InvalidOperation(
+                "Circuit has no ground reference; add a ground symbol or a GND net".to_string()
+            ));
+        }
+
+        let mut node_count = 0;
+        let circuit_nets: Vec<CircuitNet> = nets.iter().zip(&grounded)
+            .map(|(net, &ground)| {
+                let node = if ground {
+                    None
+                } else {
+                    node_count += 1;
+                    Some(node_count - 1)
+                };
+                CircuitNet { id: net.id.clone(), name: net.name.clone(), node }
+            })
+            .collect();
+
+        let mut circuit = Circuit { node_count, nets: circuit_nets, elements: Vec::new(), unsupported: Vec::new() };
+
+        for component in &schematic.components {
+            let Some(class) = classify(component) else {
+                circuit.unsupported.push(component.display_reference());
+                continue;
+            };
+            if matches!(class, DeviceClass::Ground) {
+                continue;
+            }
+
+            // Unconnected pins get a node of their own
+            let mut node_of = |pin: &Pin| -> Node {
+                match pin_nets.get(&(component.id.as_str(), pin.id.as_str())) {
+                    Some(&net) => circuit.nets[net].node,
+                    None => {
+                        circuit.node_count += 1;
+                        Some(circuit.node_count - 1)
+                    }
+                }
+            };
+            let mut terminals = Vec::new();
+            for names in class.terminal_names() {
+                let pin = find_pin(component, names, terminals.len())
+                    .ok_or_else(|| AppError::InvalidInput(format!("{}: missing pin {}", component.display_reference(), names[0])))?;
+                terminals.push((pin.id.clone(), node_of(pin)));
+            }
+
+            circuit.elements.extend(build_elements(component, &class, terminals)?);
+        }
+
+        Ok(circuit)
+    }
+
+    pub fn branch_count(&self) -> usize {
+        self.elements.iter().filter(|e| e.has_branch()).count()
+    }
+}
+
+#[derive(Debug, Clone)]
+enum DeviceClass {
+    Resistor,
+    Potentiometer,
+    Capacitor,
+    Inductor,
+    // Fuses and other near-zero resistances
+    Short,
+    Diode { emission: f64, saturation_current: f64, zener: bool, led: bool },
+    Bjt(Polarity),
+    Mosfet(Polarity),
+    VoltageSource,
+    CurrentSource,
+    PowerSupply,
+    // Mains-style sine source whose value is the RMS voltage
+    AcSource,
+    Ground,
+}
+
+impl DeviceClass {
+    // Candidate pin names or ids per terminal, in terminal order
+    fn terminal_names(&self) -> Vec<&'static [&'static str]> {
+        match self {
+            DeviceClass::Potentiometer => vec![&["CCW", "1"], &["Wiper", "W", "2"], &["CW", "3"]],
+            DeviceClass::Diode { .. } => vec![&["Anode", "A"], &["Cathode", "K"]],
+            DeviceClass::Bjt(_) => vec![&["Collector", "C"], &["Base", "B"], &["Emitter", "E"]],
+            DeviceClass::Mosfet(_) => vec![&["Drain", "D"], &["Gate", "G"], &["Source", "S"]],
+            DeviceClass::VoltageSource | DeviceClass::CurrentSource => vec![&["Positive", "+"], &["Negative", "-"]],
+            DeviceClass::PowerSupply => vec![&["DC+"], &["DC-"]],
+            DeviceClass::AcSource => vec![&["Live", "L"], &["Neutral", "N"]],
+            DeviceClass::Ground => Vec::new(),
+            _ => vec![&["1"], &["2"]],
+        }
+    }
+}
+
+fn classify(component: &Component) -> Option<DeviceClass> {
+    let diode = |emission: f64, saturation_current: f64| DeviceClass::Diode { emission, saturation_current, zener: false, led: false };
+    let class = match component.type_id.as_str() {
+        "resistor" | "thermistor" => DeviceClass::Resistor,
+        "potentiometer" => DeviceClass::Potentiometer,
+        "capacitor" | "variable-capacitor" => DeviceClass::Capacitor,
+        "inductor" => DeviceClass::Inductor,
+        "fuse" => DeviceClass::Short,
+        "diode" | "photodiode" => diode(1.0, 1e-14),
+        "schottky-diode" => diode(1.05, 1e-8),
+        "zener-diode" => DeviceClass::Diode { emission: 1.0, saturation_current: 1e-14, zener: true, led: false },
+        "led" => DeviceClass::Diode { emission: 2.0, saturation_current: 1e-18, zener: false, led: true },
+        "npn-transistor" => DeviceClass::Bjt(Polarity::N),
+        "pnp-transistor" => DeviceClass::Bjt(Polarity::P),
+        "n-mosfet" => DeviceClass::Mosfet(Polarity::N),
+        "p-mosfet" => DeviceClass::Mosfet(Polarity::P),
+        "battery" | "voltage-source" => DeviceClass::VoltageSource,
+        "current-source" => DeviceClass::CurrentSource,
+        "power-supply" => DeviceClass::PowerSupply,
+        "ac-source" => DeviceClass::AcSource,
+        "ground" => DeviceClass::Ground,
+        // Parts from other libraries fall back to their SPICE reference letter
+        _ => {
+            let pnp = component.type_id.to_lowercase().contains("pnp") || component.value.to_lowercase().contains("pnp");
+            let pmos = component.type_id.to_lowercase().contains("pmos") || component.type_id.to_lowercase().contains("p-mos");
+            match reference_prefix(&component.reference) {
+                "R" => DeviceClass::Resistor,
+                "C" => DeviceClass::Capacitor,
+                "L" => DeviceClass::Inductor,
+                "F" => DeviceClass::Short,
+                "D" => diode(1.0, 1e-14),
+                "Q" if pnp => DeviceClass::Bjt(Polarity::P),
+                "Q" => DeviceClass::Bjt(Polarity::N),
+                "M" if pmos => DeviceClass::Mosfet(Polarity::P),
+                "M" => DeviceClass::Mosfet(Polarity::N),
+                "V" | "BT" => DeviceClass::VoltageSource,
+                "I" => DeviceClass::CurrentSource,
+                _ => return None,
+            }
+        }
+    };
+    Some(class)
+}
+
+// Matches pin names or ids case-insensitively, falling back to pin order
+fn find_pin<'a>(component: &'a Component, names: &[&str], position: usize) -> Option<&'a Pin> {
+    component.pins.iter()
+        .find(|p| names.iter().any(|n| p.name.eq_ignore_ascii_case(n) || p.id.eq_ignore_ascii_case(n)))
+        .or_else(|| component.pins.get(position))
+}
+
+fn numeric_property(component: &Component, keys: &[&str]) -> Option<f64> {
+    keys.iter()
+        .filter_map(|key| component.properties.get(*key))
+        .find_map(|property| match &property.value {
+            serde_json::Value::Number(n) => n.as_f64(),
+            serde_json::Value::String(s) => parse_si_value(s),
+            _ => None,
+        })
+}
+
+// Explicit properties win over the displayed value, e.g. "4k7" or "9V"
+fn component_value(component: &Component, keys: &[&str]) -> Result<f64> {
+    numeric_property(component, keys)
+        .or_else(|| parse_si_value(&component.value))
+        .ok_or_else(|| AppError::InvalidInput(format!(
+            "{}: cannot read a {} from value \"{}\"", component.display_reference(), keys[0], component.value
+        )))
+}
+
+fn build_elements(component: &Component, class: &DeviceClass, terminals: Vec<(String, Node)>) -> Result<Vec<Element>> {
+    let element = |kind: ElementKind, terminals: &[(String, Node)]| Element {
+        component_id: component.id.clone(),
+        reference: component.display_reference(),
+        pin_ids: terminals.iter().map(|(id, _)| id.clone()).collect(),
+        nodes: terminals.iter().map(|(_, node)| *node).collect(),
+        kind,
+    };
+
+    let elements = match class {
+        DeviceClass::Resistor => {
+            let resistance = component_value(component, &["resistance", "value"])?;
+            if resistance <= 0.0 {
+                return Err(AppError::InvalidInput(format!("{}: resistance must be positive", component.display_reference())));
+            }
+            vec![element(ElementKind::Resistor { resistance }, &terminals)]
+        }
+        DeviceClass::Potentiometer => {
+            let resistance = component_value(component, &["resistance", "value"])?;
+            let position = numeric_property(component, &["position", "wiper"]).unwrap_or(0.5).clamp(0.001, 0.999);
+            vec![
+                element(ElementKind::Resistor { resistance: resistance * position }, &terminals[0..2]),
+                element(ElementKind::Resistor { resistance: resistance * (1.0 - position) }, &terminals[1..3]),
+            ]
+        }
+        DeviceClass::Capacitor => {
+            let capacitance = component_value(component, &["capacitance", "value"])?;
+            vec![element(ElementKind::Capacitor { capacitance }, &terminals)]
+        }
+        DeviceClass::Inductor => {
+            let inductance = component_value(component, &["inductance", "value"])?;
+            vec![element(ElementKind::Inductor { inductance }, &terminals)]
+        }
+        DeviceClass::Short => vec![element(ElementKind::Resistor { resistance: 1e-3 }, &terminals)],
+        DeviceClass::Diode { emission, saturation_current, zener, led } => {
+            let mut model = DiodeModel { saturation_current: *saturation_current, emission: *emission, breakdown: None };
+            if *zener {
+                model.breakdown = Some(component_value(component, &["voltage", "breakdown", "value"])?);
+            }
+            // Pick the saturation current that gives the rated forward voltage at 10 mA
+            if let Some(forward) = numeric_property(component, &["forward_voltage", "vf"]).filter(|_| *led) {
+                model.saturation_current = 0.01 / (forward / (model.emission * THERMAL_VOLTAGE)).exp();
+            }
+            vec![element(ElementKind::Diode(model), &terminals)]
+        }
+        DeviceClass::Bjt(polarity) => {
+            let model = BjtModel {
+                polarity: *polarity,
+                saturation_current: numeric_property(component, &["is"]).unwrap_or(1e-14),
+                beta_forward: numeric_property(component, &["beta", "hfe", "bf"]).unwrap_or(100.0),
+                beta_reverse: numeric_property(component, &["br"]).unwrap_or(1.0),
+            };
+            vec![element(ElementKind::Bjt(model), &terminals)]
+        }
+        DeviceClass::Mosfet(polarity) => {
+            let model = MosfetModel {
+                polarity: *polarity,
+                threshold: numeric_property(component, &["vth", "vto"]).map(f64::abs).unwrap_or(2.0),
+                transconductance: numeric_property(component, &["kp"]).unwrap_or(0.5),
+                lambda: numeric_property(component, &["lambda"]).unwrap_or(0.01),
+            };
+            vec![element(ElementKind::Mosfet(model), &terminals)]
+        }
+        DeviceClass::VoltageSource | DeviceClass::PowerSupply => {
+            let source = source(component, &["voltage", "value"], None)?;
+            vec![element(ElementKind::VoltageSource(source), &terminals)]
+        }
+        DeviceClass::AcSource => {
+            let rms = component_value(component, &["voltage", "value"])?;
+            let frequency = numeric_property(component, &["frequency"]).unwrap_or(50.0);
+            let sine = Waveform::Sine { offset: 0.0, amplitude: rms * std::f64::consts::SQRT_2, frequency, delay: 0.0, damping: 0.0 };
+            vec![element(ElementKind::VoltageSource(source(component, &["dc"], Some(sine))?), &terminals)]
+        }
+        DeviceClass::CurrentSource => {
+            let source = source(component, &["current", "value"], None)?;
+            vec![element(ElementKind::CurrentSource(source), &terminals)]
+        }
+        DeviceClass::Ground => Vec::new(),
+    };
+    Ok(elements)
+}
+
+// DC value from `keys`, an optional `waveform` property and the `ac`/`ac_phase` sweep stimulus.
+// Sources with a waveform may leave out the DC value; it then defaults to the waveform at t = 0.
+// Dedicated AC sources (those with a default waveform) drive AC sweeps with unit magnitude.
+fn source(component: &Component, keys: &[&str], default_waveform: Option<Waveform>) -> Result<Source> {
+    let ac_default = if default_waveform.is_some() { 1.0 } else { 0.0 };
+    let waveform = match component.properties.get("waveform").and_then(|p| p.value.as_str()).filter(|w| !w.trim().is_empty()) {
+        Some(text) => Some(Waveform::parse(text)?),
+        None => default_waveform,
+    };
+    let dc = match &waveform {
+        Some(waveform) => numeric_property(component, keys).unwrap_or_else(|| waveform.value_at(0.0)),
+        None => component_value(component, keys)?,
+    };
+
+    Ok(Source {
+        dc,
+        waveform,
+        ac_magnitude: numeric_property(component, &["ac", "ac_magnitude"]).unwrap_or(ac_default),
+        ac_phase: numeric_property(component, &["ac_phase"]).unwrap_or(0.0),
+    })
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use crate::models::component::{ComponentProperty, ElectricalType, PinType};
+    use crate::models::schematic::{Net, PinConnection};
+
+    // Pins are named and identified by `pins`, in that order
+    pub(crate) fn part(schematic: &mut Schematic, type_id: &str, reference: &str, value: &str, pins: &[&str]) -> String {
+        let mut component = Component::new(type_id.to_string(), 0.0, 0.0).unwrap();
+        component.reference = reference.to_string();
+        component.value = value.to_string();
+        for (i, name) in pins.iter().enumerate() {
+            component.pins.push(Pin {
+                id: format!("{}.{}", reference, name),
+                name: name.to_string(),
+                number: (i + 1).to_string(),
+                x: 0.0,
+                y: 0.0,
+                pin_type: PinType::Passive,
+                electrical: ElectricalType { voltage: None, current: None, impedance: None },
+                connected: false,
+            });
+        }
+        let id = component.id.clone();
+        schematic.add_component(component).unwrap();
+        id
+    }
+
+    fn property(schematic: &mut Schematic, reference: &str, key: &str, value: serde_json::Value) {
+        let component = schematic.components.iter_mut().find(|c| c.reference == reference).unwrap();
+        component.properties.insert(key.to_string(), ComponentProperty { value, visible: false, editable: true });
+    }
+
+    // `pins` are "REF.name" pin ids
+    pub(crate) fn connect(schematic: &mut Schematic, name: &str, pins: &[&str]) {
+        let pins = pins.iter()
+            .map(|pin_id| {
+                let reference = pin_id.split('.').next().unwrap();
+                let component = schematic.components.iter().find(|c| c.reference == reference).unwrap();
+                PinConnection { component_id: component.id.clone(), pin_id: pin_id.to_string() }
+            })
+            .collect();
+        schematic.nets.push(Net { id: format!("net-{}", name), name: name.to_string(), pins });
+    }
+
+    fn element<'a>(circuit: &'a Circuit, reference: &str) -> &'a Element {
+        circuit.elements.iter().find(|e| e.reference == reference).unwrap()
+    }
+
+    fn node(circuit: &Circuit, net: &str) -> Node {
+        circuit.nets.iter().find(|n| n.name == net).unwrap().node
+    }
+
+    fn schematic() -> Schematic {
+        let mut schematic = Schematic::new("Sim".to_string()).unwrap();
+        part(&mut schematic, "battery", "BT1", "9V", &["+", "-"]);
+        part(&mut schematic, "resistor", "R1", "4k7", &["1", "2"]);
+        part(&mut schematic, "potentiometer", "RV1", "10k", &["CCW", "Wiper", "CW"]);
+        // Pins listed out of terminal order are still matched by name
+        part(&mut schematic, "led", "D1", "red", &["K", "A"]);
+        part(&mut schematic, "npn-transistor", "Q1", "BC547", &["E", "B", "C"]);
+        part(&mut schematic, "mystery", "C5", "100n", &["1", "2"]);
+        part(&mut schematic, "op-amp", "U1", "TL072", &["IN+", "IN-", "OUT"]);
+        part(&mut schematic, "ground", "#PWR1", "GND", &["1"]);
+        property(&mut schematic, "RV1", "position", serde_json::json!(0.25));
+        property(&mut schematic, "D1", "vf", serde_json::json!("2.0"));
+
+        connect(&mut schematic, "VCC", &["BT1.+", "R1.1", "RV1.CCW", "Q1.C"]);
+        connect(&mut schematic, "MID", &["R1.2", "D1.A", "C5.1"]);
+        connect(&mut schematic, "WIPER", &["RV1.Wiper", "Q1.B"]);
+        connect(&mut schematic, "RETURN", &["BT1.-", "RV1.CW", "D1.K", "Q1.E", "C5.2", "#PWR1.1"]);
+        schematic
+    }
+
+    #[test]
+    fn test_circuit_from_schematic() {
+        let circuit = Circuit::from_schematic(&schematic()).unwrap();
+
+        // The net touching the ground symbol is the reference; the op-amp has no model
+        assert_eq!(node(&circuit, "RETURN"), None);
+        assert_eq!(circuit.unsupported, vec!["U1"]);
+        let (vcc, mid, wiper) = (node(&circuit, "VCC"), node(&circuit, "MID"), node(&circuit, "WIPER"));
+        assert!(vcc.is_some() && mid.is_some() && wiper.is_some());
+        assert_eq!(circuit.node_count, 3);
+        assert_eq!(circuit.elements.len(), 7);
+
+        let battery = element(&circuit, "BT1");
+        assert!(matches!(&battery.kind, ElementKind::VoltageSource(s) if s.dc == 9.0 && s.waveform.is_none()));
+        assert_eq!(battery.nodes, vec![vcc, None]);
+        assert_eq!(battery.pin_ids, vec!["BT1.+", "BT1.-"]);
+
+        assert!(matches!(element(&circuit, "R1").kind, ElementKind::Resistor { resistance } if resistance == 4700.0));
+        assert!(matches!(element(&circuit, "C5").kind, ElementKind::Capacitor { capacitance } if (capacitance - 100e-9).abs() < 1e-18));
+
+        let led = element(&circuit, "D1");
+        assert_eq!(led.pin_ids, vec!["D1.A", "D1.K"]);
+        assert_eq!(led.nodes, vec![mid, None]);
+        let ElementKind::Diode(model) = &led.kind else { panic!("D1 is not a diode") };
+        // Rated forward voltage at 10 mA
+        let current = model.saturation_current * (2.0 / (model.emission * THERMAL_VOLTAGE)).exp();
+        assert!((current - 0.01).abs() < 1e-9);
+
+        let transistor = element(&circuit, "Q1");
+        assert_eq!(transistor.pin_ids, vec!["Q1.C", "Q1.B", "Q1.E"]);
+        assert!(matches!(&transistor.kind, ElementKind::Bjt(m) if m.polarity == Polarity::N && m.beta_forward == 100.0));
+
+        // The potentiometer is two resistors meeting at the wiper
+        let halves: Vec<&Element> = circuit.elements.iter().filter(|e| e.reference == "RV1").collect();
+        assert_eq!(halves.len(), 2);
+        assert!(matches!(halves[0].kind, ElementKind::Resistor { resistance } if resistance == 2500.0));
+        assert!(matches!(halves[1].kind, ElementKind::Resistor { resistance } if resistance == 7500.0));
+        assert_eq!(halves[0].nodes, vec![vcc, wiper]);
+        assert_eq!(halves[1].nodes, vec![wiper, None]);
+    }
+
+    #[test]
+    fn test_circuit_values_and_ground() {
+        // A net named GND is ground without a symbol; unconnected pins get nodes of their own
+        let mut schematic = Schematic::new("Sim".to_string()).unwrap();
+        part(&mut schematic, "voltage-source", "V1", "1.5", &["+", "-"]);
+        part(&mut schematic, "resistor", "R1", "ignored", &["1", "2"]);
+        part(&mut schematic, "capacitor", "C1", "1u", &["1", "2"]);
+        property(&mut schematic, "R1", "resistance", serde_json::json!(220));
+        property(&mut schematic, "V1", "waveform", serde_json::json!("SIN(0.5 1 1k)"));
+        connect(&mut schematic, "IN", &["V1.+", "R1.1"]);
+        connect(&mut schematic, "gnd", &["V1.-", "R1.2"]);
+        let circuit = Circuit::from_schematic(&schematic).unwrap();
+        assert_eq!(node(&circuit, "gnd"), None);
+        assert!(matches!(element(&circuit, "R1").kind, ElementKind::Resistor { resistance } if resistance == 220.0));
+        let ElementKind::VoltageSource(source) = &element(&circuit, "V1").kind else { panic!("V1 is not a source") };
+        assert_eq!(source.dc, 0.5);
+        assert!(matches!(source.waveform, Some(Waveform::Sine { frequency, .. }) if frequency == 1000.0));
+        let capacitor = element(&circuit, "C1");
+        assert_eq!(circuit.node_count, 3);
+        assert!(capacitor.nodes[0].is_some() && capacitor.nodes[0] != capacitor.nodes[1]);
+
+        property(&mut schematic, "C1", "capacitance", serde_json::json!("lots"));
+        schematic.components.iter_mut().find(|c| c.reference == "C1").unwrap().value = "big".to_string();
+        let error = Circuit::from_schematic(&schematic).unwrap_err().to_string();
+        assert!(error.contains("C1") && error.contains("big"), "{}", error);
+
+        schematic.nets.retain(|n| n.name != "gnd");
+        schematic.components.retain(|c| c.reference != "C1");
+        assert!(Circuit::from_schematic(&schematic).unwrap_err().to_string().contains("no ground"));
+    }
+
+    #[test]
+    fn test_operating_point_updates_probes() {
+        use crate::models::ProbeTarget;
+        use crate::services::simulation::DcSolver;
+
+        let mut schematic = Schematic::new("Divider".to_string()).unwrap();
+        part(&mut schematic, "battery", "BT1", "10V", &["+", "-"]);
+        part(&mut schematic, "resistor", "R1", "1k", &["1", "2"]);
+        let r2 = part(&mut schematic, "resistor", "R2", "3k", &["1", "2"]);
+        connect(&mut schematic, "VCC", &["BT1.+", "R1.1"]);
+        connect(&mut schematic, "OUT", &["R1.2", "R2.1"]);
+        connect(&mut schematic, "GND", &["BT1.-", "R2.2"]);
+
+        let voltage = schematic.add_probe(ProbeTarget::Voltage { net_id: "net-OUT".to_string() }, 10.0, 10.0).unwrap().id.clone();
+        schematic.add_probe(ProbeTarget::Current { component_id: r2.clone(), pin_id: "R2.1".to_string() }, 20.0, 10.0).unwrap();
+        schematic.add_probe(ProbeTarget::Voltage { net_id: "net-missing".to_string() }, 30.0, 10.0).unwrap();
+        assert!(schematic.probes.iter().all(|p| p.value.is_none()));
+
+        let operating_point = DcSolver::operating_point(&schematic).unwrap();
+        schematic.attach_operating_point(operating_point);
+        let values: Vec<Option<f64>> = schematic.probes.iter().map(|p| p.value).collect();
+        assert!((values[0].unwrap() - 7.5).abs() < 1e-6);
+        assert!((values[1].unwrap() - 2.5e-3).abs() < 1e-9);
+        assert_eq!(values[2], None);
+
+        // New probes read the stored result straight away
+        let added = schematic.add_probe(ProbeTarget::Voltage { net_id: "net-VCC".to_string() }, 0.0, 0.0).unwrap();
+        assert!((added.value.unwrap() - 10.0).abs() < 1e-6);
+        assert!(schematic.add_probe(ProbeTarget::Voltage { net_id: "net-VCC".to_string() }, f64::NAN, 0.0).is_err());
+
+        assert_eq!(schematic.remove_probe(&voltage).unwrap().id, voltage);
+        assert!(schematic.remove_probe(&voltage).is_err());
+        assert_eq!(schematic.probes.len(), 3);
+    }
+}
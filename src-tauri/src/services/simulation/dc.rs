@@ -0,0 +1,444 @@
+use super::circuit::{BjtModel, Circuit, DiodeModel, Element, ElementKind, MosfetModel, Node, THERMAL_VOLTAGE};
+use super::mna::{Control, Linearization, Mna};
+use super::transient::Companion;
+use crate::models::{NodeVoltage, OperatingPoint, PinCurrent, Schematic};
+use crate::utils::error::{AppError, Result};
+use std::collections::HashMap;
+
+const MAX_ITERATIONS: usize = 150;
+const SOURCE_STEPS: usize = 10;
+const GMIN: f64 = 1e-12;
+const VNTOL: f64 = 1e-6;
+const RELTOL: f64 = 1e-3;
+// Exponent beyond which junction currents grow linearly instead of overflowing
+const MAX_EXPONENT: f64 = 80.0;
+
+/// DC operating point by Newton-Raphson on the MNA equations. Capacitors are open,
+/// inductors shorted; when plain iteration fails the sources are ramped up in steps.
+pub struct DcSolver;
+
+/// What a Newton solve models: the DC circuit, or one implicit time step of a transient.
+pub(crate) struct StampMode<'a> {
+    // Scale applied to every source during source stepping
+    pub source_scale: f64,
+    // Transient time for source waveforms; `None` uses DC values
+    pub time: Option<f64>,
+    // Discretised capacitors and inductors; without it they are open and shorted
+    pub companion: Option<&'a Companion>,
+}
+
+impl StampMode<'_> {
+    pub fn dc(time: Option<f64>) -> Self {
+        Self { source_scale: 1.0, time, companion: None }
+    }
+}
+
+impl DcSolver {
+    pub fn operating_point(schematic: &Schematic) -> Result<OperatingPoint> {
+        let circuit = Circuit::from_schematic(schematic)?;
+        let (solution, iterations) = Self::solve(&circuit)?;
+        Ok(Self::report(&circuit, &solution, iterations))
+    }
+
+    pub fn solve(circuit: &Circuit) -> Result<(Vec<f64>, usize)> {
+        Self::solve_at(circuit, None)
+    }
+
+    /// Operating point with sources at their DC values, or at their waveform values at `time`.
+    pub(crate) fn solve_at(circuit: &Circuit, time: Option<f64>) -> Result<(Vec<f64>, usize)> {
+        let size = circuit.node_count + circuit.branch_count();
+        let initial = vec![0.0; size];
+
+        if let Ok(result) = newton(circuit, &initial, &StampMode::dc(time)) {
+            return Ok(result);
+        }
+
+        // Source stepping: walk the supplies up from zero, reusing each solution as the next guess
+        let mut solution = initial;
+        let mut total = 0;
+        for step in 1..=SOURCE_STEPS {
+            let mode = StampMode { source_scale: step as f64 / SOURCE_STEPS as f64, ..StampMode::dc(time) };
+            let (next, iterations) = newton(circuit, &solution, &mode)?;
+            solution = next;
+            total += iterations;
+        }
+        Ok((solution, total))
+    }
+
+    fn report(circuit: &Circuit, solution: &[f64], iterations: usize) -> OperatingPoint {
+        let node_voltages = circuit.nets.iter()
+            .map(|net| NodeVoltage {
+                net_id: net.id.clone(),
+                net_name: net.name.clone(),
+                voltage: voltage(solution, net.node),
+            })
+            .collect();
+
+        OperatingPoint {
+            node_voltages,
+            pin_currents: pin_currents(circuit, solution, &StampMode::dc(None)),
+            iterations,
+            unsupported: circuit.unsupported.clone(),
+            solved_at: chrono::Utc::now(),
+        }
+    }
+}
+
+/// Newton-Raphson from `initial`; the result holds node voltages followed by branch currents.
+pub(crate) fn newton(circuit: &Circuit, initial: &[f64], mode: &StampMode) -> Result<(Vec<f64>, usize)> {
+    let mut solution = initial.to_vec();
+    // Junction voltages of the previous iteration, for SPICE-style step limiting
+    let mut junctions: Vec<[f64; 2]> = circuit.elements.iter()
+        .map(|element| junction_voltages(element, &solution))
+        .collect();
+
+    for iteration in 1..=MAX_ITERATIONS {
+        let mut mna = Mna::new(circuit.node_count, circuit.branch_count());
+        let mut limited = false;
+        let mut branch = 0;
+        for (index, element) in circuit.elements.iter().enumerate() {
+            limited |= stamp(&mut mna, index, element, &solution, &mut junctions[index], &mut branch, mode);
+        }
+        for node in 0..circuit.node_count {
+            mna.conductance(Some(node), None, GMIN);
+        }
+
+        let next = mna.solve().ok_or_else(|| AppError::InvalidOperation(
+            "Singular circuit matrix; check for voltage source loops or sources with a floating terminal".to_string()
+        ))?;
+
+        let converged = !limited && next.iter().zip(&solution).take(circuit.node_count).all(|(new, old)| {
+            (new - old).abs() <= RELTOL * new.abs().max(old.abs()) + VNTOL
+        });
+        solution = next;
+        if converged {
+            return Ok((solution, iteration));
+        }
+    }
+
+    Err(AppError::InvalidOperation(match mode.time {
+        Some(time) if mode.companion.is_some() => format!("Transient analysis did not converge at t = {:e} s", time),
+        _ => format!("DC operating point did not converge in {} iterations", MAX_ITERATIONS),
+    }))
+}
+
+/// Current into every element pin. Split elements (potentiometers) report into the same pins,
+/// so their contributions are summed.
+pub(crate) fn pin_currents(circuit: &Circuit, solution: &[f64], mode: &StampMode) -> Vec<PinCurrent> {
+    let mut currents: Vec<PinCurrent> = Vec::new();
+    let mut index: HashMap<(String, String), usize> = HashMap::new();
+    let mut branch = 0;
+    for (element_index, element) in circuit.elements.iter().enumerate() {
+        let terminal_currents = element_currents(element_index, element, solution, &mut branch, circuit.node_count, mode);
+        for (pin_id, current) in element.pin_ids.iter().zip(terminal_currents) {
+            let key = (element.component_id.clone(), pin_id.clone());
+            match index.get(&key) {
+                Some(&i) => currents[i].current += current,
+                None => {
+                    index.insert(key, currents.len());
+                    currents.push(PinCurrent {
+                        component_id: element.component_id.clone(),
+                        reference: element.reference.clone(),
+                        pin_id: pin_id.clone(),
+                        current,
+                    });
+                }
+            }
+        }
+    }
+    currents
+}
+
+pub(crate) fn voltage(solution: &[f64], node: Node) -> f64 {
+    node.map_or(0.0, |n| solution[n])
+}
+
+/// Stamps one element for a Newton iteration; returns true when a junction step was limited.
+fn stamp(mna: &mut Mna, index: usize, element: &Element, solution: &[f64], junctions: &mut [f64; 2], branch: &mut usize, mode: &StampMode) -> bool {
+    let nodes = &element.nodes;
+    match &element.kind {
+        ElementKind::Resistor { resistance } => {
+            mna.conductance(nodes[0], nodes[1], 1.0 / resistance);
+            false
+        }
+        ElementKind::Capacitor { capacitance } => {
+            if let Some(companion) = mode.companion {
+                let (conductance, offset) = companion.capacitor(index, element, *capacitance);
+                mna.conductance(nodes[0], nodes[1], conductance);
+                mna.current(nodes[0], nodes[1], offset);
+            }
+            false
+        }
+        ElementKind::Inductor { inductance } => {
+            match mode.companion {
+                Some(companion) => {
+                    let (impedance, voltage) = companion.inductor(*branch, element, *inductance);
+                    mna.voltage_branch(*branch, nodes[0], nodes[1], voltage);
+                    mna.branch_impedance(*branch, impedance);
+                }
+                None => mna.voltage_branch(*branch, nodes[0], nodes[1], 0.0),
+            }
+            *branch += 1;
+            false
+        }
+        ElementKind::VoltageSource(source) => {
+            mna.voltage_branch(*branch, nodes[0], nodes[1], source.value(mode.time) * mode.source_scale);
+            *branch += 1;
+            false
+        }
+        ElementKind::CurrentSource(source) => {
+            mna.current(nodes[0], nodes[1], source.value(mode.time) * mode.source_scale);
+            false
+        }
+        ElementKind::Diode(model) => stamp_nonlinear(mna, element, solution, junctions, |v, limit| diode(model, v, limit)),
+        ElementKind::Bjt(model) => stamp_nonlinear(mna, element, solution, junctions, |v, limit| bjt(model, v, limit)),
+        ElementKind::Mosfet(model) => stamp_nonlinear(mna, element, solution, junctions, |v, _| mosfet(model, v)),
+    }
+}
+
+// Junction voltages at a solution point, the starting reference for step limiting
+fn junction_voltages(element: &Element, solution: &[f64]) -> [f64; 2] {
+    let v: Vec<f64> = element.nodes.iter().map(|n| voltage(solution, *n)).collect();
+    match &element.kind {
+        ElementKind::Diode(_) => [v[0] - v[1], 0.0],
+        ElementKind::Bjt(model) => {
+            let p = model.polarity.sign();
+            [p * (v[1] - v[2]), p * (v[1] - v[0])]
+        }
+        _ => [0.0; 2],
+    }
+}
+
+/// Small-signal model of a nonlinear element at a solved point.
+pub(crate) fn linearize(element: &Element, solution: &[f64]) -> Option<Linearization> {
+    let v: Vec<f64> = element.nodes.iter().map(|n| voltage(solution, *n)).collect();
+    match &element.kind {
+        ElementKind::Diode(model) => Some(diode(model, &v, None).0),
+        ElementKind::Bjt(model) => Some(bjt(model, &v, None).0),
+        ElementKind::Mosfet(model) => Some(mosfet(model, &v).0),
+        _ => None,
+    }
+}
+
+fn stamp_nonlinear<F>(mna: &mut Mna, element: &Element, solution: &[f64], junctions: &mut [f64; 2], model: F) -> bool
+where
+    F: Fn(&[f64], Option<&mut [f64; 2]>) -> (Linearization, bool),
+{
+    let terminal_voltages: Vec<f64> = element.nodes.iter().map(|n| voltage(solution, *n)).collect();
+    let (linearization, limited) = model(&terminal_voltages, Some(junctions));
+    mna.nonlinear(&element.nodes, &linearization);
+    limited
+}
+
+/// Current into each terminal of an element at a solved point.
+fn element_currents(index: usize, element: &Element, solution: &[f64], branch: &mut usize, node_count: usize, mode: &StampMode) -> Vec<f64> {
+    let v: Vec<f64> = element.nodes.iter().map(|n| voltage(solution, *n)).collect();
+    match &element.kind {
+        ElementKind::Resistor { resistance } => {
+            let i = (v[0] - v[1]) / resistance;
+            vec![i, -i]
+        }
+        ElementKind::Capacitor { capacitance } => {
+            let i = mode.companion.map_or(0.0, |companion| companion.capacitor_current(index, element, *capacitance, solution));
+            vec![i, -i]
+        }
+        ElementKind::Inductor { .. } | ElementKind::VoltageSource(_) => {
+            let i = solution[node_count + *branch];
+            *branch += 1;
+            vec![i, -i]
+        }
+        ElementKind::CurrentSource(source) => {
+            let i = source.value(mode.time) * mode.source_scale;
+            vec![i, -i]
+        }
+        _ => linearize(element, solution).map(|l| l.currents).unwrap_or_default(),
+    }
+}
+
+// exp() continued linearly past MAX_EXPONENT; returns the value and its derivative
+fn limited_exp(x: f64) -> (f64, f64) {
+    if x > MAX_EXPONENT {
+        let e = MAX_EXPONENT.exp();
+        (e * (1.0 + x - MAX_EXPONENT), e)
+    } else {
+        let e = x.exp();
+        (e, e)
+    }
+}
+
+// SPICE pnjlim: keeps a forward-biased junction from jumping far up the exponential in one step
+fn limit_junction(new: f64, old: f64, vt: f64, critical: f64) -> (f64, bool) {
+    if new > critical && (new - old).abs() > 2.0 * vt {
+        let limited = if old > 0.0 {
+            let arg = 1.0 + (new - old) / vt;
+            if arg > 0.0 { old + vt * arg.ln() } else { critical }
+        } else {
+            vt * (new / vt).ln()
+        };
+        (limited, true)
+    } else {
+        (new, false)
+    }
+}
+
+fn critical_voltage(vt: f64, saturation_current: f64) -> f64 {
+    vt * (vt / (std::f64::consts::SQRT_2 * saturation_current)).ln()
+}
+
+// Terminals [anode, cathode]
+pub(crate) fn diode(model: &DiodeModel, v: &[f64], state: Option<&mut [f64; 2]>) -> (Linearization, bool) {
+    let vt = model.emission * THERMAL_VOLTAGE;
+    let mut vd = v[0] - v[1];
+    let mut limited = false;
+    if let Some(state) = state {
+        (vd, limited) = limit_junction(vd, state[0], vt, critical_voltage(vt, model.saturation_current));
+        state[0] = vd;
+    }
+
+    let (e, de) = limited_exp(vd / vt);
+    let mut current = model.saturation_current * (e - 1.0) + GMIN * vd;
+    let mut conductance = model.saturation_current * de / vt + GMIN;
+    if let Some(breakdown) = model.breakdown {
+        let (e, de) = limited_exp(-(vd + breakdown) / vt);
+        current -= model.saturation_current * e;
+        conductance += model.saturation_current * de / vt;
+    }
+
+    let linearization = Linearization {
+        currents: vec![current, -current],
+        gains: vec![vec![conductance], vec![-conductance]],
+        controls: vec![Control { plus: 0, minus: 1, sign: 1.0, value: vd }],
+    };
+    (linearization, limited)
+}
+
+// Ebers-Moll transport model; terminals [collector, base, emitter]
+pub(crate) fn bjt(model: &BjtModel, v: &[f64], state: Option<&mut [f64; 2]>) -> (Linearization, bool) {
+    let p = model.polarity.sign();
+    let vt = THERMAL_VOLTAGE;
+    let mut vbe = p * (v[1] - v[2]);
+    let mut vbc = p * (v[1] - v[0]);
+    let mut limited = false;
+    if let Some(state) = state {
+        let critical = critical_voltage(vt, model.saturation_current);
+        let (be, be_limited) = limit_junction(vbe, state[0], vt, critical);
+        let (bc, bc_limited) = limit_junction(vbc, state[1], vt, critical);
+        (vbe, vbc) = (be, bc);
+        limited = be_limited || bc_limited;
+        *state = [vbe, vbc];
+    }
+
+    let (ef, def) = limited_exp(vbe / vt);
+    let (er, der) = limited_exp(vbc / vt);
+    let forward = model.saturation_current * (ef - 1.0) + GMIN * vbe;
+    let reverse = model.saturation_current * (er - 1.0) + GMIN * vbc;
+    let gf = model.saturation_current * def / vt + GMIN;
+    let gr = model.saturation_current * der / vt + GMIN;
+
+    let collector = forward - reverse - reverse / model.beta_reverse;
+    let base = forward / model.beta_forward + reverse / model.beta_reverse;
+    let d_collector = [gf, -gr - gr / model.beta_reverse];
+    let d_base = [gf / model.beta_forward, gr / model.beta_reverse];
+
+    let linearization = Linearization {
+        currents: vec![p * collector, p * base, -p * (collector + base)],
+        gains: vec![
+            vec![p * d_collector[0], p * d_collector[1]],
+            vec![p * d_base[0], p * d_base[1]],
+            vec![-p * (d_collector[0] + d_base[0]), -p * (d_collector[1] + d_base[1])],
+        ],
+        controls: vec![
+            Control { plus: 1, minus: 2, sign: p, value: vbe },
+            Control { plus: 1, minus: 0, sign: p, value: vbc },
+        ],
+    };
+    (linearization, limited)
+}
+
+// Square-law model, symmetric in drain and source; terminals [drain, gate, source]
+pub(crate) fn mosfet(model: &MosfetModel, v: &[f64]) -> (Linearization, bool) {
+    let p = model.polarity.sign();
+    // Whichever of drain/source sits higher (for the channel type) acts as the drain
+    let (high, low) = if p * (v[0] - v[2]) >= 0.0 { (0, 2) } else { (2, 0) };
+    let vgs = p * (v[1] - v[low]);
+    let vds = p * (v[high] - v[low]);
+    let overdrive = vgs - model.threshold;
+    let k = model.transconductance;
+    let clm = 1.0 + model.lambda * vds;
+
+    let (current, gm, gds) = if overdrive <= 0.0 {
+        (0.0, 0.0, 0.0)
+    } else if vds < overdrive {
+        let core = overdrive * vds - vds * vds / 2.0;
+        (k * core * clm, k * vds * clm, k * (overdrive - vds) * clm + k * core * model.lambda)
+    } else {
+        let core = overdrive * overdrive / 2.0;
+        (k * core * clm, k * overdrive * clm, k * core * model.lambda)
+    };
+    let current = current + GMIN * vds;
+    let gds = gds + GMIN;
+
+    let mut currents = vec![0.0; 3];
+    let mut gains = vec![vec![0.0; 2]; 3];
+    currents[high] = p * current;
+    currents[low] = -p * current;
+    gains[high] = vec![p * gm, p * gds];
+    gains[low] = vec![-p * gm, -p * gds];
+
+    let linearization = Linearization {
+        currents,
+        gains,
+        controls: vec![
+            Control { plus: 1, minus: low, sign: p, value: vgs },
+            Control { plus: high, minus: low, sign: p, value: vds },
+        ],
+    };
+    (linearization, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::simulation::circuit::Source;
+
+    fn element(reference: &str, nodes: Vec<Node>, kind: ElementKind) -> Element {
+        Element {
+            component_id: reference.to_string(),
+            reference: reference.to_string(),
+            pin_ids: (1..=nodes.len()).map(|i| i.to_string()).collect(),
+            nodes,
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_divider_and_diode_operating_point() {
+        // V1 = 5 V across a 1k/1k divider, and a 1k + diode branch from the same supply
+        let circuit = Circuit {
+            node_count: 3,
+            nets: Vec::new(),
+            elements: vec![
+                element("V1", vec![Some(0), None], ElementKind::VoltageSource(Source { dc: 5.0, waveform: None, ac_magnitude: 0.0, ac_phase: 0.0 })),
+                element("R1", vec![Some(0), Some(1)], ElementKind::Resistor { resistance: 1000.0 }),
+                element("R2", vec![Some(1), None], ElementKind::Resistor { resistance: 1000.0 }),
+                element("R3", vec![Some(0), Some(2)], ElementKind::Resistor { resistance: 1000.0 }),
+                element("D1", vec![Some(2), None], ElementKind::Diode(DiodeModel {
+                    saturation_current: 1e-14,
+                    emission: 1.0,
+                    breakdown: None,
+                })),
+            ],
+            unsupported: Vec::new(),
+        };
+
+        let (solution, _) = DcSolver::solve(&circuit).unwrap();
+        assert!((solution[0] - 5.0).abs() < 1e-9);
+        assert!((solution[1] - 2.5).abs() < 1e-6);
+        assert!(solution[2] > 0.6 && solution[2] < 0.75, "diode drop {}", solution[2]);
+
+        // The source delivers the divider current plus the diode branch current
+        let currents = pin_currents(&circuit, &solution, &StampMode::dc(None));
+        let expected = 2.5e-3 + (5.0 - solution[2]) / 1000.0;
+        assert!((currents[0].current + expected).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,726 @@
+use crate::models::component::{Component, PinType};
+use crate::models::{
+    DigitalSettings, DigitalSignal, DigitalStimulus, DigitalWaveforms, LogicChange, LogicLevel, Schematic,
+};
+use crate::utils::error::{AppError, Result};
+use crate::utils::units::parse_si_value;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+const DEFAULT_DELAY: u64 = 1_000;
+const MAX_EVENTS: usize = 10_000_000;
+// Evaluation rounds allowed at one time point before a zero-delay loop is reported
+const MAX_DELTA_CYCLES: usize = 10_000;
+
+/// Event-driven four-state simulation of the digital parts in a schematic. Outputs use
+/// transport delays in integer picoseconds; undriven nets float at Z and read as X.
+pub struct DigitalSimulator;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum GateFunction {
+    And,
+    Or,
+    Xor,
+    Buffer,
+    Nand,
+    Nor,
+    Xnor,
+    Not,
+}
+
+#[derive(Debug, Clone)]
+enum Behavior {
+    Gate(GateFunction),
+    // Inputs [CLK, RST, EN]; async active-high reset, counts on rising clock edges
+    Counter { count: Option<u32> },
+    // Inputs [SER, SRCLK, RCLK], outputs [Q0, Q1, Q7S]; 74HC595-style shift and storage stages
+    ShiftRegister { shift: [LogicLevel; 8], storage: [LogicLevel; 8] },
+    // Inputs [A0, A1, EN], outputs Y0..Y3 active high
+    Decoder,
+    // Inputs [I0..I3, S0, S1], output Y
+    Multiplexer,
+}
+
+struct Device {
+    behavior: Behavior,
+    // Net per input; unconnected inputs read their default level
+    inputs: Vec<Option<usize>>,
+    input_defaults: Vec<LogicLevel>,
+    // Driver index per output
+    outputs: Vec<usize>,
+    delay: u64,
+    previous_inputs: Vec<LogicLevel>,
+}
+
+struct Driver {
+    net: Option<usize>,
+    value: LogicLevel,
+    // Last value scheduled for this driver, to drop redundant events
+    scheduled: LogicLevel,
+}
+
+struct Simulation {
+    net_values: Vec<LogicLevel>,
+    net_drivers: Vec<Vec<usize>>,
+    net_readers: Vec<Vec<usize>>,
+    drivers: Vec<Driver>,
+    devices: Vec<Device>,
+    queue: BinaryHeap<Event>,
+    sequence: usize,
+    changes: Vec<Vec<LogicChange>>,
+}
+
+// Driver change; the heap pops the earliest time first, in scheduling order within a time
+struct Event {
+    time: u64,
+    sequence: usize,
+    driver: usize,
+    value: LogicLevel,
+}
+
+impl PartialEq for Event {
+    fn eq(&self, other: &Self) -> bool {
+        (self.time, self.sequence) == (other.time, other.sequence)
+    }
+}
+
+impl Eq for Event {}
+
+impl PartialOrd for Event {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Event {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.time, other.sequence).cmp(&(self.time, self.sequence))
+    }
+}
+
+pub fn seconds_to_picoseconds(seconds: f64) -> u64 {
+    (seconds * 1e12).round().max(0.0) as u64
+}
+
+impl DigitalSimulator {
+    pub fn run(schematic: &Schematic, settings: &DigitalSettings) -> Result<DigitalWaveforms> {
+        settings.validate()?;
+        let nets = schematic.generate_netlist();
+        let mut pin_nets: HashMap<(&str, &str), usize> = HashMap::new();
+        for (index, net) in nets.iter().enumerate() {
+            for pin in &net.pins {
+                pin_nets.insert((pin.component_id.as_str(), pin.pin_id.as_str()), index);
+            }
+        }
+        let default_delay = settings.default_delay.map(seconds_to_picoseconds).unwrap_or(DEFAULT_DELAY);
+
+        let mut simulation = Simulation {
+            net_values: vec![LogicLevel::HighZ; nets.len()],
+            net_drivers: vec![Vec::new(); nets.len()],
+            net_readers: vec![Vec::new(); nets.len()],
+            drivers: Vec::new(),
+            devices: Vec::new(),
+            queue: BinaryHeap::new(),
+            sequence: 0,
+            changes: vec![vec![LogicChange { time: 0, value: LogicLevel::HighZ }]; nets.len()],
+        };
+
+        let mut unsupported = Vec::new();
+        for component in &schematic.components {
+            let net_of = |pin_id: &str| pin_nets.get(&(component.id.as_str(), pin_id)).copied();
+            match devices_for(component, &net_of, default_delay)? {
+                Some(devices) => {
+                    for (behavior, inputs, input_defaults, outputs, delay) in devices {
+                        simulation.add_device(behavior, inputs, input_defaults, outputs, delay);
+                    }
+                }
+                None => unsupported.push(component.display_reference()),
+            }
+        }
+
+        let find_net = |name: &str| {
+            nets.iter()
+                .position(|net| net.name == name || net.id == name)
+                .ok_or_else(|| AppError::InvalidInput(format!("Stimulus net not found: {}", name)))
+        };
+        let end_time = seconds_to_picoseconds(settings.stop_time);
+        for clock in &settings.clocks {
+            let driver = simulation.add_driver(Some(find_net(&clock.net)?));
+            simulation.schedule(0, driver, LogicLevel::Low);
+            let period = seconds_to_picoseconds(clock.period).max(2);
+            let high = seconds_to_picoseconds(clock.period * clock.duty).clamp(1, period - 1);
+            let mut edge = seconds_to_picoseconds(clock.delay);
+            while edge <= end_time {
+                simulation.schedule(edge, driver, LogicLevel::High);
+                simulation.schedule(edge + high, driver, LogicLevel::Low);
+                edge += period;
+            }
+        }
+        for stimulus in &settings.stimuli {
+            let driver = simulation.add_driver(Some(find_net(&stimulus.net)?));
+            for (time, level) in &stimulus.changes {
+                simulation.schedule(seconds_to_picoseconds(*time), driver, *level);
+            }
+        }
+
+        // Settle constant logic before the first stimulus edge
+        for device in 0..simulation.devices.len() {
+            simulation.evaluate(device, 0);
+        }
+        simulation.process(end_time)?;
+
+        let signals = nets.iter().zip(simulation.changes)
+            .map(|(net, changes)| DigitalSignal { net_id: net.id.clone(), name: net.name.clone(), changes })
+            .collect();
+
+        Ok(DigitalWaveforms {
+            scope: schematic.name.clone(),
+            end_time,
+            signals,
+            unsupported,
+            simulated_at: chrono::Utc::now(),
+        })
+    }
+
+    /// Parses a vector file: a header row `time NET1 NET2 ...`, then one row per change time
+    /// with an engineering-notation time and a 0/1/x/z level per net. `#` starts a comment.
+    pub fn parse_vectors(text: &str) -> Result<Vec<DigitalStimulus>> {
+        let mut rows = text.lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.split('#').next().unwrap_or("").trim()))
+            .filter(|(_, line)| !line.is_empty());
+
+        let (_, header) = rows.next().ok_or_else(|| AppError::InvalidFormat("Vector file is empty".to_string()))?;
+        let columns: Vec<&str> = header.split_whitespace().collect();
+        if columns.len() < 2 || !columns[0].eq_ignore_ascii_case("time") {
+            return Err(AppError::InvalidFormat("Vector file header must be `time` followed by net names".to_string()));
+        }
+
+        let mut stimuli: Vec<DigitalStimulus> = columns[1..].iter()
+            .map(|net| DigitalStimulus { net: net.to_string(), changes: Vec::new() })
+            .collect();
+        let mut last_time = 0.0;
+        for (line_number, row) in rows {
+            let fields: Vec<&str> = row.split_whitespace().collect();
+            let invalid = |what: &str| AppError::InvalidFormat(format!("Vector file line {}: {}", line_number, what));
+            if fields.len() != columns.len() {
+                return Err(invalid("wrong number of columns"));
+            }
+            let time = parse_si_value(fields[0]).filter(|t| *t >= last_time).ok_or_else(|| invalid("invalid or decreasing time"))?;
+            last_time = time;
+            for (stimulus, field) in stimuli.iter_mut().zip(&fields[1..]) {
+                let mut chars = field.chars();
+                let level = match (chars.next().and_then(LogicLevel::from_char), chars.next()) {
+                    (Some(level), None) => level,
+                    _ => return Err(invalid("levels must be one of 0, 1, x, z")),
+                };
+                stimulus.changes.push((time, level));
+            }
+        }
+        Ok(stimuli)
+    }
+}
+
+type DeviceSpec = (Behavior, Vec<Option<usize>>, Vec<LogicLevel>, Vec<Option<usize>>, u64);
+
+// Behavioral models for the digital templates; `None` when the part has no model
+fn devices_for(component: &Component, net_of: &dyn Fn(&str) -> Option<usize>, default_delay: u64) -> Result<Option<Vec<DeviceSpec>>> {
+    let delay = delay_property(component)?.unwrap_or(default_delay);
+    let pin = |names: &[&str]| -> Option<usize> {
+        component.pins.iter()
+            .find(|p| names.iter().any(|n| p.id.eq_ignore_ascii_case(n) || p.name.eq_ignore_ascii_case(n)))
+            .and_then(|p| net_of(&p.id))
+    };
+    let gate = |function: GateFunction| -> DeviceSpec {
+        let inputs: Vec<Option<usize>> = component.pins.iter()
+            .filter(|p| matches!(p.pin_type, PinType::Input))
+            .map(|p| net_of(&p.id))
+            .collect();
+        let output = component.pins.iter()
+            .find(|p| matches!(p.pin_type, PinType::Output))
+            .and_then(|p| net_of(&p.id));
+        let defaults = vec![LogicLevel::HighZ; inputs.len()];
+        (Behavior::Gate(function), inputs, defaults, vec![output], delay)
+    };
+
+    let devices = match component.type_id.as_str() {
+        "and-gate" => vec![gate(GateFunction::And)],
+        "or-gate" => vec![gate(GateFunction::Or)],
+        "xor-gate" => vec![gate(GateFunction::Xor)],
+        "buffer" => vec![gate(GateFunction::Buffer)],
+        "nand-gate" => vec![gate(GateFunction::Nand)],
+        "nor-gate" => vec![gate(GateFunction::Nor)],
+        "xnor-gate" => vec![gate(GateFunction::Xnor)],
+        "not-gate" => vec![gate(GateFunction::Not)],
+        // Quad NAND; a placed unit only carries the pins of its own gate
+        "74hc00" => (1..=4)
+            .filter(|n| component.pins.iter().any(|p| p.id == format!("{}Y", n)))
+            .map(|n| {
+                let inputs = vec![pin(&[&format!("{}A", n)]), pin(&[&format!("{}B", n)])];
+                (Behavior::Gate(GateFunction::Nand), inputs, vec![LogicLevel::HighZ; 2], vec![pin(&[&format!("{}Y", n)])], delay)
+            })
+            .collect(),
+        "counter" => {
+            let outputs: Vec<Option<usize>> = component.pins.iter()
+                .filter(|p| p.id.starts_with('Q'))
+                .map(|p| net_of(&p.id))
+                .collect();
+            let inputs = vec![pin(&["CLK"]), pin(&["RST"]), pin(&["EN"])];
+            let defaults = vec![LogicLevel::HighZ, LogicLevel::Low, LogicLevel::High];
+            vec![(Behavior::Counter { count: None }, inputs, defaults, outputs, delay)]
+        }
+        "shift-register" => {
+            let inputs = vec![pin(&["SER"]), pin(&["SRCLK"]), pin(&["RCLK"])];
+            let outputs = vec![pin(&["Q0"]), pin(&["Q1"]), pin(&["Q7S"])];
+            let behavior = Behavior::ShiftRegister { shift: [LogicLevel::Unknown; 8], storage: [LogicLevel::Unknown; 8] };
+            vec![(behavior, inputs, vec![LogicLevel::HighZ; 3], outputs, delay)]
+        }
+        "decoder" => {
+            let inputs = vec![pin(&["A0"]), pin(&["A1"]), pin(&["EN"])];
+            let outputs = vec![pin(&["Y0"]), pin(&["Y1"]), pin(&["Y2"]), pin(&["Y3"])];
+            vec![(Behavior::Decoder, inputs, vec![LogicLevel::HighZ, LogicLevel::HighZ, LogicLevel::High], outputs, delay)]
+        }
+        "multiplexer" => {
+            let inputs = ["I0", "I1", "I2", "I3", "S0", "S1"].iter().map(|name| pin(&[name])).collect();
+            vec![(Behavior::Multiplexer, inputs, vec![LogicLevel::HighZ; 6], vec![pin(&["Y"])], delay)]
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(devices))
+}
+
+fn delay_property(component: &Component) -> Result<Option<u64>> {
+    let Some(property) = ["delay", "tpd", "propagation_delay"].iter().find_map(|key| component.properties.get(*key)) else {
+        return Ok(None);
+    };
+    let seconds = match &property.value {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => parse_si_value(s),
+        _ => None,
+    };
+    seconds
+        .filter(|s| s.is_finite() && *s >= 0.0)
+        .map(|s| Some(seconds_to_picoseconds(s)))
+        .ok_or_else(|| AppError::InvalidInput(format!("{}: invalid propagation delay", component.display_reference())))
+}
+
+impl Simulation {
+    fn add_driver(&mut self, net: Option<usize>) -> usize {
+        let index = self.drivers.len();
+        if let Some(net) = net {
+            self.net_drivers[net].push(index);
+        }
+        self.drivers.push(Driver { net, value: LogicLevel::HighZ, scheduled: LogicLevel::HighZ });
+        index
+    }
+
+    fn add_device(&mut self, behavior: Behavior, inputs: Vec<Option<usize>>, input_defaults: Vec<LogicLevel>, outputs: Vec<Option<usize>>, delay: u64) {
+        let index = self.devices.len();
+        for net in inputs.iter().flatten() {
+            if !self.net_readers[*net].contains(&index) {
+                self.net_readers[*net].push(index);
+            }
+        }
+        let outputs = outputs.into_iter().map(|net| self.add_driver(net)).collect();
+        let previous_inputs = vec![LogicLevel::Unknown; inputs.len()];
+        self.devices.push(Device { behavior, inputs, input_defaults, outputs, delay, previous_inputs });
+    }
+
+    fn schedule(&mut self, time: u64, driver: usize, value: LogicLevel) {
+        self.drivers[driver].scheduled = value;
+        self.queue.push(Event { time, sequence: self.sequence, driver, value });
+        self.sequence += 1;
+    }
+
+    fn process(&mut self, end_time: u64) -> Result<()> {
+        let mut processed = 0;
+        let mut delta_cycles = 0;
+        let mut current_time = 0;
+
+        while let Some(time) = self.queue.peek().map(|event| event.time) {
+            if time > end_time {
+                break;
+            }
+            if time == current_time {
+                delta_cycles += 1;
+                if delta_cycles > MAX_DELTA_CYCLES {
+                    return Err(AppError::InvalidOperation(format!(
+                        "Zero-delay logic loop oscillates at {} ps", time
+                    )));
+                }
+            } else {
+                current_time = time;
+                delta_cycles = 0;
+            }
+
+            // Apply every driver change due now, then re-evaluate the readers of changed nets
+            let mut changed_nets = Vec::new();
+            while self.queue.peek().is_some_and(|event| event.time == time) {
+                let Some(event) = self.queue.pop() else { break };
+                processed += 1;
+                self.drivers[event.driver].value = event.value;
+                if let Some(net) = self.drivers[event.driver].net {
+                    if !changed_nets.contains(&net) {
+                        changed_nets.push(net);
+                    }
+                }
+            }
+            if processed > MAX_EVENTS {
+                return Err(AppError::InvalidOperation("Digital simulation exceeded the event limit".to_string()));
+            }
+
+            let mut affected = Vec::new();
+            for net in changed_nets {
+                let value = resolve(self.net_drivers[net].iter().map(|&d| self.drivers[d].value));
+                if value != self.net_values[net] {
+                    self.net_values[net] = value;
+                    record(&mut self.changes[net], time, value);
+                    for &device in &self.net_readers[net] {
+                        if !affected.contains(&device) {
+                            affected.push(device);
+                        }
+                    }
+                }
+            }
+            for device in affected {
+                self.evaluate(device, time);
+            }
+        }
+        Ok(())
+    }
+
+    fn evaluate(&mut self, index: usize, time: u64) {
+        let inputs: Vec<LogicLevel> = {
+            let device = &self.devices[index];
+            device.inputs.iter().zip(&device.input_defaults)
+                .map(|(net, default)| net.map_or(*default, |n| self.net_values[n]))
+                .collect()
+        };
+        let device = &mut self.devices[index];
+        let outputs = device.behavior.evaluate(&inputs, &device.previous_inputs);
+        device.previous_inputs = inputs;
+
+        let at = time + device.delay;
+        let drivers = device.outputs.clone();
+        for (driver, value) in drivers.into_iter().zip(outputs) {
+            if self.drivers[driver].scheduled != value {
+                self.schedule(at, driver, value);
+            }
+        }
+    }
+}
+
+// Keeps one change per time point and drops changes back to the level already recorded
+fn record(changes: &mut Vec<LogicChange>, time: u64, value: LogicLevel) {
+    if changes.last().is_some_and(|last| last.time == time) {
+        changes.pop();
+    }
+    if changes.last().is_none_or(|last| last.value != value) {
+        changes.push(LogicChange { time, value });
+    }
+}
+
+// Wired resolution: Z yields to any driver, conflicting drivers give X
+fn resolve(values: impl Iterator<Item = LogicLevel>) -> LogicLevel {
+    values.fold(LogicLevel::HighZ, |resolved, value| match (resolved, value) {
+        (LogicLevel::HighZ, v) | (v, LogicLevel::HighZ) => v,
+        (a, b) if a == b => a,
+        _ => LogicLevel::Unknown,
+    })
+}
+
+fn rising(previous: LogicLevel, current: LogicLevel) -> bool {
+    previous == LogicLevel::Low && current == LogicLevel::High
+}
+
+impl GateFunction {
+    fn evaluate(self, inputs: &[LogicLevel]) -> LogicLevel {
+        let values: Vec<Option<bool>> = inputs.iter().map(|v| v.to_bool()).collect();
+        let known = |f: &dyn Fn(&[bool]) -> bool| -> LogicLevel {
+            values.iter().copied().collect::<Option<Vec<bool>>>()
+                .map_or(LogicLevel::Unknown, |v| LogicLevel::from_bool(f(&v)))
+        };
+        // A controlling value decides the output even when other inputs are unknown
+        let and = || if values.contains(&Some(false)) { LogicLevel::Low } else { known(&|v| v.iter().all(|b| *b)) };
+        let or = || if values.contains(&Some(true)) { LogicLevel::High } else { known(&|v| v.iter().any(|b| *b)) };
+        let xor = || known(&|v| v.iter().filter(|b| **b).count() % 2 == 1);
+        let buffer = || known(&|v| v.first().copied().unwrap_or(false));
+
+        match self {
+            GateFunction::And => and(),
+            GateFunction::Or => or(),
+            GateFunction::Xor => xor(),
+            GateFunction::Buffer => buffer(),
+            GateFunction::Nand => invert(and()),
+            GateFunction::Nor => invert(or()),
+            GateFunction::Xnor => invert(xor()),
+            GateFunction::Not => invert(buffer()),
+        }
+    }
+}
+
+fn invert(value: LogicLevel) -> LogicLevel {
+    match value.to_bool() {
+        Some(b) => LogicLevel::from_bool(!b),
+        None => LogicLevel::Unknown,
+    }
+}
+
+// Two-bit index from a pair of select lines, if both are known
+fn select(low: LogicLevel, high: LogicLevel) -> Option<usize> {
+    Some(low.to_bool()? as usize + 2 * high.to_bool()? as usize)
+}
+
+impl Behavior {
+    fn evaluate(&mut self, inputs: &[LogicLevel], previous: &[LogicLevel]) -> Vec<LogicLevel> {
+        match self {
+            Behavior::Gate(function) => vec![function.evaluate(inputs)],
+            Behavior::Counter { count } => {
+                let (clock, reset, enable) = (inputs[0], inputs[1], inputs[2]);
+                match reset.to_bool() {
+                    Some(true) => *count = Some(0),
+                    None => *count = None,
+                    Some(false) if rising(previous[0], clock) => match enable.to_bool() {
+                        Some(true) => *count = count.map(|c| c.wrapping_add(1)),
+                        Some(false) => {}
+                        None => *count = None,
+                    },
+                    Some(false) => {}
+                }
+                // One level per bit; only as many as the part has Q outputs get driven
+                (0..32).map(|bit| count.map_or(LogicLevel::Unknown, |c| LogicLevel::from_bool(c >> bit & 1 == 1))).collect()
+            }
+            Behavior::ShiftRegister { shift, storage } => {
+                if rising(previous[1], inputs[1]) {
+                    shift.rotate_right(1);
+                    shift[0] = if inputs[0] == LogicLevel::HighZ { LogicLevel::Unknown } else { inputs[0] };
+                }
+                if rising(previous[2], inputs[2]) {
+                    *storage = *shift;
+                }
+                vec![storage[0], storage[1], shift[7]]
+            }
+            Behavior::Decoder => {
+                let selected = select(inputs[0], inputs[1]);
+                match inputs[2].to_bool() {
+                    Some(false) => vec![LogicLevel::Low; 4],
+                    Some(true) => (0..4)
+                        .map(|i| selected.map_or(LogicLevel::Unknown, |s| LogicLevel::from_bool(s == i)))
+                        .collect(),
+                    None => vec![LogicLevel::Unknown; 4],
+                }
+            }
+            Behavior::Multiplexer => {
+                let data: Vec<LogicLevel> = inputs[0..4].iter()
+                    .map(|v| if *v == LogicLevel::HighZ { LogicLevel::Unknown } else { *v })
+                    .collect();
+                let output = match select(inputs[4], inputs[5]) {
+                    Some(s) => data[s],
+                    None if data.iter().all(|v| *v == data[0]) => data[0],
+                    None => LogicLevel::Unknown,
+                };
+                vec![output]
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gate_logic_and_vectors() {
+        use LogicLevel::*;
+        assert_eq!(GateFunction::And.evaluate(&[Low, Unknown]), Low);
+        assert_eq!(GateFunction::Nand.evaluate(&[High, HighZ]), Unknown);
+        assert_eq!(GateFunction::Nor.evaluate(&[High, Unknown]), Low);
+        assert_eq!(GateFunction::Xor.evaluate(&[High, High, High]), High);
+        assert_eq!(resolve([HighZ, Low, HighZ].into_iter()), Low);
+        assert_eq!(resolve([High, Low].into_iter()), Unknown);
+
+        let mut counter = Behavior::Counter { count: None };
+        let mut previous = vec![Unknown; 3];
+        let mut step = |inputs: Vec<LogicLevel>| {
+            let outputs = counter.evaluate(&inputs, &previous);
+            previous = inputs;
+            outputs[..3].to_vec()
+        };
+        assert_eq!(step(vec![Low, High, High]), vec![Low, Low, Low]);
+        step(vec![Low, Low, High]);
+        assert_eq!(step(vec![High, Low, High]), vec![High, Low, Low]);
+        step(vec![Low, Low, High]);
+        assert_eq!(step(vec![High, Low, High]), vec![Low, High, Low]);
+
+        let vectors = "# reset then count\ntime RST EN\n0 1 0\n10n 0 1 # go\n";
+        let stimuli = DigitalSimulator::parse_vectors(vectors).unwrap();
+        assert_eq!(stimuli[0].net, "RST");
+        assert_eq!(stimuli[1].changes, vec![(0.0, Low), (10e-9, High)]);
+        assert!(DigitalSimulator::parse_vectors("time A\n5n 1\n1n 0\n").is_err());
+    }
+
+    #[test]
+    fn test_clocked_counter_run() {
+        use crate::models::{ClockGenerator, Component, ElectricalType, Net, Pin, PinConnection};
+        use LogicLevel::*;
+
+        let pin = |id: &str, pin_type: PinType| Pin {
+            id: id.to_string(),
+            name: id.to_string(),
+            number: id.to_string(),
+            x: 0.0,
+            y: 0.0,
+            pin_type,
+            electrical: ElectricalType { voltage: None, current: None, impedance: None },
+            connected: false,
+        };
+        let mut counter = Component::new("counter".to_string(), 0.0, 0.0).unwrap();
+        counter.reference = "U1".to_string();
+        counter.pins = vec![pin("CLK", PinType::Input), pin("RST", PinType::Input), pin("Q0", PinType::Output), pin("Q1", PinType::Output)];
+        counter.properties.insert("delay".to_string(), crate::models::ComponentProperty {
+            value: serde_json::json!("5n"),
+            visible: false,
+            editable: true,
+        });
+
+        let mut schematic = Schematic::new("Counter".to_string()).unwrap();
+        for name in ["CLK", "RST", "Q0", "Q1"] {
+            schematic.nets.push(Net {
+                id: name.to_lowercase(),
+                name: name.to_string(),
+                pins: vec![PinConnection { component_id: counter.id.clone(), pin_id: name.to_string() }],
+            });
+        }
+        schematic.add_component(counter).unwrap();
+
+        let settings = DigitalSettings {
+            stop_time: 100e-9,
+            clocks: vec![ClockGenerator { net: "CLK".to_string(), period: 20e-9, delay: 10e-9, duty: 0.5 }],
+            stimuli: vec![DigitalStimulus { net: "RST".to_string(), changes: vec![(0.0, High), (5e-9, Low)] }],
+            default_delay: None,
+        };
+        let waveforms = DigitalSimulator::run(&schematic, &settings).unwrap();
+
+        // Reset clears the count after the part's 5 ns delay, then rising edges at 10, 30, 50 ns count up
+        let q0 = waveforms.signal("Q0").unwrap();
+        let q1 = waveforms.signal("Q1").unwrap();
+        assert_eq!(q0.value_at(0), HighZ);
+        assert_eq!(q0.value_at(5_000), Low);
+        assert_eq!(q0.value_at(14_000), Low);
+        assert_eq!((q0.value_at(15_000), q1.value_at(15_000)), (High, Low));
+        assert_eq!((q0.value_at(35_000), q1.value_at(35_000)), (Low, High));
+        assert_eq!((q0.value_at(55_000), q1.value_at(55_000)), (High, High));
+        assert!(waveforms.unsupported.is_empty());
+    }
+
+    // Feeds `steps` of inputs to a behavior, each seeing the previous step as history
+    fn drive(behavior: &mut Behavior, steps: &[Vec<LogicLevel>]) -> Vec<Vec<LogicLevel>> {
+        let mut previous = vec![LogicLevel::Unknown; steps[0].len()];
+        steps.iter()
+            .map(|inputs| {
+                let outputs = behavior.evaluate(inputs, &previous);
+                previous = inputs.clone();
+                outputs
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_shift_register_decoder_and_mux() {
+        use LogicLevel::*;
+
+        // Shift in 1, 0, 1 on SRCLK edges, then latch with RCLK: Q0 = last bit, Q1 = the one before
+        let mut register = Behavior::ShiftRegister { shift: [Unknown; 8], storage: [Unknown; 8] };
+        let outputs = drive(&mut register, &[
+            vec![High, Low, Low],
+            vec![High, High, Low],
+            vec![Low, Low, Low],
+            vec![Low, High, Low],
+            vec![High, Low, Low],
+            vec![High, High, Low],
+            vec![High, High, High],
+        ]);
+        // Nothing reaches the outputs before the storage clock
+        assert_eq!(outputs[5], vec![Unknown, Unknown, Unknown]);
+        assert_eq!(outputs[6], vec![High, Low, Unknown]);
+        // Five more shifts move the first bit to Q7S; the latched outputs hold
+        let mut steps = Vec::new();
+        for _ in 0..5 {
+            steps.push(vec![Low, Low, Low]);
+            steps.push(vec![Low, High, Low]);
+        }
+        let outputs = drive(&mut register, &steps);
+        assert_eq!(outputs.last().unwrap()[2], High);
+        assert_eq!(outputs.last().unwrap()[..2], [High, Low]);
+
+        let mut decoder = Behavior::Decoder;
+        let decode = |decoder: &mut Behavior, inputs: Vec<LogicLevel>| drive(decoder, &[inputs]).remove(0);
+        assert_eq!(decode(&mut decoder, vec![Low, Low, High]), vec![High, Low, Low, Low]);
+        assert_eq!(decode(&mut decoder, vec![High, High, High]), vec![Low, Low, Low, High]);
+        assert_eq!(decode(&mut decoder, vec![Low, High, Low]), vec![Low; 4]);
+        assert_eq!(decode(&mut decoder, vec![Unknown, High, High]), vec![Unknown; 4]);
+        assert_eq!(decode(&mut decoder, vec![Low, High, HighZ]), vec![Unknown; 4]);
+
+        let mut mux = Behavior::Multiplexer;
+        let data = [Low, High, High, Low];
+        for (s0, s1, expected) in [(Low, Low, Low), (High, Low, High), (Low, High, High), (High, High, Low)] {
+            let mut inputs = data.to_vec();
+            inputs.extend([s0, s1]);
+            assert_eq!(drive(&mut mux, &[inputs]).remove(0), vec![expected]);
+        }
+        // An unknown select only matters when the data inputs differ
+        assert_eq!(drive(&mut mux, &[vec![High, High, High, High, Unknown, Low]]).remove(0), vec![High]);
+        assert_eq!(drive(&mut mux, &[vec![Low, High, Low, Low, Unknown, Low]]).remove(0), vec![Unknown]);
+        assert_eq!(drive(&mut mux, &[vec![HighZ, Low, Low, Low, Low, Low]]).remove(0), vec![Unknown]);
+    }
+
+    #[test]
+    fn test_74hc00_unit_run() {
+        use crate::models::{Component, ElectricalType, Net, Pin, PinConnection};
+        use LogicLevel::*;
+
+        // Unit B of a quad NAND only carries gate 2's pins
+        let mut gate = Component::new("74hc00".to_string(), 0.0, 0.0).unwrap();
+        gate.reference = "U1".to_string();
+        gate.unit = Some("B".to_string());
+        for (id, pin_type) in [("2A", PinType::Input), ("2B", PinType::Input), ("2Y", PinType::Output)] {
+            gate.pins.push(Pin {
+                id: id.to_string(),
+                name: id.to_string(),
+                number: id.to_string(),
+                x: 0.0,
+                y: 0.0,
+                pin_type,
+                electrical: ElectricalType { voltage: None, current: None, impedance: None },
+                connected: false,
+            });
+        }
+        let mut schematic = Schematic::new("Nand".to_string()).unwrap();
+        for (net, pin) in [("A", "2A"), ("B", "2B"), ("Y", "2Y")] {
+            schematic.nets.push(Net {
+                id: net.to_lowercase(),
+                name: net.to_string(),
+                pins: vec![PinConnection { component_id: gate.id.clone(), pin_id: pin.to_string() }],
+            });
+        }
+        schematic.add_component(gate).unwrap();
+
+        let settings = DigitalSettings {
+            stop_time: 40e-9,
+            clocks: Vec::new(),
+            stimuli: vec![
+                DigitalStimulus { net: "A".to_string(), changes: vec![(0.0, Low), (10e-9, High), (30e-9, Low)] },
+                DigitalStimulus { net: "B".to_string(), changes: vec![(0.0, High), (20e-9, High)] },
+            ],
+            default_delay: Some(2e-9),
+        };
+        let waveforms = DigitalSimulator::run(&schematic, &settings).unwrap();
+        let y = waveforms.signal("Y").unwrap();
+        assert_eq!(y.value_at(1_000), HighZ);
+        assert_eq!(y.value_at(2_000), High);
+        assert_eq!(y.value_at(11_000), High);
+        assert_eq!(y.value_at(12_000), Low);
+        assert_eq!(y.value_at(32_000), High);
+        assert!(waveforms.unsupported.is_empty());
+    }
+}
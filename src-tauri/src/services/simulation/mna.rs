@@ -0,0 +1,258 @@
+use super::circuit::Node;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+// Matrix entry type: f64 for DC and transient, Complex for AC
+pub(crate) trait Scalar:
+    Copy + PartialEq + From<f64> + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self>
+    + Neg<Output = Self> + AddAssign + SubAssign + std::iter::Sum
+{
+    fn magnitude(self) -> f64;
+    fn is_finite(self) -> bool;
+}
+
+impl Scalar for f64 {
+    fn magnitude(self) -> f64 {
+        self.abs()
+    }
+
+    fn is_finite(self) -> bool {
+        f64::is_finite(self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    pub fn from_polar(magnitude: f64, degrees: f64) -> Self {
+        let angle = degrees.to_radians();
+        Self::new(magnitude * angle.cos(), magnitude * angle.sin())
+    }
+
+    pub fn phase_degrees(self) -> f64 {
+        self.im.atan2(self.re).to_degrees()
+    }
+}
+
+impl From<f64> for Complex {
+    fn from(re: f64) -> Self {
+        Self::new(re, 0.0)
+    }
+}
+
+impl Add for Complex {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        Self::new(self.re * other.re - self.im * other.im, self.re * other.im + self.im * other.re)
+    }
+}
+
+impl Div for Complex {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        let denominator = other.re * other.re + other.im * other.im;
+        Self::new(
+            (self.re * other.re + self.im * other.im) / denominator,
+            (self.im * other.re - self.re * other.im) / denominator,
+        )
+    }
+}
+
+impl Neg for Complex {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.re, -self.im)
+    }
+}
+
+impl AddAssign for Complex {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Complex {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl std::iter::Sum for Complex {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Complex::default(), |a, b| a + b)
+    }
+}
+
+impl Scalar for Complex {
+    fn magnitude(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    fn is_finite(self) -> bool {
+        self.re.is_finite() && self.im.is_finite()
+    }
+}
+
+// Modified nodal analysis system: one row per non-ground node followed by one
+// row per branch current (voltage sources and inductors)
+pub(crate) struct Mna<T: Scalar = f64> {
+    node_count: usize,
+    matrix: Vec<Vec<T>>,
+    rhs: Vec<T>,
+}
+
+// Controlling voltage of a nonlinear device: `sign * (v[plus] - v[minus])` over its terminals
+pub(crate) struct Control {
+    pub plus: usize,
+    pub minus: usize,
+    pub sign: f64,
+    pub value: f64,
+}
+
+// Terminal currents (into the device) and their derivatives with respect to each control
+pub(crate) struct Linearization {
+    pub currents: Vec<f64>,
+    pub gains: Vec<Vec<f64>>,
+    pub controls: Vec<Control>,
+}
+
+impl<T: Scalar> Mna<T> {
+    pub fn new(node_count: usize, branch_count: usize) -> Self {
+        let size = node_count + branch_count;
+        Self {
+            node_count,
+            matrix: vec![vec![T::from(0.0); size]; size],
+            rhs: vec![T::from(0.0); size],
+        }
+    }
+
+    pub fn conductance(&mut self, a: Node, b: Node, g: T) {
+        if let Some(a) = a {
+            self.matrix[a][a] += g;
+        }
+        if let Some(b) = b {
+            self.matrix[b][b] += g;
+        }
+        if let (Some(a), Some(b)) = (a, b) {
+            self.matrix[a][b] -= g;
+            self.matrix[b][a] -= g;
+        }
+    }
+
+    /// Current `i` flowing from `a` through the element to `b`.
+    pub fn current(&mut self, a: Node, b: Node, i: T) {
+        if let Some(a) = a {
+            self.rhs[a] -= i;
+        }
+        if let Some(b) = b {
+            self.rhs[b] += i;
+        }
+    }
+
+    /// Branch forcing `v(p) - v(n) = voltage`; its unknown is the current from `p` through the branch to `n`.
+    pub fn voltage_branch(&mut self, branch: usize, p: Node, n: Node, voltage: T) {
+        let row = self.node_count + branch;
+        if let Some(p) = p {
+            self.matrix[p][row] += T::from(1.0);
+            self.matrix[row][p] += T::from(1.0);
+        }
+        if let Some(n) = n {
+            self.matrix[n][row] -= T::from(1.0);
+            self.matrix[row][n] -= T::from(1.0);
+        }
+        self.rhs[row] += voltage;
+    }
+
+    /// Series impedance in a voltage branch, turning its equation into `v(p) - v(n) - z * i = voltage`.
+    pub fn branch_impedance(&mut self, branch: usize, z: T) {
+        let row = self.node_count + branch;
+        self.matrix[row][row] -= z;
+    }
+
+    /// Newton stamp of a nonlinear device: its small-signal conductances plus the offset
+    /// that makes the linearised currents match the device at the current operating point.
+    pub fn nonlinear(&mut self, terminals: &[Node], linearization: &Linearization) {
+        self.small_signal(terminals, linearization);
+        for (k, node) in terminals.iter().enumerate() {
+            let Some(row) = *node else { continue };
+            let offset = linearization.currents[k] - linearization.controls.iter()
+                .zip(&linearization.gains[k])
+                .map(|(control, gain)| gain * control.value)
+                .sum::<f64>();
+            self.rhs[row] -= T::from(offset);
+        }
+    }
+
+    pub fn small_signal(&mut self, terminals: &[Node], linearization: &Linearization) {
+        for (k, node) in terminals.iter().enumerate() {
+            let Some(row) = *node else { continue };
+            for (control, &gain) in linearization.controls.iter().zip(&linearization.gains[k]) {
+                let gain = T::from(gain * control.sign);
+                if let Some(plus) = terminals[control.plus] {
+                    self.matrix[row][plus] += gain;
+                }
+                if let Some(minus) = terminals[control.minus] {
+                    self.matrix[row][minus] -= gain;
+                }
+            }
+        }
+    }
+
+    pub fn solve(self) -> Option<Vec<T>> {
+        solve_dense(self.matrix, self.rhs)
+    }
+}
+
+// Gaussian elimination with partial pivoting; schematic-sized circuits stay small enough for dense storage
+pub(crate) fn solve_dense<T: Scalar>(mut a: Vec<Vec<T>>, mut b: Vec<T>) -> Option<Vec<T>> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].magnitude().total_cmp(&a[j][col].magnitude()))?;
+        if a[pivot][col].magnitude() < 1e-18 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+
+        for row in col + 1..n {
+            let factor = a[row][col] / a[col][col];
+            if factor == T::from(0.0) {
+                continue;
+            }
+            let (upper, lower) = a.split_at_mut(row);
+            for (target, source) in lower[0][col..].iter_mut().zip(&upper[col][col..]) {
+                *target -= factor * *source;
+            }
+            let pivot_rhs = b[col];
+            b[row] -= factor * pivot_rhs;
+        }
+    }
+
+    let mut x = vec![T::from(0.0); n];
+    for row in (0..n).rev() {
+        let sum: T = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - sum) / a[row][row];
+    }
+    x.iter().all(|v| v.is_finite()).then_some(x)
+}
@@ -0,0 +1,17 @@
+// Circuit simulation built on the extracted netlist: element models, the MNA
+// assembly shared by the analog analyses, the analyses themselves and the
+// event-driven digital simulator
+pub mod ac;
+pub mod circuit;
+pub mod dc;
+pub mod digital;
+mod mna;
+pub mod ngspice;
+pub mod transient;
+
+pub use ac::AcSolver;
+pub use circuit::{Circuit, Element, ElementKind, Source, Waveform};
+pub use dc::DcSolver;
+pub use digital::DigitalSimulator;
+pub use ngspice::NgspiceRunner;
+pub use transient::TransientSolver;
@@ -0,0 +1,379 @@
+use super::mna::{Complex, Scalar};
+use super::transient::column;
+use crate::models::{
+    AnalysisType, NodeVoltage, OperatingPoint, PinCurrent, ProbeTarget, Schematic, SpiceAnalysis, SpiceResult,
+    WaveformColumn, Waveforms,
+};
+use crate::services::export_service::{ExportService, SpiceNetlist};
+use crate::utils::error::{AppError, Result};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const DEFAULT_BINARY: &str = "ngspice";
+const RUN_TIMEOUT: Duration = Duration::from_secs(300);
+const RAW_FILE: &str = "output.raw";
+
+/// Runs a local ngspice in batch mode on the schematic's SPICE netlist and maps the
+/// ASCII raw output back to schematic nets and pins.
+pub struct NgspiceRunner {
+    binary: PathBuf,
+}
+
+// One plot from an ngspice raw file
+#[derive(Debug)]
+struct RawPlot {
+    complex: bool,
+    // (name, kind) per variable, e.g. ("v(out)", "voltage")
+    variables: Vec<(String, String)>,
+    // One column of (real, imaginary) values per variable
+    columns: Vec<Vec<(f64, f64)>>,
+}
+
+impl NgspiceRunner {
+    pub fn new(binary: Option<PathBuf>) -> Self {
+        Self { binary: binary.unwrap_or_else(|| PathBuf::from(DEFAULT_BINARY)) }
+    }
+
+    /// ngspice version banner; fails with an install hint when the binary cannot be run.
+    pub async fn version(&self) -> Result<String> {
+        let output = self.command().arg("--version").output().await.map_err(|e| self.launch_error(e))?;
+        let text = String::from_utf8_lossy(&output.stdout);
+        Ok(text.lines().find(|line| line.to_lowercase().contains("ngspice")).unwrap_or("ngspice").trim().to_string())
+    }
+
+    pub async fn run(&self, schematic: &Schematic, analysis: &SpiceAnalysis) -> Result<SpiceResult> {
+        match analysis {
+            SpiceAnalysis::Transient(settings) => settings.validate()?,
+            SpiceAnalysis::Ac(settings) => settings.validate()?,
+            SpiceAnalysis::OperatingPoint => {}
+        }
+        let netlist = ExportService::spice_netlist(schematic)?;
+
+        let directory = std::env::temp_dir().join(format!("rust-eda-ngspice-{}", uuid::Uuid::new_v4()));
+        tokio::fs::create_dir_all(&directory).await?;
+        let result = self.run_in(&directory, &netlist, analysis).await;
+        let _ = tokio::fs::remove_dir_all(&directory).await;
+        result
+    }
+
+    async fn run_in(&self, directory: &Path, netlist: &SpiceNetlist, analysis: &SpiceAnalysis) -> Result<SpiceResult> {
+        let deck_path = directory.join("circuit.cir");
+        tokio::fs::write(&deck_path, netlist.deck(&control_block(analysis))).await?;
+
+        let run = self.command()
+            .arg("-b")
+            .arg(&deck_path)
+            .current_dir(directory)
+            .kill_on_drop(true)
+            .output();
+        let output = tokio::time::timeout(RUN_TIMEOUT, run)
+            .await
+            .map_err(|_| AppError::InvalidOperation(format!("ngspice did not finish within {} s", RUN_TIMEOUT.as_secs())))?
+            .map_err(|e| self.launch_error(e))?;
+
+        let raw = match tokio::fs::read_to_string(directory.join(RAW_FILE)).await {
+            Ok(raw) if output.status.success() => raw,
+            _ => {
+                // ngspice reports netlist and convergence errors on either stream
+                let log = format!("{}{}", String::from_utf8_lossy(&output.stderr), String::from_utf8_lossy(&output.stdout));
+                let errors: Vec<&str> = log.lines()
+                    .filter(|line| line.to_lowercase().contains("error"))
+                    .take(5)
+                    .collect();
+                let detail = if errors.is_empty() { format!("exit status {}", output.status) } else { errors.join("; ") };
+                return Err(AppError::InvalidOperation(format!("ngspice failed: {}", detail)));
+            }
+        };
+
+        let plot = parse_raw(&raw)?;
+        Ok(match analysis {
+            SpiceAnalysis::OperatingPoint => SpiceResult::OperatingPoint(operating_point(netlist, &plot)),
+            SpiceAnalysis::Transient(_) => SpiceResult::Waveforms(waveforms(netlist, &plot, AnalysisType::Transient)),
+            SpiceAnalysis::Ac(_) => SpiceResult::Waveforms(waveforms(netlist, &plot, AnalysisType::Ac)),
+        })
+    }
+
+    fn command(&self) -> tokio::process::Command {
+        let mut command = tokio::process::Command::new(&self.binary);
+        command.stdin(std::process::Stdio::null());
+        command
+    }
+
+    fn launch_error(&self, error: std::io::Error) -> AppError {
+        if error.kind() == std::io::ErrorKind::NotFound {
+            AppError::InvalidOperation(format!(
+                "ngspice was not found at \"{}\". Install ngspice (https://ngspice.sourceforge.io) and make sure it is on PATH, or set its location.",
+                self.binary.display()
+            ))
+        } else {
+            AppError::InvalidOperation(format!("Could not start ngspice: {}", error))
+        }
+    }
+}
+
+fn control_block(analysis: &SpiceAnalysis) -> String {
+    let command = match analysis {
+        SpiceAnalysis::OperatingPoint => "op".to_string(),
+        SpiceAnalysis::Transient(settings) => {
+            let mut command = format!("tran {} {} {}", settings.step, settings.stop_time, settings.start_time);
+            if settings.method == crate::models::IntegrationMethod::BackwardEuler {
+                command = format!("option method=gear maxord=1\n{}", command);
+            }
+            command
+        }
+        SpiceAnalysis::Ac(settings) => format!(
+            "ac dec {} {} {}", settings.points_per_decade, settings.start_frequency, settings.stop_frequency
+        ),
+    };
+    format!(".control\nset filetype=ascii\n{}\nwrite {} all\nquit\n.endc\n", command, RAW_FILE)
+}
+
+// Reads the first plot of an ASCII raw file
+fn parse_raw(text: &str) -> Result<RawPlot> {
+    let invalid = |what: &str| AppError::InvalidFormat(format!("ngspice raw file: {}", what));
+    let mut lines = text.lines();
+    let mut complex = false;
+    let mut variable_count = 0;
+    let mut point_count = 0;
+
+    for line in lines.by_ref() {
+        let (key, value) = line.split_once(':').unwrap_or((line, ""));
+        match key.trim().to_lowercase().as_str() {
+            "flags" => complex = value.to_lowercase().contains("complex"),
+            "no. variables" => variable_count = value.trim().parse().map_err(|_| invalid("bad variable count"))?,
+            "no. points" => point_count = value.trim().parse().map_err(|_| invalid("bad point count"))?,
+            "variables" => break,
+            "binary" => return Err(invalid("binary output; expected ASCII")),
+            _ => {}
+        }
+    }
+
+    let mut variables = Vec::with_capacity(variable_count);
+    for line in lines.by_ref().take(variable_count) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 3 {
+            return Err(invalid("bad variable line"));
+        }
+        variables.push((fields[1].to_lowercase(), fields[2].to_lowercase()));
+    }
+    if variables.len() != variable_count || !lines.next().is_some_and(|l| l.trim().eq_ignore_ascii_case("values:")) {
+        return Err(invalid("missing variables or values section"));
+    }
+
+    // Each point starts with its index, followed by one value per variable
+    let values: Vec<&str> = lines
+        .flat_map(|line| line.split_whitespace())
+        .collect();
+    let stride = variable_count + 1;
+    if values.len() < point_count * stride {
+        return Err(invalid("truncated values"));
+    }
+    let parse_value = |field: &str| -> Result<(f64, f64)> {
+        let (re, im) = field.split_once(',').unwrap_or((field, "0"));
+        Ok((re.parse().map_err(|_| invalid("bad number"))?, im.parse().map_err(|_| invalid("bad number"))?))
+    };
+    let mut columns = vec![Vec::with_capacity(point_count); variable_count];
+    for point in values.chunks(stride).take(point_count) {
+        for (column, field) in columns.iter_mut().zip(&point[1..]) {
+            column.push(parse_value(field)?);
+        }
+    }
+
+    Ok(RawPlot { complex, variables, columns })
+}
+
+// Node name from a raw variable such as "v(out)" or plain "out"
+fn node_of(variable: &str) -> &str {
+    variable.strip_prefix("v(").and_then(|v| v.strip_suffix(')')).unwrap_or(variable)
+}
+
+// Element name from a branch current such as "v1#branch" or "i(v1)"
+fn branch_of(variable: &str) -> Option<&str> {
+    variable.strip_suffix("#branch")
+        .or_else(|| variable.strip_prefix("i(").and_then(|v| v.strip_suffix(')')))
+}
+
+fn operating_point(netlist: &SpiceNetlist, plot: &RawPlot) -> OperatingPoint {
+    let value_of = |index: usize| plot.columns[index].first().map_or(0.0, |v| v.0);
+
+    let node_voltages = netlist.nets.iter()
+        .map(|net| NodeVoltage {
+            net_id: net.net_id.clone(),
+            net_name: net.net_name.clone(),
+            voltage: plot.variables.iter()
+                .position(|(name, kind)| kind == "voltage" && node_of(name) == net.node)
+                .map_or(0.0, value_of),
+        })
+        .collect();
+
+    let pin_currents = netlist.branches.iter()
+        .filter_map(|branch| {
+            let index = plot.variables.iter().position(|(name, _)| branch_of(name) == Some(branch.element.as_str()))?;
+            Some(PinCurrent {
+                component_id: branch.component_id.clone(),
+                reference: branch.element.to_uppercase(),
+                pin_id: branch.pin_id.clone(),
+                current: value_of(index),
+            })
+        })
+        .collect();
+
+    OperatingPoint {
+        node_voltages,
+        pin_currents,
+        iterations: 0,
+        unsupported: netlist.unsupported.clone(),
+        solved_at: chrono::Utc::now(),
+    }
+}
+
+// Columns for nets and branch currents in the same layout as the built-in analyses
+fn waveforms(netlist: &SpiceNetlist, plot: &RawPlot, analysis: AnalysisType) -> Waveforms {
+    let axis = match analysis {
+        AnalysisType::Transient => column("time", "s", None),
+        AnalysisType::Ac => column("frequency", "Hz", None),
+    };
+    let mut columns = vec![WaveformColumn { values: plot.columns[0].iter().map(|v| v.0.abs()).collect(), ..axis }];
+    let points = plot.columns[0].len();
+
+    let mut push = |name: String, unit: &str, target: ProbeTarget, values: Option<&Vec<(f64, f64)>>| {
+        let values: Vec<(f64, f64)> = values.cloned().unwrap_or_else(|| vec![(0.0, 0.0); points]);
+        if plot.complex {
+            let phasors: Vec<Complex> = values.iter().map(|(re, im)| Complex::new(*re, *im)).collect();
+            columns.push(WaveformColumn {
+                values: phasors.iter().map(|p| p.magnitude()).collect(),
+                ..column(&format!("|{}|", name), unit, Some(target.clone()))
+            });
+            columns.push(WaveformColumn {
+                values: phasors.iter().map(|p| p.phase_degrees()).collect(),
+                ..column(&format!("phase {}", name), "deg", Some(target))
+            });
+        } else {
+            columns.push(WaveformColumn {
+                values: values.iter().map(|v| v.0).collect(),
+                ..column(&name, unit, Some(target))
+            });
+        }
+    };
+
+    for net in &netlist.nets {
+        // Ground nets are not in the raw file and stay at zero
+        let values = plot.variables.iter()
+            .position(|(name, kind)| kind == "voltage" && node_of(name) == net.node)
+            .map(|index| &plot.columns[index]);
+        push(format!("V({})", net.net_name), "V", ProbeTarget::Voltage { net_id: net.net_id.clone() }, values);
+    }
+    for branch in &netlist.branches {
+        let Some(index) = plot.variables.iter().position(|(name, _)| branch_of(name) == Some(branch.element.as_str())) else {
+            continue;
+        };
+        push(
+            format!("I({}.{})", branch.element.to_uppercase(), branch.pin_id),
+            "A",
+            ProbeTarget::Current { component_id: branch.component_id.clone(), pin_id: branch.pin_id.clone() },
+            Some(&plot.columns[index]),
+        );
+    }
+
+    Waveforms {
+        analysis,
+        columns,
+        unsupported: netlist.unsupported.clone(),
+        simulated_at: chrono::Utc::now(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::circuit::tests::{connect, part};
+    use crate::models::{AcSweepSettings, IntegrationMethod, TransientSettings};
+
+    #[test]
+    fn test_spice_netlist_nodes() {
+        let mut schematic = Schematic::new("Divider".to_string()).unwrap();
+        let battery = part(&mut schematic, "battery", "BT1", "10V", &["+", "-"]);
+        part(&mut schematic, "resistor", "R1", "1k", &["1", "2"]);
+        part(&mut schematic, "resistor", "R2", "3k", &["1", "2"]);
+        part(&mut schematic, "resistor", "R3", "10k", &["1", "2"]);
+        part(&mut schematic, "op-amp", "U1", "TL072", &["IN+", "IN-", "OUT"]);
+        // Names that reduce to the same SPICE node get a suffix
+        connect(&mut schematic, "V IN", &["BT1.+", "R1.1"]);
+        connect(&mut schematic, "v-in", &["R1.2", "R2.1", "R3.1", "U1.IN+"]);
+        connect(&mut schematic, "GND", &["BT1.-", "R2.2"]);
+
+        let netlist = ExportService::spice_netlist(&schematic).unwrap();
+        let nodes: Vec<(&str, &str, &str)> = netlist.nets.iter()
+            .map(|n| (n.net_id.as_str(), n.net_name.as_str(), n.node.as_str()))
+            .collect();
+        assert_eq!(nodes, vec![
+            ("net-GND", "GND", "0"),
+            ("net-V IN", "V IN", "v_in"),
+            ("net-v-in", "v-in", "v_in_2"),
+        ]);
+
+        // Element names take the SPICE letter; the open R3 pin gets a node of its own
+        let lines: Vec<&str> = netlist.body.lines().collect();
+        assert!(lines.contains(&"VBT1 v_in 0 DC 10"), "{}", netlist.body);
+        assert!(lines.contains(&"R1 v_in v_in_2 1000"));
+        assert!(lines.contains(&"R2 v_in_2 0 3000"));
+        assert!(lines.iter().any(|l| l.starts_with("R3 v_in_2 nc")));
+        assert!(lines.contains(&"* U1: no simulation model"));
+        assert_eq!(netlist.unsupported, vec!["U1".to_string()]);
+
+        // Branch currents map back to the source's first pin
+        assert_eq!(netlist.branches.len(), 1);
+        assert_eq!(netlist.branches[0].element, "vbt1");
+        assert_eq!(netlist.branches[0].component_id, battery);
+        assert_eq!(netlist.branches[0].pin_id, "BT1.+");
+
+        let deck = netlist.deck(".op");
+        assert!(deck.starts_with("* Divider\n"));
+        assert!(deck.ends_with("\n.op\n.end\n"));
+    }
+
+    #[test]
+    fn test_control_block() {
+        let block = control_block(&SpiceAnalysis::OperatingPoint);
+        assert_eq!(block, ".control\nset filetype=ascii\nop\nwrite output.raw all\nquit\n.endc\n");
+
+        let transient = |method| SpiceAnalysis::Transient(TransientSettings { stop_time: 0.01, step: 1e-5, start_time: 0.002, method });
+        assert!(control_block(&transient(IntegrationMethod::Trapezoidal)).contains("\ntran 0.00001 0.01 0.002\nwrite"));
+        assert!(control_block(&transient(IntegrationMethod::BackwardEuler))
+            .contains("\noption method=gear maxord=1\ntran 0.00001 0.01 0.002\n"));
+
+        let ac = SpiceAnalysis::Ac(AcSweepSettings { start_frequency: 10.0, stop_frequency: 100000.0, points_per_decade: 20 });
+        assert!(control_block(&ac).contains("\nac dec 20 10 100000\n"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_ngspice() {
+        let binary = std::env::temp_dir().join(format!("rust-eda-no-ngspice-{}", uuid::Uuid::new_v4()));
+        let runner = NgspiceRunner::new(Some(binary));
+        assert!(runner.version().await.unwrap_err().to_string().contains("ngspice was not found"));
+
+        let mut schematic = Schematic::new("Open".to_string()).unwrap();
+        part(&mut schematic, "resistor", "R1", "1k", &["1", "2"]);
+        connect(&mut schematic, "GND", &["R1.1", "R1.2"]);
+        let error = runner.run(&schematic, &SpiceAnalysis::OperatingPoint).await.unwrap_err().to_string();
+        assert!(error.contains("ngspice was not found") && error.contains("Install ngspice"), "{}", error);
+    }
+
+    #[test]
+    fn test_parse_ascii_raw() {
+        let raw = "Title: * test\nDate: today\nPlotname: AC Analysis\nFlags: complex\nNo. Variables: 2\nNo. Points: 2\n\
+                   Variables:\n\t0\tfrequency\tfrequency grid=3\n\t1\tv(out)\tvoltage\nValues:\n\
+                   0\t1.000000e+02,0.000000e+00\n\t7.0e-01,-7.0e-01\n\
+                   1\t1.000000e+03,0.000000e+00\n\t1.0e-01,-9.9e-02\n";
+        let plot = parse_raw(raw).unwrap();
+        assert!(plot.complex);
+        assert_eq!(plot.variables[1], ("v(out)".to_string(), "voltage".to_string()));
+        assert_eq!(plot.columns[0][1], (1000.0, 0.0));
+        assert_eq!(plot.columns[1][0], (0.7, -0.7));
+
+        assert_eq!(node_of("v(out)"), "out");
+        assert_eq!(branch_of("v1#branch"), Some("v1"));
+        assert_eq!(branch_of("i(l2)"), Some("l2"));
+        assert!(parse_raw("Title: x\nBinary:\n").is_err());
+    }
+}
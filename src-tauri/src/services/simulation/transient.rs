@@ -0,0 +1,238 @@
+use super::circuit::{Circuit, Element, ElementKind};
+use super::dc::{newton, pin_currents, voltage, DcSolver, StampMode};
+use crate::models::{AnalysisType, IntegrationMethod, ProbeTarget, Schematic, TransientSettings, WaveformColumn, Waveforms};
+use crate::utils::error::Result;
+
+// Times a failing step may be halved before the analysis gives up
+const MAX_STEP_HALVINGS: usize = 10;
+
+/// Fixed-step transient analysis starting from the operating point at t = 0. Steps that
+/// fail to converge are retried as two half steps.
+pub struct TransientSolver;
+
+/// Discretised capacitors and inductors for one time step, built from the previous point.
+pub(crate) struct Companion {
+    pub step: f64,
+    pub method: IntegrationMethod,
+    pub node_count: usize,
+    pub previous: Vec<f64>,
+    // Capacitor currents at the previous point, indexed like the circuit elements
+    pub capacitor_currents: Vec<f64>,
+}
+
+impl Companion {
+    fn previous_voltage(&self, element: &Element) -> f64 {
+        voltage(&self.previous, element.nodes[0]) - voltage(&self.previous, element.nodes[1])
+    }
+
+    /// Conductance and offset of the capacitor model `i = g * v + offset`.
+    pub fn capacitor(&self, index: usize, element: &Element, capacitance: f64) -> (f64, f64) {
+        let previous_voltage = self.previous_voltage(element);
+        match self.method {
+            IntegrationMethod::BackwardEuler => {
+                let g = capacitance / self.step;
+                (g, -g * previous_voltage)
+            }
+            IntegrationMethod::Trapezoidal => {
+                let g = 2.0 * capacitance / self.step;
+                (g, -g * previous_voltage - self.capacitor_currents[index])
+            }
+        }
+    }
+
+    pub fn capacitor_current(&self, index: usize, element: &Element, capacitance: f64, solution: &[f64]) -> f64 {
+        let (g, offset) = self.capacitor(index, element, capacitance);
+        g * (voltage(solution, element.nodes[0]) - voltage(solution, element.nodes[1])) + offset
+    }
+
+    /// Series impedance and source voltage of the inductor branch `v - z * i = voltage`.
+    pub fn inductor(&self, branch: usize, element: &Element, inductance: f64) -> (f64, f64) {
+        let previous_current = self.previous[self.node_count + branch];
+        match self.method {
+            IntegrationMethod::BackwardEuler => {
+                let z = inductance / self.step;
+                (z, -z * previous_current)
+            }
+            IntegrationMethod::Trapezoidal => {
+                let z = 2.0 * inductance / self.step;
+                (z, -z * previous_current - self.previous_voltage(element))
+            }
+        }
+    }
+}
+
+impl TransientSolver {
+    pub fn run(schematic: &Schematic, settings: &TransientSettings) -> Result<Waveforms> {
+        settings.validate()?;
+        let circuit = Circuit::from_schematic(schematic)?;
+        Self::simulate(&circuit, settings)
+    }
+
+    pub fn simulate(circuit: &Circuit, settings: &TransientSettings) -> Result<Waveforms> {
+        let (mut solution, _) = DcSolver::solve_at(circuit, Some(0.0))?;
+        let mut capacitor_currents = vec![0.0; circuit.elements.len()];
+        let mut recorder = Recorder::new(circuit);
+        if settings.start_time == 0.0 {
+            recorder.record(circuit, 0.0, &solution, None);
+        }
+
+        // 1m / 1µ comes out a hair over 1000; without the tolerance the last step is empty
+        let steps = (settings.stop_time / settings.step - 1e-9).ceil() as usize;
+        let mut time = 0.0;
+        for n in 1..=steps {
+            let next_time = (n as f64 * settings.step).min(settings.stop_time);
+            if next_time <= time {
+                continue;
+            }
+            let companion = Self::advance(circuit, settings.method, &mut solution, &mut capacitor_currents, time, next_time, 0)?;
+            time = next_time;
+            if time >= settings.start_time {
+                recorder.record(circuit, time, &solution, Some(&companion));
+            }
+        }
+
+        Ok(Waveforms {
+            analysis: AnalysisType::Transient,
+            columns: recorder.columns,
+            unsupported: circuit.unsupported.clone(),
+            simulated_at: chrono::Utc::now(),
+        })
+    }
+
+    // Steps from `from` to `to`, splitting the step when Newton fails. Returns the companion
+    // models of the last sub-step so currents at `to` can be reported.
+    fn advance(
+        circuit: &Circuit,
+        method: IntegrationMethod,
+        solution: &mut Vec<f64>,
+        capacitor_currents: &mut [f64],
+        from: f64,
+        to: f64,
+        depth: usize,
+    ) -> Result<Companion> {
+        let companion = Companion {
+            step: to - from,
+            method,
+            node_count: circuit.node_count,
+            previous: solution.clone(),
+            capacitor_currents: capacitor_currents.to_vec(),
+        };
+        let mode = StampMode { source_scale: 1.0, time: Some(to), companion: Some(&companion) };
+
+        match newton(circuit, solution, &mode) {
+            Ok((next, _)) => {
+                for (index, element) in circuit.elements.iter().enumerate() {
+                    if let ElementKind::Capacitor { capacitance } = element.kind {
+                        capacitor_currents[index] = companion.capacitor_current(index, element, capacitance, &next);
+                    }
+                }
+                *solution = next;
+                Ok(companion)
+            }
+            Err(_) if depth < MAX_STEP_HALVINGS => {
+                let middle = (from + to) / 2.0;
+                Self::advance(circuit, method, solution, capacitor_currents, from, middle, depth + 1)?;
+                Self::advance(circuit, method, solution, capacitor_currents, middle, to, depth + 1)
+            }
+            Err(error) => Err(error),
+        }
+    }
+}
+
+// Collects one column per net voltage and pin current, after the time axis
+struct Recorder {
+    columns: Vec<WaveformColumn>,
+}
+
+impl Recorder {
+    fn new(circuit: &Circuit) -> Self {
+        let mut columns = vec![column("time", "s", None)];
+        for net in &circuit.nets {
+            columns.push(column(&format!("V({})", net.name), "V", Some(ProbeTarget::Voltage { net_id: net.id.clone() })));
+        }
+        let zero = vec![0.0; circuit.node_count + circuit.branch_count()];
+        for pin in pin_currents(circuit, &zero, &StampMode::dc(None)) {
+            columns.push(column(
+                &format!("I({}.{})", pin.reference, pin.pin_id),
+                "A",
+                Some(ProbeTarget::Current { component_id: pin.component_id, pin_id: pin.pin_id }),
+            ));
+        }
+        Self { columns }
+    }
+
+    fn record(&mut self, circuit: &Circuit, time: f64, solution: &[f64], companion: Option<&Companion>) {
+        let mode = StampMode { source_scale: 1.0, time: Some(time), companion };
+        let values = std::iter::once(time)
+            .chain(circuit.nets.iter().map(|net| voltage(solution, net.node)))
+            .chain(pin_currents(circuit, solution, &mode).into_iter().map(|pin| pin.current));
+        for (column, value) in self.columns.iter_mut().zip(values) {
+            column.values.push(value);
+        }
+    }
+}
+
+pub(crate) fn column(name: &str, unit: &str, target: Option<ProbeTarget>) -> WaveformColumn {
+    WaveformColumn { name: name.to_string(), unit: unit.to_string(), target, values: Vec::new() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::simulation::circuit::{CircuitNet, Source, Waveform};
+
+    // 1k / 1µF low-pass driven by a 1 V step: tau = 1 ms
+    fn rc_circuit() -> Circuit {
+        let step = Waveform::parse("PWL(0 0 1u 1 10m 1)").unwrap();
+        let element = |reference: &str, nodes: Vec<Option<usize>>, kind: ElementKind| Element {
+            component_id: reference.to_string(),
+            reference: reference.to_string(),
+            pin_ids: vec!["1".to_string(), "2".to_string()],
+            nodes,
+            kind,
+        };
+        Circuit {
+            node_count: 2,
+            nets: vec![CircuitNet { id: "out".to_string(), name: "OUT".to_string(), node: Some(1) }],
+            elements: vec![
+                element("V1", vec![Some(0), None], ElementKind::VoltageSource(Source { dc: 0.0, waveform: Some(step), ac_magnitude: 0.0, ac_phase: 0.0 })),
+                element("R1", vec![Some(0), Some(1)], ElementKind::Resistor { resistance: 1000.0 }),
+                element("C1", vec![Some(1), None], ElementKind::Capacitor { capacitance: 1e-6 }),
+            ],
+            unsupported: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_rc_step_response() {
+        let step = Waveform::parse("PWL(0 0 1u 1 10m 1)").unwrap();
+        assert_eq!(step.value_at(0.5e-6), 0.5);
+        assert_eq!(Waveform::parse("PULSE(0 5 1m 0 0 2m 4m)").unwrap().value_at(6.5e-3), 5.0);
+        assert!(Waveform::parse("SIN(0 1)").is_err());
+
+        let circuit = rc_circuit();
+        for method in [IntegrationMethod::Trapezoidal, IntegrationMethod::BackwardEuler] {
+            let settings = TransientSettings { stop_time: 5e-3, step: 1e-5, start_time: 0.0, method };
+            let waveforms = TransientSolver::simulate(&circuit, &settings).unwrap();
+            assert_eq!(waveforms.len(), 501);
+
+            let out = waveforms.column("V(OUT)").unwrap();
+            assert!((out.values[100] - (1.0 - (-1.0f64).exp())).abs() < 0.01, "{:?}: {}", method, out.values[100]);
+            assert!((out.values[500] - 1.0).abs() < 0.01);
+
+            // Capacitor current follows (1 - v) / R
+            let current = waveforms.column("I(C1.1)").unwrap();
+            assert!((current.values[100] - (1.0 - out.values[100]) / 1000.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_step_count_rounding() {
+        // 1e-3 / 1e-6 is just over 1000 in floating point
+        let settings = TransientSettings { stop_time: 1e-3, step: 1e-6, start_time: 0.0, method: IntegrationMethod::Trapezoidal };
+        let waveforms = TransientSolver::simulate(&rc_circuit(), &settings).unwrap();
+        assert_eq!(waveforms.len(), 1001);
+        let out = waveforms.column("V(OUT)").unwrap();
+        assert!((out.values[1000] - (1.0 - (-1.0f64).exp())).abs() < 0.01, "{}", out.values[1000]);
+    }
+}
@@ -0,0 +1,406 @@
+use crate::models::component::{ComponentTemplate, DrawCommand, DrawCommandType, PinTemplate, PinType};
+use crate::utils::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// Symbol coordinates use one schematic grid unit of 10 per 100 mil (2.54 mm)
+pub const SYMBOL_PIN_GRID: f64 = 10.0;
+const GRID_TOLERANCE: f64 = 1e-6;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolValidationReport {
+    pub issues: Vec<SymbolIssue>,
+    pub passed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolIssue {
+    pub issue_type: SymbolIssueType,
+    pub message: String,
+    pub severity: SymbolIssueSeverity,
+    pub pin_id: Option<String>,
+    pub command_index: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SymbolIssueType {
+    DuplicatePinNumber,
+    DuplicatePinId,
+    PinOffGrid,
+    PinInsideBody,
+    ZeroSizeShape,
+    InvalidParameterCount,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SymbolIssueSeverity {
+    Error,
+    Warning,
+}
+
+// Partial update for a pin; unset fields are left unchanged
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PinUpdate {
+    pub number: Option<String>,
+    pub name: Option<String>,
+    pub pin_type: Option<PinType>,
+    pub x: Option<f64>,
+    pub y: Option<f64>,
+}
+
+pub struct SymbolEditor;
+
+impl SymbolEditor {
+    pub fn add_draw_command(template: &mut ComponentTemplate, command: DrawCommand) -> Result<usize> {
+        if !command.command_type.accepts_parameter_count(command.parameters.len()) {
+            return Err(AppError::InvalidInput(format!(
+                "{:?} does not take {} parameters",
+                command.command_type,
+                command.parameters.len()
+            )));
+        }
+        template.symbol.draw_commands.push(command);
+        template.symbol.recompute_bounds(&template.pins);
+        Ok(template.symbol.draw_commands.len() - 1)
+    }
+
+    pub fn move_draw_command(template: &mut ComponentTemplate, index: usize, dx: f64, dy: f64) -> Result<()> {
+        Self::draw_command_mut(template, index)?.translate(dx, dy);
+        template.symbol.recompute_bounds(&template.pins);
+        Ok(())
+    }
+
+    pub fn replace_draw_command(template: &mut ComponentTemplate, index: usize, command: DrawCommand) -> Result<()> {
+        if !command.command_type.accepts_parameter_count(command.parameters.len()) {
+            return Err(AppError::InvalidInput(format!(
+                "{:?} does not take {} parameters",
+                command.command_type,
+                command.parameters.len()
+            )));
+        }
+        *Self::draw_command_mut(template, index)? = command;
+        template.symbol.recompute_bounds(&template.pins);
+        Ok(())
+    }
+
+    pub fn delete_draw_command(template: &mut ComponentTemplate, index: usize) -> Result<DrawCommand> {
+        Self::draw_command_mut(template, index)?;
+        let removed = template.symbol.draw_commands.remove(index);
+        template.symbol.recompute_bounds(&template.pins);
+        Ok(removed)
+    }
+
+    pub fn add_pin(template: &mut ComponentTemplate, pin: PinTemplate) -> Result<()> {
+        template.add_pin(pin)?;
+        template.symbol.recompute_bounds(&template.pins);
+        Ok(())
+    }
+
+    pub fn remove_pin(template: &mut ComponentTemplate, pin_id: &str) -> Result<PinTemplate> {
+        let index = template.pins.iter()
+            .position(|p| p.id == pin_id)
+            .ok_or_else(|| AppError::InvalidInput(format!("Pin not found: {}", pin_id)))?;
+        let removed = template.pins.remove(index);
+        template.symbol.recompute_bounds(&template.pins);
+        Ok(removed)
+    }
+
+    /// Reorders pins to match `pin_ids`, which must name every pin exactly once.
+    pub fn reorder_pins(template: &mut ComponentTemplate, pin_ids: &[String]) -> Result<()> {
+        if pin_ids.len() != template.pins.len() {
+            return Err(AppError::InvalidInput("Pin order must list every pin exactly once".to_string()));
+        }
+
+        // Every id is checked before anything moves, so a bad list leaves the pins as they were
+        let mut order: Vec<usize> = Vec::with_capacity(pin_ids.len());
+        for id in pin_ids {
+            let index = template.pins.iter().position(|p| &p.id == id)
+                .filter(|i| !order.contains(i))
+                .ok_or_else(|| AppError::InvalidInput(format!("Unknown or repeated pin id: {}", id)))?;
+            order.push(index);
+        }
+
+        let mut pins: Vec<Option<PinTemplate>> = template.pins.drain(..).map(Some).collect();
+        template.pins = order.into_iter().filter_map(|i| pins[i].take()).collect();
+        Ok(())
+    }
+
+    pub fn update_pin(template: &mut ComponentTemplate, pin_id: &str, update: PinUpdate) -> Result<()> {
+        if let Some(number) = &update.number {
+            if template.pins.iter().any(|p| p.id != pin_id && &p.number == number) {
+                return Err(AppError::InvalidInput(format!("Pin number {} already in use", number)));
+            }
+        }
+
+        let pin = template.pins.iter_mut()
+            .find(|p| p.id == pin_id)
+            .ok_or_else(|| AppError::InvalidInput(format!("Pin not found: {}", pin_id)))?;
+        if let Some(number) = update.number {
+            pin.number = number;
+        }
+        if let Some(name) = update.name {
+            pin.name = name;
+        }
+        if let Some(pin_type) = update.pin_type {
+            pin.pin_type = pin_type;
+        }
+        if let Some(x) = update.x {
+            pin.x = x;
+        }
+        if let Some(y) = update.y {
+            pin.y = y;
+        }
+
+        template.symbol.recompute_bounds(&template.pins);
+        Ok(())
+    }
+
+    pub fn recompute_bounds(template: &mut ComponentTemplate) {
+        template.symbol.recompute_bounds(&template.pins);
+    }
+
+    fn draw_command_mut(template: &mut ComponentTemplate, index: usize) -> Result<&mut DrawCommand> {
+        template.symbol.draw_commands.get_mut(index)
+            .ok_or_else(|| AppError::InvalidInput(format!("Draw command {} does not exist", index)))
+    }
+
+    pub fn validate(template: &ComponentTemplate) -> SymbolValidationReport {
+        let mut issues = Vec::new();
+
+        Self::check_pin_uniqueness(template, &mut issues);
+        Self::check_pin_grid(template, &mut issues);
+        Self::check_pins_outside_body(template, &mut issues);
+        Self::check_draw_commands(template, &mut issues);
+
+        SymbolValidationReport {
+            passed: !issues.iter().any(|i| i.severity == SymbolIssueSeverity::Error),
+            issues,
+        }
+    }
+
+    fn check_pin_uniqueness(template: &ComponentTemplate, issues: &mut Vec<SymbolIssue>) {
+        let mut numbers: HashMap<&str, &str> = HashMap::new();
+        let mut ids: HashMap<&str, usize> = HashMap::new();
+
+        for pin in &template.pins {
+            if let Some(first) = numbers.insert(pin.number.as_str(), pin.id.as_str()) {
+                issues.push(SymbolIssue {
+                    issue_type: SymbolIssueType::DuplicatePinNumber,
+                    message: format!("Pin number {} is used by both {} and {}", pin.number, first, pin.id),
+                    severity: SymbolIssueSeverity::Error,
+                    pin_id: Some(pin.id.clone()),
+                    command_index: None,
+                });
+            }
+            *ids.entry(pin.id.as_str()).or_default() += 1;
+        }
+
+        for (id, count) in ids.into_iter().filter(|(_, count)| *count > 1) {
+            issues.push(SymbolIssue {
+                issue_type: SymbolIssueType::DuplicatePinId,
+                message: format!("Pin id {} appears {} times", id, count),
+                severity: SymbolIssueSeverity::Error,
+                pin_id: Some(id.to_string()),
+                command_index: None,
+            });
+        }
+    }
+
+    fn check_pin_grid(template: &ComponentTemplate, issues: &mut Vec<SymbolIssue>) {
+        let on_grid = |v: f64| {
+            let remainder = (v / SYMBOL_PIN_GRID).round() * SYMBOL_PIN_GRID - v;
+            remainder.abs() < GRID_TOLERANCE
+        };
+
+        for pin in template.pins.iter().filter(|p| !on_grid(p.x) || !on_grid(p.y)) {
+            issues.push(SymbolIssue {
+                issue_type: SymbolIssueType::PinOffGrid,
+                message: format!("Pin {} at ({}, {}) is off the 100 mil grid", pin.number, pin.x, pin.y),
+                severity: SymbolIssueSeverity::Warning,
+                pin_id: Some(pin.id.clone()),
+                command_index: None,
+            });
+        }
+    }
+
+    // A pin endpoint strictly inside a body rectangle can't be reached by a wire
+    fn check_pins_outside_body(template: &ComponentTemplate, issues: &mut Vec<SymbolIssue>) {
+        let bodies: Vec<(f64, f64, f64, f64)> = template.symbol.draw_commands.iter()
+            .filter(|c| matches!(c.command_type, DrawCommandType::Rectangle))
+            .filter_map(|c| c.extent())
+            .collect();
+
+        for pin in &template.pins {
+            let inside = bodies.iter().any(|&(x0, y0, x1, y1)| {
+                pin.x > x0 + GRID_TOLERANCE && pin.x < x1 - GRID_TOLERANCE
+                    && pin.y > y0 + GRID_TOLERANCE && pin.y < y1 - GRID_TOLERANCE
+            });
+            if inside {
+                issues.push(SymbolIssue {
+                    issue_type: SymbolIssueType::PinInsideBody,
+                    message: format!("Pin {} at ({}, {}) lies inside the symbol body", pin.number, pin.x, pin.y),
+                    severity: SymbolIssueSeverity::Error,
+                    pin_id: Some(pin.id.clone()),
+                    command_index: None,
+                });
+            }
+        }
+    }
+
+    fn check_draw_commands(template: &ComponentTemplate, issues: &mut Vec<SymbolIssue>) {
+        for (index, command) in template.symbol.draw_commands.iter().enumerate() {
+            let p = &command.parameters;
+            if !command.command_type.accepts_parameter_count(p.len()) {
+                issues.push(SymbolIssue {
+                    issue_type: SymbolIssueType::InvalidParameterCount,
+                    message: format!("{:?} command {} has {} parameters", command.command_type, index, p.len()),
+                    severity: SymbolIssueSeverity::Error,
+                    pin_id: None,
+                    command_index: Some(index),
+                });
+                continue;
+            }
+
+            let zero_size = match command.command_type {
+                DrawCommandType::Rectangle => p[2] == 0.0 || p[3] == 0.0,
+                DrawCommandType::Circle | DrawCommandType::Arc => p[2] <= 0.0,
+                DrawCommandType::Line => p[0] == p[2] && p[1] == p[3],
+                DrawCommandType::Polygon => command.extent()
+                    .is_some_and(|(x0, y0, x1, y1)| x0 == x1 || y0 == y1),
+                DrawCommandType::Text => p.len() > 2 && p[2] <= 0.0,
+            };
+            if zero_size {
+                issues.push(SymbolIssue {
+                    issue_type: SymbolIssueType::ZeroSizeShape,
+                    message: format!("{:?} command {} has zero size", command.command_type, index),
+                    severity: SymbolIssueSeverity::Error,
+                    pin_id: None,
+                    command_index: Some(index),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::component::{ComponentSymbol, ElectricalType};
+
+    fn pin(id: &str, number: &str, x: f64, y: f64) -> PinTemplate {
+        PinTemplate {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            number: number.to_string(),
+            x,
+            y,
+            pin_type: PinType::Passive,
+            electrical: ElectricalType { voltage: None, current: None, impedance: None },
+        }
+    }
+
+    fn command(command_type: DrawCommandType, parameters: &[f64]) -> DrawCommand {
+        DrawCommand { command_type, parameters: parameters.to_vec(), style: None }
+    }
+
+    // A 20 x 20 body with one pin on each side
+    fn template() -> ComponentTemplate {
+        let symbol = ComponentSymbol { width: 0.0, height: 0.0, draw_commands: Vec::new(), graphics: None };
+        let mut template = ComponentTemplate::new("Part".to_string(), "passive".to_string(), symbol).unwrap();
+        SymbolEditor::add_draw_command(&mut template, command(DrawCommandType::Rectangle, &[-10.0, -10.0, 20.0, 20.0])).unwrap();
+        SymbolEditor::add_pin(&mut template, pin("a", "1", -20.0, 0.0)).unwrap();
+        SymbolEditor::add_pin(&mut template, pin("b", "2", 20.0, 0.0)).unwrap();
+        template
+    }
+
+    fn issue_types(template: &ComponentTemplate) -> Vec<SymbolIssueType> {
+        SymbolEditor::validate(template).issues.into_iter().map(|i| i.issue_type).collect()
+    }
+
+    #[test]
+    fn test_validate_symbol() {
+        let valid = template();
+        let report = SymbolEditor::validate(&valid);
+        assert!(report.passed && report.issues.is_empty(), "{:?}", report.issues);
+
+        let mut duplicates = valid.clone();
+        duplicates.pins.push(pin("a", "2", 20.0, 10.0));
+        let types = issue_types(&duplicates);
+        assert!(types.contains(&SymbolIssueType::DuplicatePinNumber));
+        assert!(types.contains(&SymbolIssueType::DuplicatePinId));
+        assert!(!SymbolEditor::validate(&duplicates).passed);
+
+        // Off-grid pins only warn
+        let mut off_grid = valid.clone();
+        SymbolEditor::update_pin(&mut off_grid, "b", PinUpdate { y: Some(5.0), ..Default::default() }).unwrap();
+        let report = SymbolEditor::validate(&off_grid);
+        assert_eq!(issue_types(&off_grid), vec![SymbolIssueType::PinOffGrid]);
+        assert!(report.passed);
+        assert_eq!(report.issues[0].pin_id.as_deref(), Some("b"));
+
+        // On the body edge is reachable; inside isn't
+        let mut inside = valid.clone();
+        SymbolEditor::update_pin(&mut inside, "a", PinUpdate { x: Some(-10.0), ..Default::default() }).unwrap();
+        assert!(issue_types(&inside).is_empty());
+        SymbolEditor::update_pin(&mut inside, "a", PinUpdate { x: Some(0.0), ..Default::default() }).unwrap();
+        assert_eq!(issue_types(&inside), vec![SymbolIssueType::PinInsideBody]);
+
+        let mut zero = valid.clone();
+        for (kind, parameters) in [
+            (DrawCommandType::Rectangle, vec![0.0, 0.0, 10.0, 0.0]),
+            (DrawCommandType::Circle, vec![0.0, 0.0, 0.0]),
+            (DrawCommandType::Line, vec![5.0, 5.0, 5.0, 5.0]),
+            (DrawCommandType::Polygon, vec![0.0, 0.0, 5.0, 0.0, 10.0, 0.0]),
+            (DrawCommandType::Text, vec![0.0, 0.0, 0.0]),
+        ] {
+            SymbolEditor::add_draw_command(&mut zero, command(kind, &parameters)).unwrap();
+        }
+        let report = SymbolEditor::validate(&zero);
+        let indices: Vec<Option<usize>> = report.issues.iter().map(|i| i.command_index).collect();
+        assert_eq!(indices, (1..=5).map(Some).collect::<Vec<_>>());
+        assert!(report.issues.iter().all(|i| i.issue_type == SymbolIssueType::ZeroSizeShape));
+
+        // Arity is enforced when editing and reported for templates loaded with bad data
+        let mut arity = valid.clone();
+        for (kind, parameters) in [
+            (DrawCommandType::Rectangle, vec![0.0, 0.0, 10.0]),
+            (DrawCommandType::Arc, vec![0.0, 0.0, 5.0, 0.0]),
+            (DrawCommandType::Polygon, vec![0.0, 0.0, 5.0, 0.0, 5.0]),
+            (DrawCommandType::Text, vec![0.0, 0.0, 1.0, 0.0, 0.0]),
+        ] {
+            assert!(SymbolEditor::add_draw_command(&mut arity, command(kind.clone(), &parameters)).is_err());
+            assert!(SymbolEditor::replace_draw_command(&mut arity, 0, command(kind.clone(), &parameters)).is_err());
+            arity.symbol.draw_commands.push(command(kind, &parameters));
+        }
+        assert_eq!(issue_types(&arity), vec![SymbolIssueType::InvalidParameterCount; 4]);
+        assert!(SymbolEditor::add_draw_command(&mut arity, command(DrawCommandType::Text, &[0.0, 0.0, 8.0, 1.0])).is_ok());
+    }
+
+    #[test]
+    fn test_edit_pins_and_commands() {
+        let mut template = template();
+        assert!(SymbolEditor::update_pin(&mut template, "a", PinUpdate { number: Some("2".to_string()), ..Default::default() }).is_err());
+        // Rejected orders leave the pins exactly as they were
+        let ids = |template: &ComponentTemplate| template.pins.iter().map(|p| p.id.clone()).collect::<Vec<_>>();
+        let before = ids(&template);
+        for order in [vec!["b", "b"], vec!["b", "x"], vec!["a"]] {
+            let order: Vec<String> = order.into_iter().map(String::from).collect();
+            assert!(SymbolEditor::reorder_pins(&mut template, &order).is_err());
+            assert_eq!(ids(&template), before);
+        }
+        SymbolEditor::reorder_pins(&mut template, &["b".to_string(), "a".to_string()]).unwrap();
+        assert_eq!(template.pins[0].id, "b");
+
+        // Bounds follow the pins and shapes
+        let width = template.symbol.width;
+        SymbolEditor::move_draw_command(&mut template, 0, 30.0, 0.0).unwrap();
+        assert!(template.symbol.width > width);
+        SymbolEditor::delete_draw_command(&mut template, 0).unwrap();
+        assert!(SymbolEditor::delete_draw_command(&mut template, 0).is_err());
+        assert_eq!(SymbolEditor::remove_pin(&mut template, "a").unwrap().number, "1");
+        assert!(SymbolEditor::remove_pin(&mut template, "a").is_err());
+    }
+}
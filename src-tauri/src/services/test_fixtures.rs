@@ -0,0 +1,26 @@
+// Board fixtures shared by the service tests
+
+use crate::models::{Board, FootprintInstance, FootprintPoint};
+use crate::services::footprint_service::FootprintService;
+
+/// Places a standard-library footprint at (`x`, `y`) with its reference as both instance
+/// and component id, and `nets` as (pad number, net id) pairs.
+pub(crate) fn place<'a>(
+    board: &'a mut Board,
+    footprint: &str,
+    reference: &str,
+    x: f64,
+    y: f64,
+    nets: &[(&str, &str)],
+) -> &'a mut FootprintInstance {
+    let footprint = FootprintService::new().get_footprint(footprint).unwrap();
+    let mut instance = FootprintInstance::new(
+        reference.to_string(), reference.to_string(), String::new(), footprint, FootprintPoint { x, y },
+    );
+    instance.id = reference.to_string();
+    for (pad, net) in nets {
+        instance.pad_nets.insert(pad.to_string(), net.to_string());
+    }
+    board.footprints.push(instance);
+    board.footprints.last_mut().unwrap()
+}
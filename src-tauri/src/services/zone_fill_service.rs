@@ -0,0 +1,380 @@
+use crate::models::{
+    Board, DesignRules, DrillShape, FilledPolygon, FootprintInstance, FootprintPoint, LayerType, Pad, ThermalRelief, Zone,
+};
+use crate::services::drc_service::{copper_items, CopperItem};
+use crate::utils::error::{AppError, Result};
+use crate::utils::geometry::{merge_spans, polygon_scanline, subtract_spans, Shape, Vec2};
+use serde::{Deserialize, Serialize};
+
+// Spacing of the scanlines a fill is traced on
+const FILL_STEP: f64 = 0.05;
+// Allowance for straight fill edges drawn between scanlines past rounded obstacles
+const FILL_MARGIN: f64 = 0.01;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoneFillSummary {
+    pub zone_id: String,
+    pub net_name: Option<String>,
+    pub layer: LayerType,
+    pub polygons: usize,
+    pub thermal_spokes: usize,
+    pub islands_removed: usize,
+}
+
+// Fill copper of one connected piece while it's being traced
+struct Island {
+    polygons: Vec<Vec<Vec2>>,
+    spokes: Vec<(Vec2, Vec2, f64)>,
+    connected: bool,
+}
+
+pub struct ZoneFillService;
+
+impl ZoneFillService {
+    /// Refills every zone, higher priorities first so lower ones keep clear of their copper.
+    pub fn fill_zones(board: &mut Board, rules: &DesignRules) -> Result<Vec<ZoneFillSummary>> {
+        Self::clear_fills(board);
+        let mut order: Vec<usize> = (0..board.zones.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(board.zones[i].priority));
+
+        let mut summaries = Vec::new();
+        for index in order {
+            let (fill, summary) = Self::fill_zone(board, rules, &board.zones[index])?;
+            board.zones[index].fill = fill;
+            summaries.push(summary);
+        }
+        Ok(summaries)
+    }
+
+    pub fn clear_fills(board: &mut Board) {
+        for zone in &mut board.zones {
+            zone.fill.clear();
+        }
+    }
+
+    /// Copper for one zone: its outline less clearances to foreign copper, holes, keepouts
+    /// and the board edge, traced so every part is at least the zone's minimum width.
+    /// Same-net pads join through thermal spokes when they or the zone ask for relief and
+    /// solidly otherwise; pieces that reach none of the net's copper are dropped as islands.
+    fn fill_zone(board: &Board, rules: &DesignRules, zone: &Zone) -> Result<(Vec<FilledPolygon>, ZoneFillSummary)> {
+        if zone.outline.len() < 3 {
+            return Err(AppError::InvalidInput("Zone outline needs at least three points".to_string()));
+        }
+        let board_outline = board.outline_contours()
+            .ok_or_else(|| AppError::InvalidOperation("The board needs a closed outline before zones can be filled".to_string()))?;
+        let net_name = zone.net_id.as_deref().and_then(|id| board.get_net(id)).map(|n| n.name.clone());
+        let same_net = |net: Option<&str>| zone.net_id.is_some() && net == zone.net_id.as_deref();
+        let half = zone.min_width.max(0.0) / 2.0;
+        let grown = |shape: &Shape, reach: f64| Shape { points: shape.points.clone(), radius: shape.radius + reach.max(FILL_STEP) };
+
+        let items: Vec<CopperItem> = copper_items(board).into_iter()
+            .filter(|i| i.layers.contains(&zone.layer) && i.id != zone.id)
+            .collect();
+        let clearance_to = |item: &CopperItem| {
+            let other = item.net_id.as_deref().and_then(|id| board.get_net(id)).map(|n| n.name.as_str());
+            zone.clearance.max(rules.clearance_between(net_name.as_deref(), other))
+        };
+
+        let mut obstacles: Vec<Shape> = items.iter()
+            .filter(|i| !same_net(i.net_id.as_deref()))
+            .map(|i| grown(&i.shape, clearance_to(i) + half + FILL_MARGIN))
+            .collect();
+
+        let mut reliefs: Vec<(&FootprintInstance, &Pad, ThermalRelief)> = Vec::new();
+        for instance in &board.footprints {
+            for pad in &instance.footprint.pads {
+                let on_layer = instance.pad_copper_layers(pad, &board.stackup).contains(&zone.layer);
+                if on_layer && same_net(instance.pad_net(&pad.pad_number)) {
+                    if let Some(relief) = pad.thermal_relief.clone().or_else(|| zone.thermal_relief.clone()) {
+                        obstacles.push(grown(&instance.pad_shape(pad), relief.gap + half + FILL_MARGIN));
+                        reliefs.push((instance, pad, relief));
+                    }
+                } else if let (false, Some(hole)) = (on_layer, hole_shape(instance, pad)) {
+                    // Unplated holes on this layer
+                    obstacles.push(grown(&hole, zone.clearance + half + FILL_MARGIN));
+                }
+            }
+        }
+
+        let keepouts: Vec<Shape> = board.keepouts.iter().cloned()
+            .chain(board.footprints.iter().filter_map(|f| f.keepout()))
+            .filter(|k| k.restrictions.no_copper && k.layers.contains(&zone.layer) && k.area.len() >= 3)
+            .map(|k| Shape::polygon(k.area.iter().map(|p| (p.x, p.y)).collect()))
+            .collect();
+        obstacles.extend(keepouts.iter().map(|k| grown(k, half + FILL_MARGIN)));
+        obstacles.extend(board.outline_segments().into_iter()
+            .map(|(a, b)| Shape::segment(a, b, rules.edge_clearance + half + FILL_MARGIN)));
+        let zone_outline: Vec<Vec2> = zone.outline.iter().map(|p| (p.x, p.y)).collect();
+        if half > 0.0 {
+            // Keep the pen inside the zone outline
+            let n = zone_outline.len();
+            obstacles.extend((0..n).map(|i| Shape::segment(zone_outline[i], zone_outline[(i + 1) % n], half)));
+        }
+
+        let mut islands = trace(&zone_outline, &board_outline, &obstacles);
+
+        // Same-net copper the fill runs into connects its island
+        let net_items: Vec<&CopperItem> = items.iter().filter(|i| same_net(i.net_id.as_deref())).collect();
+        for island in &mut islands {
+            island.connected = zone.net_id.is_none() || island.polygons.iter().any(|polygon| {
+                let shape = Shape { points: polygon.clone(), radius: half };
+                net_items.iter().any(|item| shape.distance(&item.shape) <= 1e-6)
+            });
+        }
+
+        // Thermal spokes reach from the pad centre across the relief gap into the fill
+        for (instance, pad, relief) in &reliefs {
+            if relief.spoke_count == 0 {
+                continue;
+            }
+            let position = instance.pad_position(pad);
+            let center = (position.x, position.y);
+            let pad_shape = instance.pad_shape(pad);
+            for spoke in 0..relief.spoke_count {
+                let angle = relief.angle + instance.pad_rotation(pad) + spoke as f64 * 360.0 / relief.spoke_count as f64;
+                let direction = (angle.to_radians().cos(), angle.to_radians().sin());
+                let reach = ray_extent(&pad_shape, center, direction) + relief.gap + half.max(relief.spoke_width / 2.0) + FILL_STEP;
+                let end = (center.0 + direction.0 * reach, center.1 + direction.1 * reach);
+                let shape = Shape::segment(center, end, relief.spoke_width / 2.0);
+
+                let clear = items.iter().filter(|i| !same_net(i.net_id.as_deref()))
+                    .all(|i| shape.distance(&i.shape) >= clearance_to(i) - 1e-9)
+                    && keepouts.iter().all(|k| shape.distance(k) > 0.0);
+                let landing = islands.iter().position(|island| island.polygons.iter()
+                    .any(|p| Shape { points: p.clone(), radius: half }.contains(end)));
+                if let (true, Some(index)) = (clear, landing) {
+                    islands[index].spokes.push((center, end, relief.spoke_width));
+                    islands[index].connected = true;
+                }
+            }
+        }
+
+        let point = |p: Vec2| FootprintPoint { x: p.0, y: p.1 };
+        let mut fill = Vec::new();
+        let mut summary = ZoneFillSummary {
+            zone_id: zone.id.clone(),
+            net_name,
+            layer: zone.layer.clone(),
+            polygons: 0,
+            thermal_spokes: 0,
+            islands_removed: 0,
+        };
+        for island in islands {
+            if !island.connected {
+                summary.islands_removed += 1;
+                continue;
+            }
+            summary.polygons += island.polygons.len();
+            summary.thermal_spokes += island.spokes.len();
+            fill.extend(island.polygons.into_iter()
+                .map(|p| FilledPolygon { points: p.into_iter().map(point).collect(), width: zone.min_width.max(0.0) }));
+            fill.extend(island.spokes.into_iter()
+                .map(|(a, b, width)| FilledPolygon { points: vec![point(a), point(b)], width }));
+        }
+        Ok((fill, summary))
+    }
+}
+
+// A pad's hole where it has no copper, with room for the whole slot or oval
+fn hole_shape(instance: &FootprintInstance, pad: &Pad) -> Option<Shape> {
+    let drill = pad.drill.as_ref()?;
+    let (dx, dy) = drill.offset.as_ref().map(|o| (o.x, o.y)).unwrap_or((0.0, 0.0));
+    let at = |x: f64, y: f64| {
+        let p = instance.pad_point(pad, x + dx, y + dy);
+        (p.x, p.y)
+    };
+    Some(match &drill.shape {
+        DrillShape::Circle => Shape::circle(at(0.0, 0.0), drill.diameter / 2.0),
+        DrillShape::Oval { width, height } => Shape::circle(at(0.0, 0.0), width.max(*height) / 2.0),
+        DrillShape::Slot { start, end, width } => Shape::segment(at(start.x, start.y), at(end.x, end.y), width / 2.0),
+    })
+}
+
+// Distance from `origin` along `direction` to where it leaves the shape
+fn ray_extent(shape: &Shape, origin: Vec2, direction: Vec2) -> f64 {
+    let (x0, y0, x1, y1) = shape.bounds();
+    let (mut inside, mut outside) = (0.0, (x1 - x0).hypot(y1 - y0));
+    for _ in 0..40 {
+        let middle = (inside + outside) / 2.0;
+        if shape.contains((origin.0 + direction.0 * middle, origin.1 + direction.1 * middle)) {
+            inside = middle;
+        } else {
+            outside = middle;
+        }
+    }
+    inside
+}
+
+/// Traces the area inside the zone outline and the board, less its cutouts, and clear of the obstacles on horizontal scanlines,
+/// then joins the spans into polygons: spans that continue one-to-one from line to line form
+/// one polygon, and where spans split or merge the overlap is bridged with a rectangle.
+fn trace(zone: &[Vec2], board: &[Vec<Vec2>], obstacles: &[Shape]) -> Vec<Island> {
+    let (y0, y1) = zone.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| (lo.min(p.1), hi.max(p.1)));
+    let spans_y: Vec<(f64, f64)> = obstacles.iter().map(|o| {
+        let (_, lo, _, hi) = o.bounds();
+        (lo, hi)
+    }).collect();
+
+    let lines = ((y1 - y0) / FILL_STEP).floor() as usize + 1;
+    let mut rows: Vec<(f64, Vec<(f64, f64)>)> = Vec::with_capacity(lines);
+    for line in 0..lines {
+        let y = y0 + line as f64 * FILL_STEP;
+        let inside_zone = merge_spans(polygon_scanline(zone, y));
+        let inside_board = subtract_spans(
+            &merge_spans(polygon_scanline(&board[0], y)),
+            &merge_spans(board[1..].iter().flat_map(|cutout| polygon_scanline(cutout, y)).collect()),
+        );
+        let allowed = subtract_spans(&inside_zone, &subtract_spans(&inside_zone, &inside_board));
+        let cut = merge_spans(obstacles.iter().zip(&spans_y)
+            .filter(|(_, (lo, hi))| *lo <= y && y <= *hi)
+            .flat_map(|(o, _)| o.scanline(y))
+            .collect());
+        let spans = subtract_spans(&allowed, &cut).into_iter().filter(|(lo, hi)| hi - lo > 1e-6).collect();
+        rows.push((y, spans));
+    }
+
+    // Chains of spans, with the chain of every span and bridges between chains
+    let mut chains: Vec<Vec<(f64, f64, f64)>> = Vec::new();
+    let mut chain_of: Vec<Vec<usize>> = Vec::with_capacity(rows.len());
+    let mut bridges: Vec<(usize, usize, Vec<Vec2>)> = Vec::new();
+    for (line, (y, spans)) in rows.iter().enumerate() {
+        let mut ids = Vec::with_capacity(spans.len());
+        let previous = line.checked_sub(1).map(|l| (&rows[l], &chain_of[l]));
+        for &(lo, hi) in spans {
+            let overlaps = |s: &(f64, f64), a: f64, b: f64| s.0 < b && a < s.1;
+            let above: Vec<usize> = previous
+                .map(|((_, spans), _)| (0..spans.len()).filter(|&i| overlaps(&spans[i], lo, hi)).collect())
+                .unwrap_or_default();
+            let continues = match (above.as_slice(), previous) {
+                ([i], Some(((_, spans), _))) => rows[line].1.iter().filter(|s| overlaps(s, spans[*i].0, spans[*i].1)).count() == 1,
+                _ => false,
+            };
+            let id = if let (true, Some((_, previous_ids))) = (continues, previous) {
+                previous_ids[above[0]]
+            } else {
+                chains.push(Vec::new());
+                chains.len() - 1
+            };
+            chains[id].push((*y, lo, hi));
+            if !continues {
+                if let Some(((upper_y, spans), previous_ids)) = previous {
+                    for &i in &above {
+                        let (left, right) = (spans[i].0.max(lo), spans[i].1.min(hi));
+                        let rectangle = vec![(left, *upper_y), (right, *upper_y), (right, *y), (left, *y)];
+                        bridges.push((previous_ids[i], id, rectangle));
+                    }
+                }
+            }
+            ids.push(id);
+        }
+        chain_of.push(ids);
+    }
+
+    let mut parent: Vec<usize> = (0..chains.len()).collect();
+    fn find(parent: &mut [usize], mut x: usize) -> usize {
+        while parent[x] != x {
+            parent[x] = parent[parent[x]];
+            x = parent[x];
+        }
+        x
+    }
+    for (a, b, _) in &bridges {
+        let (ra, rb) = (find(&mut parent, *a), find(&mut parent, *b));
+        parent[rb] = ra;
+    }
+
+    let mut islands: Vec<(usize, Island)> = Vec::new();
+    let mut add = |root: usize, polygon: Vec<Vec2>| {
+        match islands.iter_mut().find(|(r, _)| *r == root) {
+            Some((_, island)) => island.polygons.push(polygon),
+            None => islands.push((root, Island { polygons: vec![polygon], spokes: Vec::new(), connected: false })),
+        }
+    };
+    for (id, chain) in chains.iter().enumerate() {
+        if chain.len() < 2 {
+            continue;
+        }
+        let left = chain.iter().map(|&(y, lo, _)| (lo, y));
+        let right = chain.iter().rev().map(|&(y, _, hi)| (hi, y));
+        add(find(&mut parent, id), drop_collinear(left.chain(right).collect()));
+    }
+    for (a, _, rectangle) in bridges {
+        add(find(&mut parent, a), rectangle);
+    }
+    islands.into_iter().map(|(_, island)| island).collect()
+}
+
+fn drop_collinear(points: Vec<Vec2>) -> Vec<Vec2> {
+    let mut kept: Vec<Vec2> = Vec::with_capacity(points.len());
+    for p in points {
+        while kept.len() >= 2 {
+            let (a, b) = (kept[kept.len() - 2], kept[kept.len() - 1]);
+            if ((b.0 - a.0) * (p.1 - b.1) - (b.1 - a.1) * (p.0 - b.0)).abs() > 1e-12 {
+                break;
+            }
+            kept.pop();
+        }
+        kept.push(p);
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BoardNet, Track};
+    use crate::services::drc_service::{connectivity, DRCChecker};
+    use crate::services::fabrication_service::FabricationService;
+    use crate::services::test_fixtures::place;
+
+    #[test]
+    fn test_ground_plane_fill() {
+        let mut board = Board::new("Plane".to_string()).unwrap();
+        board.set_rectangular_outline(30.0, 20.0).unwrap();
+        board.nets = ["gnd", "sig"].iter().map(|n| BoardNet { id: n.to_string(), name: n.to_uppercase() }).collect();
+
+        place(&mut board, "R_0805", "R1", 8.0, 10.0, &[("1", "gnd"), ("2", "sig")]);
+        place(&mut board, "R_0805", "R2", 22.0, 10.0, &[("1", "sig"), ("2", "gnd")]);
+        board.add_track(Track::new(Some("sig".to_string()), LayerType::TopCopper,
+            FootprintPoint { x: 8.95, y: 10.0 }, FootprintPoint { x: 21.05, y: 10.0 }, 0.25)).unwrap();
+        // Signal track fencing off a corner leaves an island the plane can't reach
+        for (a, b) in [((0.5, 15.0), (5.0, 15.0)), ((5.0, 15.0), (5.0, 19.5))] {
+            board.add_track(Track::new(Some("sig".to_string()), LayerType::TopCopper,
+                FootprintPoint { x: a.0, y: a.1 }, FootprintPoint { x: b.0, y: b.1 }, 0.25)).unwrap();
+        }
+
+        let mut zone = Zone::new(Some("gnd".to_string()), LayerType::TopCopper, vec![
+            FootprintPoint { x: 0.0, y: 0.0 }, FootprintPoint { x: 30.0, y: 0.0 },
+            FootprintPoint { x: 30.0, y: 20.0 }, FootprintPoint { x: 0.0, y: 20.0 },
+        ]);
+        zone.thermal_relief = Some(ThermalRelief { gap: 0.5, spoke_width: 0.3, spoke_count: 4, angle: 0.0 });
+        board.add_zone(zone).unwrap();
+
+        let rules = DesignRules::default();
+        let summaries = ZoneFillService::fill_zones(&mut board, &rules).unwrap();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].islands_removed, 1);
+        assert!(summaries[0].thermal_spokes >= 4, "both ground pads get spokes");
+        assert!(board.zones[0].is_filled());
+
+        // The plane joins both ground pads and keeps clear of everything else
+        let drc = DRCChecker::new(board.clone(), rules).run_check().unwrap();
+        assert!(drc.errors.is_empty(), "{:?}", drc.errors.iter().map(|e| &e.message).collect::<Vec<_>>());
+        let items = copper_items(&board);
+        let roots = connectivity(&items);
+        let ground: Vec<usize> = (0..items.len())
+            .filter(|&i| items[i].net_id.as_deref() == Some("gnd") && items[i].pad_number.is_some())
+            .map(|i| roots[i])
+            .collect();
+        assert_eq!(ground.len(), 2);
+        assert_eq!(ground[0], ground[1]);
+
+        let gerbers = FabricationService::gerber_layers(&board);
+        let top = gerbers.iter().find(|f| f.file_name.ends_with("-F_Cu.gbr")).unwrap();
+        assert!(top.content.contains("G36*") && top.content.contains("%TO.N,GND*%"));
+
+        ZoneFillService::clear_fills(&mut board);
+        assert!(!board.zones[0].is_filled());
+    }
+}
@@ -0,0 +1,334 @@
+// Planar geometry on (x, y) tuples for the board tools. Units are whatever the caller uses.
+
+pub type Vec2 = (f64, f64);
+
+pub fn distance(a: Vec2, b: Vec2) -> f64 {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+}
+
+pub fn distance_point_segment(point: Vec2, start: Vec2, end: Vec2) -> f64 {
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let length_squared = dx * dx + dy * dy;
+    if length_squared == 0.0 {
+        return distance(point, start);
+    }
+    let t = (((point.0 - start.0) * dx + (point.1 - start.1) * dy) / length_squared).clamp(0.0, 1.0);
+    distance(point, (start.0 + t * dx, start.1 + t * dy))
+}
+
+fn cross(o: Vec2, a: Vec2, b: Vec2) -> f64 {
+    (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+}
+
+/// True when the segments cross or touch.
+pub fn segments_intersect(a1: Vec2, a2: Vec2, b1: Vec2, b2: Vec2) -> bool {
+    let d1 = cross(b1, b2, a1);
+    let d2 = cross(b1, b2, a2);
+    let d3 = cross(a1, a2, b1);
+    let d4 = cross(a1, a2, b2);
+    if ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0)) && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0)) {
+        return true;
+    }
+    // Collinear or endpoint contact
+    (d1 == 0.0 && distance_point_segment(a1, b1, b2) == 0.0)
+        || (d2 == 0.0 && distance_point_segment(a2, b1, b2) == 0.0)
+        || (d3 == 0.0 && distance_point_segment(b1, a1, a2) == 0.0)
+        || (d4 == 0.0 && distance_point_segment(b2, a1, a2) == 0.0)
+}
+
+pub fn distance_segment_segment(a1: Vec2, a2: Vec2, b1: Vec2, b2: Vec2) -> f64 {
+    if segments_intersect(a1, a2, b1, b2) {
+        return 0.0;
+    }
+    distance_point_segment(a1, b1, b2)
+        .min(distance_point_segment(a2, b1, b2))
+        .min(distance_point_segment(b1, a1, a2))
+        .min(distance_point_segment(b2, a1, a2))
+}
+
+/// Even-odd test; points exactly on an edge may land either way.
+pub fn point_in_polygon(point: Vec2, polygon: &[Vec2]) -> bool {
+    let mut inside = false;
+    let mut j = polygon.len().wrapping_sub(1);
+    for (i, &a) in polygon.iter().enumerate() {
+        let b = polygon[j];
+        if (a.1 > point.1) != (b.1 > point.1) && point.0 < (b.0 - a.0) * (point.1 - a.1) / (b.1 - a.1) + a.0 {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// Even-odd test over several contours, such as a board edge and its cutouts.
+pub fn point_in_contours(point: Vec2, contours: &[Vec<Vec2>]) -> bool {
+    contours.iter().filter(|contour| point_in_polygon(point, contour)).count() % 2 == 1
+}
+
+/// Signed area, positive for counterclockwise vertices in a y-up frame.
+pub fn polygon_area(polygon: &[Vec2]) -> f64 {
+    let n = polygon.len();
+    (0..n).map(|i| {
+        let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+        a.0 * b.1 - b.0 * a.1
+    }).sum::<f64>() / 2.0
+}
+
+/// The polygon grown outward by `margin` (shrunk when negative) with mitred corners, for
+/// either winding. Very sharp corners are clipped at four times the margin.
+pub fn offset_polygon(polygon: &[Vec2], margin: f64) -> Vec<Vec2> {
+    let n = polygon.len();
+    if n < 3 || margin == 0.0 {
+        return polygon.to_vec();
+    }
+    let outward = polygon_area(polygon).signum();
+    let normal = |a: Vec2, b: Vec2| {
+        let length = distance(a, b).max(1e-12);
+        (outward * (b.1 - a.1) / length, -outward * (b.0 - a.0) / length)
+    };
+    (0..n).map(|i| {
+        let (previous, point, next) = (polygon[(i + n - 1) % n], polygon[i], polygon[(i + 1) % n]);
+        let (n1, n2) = (normal(previous, point), normal(point, next));
+        let scale = margin / (1.0 + n1.0 * n2.0 + n1.1 * n2.1).max(0.125);
+        (point.0 + (n1.0 + n2.0) * scale, point.1 + (n1.1 + n2.1) * scale)
+    }).collect()
+}
+
+/// True when the interiors of two simple polygons meet, convex or not; polygons that only
+/// touch don't overlap. Edges that cross decide it at once. Otherwise no boundary changes
+/// sides between consecutive vertex heights, so one scanline through each band shows
+/// whether one polygon reaches into the other.
+pub fn polygons_overlap(a: &[Vec2], b: &[Vec2]) -> bool {
+    const EPSILON: f64 = 1e-9;
+    let edges = |polygon: &[Vec2]| -> Vec<(Vec2, Vec2)> {
+        (0..polygon.len()).map(|i| (polygon[i], polygon[(i + 1) % polygon.len()])).collect()
+    };
+    // Signed distance of `p` from the line through the edge
+    let side = |(s, e): (Vec2, Vec2), p: Vec2| cross(s, e, p) / distance(s, e).max(EPSILON);
+    let crosses = |x: (Vec2, Vec2), y: (Vec2, Vec2)| {
+        let (d1, d2, d3, d4) = (side(y, x.0), side(y, x.1), side(x, y.0), side(x, y.1));
+        ((d1 > EPSILON && d2 < -EPSILON) || (d1 < -EPSILON && d2 > EPSILON))
+            && ((d3 > EPSILON && d4 < -EPSILON) || (d3 < -EPSILON && d4 > EPSILON))
+    };
+    let (a_edges, b_edges) = (edges(a), edges(b));
+    if a_edges.iter().any(|&x| b_edges.iter().any(|&y| crosses(x, y))) {
+        return true;
+    }
+
+    let mut heights: Vec<f64> = a.iter().chain(b).map(|p| p.1).collect();
+    heights.sort_by(f64::total_cmp);
+    heights.windows(2).filter(|h| h[1] - h[0] > EPSILON).any(|h| {
+        let y = (h[0] + h[1]) / 2.0;
+        let (a_spans, b_spans) = (merge_spans(polygon_scanline(a, y)), merge_spans(polygon_scanline(b, y)));
+        a_spans.iter().any(|sa| b_spans.iter().any(|sb| sa.1.min(sb.1) - sa.0.max(sb.0) > EPSILON))
+    })
+}
+
+/// A point, segment or closed polygon grown by `radius`: circles, tracks, obrounds and
+/// rounded polygons all fit this form, which keeps clearance checks to one distance function.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shape {
+    pub points: Vec<Vec2>,
+    pub radius: f64,
+}
+
+impl Shape {
+    pub fn circle(center: Vec2, radius: f64) -> Self {
+        Self { points: vec![center], radius }
+    }
+
+    pub fn segment(start: Vec2, end: Vec2, half_width: f64) -> Self {
+        Self { points: vec![start, end], radius: half_width }
+    }
+
+    pub fn polygon(points: Vec<Vec2>) -> Self {
+        Self { points, radius: 0.0 }
+    }
+
+    fn edges(&self) -> Vec<(Vec2, Vec2)> {
+        match self.points.len() {
+            0 => Vec::new(),
+            1 => vec![(self.points[0], self.points[0])],
+            2 => vec![(self.points[0], self.points[1])],
+            n => (0..n).map(|i| (self.points[i], self.points[(i + 1) % n])).collect(),
+        }
+    }
+
+    fn contains_core(&self, point: Vec2) -> bool {
+        self.points.len() >= 3 && point_in_polygon(point, &self.points)
+    }
+
+    pub fn contains(&self, point: Vec2) -> bool {
+        self.contains_core(point) || self.edges().iter().any(|&(a, b)| distance_point_segment(point, a, b) <= self.radius)
+    }
+
+    /// Gap between the two outlines; zero or negative when they touch or overlap.
+    pub fn distance(&self, other: &Shape) -> f64 {
+        let inside = other.points.iter().any(|&p| self.contains_core(p))
+            || self.points.iter().any(|&p| other.contains_core(p));
+        let core = if inside {
+            0.0
+        } else {
+            let theirs = other.edges();
+            self.edges().iter()
+                .flat_map(|&(a1, a2)| theirs.iter().map(move |&(b1, b2)| distance_segment_segment(a1, a2, b1, b2)))
+                .fold(f64::INFINITY, f64::min)
+        };
+        core - self.radius - other.radius
+    }
+
+    /// (min x, min y, max x, max y) including the radius.
+    pub fn bounds(&self) -> (f64, f64, f64, f64) {
+        let (x0, y0, x1, y1) = self.points.iter().fold(
+            (f64::INFINITY, f64::INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+            |(x0, y0, x1, y1), p| (x0.min(p.0), y0.min(p.1), x1.max(p.0), y1.max(p.1)),
+        );
+        (x0 - self.radius, y0 - self.radius, x1 + self.radius, y1 + self.radius)
+    }
+
+    /// Spans of x where the line at `y` crosses the shape, merged and sorted.
+    pub fn scanline(&self, y: f64) -> Vec<(f64, f64)> {
+        let mut spans = if self.points.len() >= 3 { polygon_scanline(&self.points, y) } else { Vec::new() };
+        if self.radius > 0.0 {
+            spans.extend(self.edges().iter().filter_map(|&(a, b)| capsule_scanline(a, b, self.radius, y)));
+        }
+        merge_spans(spans)
+    }
+
+    pub fn center(&self) -> Vec2 {
+        let (x0, y0, x1, y1) = self.bounds();
+        ((x0 + x1) / 2.0, (y0 + y1) / 2.0)
+    }
+}
+
+/// Spans of x where the horizontal line at `y` is inside the polygon (even-odd), sorted.
+pub fn polygon_scanline(polygon: &[Vec2], y: f64) -> Vec<(f64, f64)> {
+    let n = polygon.len();
+    let mut crossings: Vec<f64> = (0..n).filter_map(|i| {
+        let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+        ((a.1 > y) != (b.1 > y)).then(|| a.0 + (y - a.1) * (b.0 - a.0) / (b.1 - a.1))
+    }).collect();
+    crossings.sort_by(f64::total_cmp);
+    crossings.chunks_exact(2).map(|c| (c[0], c[1])).collect()
+}
+
+// Span of the segment grown by `radius` on the line at `y`
+fn capsule_scanline(a: Vec2, b: Vec2, radius: f64, y: f64) -> Option<(f64, f64)> {
+    let mut span: Option<(f64, f64)> = None;
+    let mut include = |lo: f64, hi: f64| {
+        span = Some(span.map_or((lo, hi), |(l, h)| (l.min(lo), h.max(hi))));
+    };
+    for end in [a, b] {
+        let dy = y - end.1;
+        if dy.abs() <= radius {
+            let half = (radius * radius - dy * dy).sqrt();
+            include(end.0 - half, end.0 + half);
+        }
+    }
+    let length = distance(a, b);
+    if length > 0.0 {
+        let normal = (-(b.1 - a.1) / length * radius, (b.0 - a.0) / length * radius);
+        let band = [
+            (a.0 + normal.0, a.1 + normal.1), (b.0 + normal.0, b.1 + normal.1),
+            (b.0 - normal.0, b.1 - normal.1), (a.0 - normal.0, a.1 - normal.1),
+        ];
+        for (lo, hi) in polygon_scanline(&band, y) {
+            include(lo, hi);
+        }
+    }
+    span
+}
+
+/// Sorts the spans and joins overlapping ones.
+pub fn merge_spans(mut spans: Vec<(f64, f64)>) -> Vec<(f64, f64)> {
+    spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut merged: Vec<(f64, f64)> = Vec::with_capacity(spans.len());
+    for (lo, hi) in spans {
+        match merged.last_mut() {
+            Some(last) if lo <= last.1 => last.1 = last.1.max(hi),
+            _ => merged.push((lo, hi)),
+        }
+    }
+    merged
+}
+
+/// Parts of the merged spans `from` not covered by the merged spans `cut`.
+pub fn subtract_spans(from: &[(f64, f64)], cut: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let mut result = Vec::new();
+    for &(lo, hi) in from {
+        let mut start = lo;
+        for &(c0, c1) in cut.iter().filter(|c| c.1 > lo && c.0 < hi) {
+            if c0 > start {
+                result.push((start, c0));
+            }
+            start = start.max(c1);
+        }
+        if start < hi {
+            result.push((start, hi));
+        }
+    }
+    result
+}
+
+/// True when the boxes are closer than `margin`.
+pub fn bounds_near(a: (f64, f64, f64, f64), b: (f64, f64, f64, f64), margin: f64) -> bool {
+    a.0 <= b.2 + margin && b.0 <= a.2 + margin && a.1 <= b.3 + margin && b.1 <= a.3 + margin
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shape_distances() {
+        let track = Shape::segment((0.0, 0.0), (10.0, 0.0), 0.1);
+        let pad = Shape::polygon(vec![(4.0, 0.5), (6.0, 0.5), (6.0, 1.5), (4.0, 1.5)]);
+        assert!((track.distance(&pad) - 0.4).abs() < 1e-9);
+        assert!((Shape::circle((0.0, 3.0), 1.0).distance(&track) - 1.9).abs() < 1e-9);
+
+        // A track running through a pad without touching its corners still overlaps
+        let crossing = Shape::segment((5.0, -5.0), (5.0, 5.0), 0.1);
+        assert!(crossing.distance(&pad) < 0.0);
+        let inside = Shape::circle((5.0, 1.0), 0.1);
+        assert!(inside.distance(&pad) < 0.0);
+
+        let square = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        let touching = [(1.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 1.0)];
+        let diamond = [(1.2, 0.5), (0.5, 1.2), (-0.2, 0.5), (0.5, -0.2)];
+        assert!(!polygons_overlap(&square, &touching));
+        assert!(polygons_overlap(&square, &diamond));
+        assert!(polygons_overlap(&square, &square));
+        // Boxes sharing part of two edges overlap without any edges crossing
+        assert!(polygons_overlap(&[(0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (0.0, 1.0)], &[(1.0, 0.0), (3.0, 0.0), (3.0, 1.0), (1.0, 1.0)]));
+        // A square in the notch of an L clears it, though their separating axis doesn't exist
+        let l_shape = [(0.0, 0.0), (3.0, 0.0), (3.0, 1.0), (1.0, 1.0), (1.0, 3.0), (0.0, 3.0)];
+        let in_notch = [(1.5, 1.5), (2.5, 1.5), (2.5, 2.5), (1.5, 2.5)];
+        assert!(!polygons_overlap(&l_shape, &in_notch));
+        assert!(polygons_overlap(&l_shape, &[(0.5, 0.5), (2.0, 0.5), (2.0, 2.0), (0.5, 2.0)]));
+        // One inside the other
+        assert!(polygons_overlap(&l_shape, &[(0.2, 0.2), (0.4, 0.2), (0.4, 0.4), (0.2, 0.4)]));
+        assert!((polygon_area(&square) - 1.0).abs() < 1e-12);
+
+        // Either winding grows outward; a trapezoid's slanted sides move out square to themselves
+        let expected = [(-0.1, -0.1), (1.1, -0.1), (1.1, 1.1), (-0.1, 1.1)];
+        let reversed: Vec<Vec2> = square.iter().rev().copied().collect();
+        for (grown, polygon) in [(offset_polygon(&square, 0.1), square.to_vec()), (offset_polygon(&reversed, 0.1), reversed)] {
+            for (point, original) in grown.iter().zip(&polygon) {
+                let target = expected[square.iter().position(|p| p == original).unwrap()];
+                assert!(distance(*point, target) < 1e-12, "{:?}", grown);
+            }
+        }
+        let trapezoid = [(-2.0, 0.0), (2.0, 0.0), (1.0, 1.0), (-1.0, 1.0)];
+        let grown = offset_polygon(&trapezoid, 0.5);
+        let from_slant = |p: Vec2| cross((2.0, 0.0), (1.0, 1.0), p).abs() / 2f64.sqrt();
+        assert!((from_slant(grown[1]) - 0.5).abs() < 1e-9 && (from_slant(grown[2]) - 0.5).abs() < 1e-9);
+        assert!((polygon_area(&offset_polygon(&grown, -0.5)) - polygon_area(&trapezoid)).abs() < 1e-9);
+
+        let grown = Shape { points: square.to_vec(), radius: 0.5 };
+        assert_eq!(grown.scanline(0.5), vec![(-0.5, 1.5)]);
+        let corners = grown.scanline(-0.4)[0];
+        assert!((corners.0 + 0.3).abs() < 1e-12 && (corners.1 - 1.3).abs() < 1e-12);
+        assert!(grown.scanline(2.0).is_empty());
+        assert_eq!(subtract_spans(&[(0.0, 10.0)], &merge_spans(vec![(2.0, 3.0), (2.5, 4.0), (8.0, 12.0)])), vec![(0.0, 2.0), (4.0, 8.0)]);
+    }
+}
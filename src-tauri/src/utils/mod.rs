@@ -1,5 +1,8 @@
 pub mod error;
 pub mod validators;
+pub mod units;
+pub mod geometry;
+pub mod stroke_font;
 
 pub use error::*;
 pub use validators::*;
\ No newline at end of file
@@ -0,0 +1,113 @@
+// A small single-stroke font for plotting text as pen strokes on fabrication layers.
+// Glyphs are drawn on a grid four units wide with a cap height of six, y up.
+
+use crate::utils::geometry::Vec2;
+
+const CAP_HEIGHT: f64 = 6.0;
+// Glyph width plus the gap to the next one
+const ADVANCE: f64 = 6.0;
+const GAP: f64 = 2.0;
+
+/// Pen strokes for `text` with capitals `size` tall, centred on the origin in the y-down
+/// board frame. Lowercase prints as uppercase and characters the font lacks print as `?`.
+pub fn text_strokes(text: &str, size: f64) -> Vec<Vec<Vec2>> {
+    let scale = size / CAP_HEIGHT;
+    let count = text.chars().count() as f64;
+    let left = -(count * ADVANCE - GAP) / 2.0;
+
+    let mut strokes = Vec::new();
+    for (index, c) in text.chars().enumerate() {
+        let x0 = left + index as f64 * ADVANCE;
+        for stroke in glyph(c.to_ascii_uppercase()).split(';').filter(|s| !s.trim().is_empty()) {
+            let points: Vec<Vec2> = stroke.split_whitespace()
+                .filter_map(|point| point.split_once(','))
+                .filter_map(|(x, y)| Some((x.parse::<f64>().ok()?, y.parse::<f64>().ok()?)))
+                .map(|(x, y)| ((x0 + x) * scale, (CAP_HEIGHT / 2.0 - y) * scale))
+                .collect();
+            if !points.is_empty() {
+                strokes.push(points);
+            }
+        }
+    }
+    strokes
+}
+
+// Strokes separated by `;`, each a run of x,y grid points
+fn glyph(c: char) -> &'static str {
+    match c {
+        ' ' => "",
+        '0' => "1,0 3,0 4,1 4,5 3,6 1,6 0,5 0,1 1,0; 0,1 4,5",
+        '1' => "1,5 2,6 2,0; 1,0 3,0",
+        '2' => "0,5 1,6 3,6 4,5 4,4 0,0 4,0",
+        '3' => "0,5 1,6 3,6 4,5 4,4 3,3 4,2 4,1 3,0 1,0 0,1; 1,3 3,3",
+        '4' => "3,0 3,6 0,2 4,2",
+        '5' => "4,6 0,6 0,3 3,3 4,2 4,1 3,0 0,0",
+        '6' => "4,5 3,6 1,6 0,5 0,1 1,0 3,0 4,1 4,2 3,3 0,3",
+        '7' => "0,6 4,6 1,0",
+        '8' => "1,3 0,4 0,5 1,6 3,6 4,5 4,4 3,3 1,3 0,2 0,1 1,0 3,0 4,1 4,2 3,3",
+        '9' => "0,1 1,0 3,0 4,1 4,5 3,6 1,6 0,5 0,4 1,3 4,3",
+        'A' => "0,0 0,4 2,6 4,4 4,0; 0,3 4,3",
+        'B' => "0,0 0,6 3,6 4,5 4,4 3,3 0,3; 3,3 4,2 4,1 3,0 0,0",
+        'C' => "4,5 3,6 1,6 0,5 0,1 1,0 3,0 4,1",
+        'D' => "0,0 0,6 3,6 4,5 4,1 3,0 0,0",
+        'E' => "4,6 0,6 0,0 4,0; 0,3 3,3",
+        'F' => "4,6 0,6 0,0; 0,3 3,3",
+        'G' => "4,5 3,6 1,6 0,5 0,1 1,0 3,0 4,1 4,3 2,3",
+        'H' => "0,0 0,6; 4,0 4,6; 0,3 4,3",
+        'I' => "1,6 3,6; 2,6 2,0; 1,0 3,0",
+        'J' => "2,6 4,6; 3,6 3,1 2,0 1,0 0,1",
+        'K' => "0,0 0,6; 4,6 0,2; 1,3 4,0",
+        'L' => "0,6 0,0 4,0",
+        'M' => "0,0 0,6 2,3 4,6 4,0",
+        'N' => "0,0 0,6 4,0 4,6",
+        'O' => "1,0 3,0 4,1 4,5 3,6 1,6 0,5 0,1 1,0",
+        'P' => "0,0 0,6 3,6 4,5 4,4 3,3 0,3",
+        'Q' => "1,0 3,0 4,1 4,5 3,6 1,6 0,5 0,1 1,0; 2,2 4,0",
+        'R' => "0,0 0,6 3,6 4,5 4,4 3,3 0,3; 2,3 4,0",
+        'S' => "4,5 3,6 1,6 0,5 0,4 1,3 3,3 4,2 4,1 3,0 1,0 0,1",
+        'T' => "0,6 4,6; 2,6 2,0",
+        'U' => "0,6 0,1 1,0 3,0 4,1 4,6",
+        'V' => "0,6 2,0 4,6",
+        'W' => "0,6 1,0 2,3 3,0 4,6",
+        'X' => "0,0 4,6; 0,6 4,0",
+        'Y' => "0,6 2,3 4,6; 2,3 2,0",
+        'Z' => "0,6 4,6 0,0 4,0",
+        '-' => "1,3 3,3",
+        '+' => "1,3 3,3; 2,2 2,4",
+        '=' => "1,2 3,2; 1,4 3,4",
+        '_' => "0,-1 4,-1",
+        '.' => "1.75,0 2.25,0",
+        ',' => "2,0.5 1,-1",
+        ':' => "2,1 2,1.5; 2,4 2,4.5",
+        '/' => "0,0 4,6",
+        '(' => "3,6 2,5 2,1 3,0",
+        ')' => "1,6 2,5 2,1 1,0",
+        '*' => "2,1 2,5; 0,2 4,4; 0,4 4,2",
+        '%' => "0,0 4,6; 0,5 1,5 1,6 0,6 0,5; 3,0 4,0 4,1 3,1 3,0",
+        _ => "0,5 1,6 3,6 4,5 4,4 2,3 2,2; 1.75,0 2.25,0",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_strokes() {
+        // "R1" at 1.2 mm: two strokes each, in cells ten units wide in all, centred; the 1
+        // doesn't reach the right edge of its cell
+        let strokes = text_strokes("R1", 1.2);
+        assert_eq!(strokes.len(), 4);
+        let points: Vec<Vec2> = strokes.iter().flatten().copied().collect();
+        let (x0, x1) = points.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| (lo.min(p.0), hi.max(p.0)));
+        let (y0, y1) = points.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| (lo.min(p.1), hi.max(p.1)));
+        assert!((x0 + 1.0).abs() < 1e-12 && (x1 - 0.8).abs() < 1e-12);
+        assert!((y0 + 0.6).abs() < 1e-12 && (y1 - 0.6).abs() < 1e-12);
+        // The R's stem starts at the baseline, which is +y on the board
+        let start = strokes[0][0];
+        assert!((start.0 + 1.0).abs() < 1e-12 && (start.1 - 0.6).abs() < 1e-12);
+
+        assert_eq!(text_strokes("u?", 1.0), text_strokes("U\u{3a9}", 1.0));
+        assert!(text_strokes("  ", 1.0).is_empty());
+    }
+}
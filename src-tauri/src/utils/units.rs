@@ -0,0 +1,171 @@
+/// Parses an engineering value such as `"4.7k"`, `"100nF"`, `"10µF"`, `"4k7"` or `"25V"`
+/// into its base-unit magnitude. Trailing unit letters are ignored.
+pub fn parse_si_value(text: &str) -> Option<f64> {
+    let text: String = text.trim().replace(',', ".").split_whitespace().collect();
+    if text.is_empty() {
+        return None;
+    }
+
+    // Split the leading numeric part (including any exponent) from the suffix
+    let numeric_end = numeric_prefix_len(&text);
+    let number: f64 = text[..numeric_end].parse().ok()?;
+    let suffix = &text[numeric_end..];
+
+    let mut chars = suffix.chars();
+    let first = match chars.next() {
+        Some(c) => c,
+        None => return Some(number),
+    };
+
+    let multiplier = match si_multiplier(first, suffix) {
+        Some(m) => m,
+        None => return Some(number),
+    };
+
+    // RKM notation ("4k7", "2R2"): digits after the prefix are the fraction
+    let rest: String = chars.clone().take_while(|c| c.is_ascii_digit()).collect();
+    if !rest.is_empty() && !text[..numeric_end].contains('.') {
+        let fraction: f64 = format!("0.{}", rest).parse().ok()?;
+        return Some((number + fraction) * multiplier);
+    }
+
+    Some(number * multiplier)
+}
+
+/// Unit part of an engineering value, e.g. `"F"` for `"10µF"` or `"V"` for `"25V"`.
+/// Ohms are normalised to `Ω`; values without a unit give `None`.
+pub fn parse_si_unit(text: &str) -> Option<String> {
+    let text: String = text.trim().replace(',', ".").split_whitespace().collect();
+    let numeric_end = numeric_prefix_len(&text);
+    if numeric_end == 0 {
+        return None;
+    }
+
+    let mut suffix = &text[numeric_end..];
+    let first = suffix.chars().next()?;
+    if suffix.to_lowercase().starts_with("meg") {
+        suffix = &suffix[3..];
+    } else if si_multiplier(first, suffix).is_some() {
+        suffix = &suffix[first.len_utf8()..];
+    }
+    let unit = suffix.trim_start_matches(|c: char| c.is_ascii_digit());
+
+    match unit.to_lowercase().as_str() {
+        "" => None,
+        "ohm" | "ohms" | "Ω" | "ω" => Some("Ω".to_string()),
+        "hz" => Some("Hz".to_string()),
+        other => Some(other.to_uppercase()),
+    }
+}
+
+fn numeric_prefix_len(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    let mut numeric_end = 0;
+    while numeric_end < bytes.len() {
+        let c = bytes[numeric_end];
+        let is_sign = (c == b'-' || c == b'+')
+            && (numeric_end == 0 || matches!(bytes[numeric_end - 1], b'e' | b'E'));
+        let is_exponent = (c == b'e' || c == b'E')
+            && bytes
+                .get(numeric_end + 1)
+                .is_some_and(|n| n.is_ascii_digit() || *n == b'-');
+        if c.is_ascii_digit() || c == b'.' || is_sign || is_exponent {
+            numeric_end += 1;
+        } else {
+            break;
+        }
+    }
+    numeric_end
+}
+
+fn si_multiplier(prefix: char, suffix: &str) -> Option<f64> {
+    // "meg" is SPICE for mega; a lone "m" is milli
+    if suffix.to_lowercase().starts_with("meg") {
+        return Some(1e6);
+    }
+    match prefix {
+        'f' => Some(1e-15),
+        'p' => Some(1e-12),
+        'n' => Some(1e-9),
+        'u' | 'µ' | 'μ' => Some(1e-6),
+        'm' => Some(1e-3),
+        'R' | 'r' => Some(1.0),
+        'k' | 'K' => Some(1e3),
+        'M' => Some(1e6),
+        'G' => Some(1e9),
+        'T' => Some(1e12),
+        _ => None,
+    }
+}
+
+/// Formats a magnitude with an SI prefix, e.g. `format_si_value(4700.0, "Ω")` gives `"4.7kΩ"`.
+pub fn format_si_value(value: f64, unit: &str) -> String {
+    if value == 0.0 || !value.is_finite() {
+        return format!("{}{}", value, unit);
+    }
+
+    const PREFIXES: [(f64, &str); 9] = [
+        (1e12, "T"),
+        (1e9, "G"),
+        (1e6, "M"),
+        (1e3, "k"),
+        (1.0, ""),
+        (1e-3, "m"),
+        (1e-6, "µ"),
+        (1e-9, "n"),
+        (1e-12, "p"),
+    ];
+
+    let magnitude = value.abs();
+    let (scale, prefix) = PREFIXES
+        .iter()
+        .find(|(scale, _)| magnitude >= *scale * 0.999_999)
+        .copied()
+        .unwrap_or((1e-15, "f"));
+
+    let scaled = value / scale;
+    let rounded = (scaled * 1000.0).round() / 1000.0;
+    format!("{}{}{}", rounded, prefix, unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: Option<f64>, b: f64) -> bool {
+        a.is_some_and(|a| (a - b).abs() <= b.abs() * 1e-9)
+    }
+
+    #[test]
+    fn test_parse_si_value() {
+        assert!(approx(parse_si_value("4.7k"), 4700.0));
+        assert!(approx(parse_si_value("100nF"), 100e-9));
+        assert!(approx(parse_si_value("10µF"), 10e-6));
+        assert!(approx(parse_si_value("10uF"), 10e-6));
+        assert!(approx(parse_si_value("4k7"), 4700.0));
+        assert!(approx(parse_si_value("2R2"), 2.2));
+        assert!(approx(parse_si_value("25V"), 25.0));
+        assert!(approx(parse_si_value("1Meg"), 1e6));
+        assert!(approx(parse_si_value("1e-3"), 1e-3));
+        assert_eq!(parse_si_value("Resistor"), None);
+        assert_eq!(parse_si_value(""), None);
+    }
+
+    #[test]
+    fn test_parse_si_unit() {
+        assert_eq!(parse_si_unit("10µF").as_deref(), Some("F"));
+        assert_eq!(parse_si_unit("25V").as_deref(), Some("V"));
+        assert_eq!(parse_si_unit("4.7kohm").as_deref(), Some("Ω"));
+        assert_eq!(parse_si_unit("100uf").as_deref(), Some("F"));
+        assert_eq!(parse_si_unit("4k7"), None);
+        assert_eq!(parse_si_unit("1Meg"), None);
+        assert_eq!(parse_si_unit("0805"), None);
+    }
+
+    #[test]
+    fn test_format_si_value() {
+        assert_eq!(format_si_value(4700.0, "Ω"), "4.7kΩ");
+        assert_eq!(format_si_value(100e-9, "F"), "100nF");
+        assert_eq!(format_si_value(5.0, "V"), "5V");
+    }
+}
@@ -145,6 +145,18 @@ pub fn sanitize_filename(filename: &str) -> String {
     FILENAME_RE.replace_all(filename, "_").to_string()
 }
 
+/// Escapes text for use in HTML/XML content and double-quoted attributes.
+pub fn escape_markup(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// True for absolute http(s) URLs, the only links worth putting in generated documents.
+pub fn validate_web_url(url: &str) -> bool {
+    let lower = url.trim().to_ascii_lowercase();
+    ["http://", "https://"].iter()
+        .any(|scheme| lower.starts_with(scheme) && lower.len() > scheme.len())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -238,4 +250,14 @@ mod tests {
         assert_eq!(sanitize_filename("file<name>.txt"), "file_name_.txt");
         assert_eq!(sanitize_filename("file/name"), "file_name");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_markup_and_urls() {
+        assert_eq!(escape_markup(r#"<a href="x">R&D</a>"#), "&lt;a href=&quot;x&quot;&gt;R&amp;D&lt;/a&gt;");
+        assert!(validate_web_url("https://example.com/ds.pdf"));
+        assert!(validate_web_url("HTTP://example.com"));
+        assert!(!validate_web_url("javascript:alert(1)"));
+        assert!(!validate_web_url("file:///etc/passwd"));
+        assert!(!validate_web_url("https://"));
+    }
+}